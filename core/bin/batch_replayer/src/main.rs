@@ -0,0 +1,109 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::Context as _;
+use clap::Parser;
+use micro_config::{configs::chain::NetworkConfig, DBConfig, PostgresConfig};
+use micro_core::batch_replayer::BatchReplayer;
+use micro_dal::ConnectionPool;
+use micro_env_config::FromEnv;
+use micro_types::L1BatchNumber;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Zkamoeba",
+    version,
+    about = "Re-executes sealed L1 batches and compares the results with the stored data",
+    long_about = None
+)]
+struct Cli {
+    /// Number of the first L1 batch to replay.
+    #[arg(long)]
+    from_batch: u32,
+    /// Number of the last L1 batch to replay (inclusive). If not specified, only `from_batch` is replayed.
+    #[arg(long)]
+    to_batch: Option<u32>,
+    /// Path to write the JSON report to. If not specified, the report is printed to stdout.
+    #[arg(long)]
+    output: Option<PathBuf>,
+    /// Path to the Merkle tree RocksDB instance. The tree must not be used by the server at the same time;
+    /// consider pointing to a copy of the tree. If not specified, the path from the database config is used.
+    #[arg(long)]
+    merkle_tree_path: Option<PathBuf>,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let log_format = vlog::log_format_from_env();
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let sentry_url = vlog::sentry_url_from_env();
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let environment = vlog::environment_from_env();
+
+    let mut builder = vlog::ObservabilityBuilder::new().with_log_format(log_format);
+    if let Some(sentry_url) = sentry_url {
+        builder = builder
+            .with_sentry_url(&sentry_url)
+            .context("Invalid Sentry URL")?
+            .with_sentry_environment(environment);
+    }
+    let _guard = builder.build();
+
+    let opt = Cli::parse();
+    let to_batch = opt.to_batch.unwrap_or(opt.from_batch);
+    anyhow::ensure!(
+        opt.from_batch <= to_batch,
+        "`from_batch` must not be greater than `to_batch`"
+    );
+
+    let network_config = NetworkConfig::from_env().context("NetworkConfig::from_env()")?;
+    let postgres_config = PostgresConfig::from_env().context("PostgresConfig::from_env()")?;
+    let merkle_tree_path = match opt.merkle_tree_path {
+        Some(path) => path,
+        None => {
+            let db_config = DBConfig::from_env().context("DBConfig::from_env()")?;
+            db_config.merkle_tree.path.into()
+        }
+    };
+    // Replaying is read-only, so the replica database is used.
+    let connection_pool = ConnectionPool::builder(
+        postgres_config.replica_url()?,
+        postgres_config.max_connections()?,
+    )
+    .build()
+    .await
+    .context("failed to build a connection pool")?;
+    let replayer = BatchReplayer::new(
+        connection_pool,
+        network_config.micro_network_id,
+        merkle_tree_path,
+    );
+
+    let mut reports = Vec::with_capacity((to_batch - opt.from_batch + 1) as usize);
+    for l1_batch_number in opt.from_batch..=to_batch {
+        let report = replayer
+            .replay(L1BatchNumber(l1_batch_number))
+            .await
+            .with_context(|| format!("failed replaying L1 batch #{l1_batch_number}"))?;
+        reports.push(report);
+    }
+
+    let report_json = serde_json::to_string_pretty(&reports)?;
+    if let Some(output) = &opt.output {
+        fs::write(output, report_json)
+            .with_context(|| format!("failed writing report to {}", output.display()))?;
+    } else {
+        println!("{report_json}");
+    }
+
+    let mismatched_batches: Vec<_> = reports
+        .iter()
+        .filter(|report| !report.is_deterministic())
+        .map(|report| report.l1_batch_number)
+        .collect();
+    anyhow::ensure!(
+        mismatched_batches.is_empty(),
+        "found mismatches in L1 batches: {mismatched_batches:?}"
+    );
+    Ok(())
+}
@@ -1,10 +1,14 @@
-use std::{env, time::Duration};
+use std::{env, net::IpAddr, num::NonZeroU32, time::Duration};
 
 use anyhow::Context;
 use micro_basic_types::{Address, L1ChainId, L2ChainId, MiniblockNumber};
 use micro_core::api_server::{
     tx_sender::TxSenderConfig,
-    web3::{state::InternalApiConfig, Namespace},
+    web3::{
+        rate_limiter::{ClientRateLimitKey, ClientRateLimits, MethodWeights},
+        state::InternalApiConfig,
+        Namespace,
+    },
 };
 use micro_types::api::BridgeAddresses;
 use micro_web3_decl::{
@@ -119,6 +123,27 @@ pub struct OptionalENConfig {
     /// Maximum response body size in MiBs. Default is 10 MiB.
     #[serde(default = "OptionalENConfig::default_max_response_body_size_mb")]
     pub max_response_body_size_mb: usize,
+    /// Sustained number of request weight units per second that a single API client may spend.
+    /// Each method has a weight (1 by default); methods executing the VM and `eth_getLogs` are heavier.
+    /// Rate-limited requests are rejected with the JSON-RPC error code -32005 ("limit exceeded" as per EIP-1474).
+    /// If not set, per-client rate limiting is disabled.
+    pub client_requests_per_second_limit: Option<u32>,
+    /// Maximum number of request weight units a single API client may spend in a burst.
+    /// If not set, equals to `client_requests_per_second_limit`.
+    pub client_requests_burst_limit: Option<u32>,
+    /// How API clients are identified for per-client rate limiting: by IP address (`ip`) or by API key (`api_key`).
+    #[serde(default = "OptionalENConfig::default_client_rate_limit_key")]
+    pub client_rate_limit_key: ClientRateLimitKey,
+    /// Name of the HTTP header containing the client API key.
+    #[serde(default = "OptionalENConfig::default_client_api_key_header")]
+    pub client_api_key_header: String,
+    /// Overrides for method weights used in per-client rate limiting, in the `method=weight` format.
+    #[serde(default)]
+    pub method_weights: Vec<String>,
+    /// IP addresses of reverse proxies whose `X-Forwarded-For` / `X-Real-IP` headers are trusted when determining
+    /// client IP addresses. If empty, client IP addresses are unknown to the API servers.
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
 
     // Other API config settings
     /// Interval between polling DB for pubsub (in ms).
@@ -289,6 +314,14 @@ impl OptionalENConfig {
         10
     }
 
+    const fn default_client_rate_limit_key() -> ClientRateLimitKey {
+        ClientRateLimitKey::Ip
+    }
+
+    fn default_client_api_key_header() -> String {
+        "x-api-key".to_owned()
+    }
+
     const fn default_enum_index_migration_chunk_size() -> usize {
         5000
     }
@@ -344,6 +377,28 @@ impl OptionalENConfig {
     pub fn max_response_body_size(&self) -> usize {
         self.max_response_body_size_mb * BYTES_IN_MEGABYTE
     }
+
+    /// Returns per-client rate limits for the API servers, or `None` if per-client rate limiting is disabled.
+    pub fn client_rate_limits(&self) -> anyhow::Result<Option<ClientRateLimits>> {
+        let Some(requests_per_second) = self.client_requests_per_second_limit else {
+            return Ok(None);
+        };
+        let requests_per_second = NonZeroU32::new(requests_per_second)
+            .context("`client_requests_per_second_limit` must be positive")?;
+        let burst = match self.client_requests_burst_limit {
+            Some(burst) => {
+                NonZeroU32::new(burst).context("`client_requests_burst_limit` must be positive")?
+            }
+            None => requests_per_second,
+        };
+        Ok(Some(ClientRateLimits {
+            requests_per_second,
+            burst,
+            key: self.client_rate_limit_key,
+            api_key_header: self.client_api_key_header.clone(),
+            method_weights: MethodWeights::with_overrides(&self.method_weights)?,
+        }))
+    }
 }
 
 /// This part of the external node config is required for its operation.
@@ -538,6 +593,12 @@ impl From<ExternalNodeConfig> for TxSenderConfig {
             max_allowed_l2_tx_gas_limit: u32::MAX,
             validation_computational_gas_limit: u32::MAX,
             chain_id: config.remote.l2_chain_id,
+            // Transactions are proxied to the main node, which enforces the replacement policy
+            // and per-account limits.
+            replacement_fee_bump_percent: 0,
+            max_pending_txs_per_account: None,
+            max_nonce_gap_per_account: None,
+            max_txs_per_ip_per_minute: None,
         }
     }
 }
@@ -25,6 +25,7 @@ fn parsing_optional_config_from_empty_env() {
         128 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), 10 * BYTES_IN_MEGABYTE);
+    assert!(config.client_rate_limits().unwrap().is_none());
 }
 
 #[test]
@@ -44,6 +45,10 @@ fn parsing_optional_config_from_env() {
         ("EN_MERKLE_TREE_MULTI_GET_CHUNK_SIZE", "1000"),
         ("EN_MERKLE_TREE_BLOCK_CACHE_SIZE_MB", "32"),
         ("EN_MAX_RESPONSE_BODY_SIZE_MB", "1"),
+        ("EN_CLIENT_REQUESTS_PER_SECOND_LIMIT", "100"),
+        ("EN_CLIENT_RATE_LIMIT_KEY", "api_key"),
+        ("EN_METHOD_WEIGHTS", "eth_call=5,eth_getLogs=50"),
+        ("EN_TRUSTED_PROXIES", "10.0.0.1,::1"),
     ];
     let env_vars = env_vars
         .into_iter()
@@ -70,4 +75,13 @@ fn parsing_optional_config_from_env() {
         32 * BYTES_IN_MEGABYTE
     );
     assert_eq!(config.max_response_body_size(), BYTES_IN_MEGABYTE);
+    let client_rate_limits = config.client_rate_limits().unwrap().unwrap();
+    assert_eq!(client_rate_limits.requests_per_second.get(), 100);
+    assert_eq!(client_rate_limits.burst.get(), 100);
+    assert_eq!(client_rate_limits.key, ClientRateLimitKey::ApiKey);
+    assert_eq!(client_rate_limits.api_key_header, "x-api-key");
+    assert_eq!(
+        config.trusted_proxies,
+        [IpAddr::from([10, 0, 0, 1]), "::1".parse().unwrap()]
+    );
 }
@@ -13,7 +13,7 @@ use micro_core::{
         execution_sandbox::VmConcurrencyLimiter,
         healthcheck::HealthCheckHandle,
         tx_sender::{ApiContracts, TxSenderBuilder},
-        web3::{ApiBuilder, Namespace},
+        web3::{statistics::StatisticsCache, ApiBuilder, Namespace},
     },
     block_reverter::{BlockReverter, BlockReverterFlags, L1ExecutedBatchesRevert},
     consistency_checker::ConsistencyChecker,
@@ -31,8 +31,8 @@ use micro_core::{
     reorg_detector::ReorgDetector,
     setup_sigint_handler,
     state_keeper::{
-        L1BatchExecutorBuilder, MainBatchExecutorBuilder, MicroStateKeeper, MiniblockSealer,
-        MiniblockSealerHandle,
+        L1BatchExecutorBuilder, MainBatchExecutorBuilder, MicroStateKeeper, MiniblockNotifications,
+        MiniblockSealer, MiniblockSealerHandle,
     },
     sync_layer::{
         batch_status_updater::BatchStatusUpdater, external_io::ExternalIO, fetcher::FetcherCursor,
@@ -77,8 +77,10 @@ async fn build_state_keeper(
     // node has already executed the transaction, then the external node must execute it too.
     let max_allowed_l2_tx_gas_limit = u32::MAX.into();
     let validation_computational_gas_limit = u32::MAX;
-    // We only need call traces on the external node if the `debug_` namespace is enabled.
-    let save_call_traces = config.optional.api_namespaces().contains(&Namespace::Debug);
+    // We only need call traces on the external node if the `debug_` or `trace_` namespace is enabled.
+    let api_namespaces = config.optional.api_namespaces();
+    let save_call_traces =
+        api_namespaces.contains(&Namespace::Debug) || api_namespaces.contains(&Namespace::Trace);
 
     let batch_executor_base: Box<dyn L1BatchExecutorBuilder> =
         Box::new(MainBatchExecutorBuilder::new(
@@ -181,10 +183,14 @@ async fn init_tasks(
     let (action_queue_sender, action_queue) = ActionQueue::new();
 
     let mut task_handles = vec![];
+    // The state keeper runs in the same process as the WS API, so pubsub notifiers can receive
+    // sealed miniblocks directly.
+    let miniblock_notifications = MiniblockNotifications::default();
     let (miniblock_sealer, miniblock_sealer_handle) = MiniblockSealer::new(
         connection_pool.clone(),
         config.optional.miniblock_seal_queue_capacity,
     );
+    let miniblock_sealer = miniblock_sealer.with_notifications(miniblock_notifications.clone());
     task_handles.push(tokio::spawn(miniblock_sealer.run()));
 
     let state_keeper = build_state_keeper(
@@ -308,15 +314,31 @@ async fn init_tasks(
         (tx_sender, vm_barrier, cache_update_handle)
     };
 
+    let client_rate_limits = config
+        .optional
+        .client_rate_limits()
+        .context("invalid per-client rate limits")?;
+    // Network statistics are aggregated once and shared by both API servers.
+    let (statistics, statistics_update_task) = StatisticsCache::new(
+        connection_pool.clone(),
+        StatisticsCache::DEFAULT_UPDATE_INTERVAL,
+    );
+    // The update task takes care of its termination, so we don't need to retain its handle.
+    tokio::spawn(statistics_update_task);
+
     let http_server_handles =
         ApiBuilder::jsonrpc_backend(config.clone().into(), connection_pool.clone())
             .http(config.required.http_port)
             .with_filter_limit(config.optional.filters_limit)
             .with_batch_request_size_limit(config.optional.max_batch_request_size)
             .with_response_body_size_limit(config.optional.max_response_body_size())
+            .with_client_rate_limits(client_rate_limits.clone())
+            .with_trusted_proxies(config.optional.trusted_proxies.clone())
             .with_threads(config.required.threads_per_server)
+            .with_miniblock_notifications(miniblock_notifications.clone())
             .with_tx_sender(tx_sender.clone(), vm_barrier.clone())
             .with_sync_state(sync_state.clone())
+            .with_statistics(statistics.clone())
             .enable_api_namespaces(config.optional.api_namespaces())
             .build(stop_receiver.clone())
             .await
@@ -330,9 +352,13 @@ async fn init_tasks(
             .with_batch_request_size_limit(config.optional.max_batch_request_size)
             .with_response_body_size_limit(config.optional.max_response_body_size())
             .with_polling_interval(config.optional.polling_interval())
+            .with_miniblock_notifications(miniblock_notifications)
+            .with_client_rate_limits(client_rate_limits)
+            .with_trusted_proxies(config.optional.trusted_proxies.clone())
             .with_threads(config.required.threads_per_server)
             .with_tx_sender(tx_sender, vm_barrier)
             .with_sync_state(sync_state)
+            .with_statistics(statistics)
             .enable_api_namespaces(config.optional.api_namespaces())
             .build(stop_receiver.clone())
             .await
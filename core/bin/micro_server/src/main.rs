@@ -21,7 +21,7 @@ use micro_core::{
     genesis_init, initialize_components, is_genesis_needed, setup_sigint_handler,
     temp_config_store::TempConfigStore, Component, Components,
 };
-use micro_env_config::FromEnv;
+use micro_env_config::{object_store::MirrorObjectStoreConfig, FromEnv};
 use micro_storage::RocksDB;
 use micro_utils::wait_for_tasks::wait_for_tasks;
 
@@ -122,6 +122,9 @@ async fn main() -> anyhow::Result<()> {
         gas_adjuster_config: GasAdjusterConfig::from_env().ok(),
         prover_configs: ProverConfigs::from_env().ok(),
         object_store_config: ObjectStoreConfig::from_env().ok(),
+        mirror_object_store_config: MirrorObjectStoreConfig::from_env()
+            .ok()
+            .map(|config| config.0),
     };
 
     let postgres_config = configs.postgres_config.clone().context("PostgresConfig")?;
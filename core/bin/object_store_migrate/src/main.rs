@@ -0,0 +1,99 @@
+use std::time::Instant;
+
+use anyhow::Context as _;
+use clap::Parser;
+use micro_config::ObjectStoreConfig;
+use micro_object_store::{Bucket, ObjectStoreFactory};
+
+use crate::migration::{migrate_bucket, MigrationOptions};
+
+mod migration;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Parser)]
+#[command(
+    author = "Zkamoeba",
+    version,
+    about = "Copies object store buckets between storage backends",
+    long_about = None
+)]
+struct Cli {
+    /// Prefix of env variables with the source object store config.
+    #[arg(long, default_value = "OBJECT_STORE_")]
+    source_env_prefix: String,
+    /// Prefix of env variables with the target object store config.
+    #[arg(long, default_value = "MIRROR_OBJECT_STORE_")]
+    target_env_prefix: String,
+    /// Buckets to copy, e.g. `proofs_fri`. If not specified, all buckets are copied.
+    #[arg(long = "bucket")]
+    buckets: Vec<Bucket>,
+    /// Only copy objects with keys starting with this prefix.
+    #[arg(long, default_value = "")]
+    key_prefix: String,
+    /// Maximum number of objects copied concurrently.
+    #[arg(long, default_value_t = 16)]
+    parallelism: usize,
+    /// Read back each copied object from the target store and check that it matches the source.
+    #[arg(long)]
+    verify: bool,
+    /// Only log objects that would be copied without copying them.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+fn load_config(env_prefix: &str) -> anyhow::Result<ObjectStoreConfig> {
+    envy::prefixed(env_prefix)
+        .from_env()
+        .with_context(|| format!("cannot load object store config with `{env_prefix}` prefix"))
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    #[allow(deprecated)] // TODO (QIT-21): Use centralized configuration approach.
+    let log_format = vlog::log_format_from_env();
+    let _guard = vlog::ObservabilityBuilder::new()
+        .with_log_format(log_format)
+        .build();
+
+    let cli = Cli::parse();
+    let source_config = load_config(&cli.source_env_prefix)?;
+    let target_config = load_config(&cli.target_env_prefix)?;
+    anyhow::ensure!(
+        source_config != target_config,
+        "source and target object store configs are the same"
+    );
+    let source = ObjectStoreFactory::new(source_config).create_store().await;
+    let target = ObjectStoreFactory::new(target_config).create_store().await;
+
+    let options = MigrationOptions {
+        parallelism: cli.parallelism.max(1),
+        verify: cli.verify,
+        dry_run: cli.dry_run,
+    };
+    let buckets = if cli.buckets.is_empty() {
+        Bucket::ALL.to_vec()
+    } else {
+        cli.buckets
+    };
+
+    let started_at = Instant::now();
+    let mut failed_buckets = vec![];
+    for bucket in buckets {
+        tracing::info!("Migrating bucket `{bucket}` with options {options:?}");
+        let stats = migrate_bucket(&*source, &*target, bucket, &cli.key_prefix, options)
+            .await
+            .with_context(|| format!("failed migrating bucket `{bucket}`"))?;
+        tracing::info!("Finished migrating bucket `{bucket}`: {stats:?}");
+        if stats.failed > 0 {
+            failed_buckets.push(bucket);
+        }
+    }
+
+    anyhow::ensure!(
+        failed_buckets.is_empty(),
+        "some objects in buckets {failed_buckets:?} were not migrated; re-run the tool to resume migration"
+    );
+    tracing::info!("Migration finished in {:?}", started_at.elapsed());
+    Ok(())
+}
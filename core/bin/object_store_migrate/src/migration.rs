@@ -0,0 +1,162 @@
+//! Copying objects between object stores.
+
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use futures::{stream, StreamExt};
+use micro_object_store::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
+
+/// Number of processed objects after which the migration progress is logged.
+const PROGRESS_REPORTING_INTERVAL: u64 = 1_000;
+/// Maximum number of objects returned by a single listing call to an object store.
+const LISTING_PAGE_SIZE: usize = 1_000;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct MigrationOptions {
+    pub parallelism: usize,
+    pub verify: bool,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub(crate) struct MigrationStats {
+    /// Number of objects copied to the target store.
+    pub copied: u64,
+    /// Total size of objects copied to the target store.
+    pub copied_bytes: u64,
+    /// Number of objects skipped because they are already present in the target store.
+    pub skipped: u64,
+    /// Number of objects that failed to be copied.
+    pub failed: u64,
+}
+
+/// Copies all objects with the specified key prefix in `bucket` from `source` to `target`.
+///
+/// Objects already present in the target store with the same size are skipped, so the migration
+/// can be resumed by re-running it. Failures to copy individual objects are logged and reflected
+/// in the returned stats; they do not abort the migration.
+pub(crate) async fn migrate_bucket(
+    source: &dyn ObjectStore,
+    target: &dyn ObjectStore,
+    bucket: Bucket,
+    key_prefix: &str,
+    options: MigrationOptions,
+) -> anyhow::Result<MigrationStats> {
+    let source_objects = list_objects(source, bucket, key_prefix)
+        .await
+        .context("failed listing objects in source store")?;
+    let target_sizes: HashMap<_, _> = list_objects(target, bucket, key_prefix)
+        .await
+        .context("failed listing objects in target store")?
+        .into_iter()
+        .map(|object| (object.key, object.size))
+        .collect();
+
+    let mut stats = MigrationStats::default();
+    let objects_to_copy: Vec<_> = source_objects
+        .into_iter()
+        .filter(|object| {
+            let is_copied = target_sizes.get(&object.key) == Some(&object.size);
+            stats.skipped += u64::from(is_copied);
+            !is_copied
+        })
+        .collect();
+    let total_count = objects_to_copy.len();
+    tracing::info!(
+        "Copying {total_count} objects from bucket `{bucket}`, skipping {} objects already present in target store",
+        stats.skipped
+    );
+    if options.dry_run {
+        for object in &objects_to_copy {
+            tracing::info!(
+                "Object `{}` ({} bytes) would be copied",
+                object.key,
+                object.size
+            );
+        }
+        return Ok(stats);
+    }
+
+    let copy_results = stream::iter(objects_to_copy)
+        .map(|object| async move {
+            let result = copy_object(source, target, bucket, &object, options.verify).await;
+            (object, result)
+        })
+        .buffer_unordered(options.parallelism);
+    tokio::pin!(copy_results);
+
+    let mut processed_count = 0_u64;
+    while let Some((object, result)) = copy_results.next().await {
+        match result {
+            Ok(()) => {
+                stats.copied += 1;
+                stats.copied_bytes += object.size;
+            }
+            Err(err) => {
+                tracing::warn!("Failed copying object `{}`: {err:#}", object.key);
+                stats.failed += 1;
+            }
+        }
+        processed_count += 1;
+        if processed_count % PROGRESS_REPORTING_INTERVAL == 0 {
+            tracing::info!(
+                "Processed {processed_count}/{total_count} objects from bucket `{bucket}`"
+            );
+        }
+    }
+    Ok(stats)
+}
+
+async fn list_objects(
+    store: &dyn ObjectStore,
+    bucket: Bucket,
+    key_prefix: &str,
+) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+    let mut objects = vec![];
+    loop {
+        let start_after = objects
+            .last()
+            .map(|object: &ObjectMetadata| object.key.as_str());
+        let page = store
+            .list_raw(bucket, key_prefix, start_after, LISTING_PAGE_SIZE)
+            .await?;
+        let is_last_page = page.len() < LISTING_PAGE_SIZE;
+        objects.extend(page);
+        if is_last_page {
+            return Ok(objects);
+        }
+    }
+}
+
+async fn copy_object(
+    source: &dyn ObjectStore,
+    target: &dyn ObjectStore,
+    bucket: Bucket,
+    object: &ObjectMetadata,
+    verify: bool,
+) -> anyhow::Result<()> {
+    let value = source
+        .get_raw(bucket, &object.key)
+        .await
+        .context("failed fetching object from source store")?;
+    if !verify {
+        return target
+            .put_raw(bucket, &object.key, value)
+            .await
+            .context("failed putting object to target store");
+    }
+
+    target
+        .put_raw(bucket, &object.key, value.clone())
+        .await
+        .context("failed putting object to target store")?;
+    let copied_value = target
+        .get_raw(bucket, &object.key)
+        .await
+        .context("failed fetching copied object from target store")?;
+    anyhow::ensure!(
+        copied_value == value,
+        "object in target store differs from the source one"
+    );
+    Ok(())
+}
@@ -0,0 +1,99 @@
+//! Tests for object store migration.
+
+use micro_object_store::{Bucket, ObjectStore, ObjectStoreFactory};
+
+use crate::migration::{migrate_bucket, MigrationOptions, MigrationStats};
+
+const OPTIONS: MigrationOptions = MigrationOptions {
+    parallelism: 4,
+    verify: true,
+    dry_run: false,
+};
+
+async fn create_stores() -> (Box<dyn ObjectStore>, Box<dyn ObjectStore>) {
+    let source = ObjectStoreFactory::mock().create_store().await;
+    let target = ObjectStoreFactory::mock().create_store().await;
+    for i in 0..10_u8 {
+        let key = format!("{i}_object.bin");
+        source
+            .put_raw(Bucket::ProofsFri, &key, vec![i; usize::from(i)])
+            .await
+            .unwrap();
+    }
+    source
+        .put_raw(Bucket::ProverJobsFri, "other.bin", vec![1])
+        .await
+        .unwrap();
+    (source, target)
+}
+
+#[tokio::test]
+async fn migrating_bucket() {
+    let (source, target) = create_stores().await;
+    let stats = migrate_bucket(&*source, &*target, Bucket::ProofsFri, "", OPTIONS)
+        .await
+        .unwrap();
+    assert_eq!(
+        stats,
+        MigrationStats {
+            copied: 10,
+            copied_bytes: 45,
+            skipped: 0,
+            failed: 0,
+        }
+    );
+
+    for i in 0..10_u8 {
+        let key = format!("{i}_object.bin");
+        let value = target.get_raw(Bucket::ProofsFri, &key).await.unwrap();
+        assert_eq!(value, vec![i; usize::from(i)]);
+    }
+    let other_bucket_objects = target
+        .list_raw(Bucket::ProverJobsFri, "", None, 10)
+        .await
+        .unwrap();
+    assert!(other_bucket_objects.is_empty());
+}
+
+#[tokio::test]
+async fn resuming_migration() {
+    let (source, target) = create_stores().await;
+    let stats = migrate_bucket(&*source, &*target, Bucket::ProofsFri, "1", OPTIONS)
+        .await
+        .unwrap();
+    assert_eq!(stats.copied, 1);
+
+    // Emulate a partially copied object.
+    target
+        .put_raw(Bucket::ProofsFri, "2_object.bin", vec![])
+        .await
+        .unwrap();
+    let stats = migrate_bucket(&*source, &*target, Bucket::ProofsFri, "", OPTIONS)
+        .await
+        .unwrap();
+    assert_eq!(stats.copied, 9);
+    assert_eq!(stats.skipped, 1);
+    let value = target
+        .get_raw(Bucket::ProofsFri, "2_object.bin")
+        .await
+        .unwrap();
+    assert_eq!(value, [2, 2]);
+}
+
+#[tokio::test]
+async fn dry_run_migration() {
+    let (source, target) = create_stores().await;
+    let options = MigrationOptions {
+        dry_run: true,
+        ..OPTIONS
+    };
+    let stats = migrate_bucket(&*source, &*target, Bucket::ProofsFri, "", options)
+        .await
+        .unwrap();
+    assert_eq!(stats, MigrationStats::default());
+    let target_objects = target
+        .list_raw(Bucket::ProofsFri, "", None, 10)
+        .await
+        .unwrap();
+    assert!(target_objects.is_empty());
+}
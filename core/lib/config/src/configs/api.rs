@@ -1,4 +1,7 @@
-use std::{net::SocketAddr, time::Duration};
+use std::{
+    net::{IpAddr, SocketAddr},
+    time::Duration,
+};
 
 use micro_basic_types::H256;
 use serde::Deserialize;
@@ -85,10 +88,56 @@ pub struct Web3JsonRpcConfig {
     pub max_response_body_size_mb: Option<usize>,
     /// Maximum number of requests per minute for the WebSocket server.
     /// The value is per active connection.
-    /// Note: For HTTP, rate limiting is expected to be configured on the infra level.
+    /// Note: For per-client rate limiting applicable to HTTP, see `client_requests_per_second_limit`.
     pub websocket_requests_per_minute_limit: Option<u32>,
     /// Tree API url, currently used to proxy `getProof` calls to the tree
     pub tree_api_url: Option<String>,
+    /// Sustained number of request weight units per second that a single client may spend.
+    /// Each method has a weight (1 by default); methods executing the VM and `eth_getLogs` are heavier.
+    /// Rate-limited requests are rejected with the JSON-RPC error code -32005 ("limit exceeded" as per EIP-1474).
+    /// If not set, per-client rate limiting is disabled. Not supported by the WebSocket `jsonrpsee` backend.
+    pub client_requests_per_second_limit: Option<u32>,
+    /// Maximum number of request weight units a single client may spend in a burst.
+    /// If not set, equals to `client_requests_per_second_limit`.
+    pub client_requests_burst_limit: Option<u32>,
+    /// How clients are identified for per-client rate limiting. Default is `ip`.
+    pub client_rate_limit_key: Option<ClientRateLimitKey>,
+    /// Name of the HTTP header containing the client API key. Default is `x-api-key`.
+    pub client_api_key_header: Option<String>,
+    /// Overrides for method weights used in per-client rate limiting, in the `method=weight` format.
+    pub method_weights: Option<Vec<String>>,
+    /// IP addresses of reverse proxies in front of the API servers. Client IP addresses used for per-IP limits
+    /// are taken from the `X-Forwarded-For` / `X-Real-IP` headers only if this list is non-empty; addresses
+    /// of the listed proxies are skipped in `X-Forwarded-For`. The servers must not be reachable bypassing
+    /// the proxies, since the forwarding headers cannot be verified otherwise.
+    pub trusted_proxies: Option<Vec<IpAddr>>,
+    /// Port to which the GraphQL server is listening. If not set, the GraphQL server is disabled.
+    pub graphql_port: Option<u16>,
+    /// Maximum complexity of a GraphQL query. Default is 1000.
+    pub graphql_max_complexity: Option<usize>,
+    /// Maximum nesting depth of a GraphQL query. Default is 10.
+    pub graphql_max_depth: Option<usize>,
+    /// Address of the internal HTTP server exposing the `admin` namespace (deny-list management and
+    /// protocol upgrade scheduling). If not set, the admin server is disabled. The namespace is never exposed
+    /// by the public servers; this address must not be reachable from the public network.
+    pub admin_http_addr: Option<SocketAddr>,
+    /// Whether the `txpool` namespace (mempool inspection) is exposed by the API servers. Default is `false`.
+    /// The namespace is only available on servers running together with the state keeper.
+    pub txpool_namespace_enabled: Option<bool>,
+    /// Whether the `prover` namespace (prover assignments and deposits) is exposed by the API servers.
+    /// Default is `false`.
+    pub prover_namespace_enabled: Option<bool>,
+}
+
+/// Source of client identity for per-client rate limiting in the Web3 API servers.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientRateLimitKey {
+    /// Client IP address as reported by the `X-Forwarded-For` or `X-Real-IP` header set by a trusted proxy
+    /// (see `trusted_proxies`).
+    Ip,
+    /// API key from a configurable HTTP header.
+    ApiKey,
 }
 
 impl Web3JsonRpcConfig {
@@ -126,6 +175,18 @@ impl Web3JsonRpcConfig {
             max_response_body_size_mb: Default::default(),
             websocket_requests_per_minute_limit: Default::default(),
             tree_api_url: None,
+            client_requests_per_second_limit: None,
+            client_requests_burst_limit: None,
+            client_rate_limit_key: None,
+            client_api_key_header: None,
+            method_weights: None,
+            trusted_proxies: None,
+            graphql_port: None,
+            graphql_max_complexity: None,
+            graphql_max_depth: None,
+            admin_http_addr: None,
+            txpool_namespace_enabled: None,
+            prover_namespace_enabled: None,
         }
     }
 
@@ -137,6 +198,11 @@ impl Web3JsonRpcConfig {
         SocketAddr::new("0.0.0.0".parse().unwrap(), self.ws_port)
     }
 
+    pub fn graphql_bind_addr(&self) -> Option<SocketAddr> {
+        let port = self.graphql_port?;
+        Some(SocketAddr::new("0.0.0.0".parse().unwrap(), port))
+    }
+
     pub fn req_entities_limit(&self) -> usize {
         self.req_entities_limit.unwrap_or_else(|| 2u32.pow(10)) as usize
     }
@@ -212,6 +278,47 @@ impl Web3JsonRpcConfig {
     pub fn tree_api_url(&self) -> Option<String> {
         self.tree_api_url.clone()
     }
+
+    pub fn client_requests_burst_limit(&self) -> Option<u32> {
+        self.client_requests_burst_limit
+            .or(self.client_requests_per_second_limit)
+    }
+
+    pub fn client_rate_limit_key(&self) -> ClientRateLimitKey {
+        self.client_rate_limit_key.unwrap_or(ClientRateLimitKey::Ip)
+    }
+
+    pub fn client_api_key_header(&self) -> &str {
+        self.client_api_key_header.as_deref().unwrap_or("x-api-key")
+    }
+
+    pub fn method_weights(&self) -> &[String] {
+        self.method_weights.as_deref().unwrap_or_default()
+    }
+
+    pub fn trusted_proxies(&self) -> &[IpAddr] {
+        self.trusted_proxies.as_deref().unwrap_or_default()
+    }
+
+    pub fn graphql_max_complexity(&self) -> usize {
+        self.graphql_max_complexity.unwrap_or(1_000)
+    }
+
+    pub fn graphql_max_depth(&self) -> usize {
+        self.graphql_max_depth.unwrap_or(10)
+    }
+
+    pub fn admin_http_addr(&self) -> Option<SocketAddr> {
+        self.admin_http_addr
+    }
+
+    pub fn txpool_namespace_enabled(&self) -> bool {
+        self.txpool_namespace_enabled.unwrap_or(false)
+    }
+
+    pub fn prover_namespace_enabled(&self) -> bool {
+        self.prover_namespace_enabled.unwrap_or(false)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
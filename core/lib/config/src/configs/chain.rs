@@ -1,7 +1,7 @@
 use std::{str::FromStr, time::Duration};
 
 use micro_basic_types::{network::Network, Address, L2ChainId};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
 pub struct ChainConfig {
@@ -93,6 +93,15 @@ pub struct StateKeeperConfig {
 
     /// Number of keys that is processed by enum_index migration in State Keeper each L1 batch.
     pub enum_index_migration_chunk_size: Option<usize>,
+
+    /// Upper bound for the L1 batch commit deadline (in ms) if the provers are lagging behind. If set,
+    /// `block_commit_deadline_ms` is stretched up to this value depending on the number of unproven L1 batches
+    /// and the share of timed out prover assignments, so that fewer, larger batches are produced.
+    pub prover_backlog_max_block_commit_deadline_ms: Option<u64>,
+    /// Number of unproven L1 batches at which the commit deadline starts being stretched. Default is 5.
+    pub prover_backlog_threshold: Option<u32>,
+    /// Number of unproven L1 batches at which the commit deadline reaches its upper bound. Default is 50.
+    pub prover_backlog_saturation: Option<u32>,
 }
 
 impl StateKeeperConfig {
@@ -121,12 +130,23 @@ impl StateKeeperConfig {
             virtual_blocks_per_miniblock: 1,
             upload_witness_inputs_to_gcs: false,
             enum_index_migration_chunk_size: None,
+            prover_backlog_max_block_commit_deadline_ms: None,
+            prover_backlog_threshold: None,
+            prover_backlog_saturation: None,
         }
     }
 
     pub fn enum_index_migration_chunk_size(&self) -> usize {
         self.enum_index_migration_chunk_size.unwrap_or(1_000)
     }
+
+    pub fn prover_backlog_threshold(&self) -> u32 {
+        self.prover_backlog_threshold.unwrap_or(5)
+    }
+
+    pub fn prover_backlog_saturation(&self) -> u32 {
+        self.prover_backlog_saturation.unwrap_or(50)
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq)]
@@ -167,9 +187,45 @@ pub struct MempoolConfig {
     pub stuck_tx_timeout: u64,
     pub remove_stuck_txs: bool,
     pub delay_interval: u64,
+    /// Policy used to order L2 transactions from different accounts.
+    #[serde(default)]
+    pub ordering: MempoolOrdering,
+    /// Minimum increase (in percent) of both `max_fee_per_gas` and `max_priority_fee_per_gas` required
+    /// for a transaction to replace a pending transaction with the same initiator and nonce.
+    /// If not specified, 10% is used.
+    pub replacement_fee_bump_percent: Option<u64>,
+    /// Policy used to evict L2 transactions once the mempool capacity is reached.
+    #[serde(default)]
+    pub eviction_policy: MempoolEvictionPolicy,
+    /// Maximum number of pending L2 transactions a single account may have. Not limited if not specified.
+    pub max_pending_txs_per_account: Option<u32>,
+    /// Maximum distance between the nonce of a submitted transaction and the next nonce
+    /// after the pending transactions of the account. Not limited if not specified.
+    pub max_nonce_gap_per_account: Option<u32>,
+    /// Maximum number of transactions that can be submitted from a single IP address per minute.
+    /// Not limited if not specified.
+    pub max_txs_per_ip_per_minute: Option<u32>,
 }
 
 impl MempoolConfig {
+    /// Creates a config object suitable for use in unit tests.
+    pub fn for_tests() -> Self {
+        Self {
+            sync_interval_ms: 10,
+            sync_batch_size: 1_000,
+            capacity: 1_000_000,
+            stuck_tx_timeout: 86_400,
+            remove_stuck_txs: true,
+            delay_interval: 100,
+            ordering: MempoolOrdering::Fifo,
+            replacement_fee_bump_percent: None,
+            eviction_policy: MempoolEvictionPolicy::Disabled,
+            max_pending_txs_per_account: None,
+            max_nonce_gap_per_account: None,
+            max_txs_per_ip_per_minute: None,
+        }
+    }
+
     pub fn sync_interval(&self) -> Duration {
         Duration::from_millis(self.sync_interval_ms)
     }
@@ -181,4 +237,34 @@ impl MempoolConfig {
     pub fn delay_interval(&self) -> Duration {
         Duration::from_millis(self.delay_interval)
     }
+
+    pub fn replacement_fee_bump_percent(&self) -> u64 {
+        self.replacement_fee_bump_percent.unwrap_or(10)
+    }
+}
+
+/// Policy for ordering L2 transactions from different accounts in the mempool. Transactions from
+/// the same account are always executed in the nonce order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolOrdering {
+    /// Transactions are ordered by the time they were received.
+    #[default]
+    Fifo,
+    /// Transactions offering a higher effective tip (i.e., the priority fee capped by the excess of the max fee
+    /// over the base fee) go first; ties are broken by the receive time.
+    PriorityFee,
+}
+
+/// Policy for evicting L2 transactions once the mempool reaches its capacity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MempoolEvictionPolicy {
+    /// Transactions are not evicted; only accounts blocked by nonce gaps are purged.
+    #[default]
+    Disabled,
+    /// Transactions with the lowest max fee per gas are evicted first.
+    LowestFee,
+    /// Transactions received earliest are evicted first.
+    Oldest,
 }
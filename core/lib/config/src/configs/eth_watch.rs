@@ -11,6 +11,9 @@ pub struct ETHWatchConfig {
     /// How often we want to poll the Ethereum node.
     /// Value in milliseconds.
     pub eth_node_poll_interval: u64,
+    /// Number of L1 blocks before the deadline of a priority operation at which the operation
+    /// is reported as approaching its deadline. If not specified, a quarter of the expiration period is used.
+    pub priority_op_deadline_warning_blocks: Option<u64>,
 }
 
 impl ETHWatchConfig {
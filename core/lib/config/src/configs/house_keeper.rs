@@ -1,3 +1,5 @@
+use std::{convert::TryFrom, fmt, str::FromStr, time::Duration};
+
 use serde::Deserialize;
 
 /// Configuration for the house keeper.
@@ -16,4 +18,105 @@ pub struct HouseKeeperConfig {
     pub fri_prover_stats_reporting_interval_ms: u64,
     pub fri_proof_compressor_job_retrying_interval_ms: u64,
     pub fri_proof_compressor_stats_reporting_interval_ms: u64,
+    /// Interval between runs of the object store blobs cleaner. If not specified, blobs are never cleaned.
+    pub blobs_cleaning_interval_ms: Option<u64>,
+    /// If set, the blobs cleaner only reports which blobs would be removed without actually removing them.
+    #[serde(default)]
+    pub blobs_cleaning_dry_run: bool,
+    /// Retention policies for object store buckets, e.g. `proofs_fri:executed:7,prover_jobs_fri:proven:1`.
+    /// Buckets without a policy are never cleaned. L1 batches of FRI proofs (keyed by the prover job ID)
+    /// are resolved using the prover database. Policies are ignored if the object store doesn't support listing
+    /// blobs (e.g., the HTTP-backed store).
+    #[serde(default)]
+    pub blobs_retention_policies: Vec<BlobRetentionPolicy>,
+}
+
+/// Lifecycle stage of an L1 batch used as the reference point for blob retention.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum L1BatchRetentionStage {
+    /// The batch commitment is confirmed on L1.
+    Committed,
+    /// The batch proof is confirmed on L1.
+    Proven,
+    /// The batch is executed on L1.
+    Executed,
+}
+
+impl L1BatchRetentionStage {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Committed => "committed",
+            Self::Proven => "proven",
+            Self::Executed => "executed",
+        }
+    }
+}
+
+impl fmt::Display for L1BatchRetentionStage {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for L1BatchRetentionStage {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "committed" => Ok(Self::Committed),
+            "proven" => Ok(Self::Proven),
+            "executed" => Ok(Self::Executed),
+            _ => Err(format!(
+                "unknown L1 batch retention stage `{s}`; expected one of `committed`, `proven` or `executed`"
+            )),
+        }
+    }
+}
+
+/// Retention policy for blobs in a single object store bucket. Blobs belonging to an L1 batch
+/// are retained until the batch reaches `stage` on L1, plus `retention_days`.
+///
+/// Parsed from strings in the `{bucket}:{stage}:{retention_days}` format, e.g. `proofs_fri:executed:7`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct BlobRetentionPolicy {
+    /// Name of the object store bucket, e.g. `prover_jobs_fri`.
+    pub bucket: String,
+    pub stage: L1BatchRetentionStage,
+    pub retention_days: u32,
+}
+
+impl BlobRetentionPolicy {
+    pub fn retention_period(&self) -> Duration {
+        Duration::from_secs(u64::from(self.retention_days) * 24 * 3_600)
+    }
+}
+
+impl FromStr for BlobRetentionPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.trim().split(':').collect();
+        let [bucket, stage, retention_days] = parts.as_slice() else {
+            return Err(format!(
+                "invalid blob retention policy `{s}`; expected `{{bucket}}:{{stage}}:{{retention_days}}`"
+            ));
+        };
+        let retention_days = retention_days.parse().map_err(|err| {
+            format!("invalid retention days in blob retention policy `{s}`: {err}")
+        })?;
+        Ok(Self {
+            bucket: (*bucket).to_owned(),
+            stage: stage.parse()?,
+            retention_days,
+        })
+    }
+}
+
+impl TryFrom<String> for BlobRetentionPolicy {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
 }
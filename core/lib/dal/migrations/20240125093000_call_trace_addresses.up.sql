@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS call_trace_addresses (
+    address BYTEA NOT NULL,
+    miniblock_number BIGINT NOT NULL,
+    tx_hash BYTEA NOT NULL,
+    PRIMARY KEY (address, miniblock_number, tx_hash),
+    FOREIGN KEY (tx_hash) REFERENCES call_traces (tx_hash) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS call_trace_addresses_tx_hash_idx ON call_trace_addresses (tx_hash);
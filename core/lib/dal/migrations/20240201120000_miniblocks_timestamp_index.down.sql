@@ -0,0 +1 @@
+DROP INDEX IF EXISTS miniblocks_timestamp_idx;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS miniblocks_timestamp_idx ON miniblocks (timestamp);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS deny_list_audit_log;
+DROP TABLE IF EXISTS deny_list;
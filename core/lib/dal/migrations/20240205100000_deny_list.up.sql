@@ -0,0 +1,16 @@
+CREATE TABLE IF NOT EXISTS deny_list (
+    address BYTEA NOT NULL PRIMARY KEY,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS deny_list_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    address BYTEA NOT NULL,
+    action TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS deny_list_audit_log_address_idx ON deny_list_audit_log (address);
@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS transactions_rejected_updated_at_idx;
+
+ALTER TABLE transactions DROP COLUMN IF EXISTS rejection_code;
@@ -0,0 +1,4 @@
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS rejection_code VARCHAR;
+
+CREATE INDEX IF NOT EXISTS transactions_rejected_updated_at_idx
+    ON transactions (updated_at) WHERE rejection_code IS NOT NULL;
@@ -0,0 +1,9 @@
+CREATE TABLE IF NOT EXISTS protocol_upgrade_schedules (
+    protocol_version INT NOT NULL PRIMARY KEY REFERENCES protocol_versions (id) ON DELETE CASCADE,
+    activation_timestamp BIGINT,
+    activation_l1_batch_number BIGINT,
+    preflight_checked_at TIMESTAMP,
+    preflight_error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    updated_at TIMESTAMP NOT NULL
+);
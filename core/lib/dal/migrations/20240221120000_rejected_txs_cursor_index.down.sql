@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS transactions_rejected_updated_at_hash_idx;
+CREATE INDEX IF NOT EXISTS transactions_rejected_updated_at_idx
+    ON transactions (updated_at) WHERE rejection_code IS NOT NULL;
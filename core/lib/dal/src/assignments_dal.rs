@@ -1,16 +1,35 @@
 use std::{str::FromStr, time::Duration};
 
-use micro_types::{Address, L1BatchNumber, MiniblockNumber, H256};
+use micro_types::{l2::prover::Assignment, Address, L1BatchNumber, MiniblockNumber, H256};
+use sqlx::Row;
 use strum::{Display, EnumString};
 
 use crate::{
-    instrument::InstrumentExt, time_utils::pg_interval_from_duration, SqlxError, StorageProcessor,
+    instrument::InstrumentExt, models::storage_assignment::StorageAssignment,
+    time_utils::pg_interval_from_duration, SqlxError, StorageProcessor,
 };
+
+const ASSIGNMENT_COLUMNS: &str = "verification_address, l1_batch_number, miniblock_number, \
+    storage_index, batch_hash, status, tx_hash, created_at, updated_at";
+
 #[derive(Debug)]
 pub struct AssignmentsDal<'a, 'c> {
     pub(crate) storage: &'a mut StorageProcessor<'c>,
 }
 
+/// Aggregated statistics for assignments made within a certain time window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AssignmentStatistics {
+    /// Average time between assignment and proof submission for successfully proven batches.
+    pub avg_proving_time: Option<Duration>,
+    /// Number of distinct provers that picked or proved their assignments.
+    pub active_provers: u32,
+    /// Total number of assignments, excluding rolled back ones.
+    pub total_assignments: u64,
+    /// Number of assignments for which provers were or are about to be penalized.
+    pub penalized_assignments: u64,
+}
+
 #[derive(Debug, EnumString, Display)]
 pub enum ProverResultStatus {
     #[strum(serialize = "assigned_not_certified")]
@@ -290,6 +309,112 @@ impl AssignmentsDal<'_, '_> {
         Ok(())
     }
 
+    /// Returns all assignments for the specified L1 batch in the order they were made. A batch can have
+    /// several assignments, e.g. if the previously assigned prover was penalized.
+    pub async fn get_assignments_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Vec<Assignment>, SqlxError> {
+        let query = format!(
+            "SELECT {ASSIGNMENT_COLUMNS} FROM assignments WHERE l1_batch_number = $1 ORDER BY id"
+        );
+        let rows: Vec<StorageAssignment> = sqlx::query_as(&query)
+            .bind(l1_batch_number.0 as i64)
+            .instrument("get_assignments_for_l1_batch")
+            .with_arg("l1_batch_number", &l1_batch_number)
+            .fetch_all(self.storage.conn())
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns assignments for the specified prover, starting from the most recent ones.
+    pub async fn get_prover_assignments(
+        &mut self,
+        prover: Address,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Assignment>, SqlxError> {
+        let query = format!(
+            "SELECT {ASSIGNMENT_COLUMNS} FROM assignments WHERE verification_address = $1 \
+             ORDER BY id DESC OFFSET $2 LIMIT $3"
+        );
+        let rows: Vec<StorageAssignment> = sqlx::query_as(&query)
+            .bind(prover.as_bytes())
+            .bind(offset as i64)
+            .bind(limit as i64)
+            .instrument("get_prover_assignments")
+            .with_arg("prover", &prover)
+            .with_arg("offset", &offset)
+            .with_arg("limit", &limit)
+            .report_latency()
+            .fetch_all(self.storage.conn())
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns assignments for which provers were or are about to be penalized, starting from the most recent ones.
+    /// If `prover` is specified, only penalties for this prover are returned.
+    pub async fn get_penalties(
+        &mut self,
+        prover: Option<Address>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<Assignment>, SqlxError> {
+        let query = format!(
+            "SELECT {ASSIGNMENT_COLUMNS} FROM assignments \
+             WHERE status IN ('be_punished', 'failed') \
+                AND ($1::bytea IS NULL OR verification_address = $1) \
+             ORDER BY id DESC OFFSET $2 LIMIT $3"
+        );
+        let rows: Vec<StorageAssignment> = sqlx::query_as(&query)
+            .bind(prover.as_ref().map(Address::as_bytes))
+            .bind(offset as i64)
+            .bind(limit as i64)
+            .instrument("get_penalties")
+            .with_arg("prover", &prover)
+            .with_arg("offset", &offset)
+            .with_arg("limit", &limit)
+            .report_latency()
+            .fetch_all(self.storage.conn())
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns statistics for assignments made within the specified window before the current time.
+    pub async fn get_assignment_statistics(
+        &mut self,
+        window: Duration,
+    ) -> Result<AssignmentStatistics, SqlxError> {
+        let window = pg_interval_from_duration(window);
+        let row = sqlx::query(
+            "SELECT \
+                AVG(EXTRACT(EPOCH FROM updated_at - created_at)::FLOAT8) \
+                    FILTER (WHERE status = 'successful') AS avg_proving_time, \
+                COUNT(DISTINCT verification_address) \
+                    FILTER (WHERE status IN ('picked_by_prover', 'successful')) AS active_provers, \
+                COUNT(*) FILTER (WHERE status != 'rollbacked') AS total_assignments, \
+                COUNT(*) FILTER (WHERE status IN ('be_punished', 'failed')) AS penalized_assignments \
+             FROM assignments \
+             WHERE created_at > now() - $1::interval",
+        )
+        .bind(window)
+        .instrument("get_assignment_statistics")
+        .report_latency()
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        let avg_proving_time: Option<f64> = row.get("avg_proving_time");
+        let active_provers: i64 = row.get("active_provers");
+        let total_assignments: i64 = row.get("total_assignments");
+        let penalized_assignments: i64 = row.get("penalized_assignments");
+        Ok(AssignmentStatistics {
+            avg_proving_time: avg_proving_time.map(|secs| Duration::from_secs_f64(secs.max(0.0))),
+            active_provers: active_provers as u32,
+            total_assignments: total_assignments as u64,
+            penalized_assignments: penalized_assignments as u64,
+        })
+    }
+
     pub async fn get_max_mini_number(&mut self) -> Result<MiniblockNumber, sqlx::Error> {
         let number = sqlx::query!(
             "select max(miniblock_number) number from assignments where status != 'rollbacked'"
@@ -320,3 +445,132 @@ impl AssignmentsDal<'_, '_> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use micro_contracts::BaseSystemContractsHashes;
+    use micro_types::{
+        block::{BlockGasCount, L1BatchHeader},
+        l2::prover::AssignmentStatus,
+        ProtocolVersion, ProtocolVersionId,
+    };
+
+    use super::*;
+    use crate::ConnectionPool;
+
+    async fn insert_l1_batch(conn: &mut StorageProcessor<'_>, number: u32) {
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(number),
+            100,
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::default(),
+        );
+        header.is_finished = true;
+        conn.blocks_dal()
+            .insert_l1_batch(&header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn getting_assignments_and_penalties() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        insert_l1_batch(&mut conn, 1).await;
+        insert_l1_batch(&mut conn, 2).await;
+
+        let first_prover = Address::repeat_byte(1);
+        let second_prover = Address::repeat_byte(2);
+        let mut dal = conn.assignments_dal();
+        dal.insert_and_update_assignments(first_prover, L1BatchNumber(1), MiniblockNumber(1), 0)
+            .await
+            .unwrap();
+        dal.insert_and_update_assignments(second_prover, L1BatchNumber(2), MiniblockNumber(2), 1)
+            .await
+            .unwrap();
+        // Both assignments time out, and the first prover gets penalized.
+        dal.update_assigments_status_for_time(Duration::ZERO)
+            .await
+            .unwrap();
+        let penalty_tx_hash = H256::repeat_byte(1);
+        dal.update_assigments_status_by_punished(
+            0,
+            first_prover,
+            L1BatchNumber(1),
+            penalty_tx_hash,
+        )
+        .await
+        .unwrap();
+        dal.insert_and_update_assignments(second_prover, L1BatchNumber(1), MiniblockNumber(3), 2)
+            .await
+            .unwrap();
+
+        let assignments = dal
+            .get_assignments_for_l1_batch(L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(assignments.len(), 2);
+        assert_eq!(assignments[0].prover, first_prover);
+        assert_eq!(assignments[0].status, AssignmentStatus::Failed);
+        assert_eq!(assignments[0].penalty_tx_hash, Some(penalty_tx_hash));
+        assert_eq!(assignments[1].prover, second_prover);
+        assert_eq!(assignments[1].miniblock_number, MiniblockNumber(3));
+        assert_eq!(assignments[1].storage_index, 2);
+        assert_eq!(
+            assignments[1].status,
+            AssignmentStatus::AssignedNotCertified
+        );
+        assert_eq!(assignments[1].penalty_tx_hash, None);
+
+        let assignments = dal
+            .get_prover_assignments(second_prover, 0, 10)
+            .await
+            .unwrap();
+        let batch_numbers: Vec<_> = assignments.iter().map(|a| a.l1_batch_number).collect();
+        assert_eq!(batch_numbers, [L1BatchNumber(1), L1BatchNumber(2)]);
+        let assignments = dal
+            .get_prover_assignments(second_prover, 1, 1)
+            .await
+            .unwrap();
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].l1_batch_number, L1BatchNumber(2));
+
+        let penalties = dal.get_penalties(None, 0, 10).await.unwrap();
+        let penalized: Vec<_> = penalties
+            .iter()
+            .map(|a| (a.prover, a.l1_batch_number, a.status))
+            .collect();
+        assert_eq!(
+            penalized,
+            [
+                (
+                    second_prover,
+                    L1BatchNumber(2),
+                    AssignmentStatus::BePunished
+                ),
+                (first_prover, L1BatchNumber(1), AssignmentStatus::Failed),
+            ]
+        );
+        let penalties = dal.get_penalties(Some(first_prover), 0, 10).await.unwrap();
+        assert_eq!(penalties.len(), 1);
+        assert!(penalties[0].status.is_penalty());
+
+        let stats = dal
+            .get_assignment_statistics(Duration::from_secs(3_600))
+            .await
+            .unwrap();
+        assert_eq!(
+            stats,
+            AssignmentStatistics {
+                avg_proving_time: None,
+                active_provers: 0,
+                total_assignments: 3,
+                penalized_assignments: 2,
+            }
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::{
     collections::HashMap,
     convert::{Into, TryInto},
     ops,
+    time::Duration,
 };
 
 use anyhow::Context as _;
@@ -19,6 +20,7 @@ pub use crate::models::storage_sync::ConsensusBlockFields;
 use crate::{
     instrument::InstrumentExt,
     models::storage_block::{StorageL1Batch, StorageL1BatchHeader, StorageMiniblockHeader},
+    time_utils::pg_interval_from_duration,
     StorageProcessor,
 };
 
@@ -765,6 +767,35 @@ impl BlocksDal<'_, '_> {
         .map(|row| L1BatchNumber(row.number as u32)))
     }
 
+    /// Returns the number of the last L1 batch for which an Ethereum tx of the specified type
+    /// was confirmed at least `min_age` ago.
+    pub async fn get_last_l1_batch_with_eth_tx_confirmed_before(
+        &mut self,
+        tx_type: AggregatedActionType,
+        min_age: Duration,
+    ) -> sqlx::Result<Option<L1BatchNumber>> {
+        let tx_id_column = match tx_type {
+            AggregatedActionType::Commit => "eth_commit_tx_id",
+            AggregatedActionType::PublishProofOnchain => "eth_prove_tx_id",
+            AggregatedActionType::Execute => "eth_execute_tx_id",
+        };
+        let min_age = pg_interval_from_duration(min_age);
+        let row = sqlx::query(&format!(
+            "SELECT MAX(number) AS number FROM l1_batches \
+            INNER JOIN eth_txs_history ON (l1_batches.{tx_id_column} = eth_txs_history.eth_tx_id) \
+            WHERE eth_txs_history.confirmed_at IS NOT NULL \
+                AND eth_txs_history.confirmed_at < now() - $1::interval"
+        ))
+        .bind(min_age)
+        .instrument("get_last_l1_batch_with_eth_tx_confirmed_before")
+        .with_arg("tx_type", &tx_type)
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        let number: Option<i64> = row.get("number");
+        Ok(number.map(|number| L1BatchNumber(number as u32)))
+    }
+
     /// This method returns batches that are confirmed on L1. That is, it doesn't wait for the proofs to be generated.
     pub async fn get_ready_for_dummy_proof_l1_batches(
         &mut self,
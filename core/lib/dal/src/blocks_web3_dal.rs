@@ -1,7 +1,13 @@
+use std::{ops, str::FromStr};
+
 use bigdecimal::BigDecimal;
 use micro_system_constants::EMPTY_UNCLES_HASH;
 use micro_types::{
-    api,
+    api::{
+        self,
+        finality::{L1BatchLifecycle, ProverAssignment},
+        trace::TraceLocation,
+    },
     ethabi::Address,
     l2_to_l1_log::L2ToL1Log,
     vm_trace::Call,
@@ -10,7 +16,11 @@ use micro_types::{
     Bytes, L1BatchNumber, L2ChainId, MiniblockNumber, H160, H2048, H256, U256,
 };
 use micro_utils::bigdecimal_to_u256;
-use sqlx::Row;
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, NaiveDateTime, Utc},
+    Row,
+};
 
 use crate::{
     instrument::InstrumentExt,
@@ -26,6 +36,139 @@ use crate::{
 
 const BLOCK_GAS_LIMIT: u32 = system_params::VM_INITIAL_FRAME_ERGS;
 
+const CALL_TRACES_WITH_LOCATION_SELECT: &str = "SELECT call_traces.tx_hash, call_traces.call_trace, \
+        transactions.index_in_block, transactions.miniblock_number, miniblocks.hash AS miniblock_hash \
+    FROM call_traces \
+    INNER JOIN transactions ON transactions.hash = call_traces.tx_hash \
+    INNER JOIN miniblocks ON miniblocks.number = transactions.miniblock_number";
+
+const WEB3_BLOCK_SELECT_SQL: &str = "miniblocks.hash as block_hash, \
+        miniblocks.number, \
+        miniblocks.l1_batch_number, \
+        miniblocks.timestamp, \
+        miniblocks.base_fee_per_gas, \
+        prev_miniblock.hash as parent_hash, \
+        l1_batches.timestamp as l1_batch_timestamp, \
+        transactions.gas_limit as gas_limit, \
+        transactions.refunded_gas as refunded_gas";
+
+const WEB3_BLOCK_FROM_SQL: &str = "miniblocks \
+    LEFT JOIN miniblocks prev_miniblock ON prev_miniblock.number = miniblocks.number - 1 \
+    LEFT JOIN l1_batches ON l1_batches.number = miniblocks.l1_batch_number \
+    LEFT JOIN transactions ON transactions.miniblock_number = miniblocks.number";
+
+/// Extracts the block header (without transactions) from a row selected with [`WEB3_BLOCK_SELECT_SQL`].
+fn web3_block_from_row(db_row: &PgRow) -> api::Block<api::TransactionVariant> {
+    let hash = db_row
+        .try_get("block_hash")
+        .map_or_else(|_| H256::zero(), H256::from_slice);
+    let number = U64::from(db_row.get::<i64, &str>("number"));
+    let l1_batch_number = db_row
+        .try_get::<i64, &str>("l1_batch_number")
+        .map(U64::from)
+        .ok();
+    let l1_batch_timestamp = db_row
+        .try_get::<i64, &str>("l1_batch_timestamp")
+        .map(U256::from)
+        .ok();
+    let parent_hash = db_row
+        .try_get("parent_hash")
+        .map_or_else(|_| H256::zero(), H256::from_slice);
+    let base_fee_per_gas = db_row.get::<BigDecimal, &str>("base_fee_per_gas");
+
+    api::Block {
+        hash,
+        parent_hash,
+        uncles_hash: EMPTY_UNCLES_HASH,
+        number,
+        l1_batch_number,
+        gas_limit: BLOCK_GAS_LIMIT.into(),
+        base_fee_per_gas: bigdecimal_to_u256(base_fee_per_gas),
+        timestamp: db_row.get::<i64, &str>("timestamp").into(),
+        l1_batch_timestamp,
+        // TODO: include logs
+        ..api::Block::default()
+    }
+}
+
+/// Returns gas used by the transaction from a row selected with [`WEB3_BLOCK_SELECT_SQL`].
+fn tx_gas_used_from_row(db_row: &PgRow) -> U256 {
+    let tx_gas_limit = bigdecimal_to_u256(db_row.get::<BigDecimal, &str>("gas_limit"));
+    let tx_refunded_gas = U256::from((db_row.get::<i64, &str>("refunded_gas")) as u32);
+    tx_gas_limit - tx_refunded_gas
+}
+
+fn located_call_trace(row: PgRow) -> (TraceLocation, Call) {
+    let tx_hash: Vec<u8> = row.get("tx_hash");
+    let location = TraceLocation {
+        block_number: MiniblockNumber(row.get::<i64, _>("miniblock_number") as u32),
+        block_hash: H256::from_slice(row.get("miniblock_hash")),
+        transaction_hash: H256::from_slice(&tx_hash),
+        transaction_position: row.get::<i32, _>("index_in_block") as u32,
+    };
+    let call_trace = CallTrace {
+        tx_hash,
+        call_trace: row.get("call_trace"),
+    };
+    (location, call_trace.into())
+}
+
+/// Selects L1 batch lifecycle data. Prover assignments are taken from the `assignments` table; if there are
+/// several assignments for a batch, the successful one is preferred, then the most recent one.
+const L1_BATCH_LIFECYCLE_SELECT: &str = "SELECT l1_batches.number, l1_batches.timestamp, \
+        l1_batches.hash AS root_hash, \
+        commit_tx.tx_hash AS commit_tx_hash, commit_tx.confirmed_at AS committed_at, \
+        prove_tx.tx_hash AS prove_tx_hash, prove_tx.confirmed_at AS proven_at, \
+        execute_tx.tx_hash AS execute_tx_hash, execute_tx.confirmed_at AS executed_at, \
+        assignment.verification_address AS prover_address, assignment.status AS assignment_status, \
+        assignment.created_at AS assigned_at, assignment.updated_at AS assignment_updated_at \
+    FROM l1_batches \
+    LEFT JOIN eth_txs_history AS commit_tx \
+        ON (l1_batches.eth_commit_tx_id = commit_tx.eth_tx_id AND commit_tx.confirmed_at IS NOT NULL) \
+    LEFT JOIN eth_txs_history AS prove_tx \
+        ON (l1_batches.eth_prove_tx_id = prove_tx.eth_tx_id AND prove_tx.confirmed_at IS NOT NULL) \
+    LEFT JOIN eth_txs_history AS execute_tx \
+        ON (l1_batches.eth_execute_tx_id = execute_tx.eth_tx_id AND execute_tx.confirmed_at IS NOT NULL) \
+    LEFT JOIN LATERAL ( \
+        SELECT verification_address, status, created_at, updated_at FROM assignments \
+        WHERE assignments.l1_batch_number = l1_batches.number AND status != 'rollbacked' \
+        ORDER BY (status = 'successful') DESC, id DESC \
+        LIMIT 1 \
+    ) AS assignment ON TRUE";
+
+fn l1_batch_lifecycle(row: PgRow) -> L1BatchLifecycle {
+    let eth_tx_hash = |column: &str| {
+        row.get::<Option<String>, _>(column)
+            .map(|hash| H256::from_str(&hash).expect("Incorrect Ethereum tx hash"))
+    };
+    let timestamp = |column: &str| {
+        row.get::<Option<NaiveDateTime>, _>(column)
+            .map(|time| DateTime::<Utc>::from_naive_utc_and_offset(time, Utc))
+    };
+
+    let prover_address: Option<Vec<u8>> = row.get("prover_address");
+    let prover_assignment = prover_address.map(|address| ProverAssignment {
+        prover: Address::from_slice(&address),
+        status: row.get("assignment_status"),
+        assigned_at: timestamp("assigned_at").unwrap(),
+        updated_at: timestamp("assignment_updated_at").unwrap(),
+    });
+    L1BatchLifecycle {
+        number: L1BatchNumber(row.get::<i64, _>("number") as u32),
+        timestamp: row.get::<i64, _>("timestamp") as u64,
+        root_hash: row
+            .get::<Option<Vec<u8>>, _>("root_hash")
+            .map(|hash| H256::from_slice(&hash)),
+        commit_tx_hash: eth_tx_hash("commit_tx_hash"),
+        committed_at: timestamp("committed_at"),
+        prove_tx_hash: eth_tx_hash("prove_tx_hash"),
+        proven_at: timestamp("proven_at"),
+        execute_tx_hash: eth_tx_hash("execute_tx_hash"),
+        executed_at: timestamp("executed_at"),
+        prover_assignment,
+    }
+}
+
 #[derive(Debug)]
 pub struct BlocksWeb3Dal<'a, 'c> {
     pub(crate) storage: &'a mut StorageProcessor<'c>,
@@ -67,26 +210,9 @@ impl BlocksWeb3Dal<'_, '_> {
         };
 
         let query = format!(
-            "SELECT
-                miniblocks.hash as block_hash,
-                miniblocks.number,
-                miniblocks.l1_batch_number,
-                miniblocks.timestamp,
-                miniblocks.base_fee_per_gas,
-                prev_miniblock.hash as parent_hash,
-                l1_batches.timestamp as l1_batch_timestamp,
-                transactions.gas_limit as gas_limit,
-                transactions.refunded_gas as refunded_gas,
-                {}
-            FROM miniblocks
-            LEFT JOIN miniblocks prev_miniblock
-                ON prev_miniblock.number = miniblocks.number - 1
-            LEFT JOIN l1_batches
-                ON l1_batches.number = miniblocks.l1_batch_number
-            LEFT JOIN transactions
-                ON transactions.miniblock_number = miniblocks.number
+            "SELECT {WEB3_BLOCK_SELECT_SQL}, {transactions_sql}
+            FROM {WEB3_BLOCK_FROM_SQL}
             WHERE {}",
-            transactions_sql,
             web3_block_where_sql(block_id, 1)
         );
 
@@ -94,45 +220,11 @@ impl BlocksWeb3Dal<'_, '_> {
         let rows = query.fetch_all(self.storage.conn()).await?.into_iter();
 
         let block = rows.fold(None, |prev_block, db_row| {
-            let mut block = prev_block.unwrap_or_else(|| {
-                // This code will be only executed for the first row in the DB response.
-                // All other rows will only be used to extract relevant transactions.
-                let hash = db_row
-                    .try_get("block_hash")
-                    .map_or_else(|_| H256::zero(), H256::from_slice);
-                let number = U64::from(db_row.get::<i64, &str>("number"));
-                let l1_batch_number = db_row
-                    .try_get::<i64, &str>("l1_batch_number")
-                    .map(U64::from)
-                    .ok();
-                let l1_batch_timestamp = db_row
-                    .try_get::<i64, &str>("l1_batch_timestamp")
-                    .map(U256::from)
-                    .ok();
-                let parent_hash = db_row
-                    .try_get("parent_hash")
-                    .map_or_else(|_| H256::zero(), H256::from_slice);
-                let base_fee_per_gas = db_row.get::<BigDecimal, &str>("base_fee_per_gas");
-
-                api::Block {
-                    hash,
-                    parent_hash,
-                    uncles_hash: EMPTY_UNCLES_HASH,
-                    number,
-                    l1_batch_number,
-                    gas_limit: BLOCK_GAS_LIMIT.into(),
-                    base_fee_per_gas: bigdecimal_to_u256(base_fee_per_gas),
-                    timestamp: db_row.get::<i64, &str>("timestamp").into(),
-                    l1_batch_timestamp,
-                    // TODO: include logs
-                    ..api::Block::default()
-                }
-            });
+            // The block header is only extracted from the first row in the DB response.
+            // All other rows will only be used to extract relevant transactions.
+            let mut block = prev_block.unwrap_or_else(|| web3_block_from_row(&db_row));
             if db_row.try_get::<&[u8], &str>("tx_hash").is_ok() {
-                let tx_gas_limit = bigdecimal_to_u256(db_row.get::<BigDecimal, &str>("gas_limit"));
-                let tx_refunded_gas = U256::from((db_row.get::<i64, &str>("refunded_gas")) as u32);
-
-                block.gas_used += tx_gas_limit - tx_refunded_gas;
+                block.gas_used += tx_gas_used_from_row(&db_row);
                 let tx = if include_full_transactions {
                     let tx = extract_web3_transaction(db_row, chain_id);
                     api::TransactionVariant::Full(tx)
@@ -146,6 +238,45 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok(block)
     }
 
+    /// Returns blocks with the specified numbers together with hashes of their transactions, ordered by the block number.
+    /// Non-existing blocks are skipped.
+    pub async fn get_blocks(
+        &mut self,
+        numbers: &[MiniblockNumber],
+    ) -> sqlx::Result<Vec<api::Block<api::TransactionVariant>>> {
+        let query = format!(
+            "SELECT {WEB3_BLOCK_SELECT_SQL}, transactions.hash as tx_hash
+            FROM {WEB3_BLOCK_FROM_SQL}
+            WHERE miniblocks.number = ANY($1)
+            ORDER BY miniblocks.number, transactions.index_in_block"
+        );
+        let numbers_sql: Vec<_> = numbers.iter().map(|number| i64::from(number.0)).collect();
+        let rows = sqlx::query(&query)
+            .bind(&numbers_sql)
+            .instrument("get_web3_blocks")
+            .with_arg("numbers.len", &numbers.len())
+            .report_latency()
+            .fetch_all(self.storage.conn())
+            .await?;
+
+        let mut blocks: Vec<api::Block<api::TransactionVariant>> = vec![];
+        for db_row in rows {
+            let number = U64::from(db_row.get::<i64, &str>("number"));
+            if blocks.last().map_or(true, |block| block.number != number) {
+                blocks.push(web3_block_from_row(&db_row));
+            }
+            let block = blocks.last_mut().unwrap();
+            if let Ok(tx_hash) = db_row.try_get::<&[u8], &str>("tx_hash") {
+                block.gas_used += tx_gas_used_from_row(&db_row);
+                let tx_hash = H256::from_slice(tx_hash);
+                block
+                    .transactions
+                    .push(api::TransactionVariant::Hash(tx_hash));
+            }
+        }
+        Ok(blocks)
+    }
+
     pub async fn get_block_tx_count(
         &mut self,
         block_id: api::BlockId,
@@ -341,6 +472,31 @@ impl BlocksWeb3Dal<'_, '_> {
         Ok(MiniblockNumber(number as u32))
     }
 
+    /// Returns the number of transactions included into miniblocks with timestamps no earlier than
+    /// `since_timestamp`, together with the total gas used by these transactions.
+    pub async fn get_tx_throughput_since(
+        &mut self,
+        since_timestamp: u64,
+    ) -> sqlx::Result<(u64, U256)> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS tx_count, \
+                COALESCE(SUM(transactions.gas_limit - transactions.refunded_gas), 0) AS gas_used \
+             FROM transactions \
+             JOIN miniblocks ON miniblocks.number = transactions.miniblock_number \
+             WHERE miniblocks.timestamp >= $1",
+        )
+        .bind(since_timestamp as i64)
+        .instrument("get_tx_throughput_since")
+        .with_arg("since_timestamp", &since_timestamp)
+        .report_latency()
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        let tx_count: i64 = row.get("tx_count");
+        let gas_used: BigDecimal = row.get("gas_used");
+        Ok((tx_count as u64, bigdecimal_to_u256(gas_used)))
+    }
+
     pub async fn get_miniblock_range_of_l1_batch(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -403,6 +559,60 @@ impl BlocksWeb3Dal<'_, '_> {
         .collect())
     }
 
+    /// Returns call traces of transactions in the specified miniblock range, together with the transaction locations,
+    /// ordered by miniblock number and index in the miniblock. If `addresses` are non-empty, only transactions
+    /// with at least one of these addresses participating in the call trace are returned.
+    pub async fn get_call_traces_in_range(
+        &mut self,
+        miniblocks: ops::RangeInclusive<MiniblockNumber>,
+        addresses: &[Address],
+        limit: Option<usize>,
+    ) -> sqlx::Result<Vec<(TraceLocation, Call)>> {
+        let addresses: Vec<_> = addresses
+            .iter()
+            .map(|address| address.as_bytes().to_vec())
+            .collect();
+        let rows = sqlx::query(&format!(
+            "{CALL_TRACES_WITH_LOCATION_SELECT} \
+            WHERE transactions.miniblock_number BETWEEN $1 AND $2 \
+                AND (cardinality($3::bytea[]) = 0 OR call_traces.tx_hash IN ( \
+                    SELECT tx_hash FROM call_trace_addresses \
+                    WHERE address = ANY($3) AND miniblock_number BETWEEN $1 AND $2 \
+                )) \
+            ORDER BY transactions.miniblock_number, transactions.index_in_block \
+            LIMIT $4"
+        ))
+        .bind(miniblocks.start().0 as i64)
+        .bind(miniblocks.end().0 as i64)
+        .bind(&addresses)
+        .bind(limit.map(|limit| limit as i64))
+        .instrument("get_call_traces_in_range")
+        .with_arg("miniblocks", &miniblocks)
+        .with_arg("addresses.len", &addresses.len())
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows.into_iter().map(located_call_trace).collect())
+    }
+
+    /// Returns the call trace of the specified transaction together with the transaction location.
+    pub async fn get_call_trace_with_location(
+        &mut self,
+        tx_hash: H256,
+    ) -> sqlx::Result<Option<(TraceLocation, Call)>> {
+        let row = sqlx::query(&format!(
+            "{CALL_TRACES_WITH_LOCATION_SELECT} WHERE call_traces.tx_hash = $1"
+        ))
+        .bind(tx_hash.as_bytes())
+        .instrument("get_call_trace_with_location")
+        .with_arg("tx_hash", &tx_hash)
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.map(located_call_trace))
+    }
+
     /// Returns `base_fee_per_gas` for miniblock range [min(newest_block - block_count + 1, 0), newest_block]
     /// in descending order of miniblock numbers.
     pub async fn get_fee_history(
@@ -474,6 +684,27 @@ impl BlocksWeb3Dal<'_, '_> {
         }
     }
 
+    /// Returns lifecycle information for sealed L1 batches in the specified range, ordered by the batch number.
+    pub async fn get_l1_batches_lifecycle(
+        &mut self,
+        l1_batches: ops::RangeInclusive<L1BatchNumber>,
+    ) -> sqlx::Result<Vec<L1BatchLifecycle>> {
+        let rows = sqlx::query(&format!(
+            "{L1_BATCH_LIFECYCLE_SELECT} \
+            WHERE l1_batches.number BETWEEN $1 AND $2 AND l1_batches.is_finished = TRUE \
+            ORDER BY l1_batches.number"
+        ))
+        .bind(l1_batches.start().0 as i64)
+        .bind(l1_batches.end().0 as i64)
+        .instrument("get_l1_batches_lifecycle")
+        .with_arg("l1_batches", &l1_batches)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows.into_iter().map(l1_batch_lifecycle).collect())
+    }
+
     pub async fn get_l1_batch_details(
         &mut self,
         l1_batch_number: L1BatchNumber,
@@ -518,13 +749,20 @@ impl BlocksWeb3Dal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
+    use assert_matches::assert_matches;
+    use micro_contracts::BaseSystemContractsHashes;
     use micro_types::{
-        block::{MiniblockHasher, MiniblockHeader},
+        api::finality::L1BatchStage,
+        block::{BlockGasCount, L1BatchHeader, MiniblockHasher, MiniblockHeader},
+        fee::TransactionExecutionMetrics,
         MiniblockNumber, ProtocolVersion, ProtocolVersionId,
     };
 
     use super::*;
-    use crate::{tests::create_miniblock_header, ConnectionPool};
+    use crate::{
+        tests::{create_miniblock_header, mock_execution_result, mock_l2_transaction},
+        ConnectionPool,
+    };
 
     #[tokio::test]
     async fn getting_web3_block_and_tx_count() {
@@ -585,6 +823,63 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn getting_multiple_web3_blocks() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.blocks_dal()
+            .delete_miniblocks(MiniblockNumber(0))
+            .await
+            .unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(0))
+            .await
+            .unwrap();
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        conn.transactions_dal()
+            .insert_transaction_l2(tx.clone(), TransactionExecutionMetrics::default())
+            .await;
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(1))
+            .await
+            .unwrap();
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_miniblock(
+                MiniblockNumber(1),
+                &[mock_execution_result(tx)],
+                U256::from(1),
+            )
+            .await;
+
+        let numbers = [MiniblockNumber(2), MiniblockNumber(1), MiniblockNumber(0)];
+        let blocks = conn.blocks_web3_dal().get_blocks(&numbers).await.unwrap();
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0].number, U64::zero());
+        assert!(blocks[0].transactions.is_empty());
+        assert_eq!(blocks[1].number, U64::one());
+        assert_eq!(blocks[1].parent_hash, blocks[0].hash);
+        assert_matches!(
+            blocks[1].transactions.as_slice(),
+            [api::TransactionVariant::Hash(hash)] if *hash == tx_hash
+        );
+
+        let block = conn
+            .blocks_web3_dal()
+            .get_block_by_web3_block_id(
+                api::BlockId::Number(api::BlockNumber::Number(1.into())),
+                false,
+                L2ChainId::from(270),
+            )
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(block, blocks[1]);
+    }
+
     #[tokio::test]
     async fn resolving_earliest_block_id() {
         let connection_pool = ConnectionPool::test_pool().await;
@@ -689,4 +984,175 @@ mod tests {
             .await;
         assert_eq!(miniblock_number.unwrap(), None);
     }
+
+    #[tokio::test]
+    async fn getting_call_traces_by_address() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.blocks_dal()
+            .delete_miniblocks(MiniblockNumber(0))
+            .await
+            .unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(0))
+            .await
+            .unwrap();
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        conn.transactions_dal()
+            .insert_transaction_l2(tx.clone(), TransactionExecutionMetrics::default())
+            .await;
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(1))
+            .await
+            .unwrap();
+
+        let caller = Address::repeat_byte(1);
+        let callee = Address::repeat_byte(2);
+        let call = Call {
+            from: caller,
+            to: callee,
+            ..Call::default()
+        };
+        let mut tx_result = mock_execution_result(tx);
+        tx_result.call_traces = vec![call.clone()];
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_miniblock(MiniblockNumber(1), &[tx_result], U256::from(1))
+            .await;
+
+        let miniblocks = MiniblockNumber(0)..=MiniblockNumber(1);
+        for addresses in [&[] as &[_], &[callee], &[caller, Address::repeat_byte(3)]] {
+            let call_traces = conn
+                .blocks_web3_dal()
+                .get_call_traces_in_range(miniblocks.clone(), addresses, None)
+                .await
+                .unwrap();
+            assert_eq!(call_traces.len(), 1, "{addresses:?}");
+            let (location, call_trace) = &call_traces[0];
+            assert_eq!(location.block_number, MiniblockNumber(1));
+            assert_eq!(location.transaction_hash, tx_hash);
+            assert_eq!(location.transaction_position, 0);
+            assert_eq!(call_trace.calls, [call.clone()]);
+        }
+
+        let call_traces = conn
+            .blocks_web3_dal()
+            .get_call_traces_in_range(miniblocks.clone(), &[Address::repeat_byte(3)], None)
+            .await
+            .unwrap();
+        assert!(call_traces.is_empty());
+        let call_traces = conn
+            .blocks_web3_dal()
+            .get_call_traces_in_range(MiniblockNumber(0)..=MiniblockNumber(0), &[], None)
+            .await
+            .unwrap();
+        assert!(call_traces.is_empty());
+
+        let (location, _) = conn
+            .blocks_web3_dal()
+            .get_call_trace_with_location(tx_hash)
+            .await
+            .unwrap()
+            .expect("no call trace");
+        assert_eq!(location.block_number, MiniblockNumber(1));
+        assert_eq!(location.block_hash, create_miniblock_header(1).hash);
+    }
+
+    #[tokio::test]
+    async fn getting_tx_throughput() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.blocks_dal()
+            .delete_miniblocks(MiniblockNumber(0))
+            .await
+            .unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        conn.blocks_dal()
+            .insert_miniblock(&create_miniblock_header(0))
+            .await
+            .unwrap();
+        let tx = mock_l2_transaction();
+        let gas_limit = tx.common_data.fee.gas_limit;
+        conn.transactions_dal()
+            .insert_transaction_l2(tx.clone(), TransactionExecutionMetrics::default())
+            .await;
+        let header = MiniblockHeader {
+            timestamp: 100,
+            l2_tx_count: 1,
+            ..create_miniblock_header(1)
+        };
+        conn.blocks_dal().insert_miniblock(&header).await.unwrap();
+        let mut tx_result = mock_execution_result(tx);
+        tx_result.refunded_gas = 100_000;
+        conn.transactions_dal()
+            .mark_txs_as_executed_in_miniblock(MiniblockNumber(1), &[tx_result], U256::from(1))
+            .await;
+
+        let expected_gas_used = gas_limit - U256::from(100_000);
+        for since_timestamp in [0, 100] {
+            let throughput = conn
+                .blocks_web3_dal()
+                .get_tx_throughput_since(since_timestamp)
+                .await
+                .unwrap();
+            assert_eq!(throughput, (1, expected_gas_used), "{since_timestamp}");
+        }
+        let throughput = conn
+            .blocks_web3_dal()
+            .get_tx_throughput_since(101)
+            .await
+            .unwrap();
+        assert_eq!(throughput, (0, U256::zero()));
+    }
+
+    #[tokio::test]
+    async fn getting_l1_batch_lifecycle() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.blocks_dal()
+            .delete_l1_batches(L1BatchNumber(0))
+            .await
+            .unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            100,
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::default(),
+        );
+        header.is_finished = true;
+        conn.blocks_dal()
+            .insert_l1_batch(&header, &[], BlockGasCount::default(), &[], &[])
+            .await
+            .unwrap();
+
+        let prover = Address::repeat_byte(1);
+        conn.assignments_dal()
+            .insert_and_update_assignments(prover, L1BatchNumber(1), MiniblockNumber(1), 0)
+            .await
+            .unwrap();
+
+        let batches = conn
+            .blocks_web3_dal()
+            .get_l1_batches_lifecycle(L1BatchNumber(1)..=L1BatchNumber(2))
+            .await
+            .unwrap();
+        assert_eq!(batches.len(), 1);
+        let batch = &batches[0];
+        assert_eq!(batch.number, L1BatchNumber(1));
+        assert_eq!(batch.timestamp, 100);
+        assert_eq!(batch.stage(), L1BatchStage::Sealed);
+        assert_eq!(batch.commit_tx_hash, None);
+        let assignment = batch.prover_assignment.as_ref().unwrap();
+        assert_eq!(assignment.prover, prover);
+        assert_eq!(assignment.status, "assigned_not_certified");
+    }
 }
@@ -0,0 +1,198 @@
+use micro_types::{
+    deny_list::{DenyListAction, DenyListAuditRecord, DenyListEntry},
+    Address,
+};
+
+use crate::{
+    instrument::InstrumentExt,
+    models::storage_deny_list::{StorageDenyListAuditRecord, StorageDenyListEntry},
+    StorageProcessor,
+};
+
+/// DAL for the deny-list of accounts and contracts. All changes to the deny-list
+/// are recorded in the audit log.
+#[derive(Debug)]
+pub struct DenyListDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl DenyListDal<'_, '_> {
+    /// Adds an address to the deny-list, or updates the reason if the address is already listed.
+    pub async fn add_address(&mut self, address: Address, reason: &str) -> sqlx::Result<()> {
+        let mut transaction = self.storage.start_transaction().await?;
+        sqlx::query(
+            "INSERT INTO deny_list (address, reason, created_at, updated_at) \
+             VALUES ($1, $2, NOW(), NOW()) \
+             ON CONFLICT (address) DO UPDATE SET reason = $2, updated_at = NOW()",
+        )
+        .bind(address.as_bytes())
+        .bind(reason)
+        .instrument("add_deny_list_address")
+        .with_arg("address", &address)
+        .execute(transaction.conn())
+        .await?;
+
+        DenyListDal {
+            storage: &mut transaction,
+        }
+        .insert_audit_record(address, DenyListAction::Added, reason)
+        .await?;
+        transaction.commit().await
+    }
+
+    /// Removes an address from the deny-list. Returns `false` if the address was not listed.
+    pub async fn remove_address(&mut self, address: Address, reason: &str) -> sqlx::Result<bool> {
+        let mut transaction = self.storage.start_transaction().await?;
+        let result = sqlx::query("DELETE FROM deny_list WHERE address = $1")
+            .bind(address.as_bytes())
+            .instrument("remove_deny_list_address")
+            .with_arg("address", &address)
+            .execute(transaction.conn())
+            .await?;
+        if result.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        DenyListDal {
+            storage: &mut transaction,
+        }
+        .insert_audit_record(address, DenyListAction::Removed, reason)
+        .await?;
+        transaction.commit().await?;
+        Ok(true)
+    }
+
+    async fn insert_audit_record(
+        &mut self,
+        address: Address,
+        action: DenyListAction,
+        reason: &str,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            "INSERT INTO deny_list_audit_log (address, action, reason, created_at) \
+             VALUES ($1, $2, $3, NOW())",
+        )
+        .bind(address.as_bytes())
+        .bind(action.as_str())
+        .bind(reason)
+        .instrument("insert_deny_list_audit_record")
+        .with_arg("address", &address)
+        .with_arg("action", &action)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns all addresses on the deny-list ordered by the time they were added.
+    pub async fn get_entries(&mut self) -> sqlx::Result<Vec<DenyListEntry>> {
+        let rows = sqlx::query_as::<_, StorageDenyListEntry>(
+            "SELECT address, reason, created_at FROM deny_list ORDER BY created_at, address",
+        )
+        .instrument("get_deny_list_entries")
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Returns audit log records, newest first, optionally filtered by address.
+    pub async fn get_audit_log(
+        &mut self,
+        address: Option<Address>,
+        offset: usize,
+        limit: usize,
+    ) -> sqlx::Result<Vec<DenyListAuditRecord>> {
+        let rows = sqlx::query_as::<_, StorageDenyListAuditRecord>(
+            "SELECT id, address, action, reason, created_at FROM deny_list_audit_log \
+             WHERE $1::bytea IS NULL OR address = $1 \
+             ORDER BY id DESC \
+             OFFSET $2 LIMIT $3",
+        )
+        .bind(address.as_ref().map(Address::as_bytes))
+        .bind(offset as i64)
+        .bind(limit as i64)
+        .instrument("get_deny_list_audit_log")
+        .with_arg("address", &address)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ConnectionPool;
+
+    #[tokio::test]
+    async fn managing_deny_list() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let address = Address::repeat_byte(1);
+        let other_address = Address::repeat_byte(2);
+
+        conn.deny_list_dal()
+            .add_address(address, "compromised")
+            .await
+            .unwrap();
+        conn.deny_list_dal()
+            .add_address(other_address, "sanctioned")
+            .await
+            .unwrap();
+        conn.deny_list_dal()
+            .add_address(address, "compromised key")
+            .await
+            .unwrap();
+
+        let entries = conn.deny_list_dal().get_entries().await.unwrap();
+        assert_eq!(entries.len(), 2);
+        let entry = entries.iter().find(|entry| entry.address == address);
+        assert_eq!(entry.unwrap().reason, "compromised key");
+
+        let removed = conn
+            .deny_list_dal()
+            .remove_address(other_address, "false positive")
+            .await
+            .unwrap();
+        assert!(removed);
+        let removed = conn
+            .deny_list_dal()
+            .remove_address(other_address, "false positive")
+            .await
+            .unwrap();
+        assert!(!removed);
+
+        let entries = conn.deny_list_dal().get_entries().await.unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].address, address);
+
+        let log = conn
+            .deny_list_dal()
+            .get_audit_log(None, 0, 10)
+            .await
+            .unwrap();
+        let actions: Vec<_> = log
+            .iter()
+            .map(|record| (record.address, record.action))
+            .collect();
+        assert_eq!(
+            actions,
+            [
+                (other_address, DenyListAction::Removed),
+                (address, DenyListAction::Added),
+                (other_address, DenyListAction::Added),
+                (address, DenyListAction::Added),
+            ]
+        );
+
+        let log = conn
+            .deny_list_dal()
+            .get_audit_log(Some(other_address), 0, 1)
+            .await
+            .unwrap();
+        assert_eq!(log.len(), 1);
+        assert_eq!(log[0].action, DenyListAction::Removed);
+        assert_eq!(log[0].reason, "false positive");
+    }
+}
@@ -426,15 +426,14 @@ impl EthSenderDal<'_, '_> {
         Ok(())
     }
 
-    pub async fn get_latest_proof_time(&mut self) -> i64 {
+    pub async fn get_latest_proof_time(&mut self) -> sqlx::Result<i64> {
         let result= sqlx::query!(
                 r#"SELECT updated_at as "updated_at?"  from eth_txs WHERE confirmed_eth_tx_history_id IS NOT NULL and tx_type = 'PublishProofBlocksOnchain'  ORDER BY id DESC limit 1"#
             )
             .fetch_optional(self.storage.conn())
-            .await
-            .unwrap()
-            .map(|record| record.updated_at.unwrap().timestamp())
-            .unwrap_or(0);
-        result
+            .await?
+            .and_then(|record| record.updated_at)
+            .map_or(0, |updated_at| updated_at.timestamp());
+        Ok(result)
     }
 }
@@ -192,6 +192,24 @@ impl EventsDal<'_, '_> {
         .fetch_all(self.storage.conn())
         .await
     }
+
+    pub(crate) async fn l2_to_l1_logs_in_miniblock(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+    ) -> Result<Vec<StorageL2ToL1Log>, SqlxError> {
+        sqlx::query_as(
+            "SELECT \
+                miniblock_number, log_index_in_miniblock, log_index_in_tx, tx_hash, \
+                Null::bytea as \"block_hash\", Null::bigint as \"l1_batch_number\", \
+                shard_id, is_service, tx_index_in_miniblock, tx_index_in_l1_batch, sender, key, value \
+            FROM l2_to_l1_logs \
+            WHERE miniblock_number = $1 \
+            ORDER BY log_index_in_miniblock ASC",
+        )
+        .bind(miniblock_number.0 as i64)
+        .fetch_all(self.storage.conn())
+        .await
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,5 @@
 use micro_types::{
-    api::{GetLogsFilter, Log},
+    api::{GetLogsFilter, Log, LogCursor},
     Address, MiniblockNumber, H256,
 };
 use sqlx::Row;
@@ -59,14 +59,29 @@ impl EventsWeb3Dal<'_, '_> {
     }
 
     /// Returns logs for given filter.
-    #[allow(clippy::type_complexity)]
     pub async fn get_logs(
         &mut self,
         filter: GetLogsFilter,
         limit: usize,
+    ) -> Result<Vec<Log>, SqlxError> {
+        self.get_logs_after(filter, None, limit).await
+    }
+
+    /// Returns logs for given filter located strictly after `cursor`.
+    pub async fn get_logs_after(
+        &mut self,
+        filter: GetLogsFilter,
+        cursor: Option<LogCursor>,
+        limit: usize,
     ) -> Result<Vec<Log>, SqlxError> {
         {
-            let (where_sql, arg_index) = self.build_get_logs_where_clause(&filter);
+            let (mut where_sql, arg_index) = self.build_get_logs_where_clause(&filter);
+            if let Some(cursor) = cursor {
+                where_sql += &format!(
+                    " AND ((miniblock_number, event_index_in_block) > ({}, {}))",
+                    cursor.block_number.0 as i64, cursor.log_index as i64
+                );
+            }
 
             let query = format!(
                 r#"
@@ -103,6 +118,7 @@ impl EventsWeb3Dal<'_, '_> {
                 .instrument("get_logs")
                 .report_latency()
                 .with_arg("filter", &filter)
+                .with_arg("cursor", &cursor)
                 .with_arg("limit", &limit)
                 .fetch_all(self.storage.conn())
                 .await?;
@@ -167,10 +183,12 @@ impl EventsWeb3Dal<'_, '_> {
 
 #[cfg(test)]
 mod tests {
-    use micro_types::{Address, H256};
+    use micro_types::{
+        tx::IncludedTxLocation, Address, L1BatchNumber, ProtocolVersion, VmEvent, H256,
+    };
 
     use super::*;
-    use crate::connection::ConnectionPool;
+    use crate::{connection::ConnectionPool, tests::create_miniblock_header};
 
     #[tokio::test]
     async fn test_build_get_logs_where_clause() {
@@ -192,4 +210,74 @@ mod tests {
         assert_eq!(actual_sql, expected_sql);
         assert_eq!(actual_arg_index, expected_arg_index);
     }
+
+    #[tokio::test]
+    async fn paginating_logs_with_cursor() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.events_dal().rollback_events(MiniblockNumber(0)).await;
+        conn.blocks_dal()
+            .delete_miniblocks(MiniblockNumber(0))
+            .await
+            .unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+
+        let events: Vec<_> = (0..3_u8)
+            .map(|i| VmEvent {
+                location: (L1BatchNumber(1), u32::from(i)),
+                address: Address::repeat_byte(i % 2),
+                indexed_topics: vec![],
+                value: vec![i],
+            })
+            .collect();
+        for number in 1..=2 {
+            conn.blocks_dal()
+                .insert_miniblock(&create_miniblock_header(number))
+                .await
+                .unwrap();
+            let location = IncludedTxLocation {
+                tx_hash: H256::from_low_u64_be(number.into()),
+                tx_index_in_miniblock: 0,
+                tx_initiator_address: Address::default(),
+            };
+            conn.events_dal()
+                .save_events(
+                    MiniblockNumber(number),
+                    &[(location, events.iter().collect())],
+                )
+                .await;
+        }
+
+        let filter = GetLogsFilter {
+            from_block: MiniblockNumber(1),
+            to_block: MiniblockNumber(2),
+            addresses: vec![Address::repeat_byte(0)],
+            topics: vec![],
+        };
+        let mut cursor = None;
+        let mut positions = vec![];
+        loop {
+            let logs = conn
+                .events_web3_dal()
+                .get_logs_after(filter.clone(), cursor, 3)
+                .await
+                .unwrap();
+            let Some(last_log) = logs.last() else {
+                break;
+            };
+            cursor = Some(LogCursor {
+                block_number: MiniblockNumber(last_log.block_number.unwrap().as_u32()),
+                log_index: last_log.log_index.unwrap().as_u32(),
+            });
+            positions.extend(logs.iter().map(|log| {
+                (
+                    log.block_number.unwrap().as_u32(),
+                    log.log_index.unwrap().as_u32(),
+                )
+            }));
+        }
+        assert_eq!(positions, [(1, 0), (1, 2), (2, 0), (2, 2)]);
+    }
 }
@@ -408,6 +408,29 @@ impl FriProverDal<'_, '_> {
         .map(|row| row.id as u32)
     }
 
+    /// Returns L1 batch numbers for the specified prover job IDs. Jobs that do not exist
+    /// are not included into the returned map.
+    pub async fn get_l1_batch_numbers_for_jobs(
+        &mut self,
+        job_ids: &[u32],
+    ) -> sqlx::Result<HashMap<u32, L1BatchNumber>> {
+        let job_ids: Vec<_> = job_ids.iter().map(|&id| i64::from(id)).collect();
+        let rows = sqlx::query!(
+            "SELECT id, l1_batch_number FROM prover_jobs_fri \
+             WHERE id = ANY($1)",
+            &job_ids,
+        )
+        .instrument("get_l1_batch_numbers_for_jobs")
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| (row.id as u32, L1BatchNumber(row.l1_batch_number as u32)))
+            .collect())
+    }
+
     pub async fn get_job_status_details(
         &mut self,
         offset: u32,
@@ -10,13 +10,15 @@ use crate::{
     basic_witness_input_producer_dal::BasicWitnessInputProducerDal, blocks_dal::BlocksDal,
     blocks_web3_dal::BlocksWeb3Dal, connection::holder::ConnectionHolder,
     consensus_dal::ConsensusDal, contract_verification_dal::ContractVerificationDal,
-    eth_sender_dal::EthSenderDal, events_dal::EventsDal, events_web3_dal::EventsWeb3Dal,
-    fri_gpu_prover_queue_dal::FriGpuProverQueueDal,
+    deny_list_dal::DenyListDal, eth_sender_dal::EthSenderDal, events_dal::EventsDal,
+    events_web3_dal::EventsWeb3Dal, fri_gpu_prover_queue_dal::FriGpuProverQueueDal,
     fri_proof_compressor_dal::FriProofCompressorDal,
     fri_protocol_versions_dal::FriProtocolVersionsDal, fri_prover_dal::FriProverDal,
     fri_scheduler_dependency_tracker_dal::FriSchedulerDependencyTrackerDal,
     fri_witness_generator_dal::FriWitnessGeneratorDal, gpu_prover_queue_dal::GpuProverQueueDal,
-    proof_generation_dal::ProofGenerationDal, protocol_versions_dal::ProtocolVersionsDal,
+    proof_generation_dal::ProofGenerationDal,
+    protocol_upgrade_schedules_dal::ProtocolUpgradeSchedulesDal,
+    protocol_versions_dal::ProtocolVersionsDal,
     protocol_versions_web3_dal::ProtocolVersionsWeb3Dal, prover_dal::ProverDal,
     snapshots_creator_dal::SnapshotsCreatorDal, snapshots_dal::SnapshotsDal,
     storage_dal::StorageDal, storage_logs_dal::StorageLogsDal,
@@ -37,6 +39,7 @@ pub mod blocks_web3_dal;
 pub mod connection;
 pub mod consensus_dal;
 pub mod contract_verification_dal;
+pub mod deny_list_dal;
 pub mod eth_sender_dal;
 pub mod events_dal;
 pub mod events_web3_dal;
@@ -52,6 +55,7 @@ mod instrument;
 mod metrics;
 mod models;
 pub mod proof_generation_dal;
+pub mod protocol_upgrade_schedules_dal;
 pub mod protocol_versions_dal;
 pub mod protocol_versions_web3_dal;
 pub mod prover_dal;
@@ -263,4 +267,12 @@ impl<'a> StorageProcessor<'a> {
     pub fn application_monitor_dal(&mut self) -> ApplicationMonitorDal<'_, 'a> {
         ApplicationMonitorDal { storage: self }
     }
+
+    pub fn deny_list_dal(&mut self) -> DenyListDal<'_, 'a> {
+        DenyListDal { storage: self }
+    }
+
+    pub fn protocol_upgrade_schedules_dal(&mut self) -> ProtocolUpgradeSchedulesDal<'_, 'a> {
+        ProtocolUpgradeSchedulesDal { storage: self }
+    }
 }
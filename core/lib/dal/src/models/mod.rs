@@ -1,10 +1,13 @@
 mod proto;
 pub mod storage_app_monitor;
+pub mod storage_assignment;
 pub mod storage_block;
+pub mod storage_deny_list;
 pub mod storage_eth_tx;
 pub mod storage_event;
 pub mod storage_fee_monitor;
 pub mod storage_log;
+pub mod storage_protocol_upgrade_schedule;
 pub mod storage_protocol_version;
 pub mod storage_prover_job_info;
 pub mod storage_sync;
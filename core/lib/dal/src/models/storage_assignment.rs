@@ -0,0 +1,53 @@
+use std::str::FromStr;
+
+use micro_types::{
+    l2::prover::{Assignment, AssignmentStatus},
+    Address, L1BatchNumber, MiniblockNumber, H256,
+};
+use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
+
+use crate::assignments_dal::ProverResultStatus;
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StorageAssignment {
+    pub verification_address: Vec<u8>,
+    pub l1_batch_number: i64,
+    pub miniblock_number: i64,
+    pub storage_index: i64,
+    pub batch_hash: Option<Vec<u8>>,
+    pub status: String,
+    pub tx_hash: Option<Vec<u8>>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<ProverResultStatus> for AssignmentStatus {
+    fn from(status: ProverResultStatus) -> Self {
+        match status {
+            ProverResultStatus::AssignedNotCertified => Self::AssignedNotCertified,
+            ProverResultStatus::PickedByProver => Self::PickedByProver,
+            ProverResultStatus::Successful => Self::Successful,
+            ProverResultStatus::BePunished => Self::BePunished,
+            ProverResultStatus::Failed => Self::Failed,
+            ProverResultStatus::Rollbacked => Self::Rollbacked,
+        }
+    }
+}
+
+impl From<StorageAssignment> for Assignment {
+    fn from(row: StorageAssignment) -> Self {
+        let status = ProverResultStatus::from_str(&row.status)
+            .unwrap_or_else(|_| panic!("Unknown assignment status: {}", row.status));
+        Self {
+            prover: Address::from_slice(&row.verification_address),
+            l1_batch_number: L1BatchNumber(row.l1_batch_number as u32),
+            miniblock_number: MiniblockNumber(row.miniblock_number as u32),
+            storage_index: row.storage_index as u64,
+            batch_hash: row.batch_hash.map(|hash| H256::from_slice(&hash)),
+            status: status.into(),
+            penalty_tx_hash: row.tx_hash.map(|hash| H256::from_slice(&hash)),
+            assigned_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+            updated_at: DateTime::<Utc>::from_naive_utc_and_offset(row.updated_at, Utc),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::str::FromStr;
+
+use micro_types::{
+    deny_list::{DenyListAction, DenyListAuditRecord, DenyListEntry},
+    Address,
+};
+use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StorageDenyListEntry {
+    pub address: Vec<u8>,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<StorageDenyListEntry> for DenyListEntry {
+    fn from(row: StorageDenyListEntry) -> Self {
+        Self {
+            address: Address::from_slice(&row.address),
+            reason: row.reason,
+            added_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StorageDenyListAuditRecord {
+    pub id: i64,
+    pub address: Vec<u8>,
+    pub action: String,
+    pub reason: String,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<StorageDenyListAuditRecord> for DenyListAuditRecord {
+    fn from(row: StorageDenyListAuditRecord) -> Self {
+        let action = DenyListAction::from_str(&row.action).unwrap_or_else(|err| panic!("{err}"));
+        Self {
+            id: row.id as u64,
+            address: Address::from_slice(&row.address),
+            action,
+            reason: row.reason,
+            created_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+        }
+    }
+}
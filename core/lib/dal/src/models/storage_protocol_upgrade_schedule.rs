@@ -0,0 +1,31 @@
+use micro_types::{api::protocol_upgrades::ProtocolUpgradeSchedule, L1BatchNumber};
+use sqlx::types::chrono::{DateTime, NaiveDateTime, Utc};
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StorageProtocolUpgradeSchedule {
+    pub protocol_version: i32,
+    pub l1_activation_timestamp: i64,
+    pub activation_timestamp: Option<i64>,
+    pub activation_l1_batch_number: Option<i64>,
+    pub preflight_checked_at: Option<NaiveDateTime>,
+    pub preflight_error: Option<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<StorageProtocolUpgradeSchedule> for ProtocolUpgradeSchedule {
+    fn from(row: StorageProtocolUpgradeSchedule) -> Self {
+        Self {
+            protocol_version: row.protocol_version as u16,
+            l1_activation_timestamp: row.l1_activation_timestamp as u64,
+            activation_timestamp: row.activation_timestamp.map(|timestamp| timestamp as u64),
+            activation_l1_batch_number: row
+                .activation_l1_batch_number
+                .map(|number| L1BatchNumber(number as u32)),
+            preflight_checked_at: row
+                .preflight_checked_at
+                .map(|checked_at| DateTime::<Utc>::from_naive_utc_and_offset(checked_at, Utc)),
+            preflight_error: row.preflight_error,
+            created_at: DateTime::<Utc>::from_naive_utc_and_offset(row.created_at, Utc),
+        }
+    }
+}
@@ -15,7 +15,7 @@ use micro_types::{
     Nonce, PackedEthSignature, PriorityOpId, Transaction, EIP_1559_TX_TYPE, EIP_2930_TX_TYPE,
     EIP_712_TX_TYPE, H160, H256, PRIORITY_OPERATION_L2_TX_TYPE, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
-use micro_utils::bigdecimal_to_u256;
+use micro_utils::{bigdecimal_to_u256, h256_to_account_address};
 use serde::{Deserialize, Serialize};
 use sqlx::{
     postgres::PgRow,
@@ -70,6 +70,9 @@ pub struct StorageTransaction {
 
     pub created_at: NaiveDateTime,
     pub updated_at: NaiveDateTime,
+
+    pub rejection_code: Option<String>,
+    pub l1_deadline_block: Option<i64>,
 }
 
 impl From<StorageTransaction> for L1TxCommonData {
@@ -141,7 +144,8 @@ impl From<StorageTransaction> for L1TxCommonData {
                 .gas_per_pubdata_limit
                 .map(bigdecimal_to_u256)
                 .unwrap_or_else(|| U256::from(1u32)),
-            deadline_block: 0,
+            // Using 0 for old transactions that did not have the deadline stored
+            deadline_block: tx.l1_deadline_block.unwrap_or_default() as u64,
             eth_hash: Default::default(),
             eth_block: tx.l1_block_number.unwrap_or_default() as u64,
             canonical_tx_hash,
@@ -475,6 +479,84 @@ impl From<StorageTransactionDetails> for TransactionDetails {
             eth_commit_tx_hash,
             eth_prove_tx_hash,
             eth_execute_tx_hash,
+            rejection: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct StorageTransactionReceipt {
+    pub tx_hash: Vec<u8>,
+    pub index_in_block: Option<i32>,
+    pub l1_batch_tx_index: Option<i32>,
+    pub block_number: Option<i64>,
+    pub error: Option<String>,
+    pub effective_gas_price: Option<BigDecimal>,
+    pub initiator_address: Vec<u8>,
+    pub transfer_to: Option<serde_json::Value>,
+    pub execute_contract_address: Option<serde_json::Value>,
+    pub tx_format: Option<i32>,
+    pub refunded_gas: i64,
+    pub gas_limit: Option<BigDecimal>,
+    pub block_hash: Option<Vec<u8>>,
+    pub l1_batch_number: Option<i64>,
+    pub contract_address: Option<Vec<u8>>,
+}
+
+impl From<StorageTransactionReceipt> for api::TransactionReceipt {
+    fn from(db_row: StorageTransactionReceipt) -> Self {
+        let status = match (db_row.block_number, db_row.error) {
+            (_, Some(_)) => Some(U64::from(0)),
+            (Some(_), None) => Some(U64::from(1)),
+            // tx not executed yet
+            _ => None,
+        };
+        let tx_type = db_row.tx_format.map(U64::from).unwrap_or_default();
+        let transaction_index = db_row.index_in_block.map(U64::from).unwrap_or_default();
+
+        let block_hash = db_row.block_hash.map(|bytes| H256::from_slice(&bytes));
+        api::TransactionReceipt {
+            transaction_hash: H256::from_slice(&db_row.tx_hash),
+            transaction_index,
+            block_hash,
+            block_number: db_row.block_number.map(U64::from),
+            l1_batch_tx_index: db_row.l1_batch_tx_index.map(U64::from),
+            l1_batch_number: db_row.l1_batch_number.map(U64::from),
+            from: H160::from_slice(&db_row.initiator_address),
+            to: db_row
+                .transfer_to
+                .or(db_row.execute_contract_address)
+                .map(|addr| {
+                    serde_json::from_value::<Address>(addr)
+                        .expect("invalid address value in the database")
+                })
+                // For better compatibility with various clients, we never return null.
+                .or_else(|| Some(Address::default())),
+            cumulative_gas_used: Default::default(), // TODO: Should be actually calculated (SMA-1183).
+            gas_used: {
+                let refunded_gas: U256 = db_row.refunded_gas.into();
+                db_row.gas_limit.map(|val| {
+                    let gas_limit = bigdecimal_to_u256(val);
+                    gas_limit - refunded_gas
+                })
+            },
+            effective_gas_price: Some(
+                db_row
+                    .effective_gas_price
+                    .map(bigdecimal_to_u256)
+                    .unwrap_or_default(),
+            ),
+            contract_address: db_row
+                .contract_address
+                .map(|addr| h256_to_account_address(&H256::from_slice(&addr))),
+            logs: vec![],
+            l2_to_l1_logs: vec![],
+            status,
+            root: block_hash,
+            logs_bloom: Default::default(),
+            // Even though the Rust SDK recommends us to supply "None" for legacy transactions
+            // we always supply some number anyway to have the same behaviour as most popular RPCs
+            transaction_type: Some(tx_type),
         }
     }
 }
@@ -1,9 +1,12 @@
 use std::time::Duration;
 
 use micro_types::L1BatchNumber;
+use sqlx::Row;
 use strum::{Display, EnumString};
 
-use crate::{time_utils::pg_interval_from_duration, SqlxError, StorageProcessor};
+use crate::{
+    instrument::InstrumentExt, time_utils::pg_interval_from_duration, SqlxError, StorageProcessor,
+};
 
 #[derive(Debug)]
 pub struct ProofGenerationDal<'a, 'c> {
@@ -140,4 +143,20 @@ impl ProofGenerationDal<'_, '_> {
 
         result
     }
+
+    /// Returns the number of L1 batches for which proofs are not generated yet.
+    pub async fn get_unproven_l1_batch_count(&mut self) -> Result<u64, SqlxError> {
+        let row = sqlx::query(
+            "SELECT COUNT(*) AS count \
+             FROM proof_generation_details \
+             WHERE status NOT IN ('generated', 'skipped')",
+        )
+        .instrument("get_unproven_l1_batch_count")
+        .report_latency()
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        let count: i64 = row.get("count");
+        Ok(count as u64)
+    }
 }
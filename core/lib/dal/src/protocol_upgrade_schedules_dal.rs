@@ -0,0 +1,315 @@
+use micro_types::{
+    api::protocol_upgrades::ProtocolUpgradeSchedule, L1BatchNumber, ProtocolVersionId,
+};
+use sqlx::Row;
+
+use crate::{
+    instrument::InstrumentExt,
+    models::storage_protocol_upgrade_schedule::StorageProtocolUpgradeSchedule, StorageProcessor,
+};
+
+/// Outcome of [`ProtocolUpgradeSchedulesDal::set_activation()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpgradeActivationUpdate {
+    Updated,
+    /// The protocol version is unknown or is not pending.
+    NotPending,
+    /// The requested activation timestamp is later than the timestamp at which the upgrade is activated on L1.
+    /// L1 batches committed after the L1 activation must use the upgraded protocol, so such a schedule is rejected.
+    AfterL1Activation {
+        l1_activation_timestamp: u64,
+    },
+}
+
+/// DAL for activation schedules of protocol upgrades. An upgrade is pending if its protocol version
+/// is newer than the version used by the latest sealed miniblock.
+#[derive(Debug)]
+pub struct ProtocolUpgradeSchedulesDal<'a, 'c> {
+    pub(crate) storage: &'a mut StorageProcessor<'c>,
+}
+
+impl ProtocolUpgradeSchedulesDal<'_, '_> {
+    /// Creates schedules for pending upgrades that do not have them yet. Returns the raw protocol versions
+    /// of the newly recorded upgrades.
+    pub async fn record_pending_upgrades(&mut self) -> sqlx::Result<Vec<u16>> {
+        let rows = sqlx::query(
+            "INSERT INTO protocol_upgrade_schedules (protocol_version, created_at, updated_at) \
+             SELECT id, NOW(), NOW() FROM protocol_versions \
+             WHERE id > COALESCE((SELECT protocol_version FROM miniblocks ORDER BY number DESC LIMIT 1), -1) \
+             ON CONFLICT (protocol_version) DO NOTHING \
+             RETURNING protocol_version",
+        )
+        .instrument("record_pending_protocol_upgrades")
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let mut versions: Vec<_> = rows
+            .into_iter()
+            .map(|row| row.get::<i32, _>("protocol_version") as u16)
+            .collect();
+        versions.sort_unstable();
+        Ok(versions)
+    }
+
+    /// Returns schedules of pending upgrades ordered by protocol version.
+    pub async fn get_pending_upgrades(&mut self) -> sqlx::Result<Vec<ProtocolUpgradeSchedule>> {
+        let rows = sqlx::query_as::<_, StorageProtocolUpgradeSchedule>(
+            "SELECT protocol_upgrade_schedules.protocol_version, \
+                protocol_versions.timestamp AS l1_activation_timestamp, \
+                protocol_upgrade_schedules.activation_timestamp, \
+                protocol_upgrade_schedules.activation_l1_batch_number, \
+                protocol_upgrade_schedules.preflight_checked_at, \
+                protocol_upgrade_schedules.preflight_error, \
+                protocol_upgrade_schedules.created_at \
+             FROM protocol_upgrade_schedules \
+             JOIN protocol_versions ON protocol_versions.id = protocol_upgrade_schedules.protocol_version \
+             WHERE protocol_upgrade_schedules.protocol_version > \
+                COALESCE((SELECT protocol_version FROM miniblocks ORDER BY number DESC LIMIT 1), -1) \
+             ORDER BY protocol_upgrade_schedules.protocol_version",
+        )
+        .instrument("get_pending_protocol_upgrades")
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Sets the activation conditions for a pending upgrade; `None` values lift the corresponding condition.
+    /// The activation timestamp cannot be later than the timestamp at which the upgrade is activated on L1.
+    pub async fn set_activation(
+        &mut self,
+        protocol_version: u16,
+        activation_timestamp: Option<u64>,
+        activation_l1_batch_number: Option<L1BatchNumber>,
+    ) -> sqlx::Result<UpgradeActivationUpdate> {
+        let row = sqlx::query(
+            "SELECT timestamp FROM protocol_versions \
+             WHERE id = $1 \
+                AND id > COALESCE((SELECT protocol_version FROM miniblocks ORDER BY number DESC LIMIT 1), -1)",
+        )
+        .bind(i32::from(protocol_version))
+        .instrument("get_pending_protocol_version_timestamp")
+        .with_arg("protocol_version", &protocol_version)
+        .fetch_optional(self.storage.conn())
+        .await?;
+        let Some(row) = row else {
+            return Ok(UpgradeActivationUpdate::NotPending);
+        };
+        let l1_activation_timestamp = row.get::<i64, _>("timestamp") as u64;
+        if activation_timestamp.map_or(false, |timestamp| timestamp > l1_activation_timestamp) {
+            return Ok(UpgradeActivationUpdate::AfterL1Activation {
+                l1_activation_timestamp,
+            });
+        }
+
+        let result = sqlx::query(
+            "INSERT INTO protocol_upgrade_schedules \
+                (protocol_version, activation_timestamp, activation_l1_batch_number, created_at, updated_at) \
+             SELECT id, $2, $3, NOW(), NOW() FROM protocol_versions \
+             WHERE id = $1 \
+                AND id > COALESCE((SELECT protocol_version FROM miniblocks ORDER BY number DESC LIMIT 1), -1) \
+             ON CONFLICT (protocol_version) DO UPDATE \
+             SET activation_timestamp = $2, activation_l1_batch_number = $3, updated_at = NOW()",
+        )
+        .bind(i32::from(protocol_version))
+        .bind(activation_timestamp.map(|timestamp| timestamp as i64))
+        .bind(activation_l1_batch_number.map(|number| i64::from(number.0)))
+        .instrument("set_protocol_upgrade_activation")
+        .with_arg("protocol_version", &protocol_version)
+        .with_arg("activation_timestamp", &activation_timestamp)
+        .with_arg("activation_l1_batch_number", &activation_l1_batch_number)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(if result.rows_affected() > 0 {
+            UpgradeActivationUpdate::Updated
+        } else {
+            UpgradeActivationUpdate::NotPending
+        })
+    }
+
+    /// Saves the outcome of pre-flight checks for an upgrade. `error` is `None` if all checks have passed.
+    pub async fn save_preflight_result(
+        &mut self,
+        protocol_version: u16,
+        error: Option<&str>,
+    ) -> sqlx::Result<()> {
+        sqlx::query(
+            "UPDATE protocol_upgrade_schedules \
+             SET preflight_checked_at = NOW(), preflight_error = $2, updated_at = NOW() \
+             WHERE protocol_version = $1",
+        )
+        .bind(i32::from(protocol_version))
+        .bind(error)
+        .instrument("save_protocol_upgrade_preflight_result")
+        .with_arg("protocol_version", &protocol_version)
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Returns the newest protocol version that can be used by an L1 batch with the specified timestamp and number.
+    ///
+    /// Upgrades are activated in order, so a pending upgrade that is not ready (including an upgrade that has
+    /// not been recorded yet) blocks all newer versions.
+    pub async fn activatable_version(
+        &mut self,
+        timestamp: u64,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<Option<ProtocolVersionId>> {
+        let row = sqlx::query(
+            "SELECT MAX(id) AS id FROM protocol_versions \
+             WHERE timestamp <= $1 AND id < COALESCE(( \
+                SELECT MIN(protocol_versions.id) FROM protocol_versions \
+                LEFT JOIN protocol_upgrade_schedules \
+                    ON protocol_upgrade_schedules.protocol_version = protocol_versions.id \
+                WHERE protocol_versions.id > COALESCE((SELECT protocol_version FROM miniblocks ORDER BY number DESC LIMIT 1), -1) \
+                    AND ( \
+                        protocol_upgrade_schedules.protocol_version IS NULL \
+                        OR protocol_upgrade_schedules.preflight_checked_at IS NULL \
+                        OR protocol_upgrade_schedules.preflight_error IS NOT NULL \
+                        OR protocol_upgrade_schedules.activation_timestamp > $1 \
+                        OR protocol_upgrade_schedules.activation_l1_batch_number > $2 \
+                    ) \
+             ), 2147483647)",
+        )
+        .bind(timestamp as i64)
+        .bind(i64::from(l1_batch_number.0))
+        .instrument("get_activatable_protocol_version")
+        .with_arg("timestamp", &timestamp)
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .report_latency()
+        .fetch_one(self.storage.conn())
+        .await?;
+
+        let id: Option<i32> = row.get("id");
+        Ok(id.map(|id| (id as u16).try_into().unwrap()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use micro_types::{
+        api::protocol_upgrades::ProtocolUpgradeStage, protocol_version::ProtocolVersion,
+    };
+
+    use super::*;
+    use crate::{tests::create_miniblock_header, ConnectionPool};
+
+    #[tokio::test]
+    async fn scheduling_protocol_upgrades() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut conn = pool.access_storage().await.unwrap();
+        let current_version = ProtocolVersionId::latest();
+        let next_version = ProtocolVersionId::next();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion {
+                id: current_version,
+                ..ProtocolVersion::default()
+            })
+            .await;
+        let mut miniblock_header = create_miniblock_header(0);
+        miniblock_header.protocol_version = Some(current_version);
+        conn.blocks_dal()
+            .insert_miniblock(&miniblock_header)
+            .await
+            .unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion {
+                id: next_version,
+                timestamp: 200,
+                ..ProtocolVersion::default()
+            })
+            .await;
+
+        // Unrecorded upgrades are not activated.
+        let mut dal = conn.protocol_upgrade_schedules_dal();
+        let version = dal
+            .activatable_version(200, L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(current_version));
+
+        let recorded = dal.record_pending_upgrades().await.unwrap();
+        assert_eq!(recorded, [next_version as u16]);
+        let recorded = dal.record_pending_upgrades().await.unwrap();
+        assert!(recorded.is_empty());
+        let version = dal
+            .activatable_version(200, L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(current_version));
+
+        dal.save_preflight_result(next_version as u16, Some("missing bootloader"))
+            .await
+            .unwrap();
+        let version = dal
+            .activatable_version(200, L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(current_version));
+        dal.save_preflight_result(next_version as u16, None)
+            .await
+            .unwrap();
+        let version = dal
+            .activatable_version(200, L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(next_version));
+
+        // The upgrade cannot be scheduled after it is activated on L1.
+        let update = dal
+            .set_activation(next_version as u16, Some(201), None)
+            .await
+            .unwrap();
+        assert_eq!(
+            update,
+            UpgradeActivationUpdate::AfterL1Activation {
+                l1_activation_timestamp: 200
+            }
+        );
+        let update = dal
+            .set_activation(next_version as u16, Some(200), Some(L1BatchNumber(5)))
+            .await
+            .unwrap();
+        assert_eq!(update, UpgradeActivationUpdate::Updated);
+        let version = dal
+            .activatable_version(200, L1BatchNumber(4))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(current_version));
+        let version = dal
+            .activatable_version(199, L1BatchNumber(5))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(current_version));
+        let version = dal
+            .activatable_version(200, L1BatchNumber(5))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(next_version));
+
+        // The current version is not pending, so it cannot be scheduled.
+        let update = dal
+            .set_activation(current_version as u16, Some(200), None)
+            .await
+            .unwrap();
+        assert_eq!(update, UpgradeActivationUpdate::NotPending);
+
+        let pending_upgrades = dal.get_pending_upgrades().await.unwrap();
+        assert_eq!(pending_upgrades.len(), 1);
+        let upgrade = &pending_upgrades[0];
+        assert_eq!(upgrade.protocol_version, next_version as u16);
+        assert_eq!(upgrade.l1_activation_timestamp, 200);
+        assert_eq!(upgrade.activation_timestamp, Some(200));
+        assert_eq!(upgrade.activation_l1_batch_number, Some(L1BatchNumber(5)));
+        assert_eq!(upgrade.preflight_error, None);
+        assert_eq!(
+            upgrade.stage(100, L1BatchNumber(5)),
+            ProtocolUpgradeStage::Scheduled
+        );
+        assert_eq!(
+            upgrade.stage(200, L1BatchNumber(5)),
+            ProtocolUpgradeStage::Ready
+        );
+    }
+}
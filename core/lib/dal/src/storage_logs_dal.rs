@@ -422,6 +422,37 @@ impl StorageLogsDal<'_, '_> {
             .await
     }
 
+    /// Returns storage logs produced by the specified transaction in the specified miniblock,
+    /// ordered by the operation number.
+    pub async fn get_tx_storage_logs(
+        &mut self,
+        miniblock_number: MiniblockNumber,
+        tx_hash: H256,
+    ) -> sqlx::Result<Vec<StorageLog>> {
+        let rows = sqlx::query(
+            "SELECT address, key, value FROM storage_logs \
+            WHERE miniblock_number = $1 AND tx_hash = $2 \
+            ORDER BY operation_number",
+        )
+        .bind(miniblock_number.0 as i64)
+        .bind(tx_hash.as_bytes())
+        .instrument("get_tx_storage_logs")
+        .with_arg("miniblock_number", &miniblock_number)
+        .with_arg("tx_hash", &tx_hash)
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let address = Address::from_slice(row.get("address"));
+                let key = H256::from_slice(row.get("key"));
+                let storage_key = StorageKey::new(AccountTreeId::new(address), key);
+                StorageLog::new_write_log(storage_key, H256::from_slice(row.get("value")))
+            })
+            .collect())
+    }
+
     /// Counts the total number of storage logs in the specified miniblock,
     // TODO(PLA-596): add storage log count to snapshot metadata instead?
     pub async fn count_miniblock_storage_logs(
@@ -672,6 +703,19 @@ mod tests {
         assert_eq!(touched_slots[&first_key], H256::repeat_byte(3));
         assert_eq!(touched_slots[&second_key], H256::repeat_byte(2));
 
+        let tx_logs = conn
+            .storage_logs_dal()
+            .get_tx_storage_logs(MiniblockNumber(1), H256::zero())
+            .await
+            .unwrap();
+        assert_eq!(tx_logs, [log, other_log]);
+        let tx_logs = conn
+            .storage_logs_dal()
+            .get_tx_storage_logs(MiniblockNumber(1), H256::repeat_byte(1))
+            .await
+            .unwrap();
+        assert_eq!(tx_logs, [third_log]);
+
         test_rollback(&mut conn, first_key, second_key).await;
     }
 
@@ -18,7 +18,7 @@ use crate::{
     connection::ConnectionPool,
     protocol_versions_dal::ProtocolVersionsDal,
     prover_dal::{GetProverJobsParams, ProverDal},
-    transactions_dal::{L2TxSubmissionResult, TransactionsDal},
+    transactions_dal::{L2TxSubmissionResult, StoredExecutionOutcome, TransactionsDal},
     transactions_web3_dal::TransactionsWeb3Dal,
 };
 
@@ -165,6 +165,96 @@ async fn workflow_with_submit_tx_diff_hashes() {
     assert_eq!(result, L2TxSubmissionResult::Replaced);
 }
 
+#[tokio::test]
+async fn replacing_tx_with_fee_bump() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let mut transactions_dal = TransactionsDal { storage };
+
+    let tx = mock_l2_transaction();
+    let nonce = tx.common_data.nonce;
+    let initiator_address = tx.common_data.initiator_address;
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(tx.clone(), mock_tx_execution_metrics(), 10)
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::Added);
+    // Resubmitting the same transaction doesn't require a fee bump.
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(tx.clone(), mock_tx_execution_metrics(), 10)
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::Replaced);
+
+    let mut replacement = mock_l2_transaction();
+    replacement.common_data.nonce = nonce;
+    replacement.common_data.initiator_address = initiator_address;
+    replacement.common_data.fee.max_fee_per_gas = tx.common_data.fee.max_fee_per_gas * 109 / 100;
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(replacement.clone(), mock_tx_execution_metrics(), 10)
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::ReplacementUnderpriced);
+
+    replacement.common_data.fee.max_fee_per_gas = tx.common_data.fee.max_fee_per_gas * 110 / 100;
+    let result = transactions_dal
+        .insert_transaction_l2_with_fee_bump(replacement, mock_tx_execution_metrics(), 10)
+        .await;
+    assert_eq!(result, L2TxSubmissionResult::Replaced);
+}
+
+#[tokio::test]
+async fn evicting_pending_l2_txs() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let mut transactions_dal = TransactionsDal { storage };
+
+    let tx = mock_l2_transaction();
+    let (hash, initiator_address) = (tx.hash(), tx.common_data.initiator_address);
+    transactions_dal
+        .insert_transaction_l2(tx, mock_tx_execution_metrics())
+        .await;
+    let pending_nonces = transactions_dal
+        .get_pending_l2_tx_nonces(initiator_address)
+        .await
+        .unwrap();
+    assert_eq!(pending_nonces, [micro_types::Nonce(0)]);
+
+    transactions_dal.mark_txs_as_evicted(&[hash]).await.unwrap();
+    let pending_nonces = transactions_dal
+        .get_pending_l2_tx_nonces(initiator_address)
+        .await
+        .unwrap();
+    assert!(pending_nonces.is_empty());
+}
+
+#[tokio::test]
+async fn getting_unexecuted_priority_ops() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    let mut transactions_dal = TransactionsDal { storage };
+
+    let tx = mock_l1_execute();
+    let tx_hash = tx.hash();
+    transactions_dal
+        .insert_transaction_l1(tx, L1BlockNumber(10))
+        .await;
+    let ops = transactions_dal
+        .get_unexecuted_priority_ops(10)
+        .await
+        .unwrap();
+    assert_eq!(ops.len(), 1);
+    assert_eq!(ops[0].serial_id, PriorityOpId(1));
+    assert_eq!(ops[0].tx_hash, tx_hash);
+    assert_eq!(ops[0].l1_block_number, Some(L1BlockNumber(10)));
+    // The deadline is taken from the L1 event rather than derived from the L1 block number.
+    assert_eq!(ops[0].deadline_block, Some(100_000));
+    assert_eq!(ops[0].miniblock_number, None);
+
+    let ops = transactions_dal
+        .get_unexecuted_priority_ops(0)
+        .await
+        .unwrap();
+    assert!(ops.is_empty());
+}
+
 #[tokio::test]
 async fn remove_stuck_txs() {
     let connection_pool = ConnectionPool::test_pool().await;
@@ -257,6 +347,100 @@ async fn remove_stuck_txs() {
         .unwrap();
 }
 
+#[tokio::test]
+async fn getting_execution_outcomes_for_l1_batch() {
+    let connection_pool = ConnectionPool::test_pool().await;
+    let storage = &mut connection_pool.access_storage().await.unwrap();
+    storage
+        .protocol_versions_dal()
+        .save_protocol_version_with_tx(Default::default())
+        .await;
+
+    let txs = [mock_l2_transaction(), mock_l2_transaction()];
+    for tx in &txs {
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(tx.clone(), mock_tx_execution_metrics())
+            .await;
+    }
+    let mut miniblock_header = create_miniblock_header(1);
+    miniblock_header.l2_tx_count = 2;
+    storage
+        .blocks_dal()
+        .insert_miniblock(&miniblock_header)
+        .await
+        .unwrap();
+
+    let mut tx_results: Vec<_> = txs.into_iter().map(mock_execution_result).collect();
+    tx_results[0].execution_info.gas_used = 100;
+    tx_results[0].refunded_gas = 10;
+    tx_results[1].execution_info.gas_used = 200;
+    tx_results[1].execution_status = TxExecutionStatus::Failure;
+    storage
+        .transactions_dal()
+        .mark_txs_as_executed_in_miniblock(MiniblockNumber(1), &tx_results, U256::from(1))
+        .await;
+
+    let outcomes = storage
+        .transactions_dal()
+        .get_execution_outcomes_for_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert!(outcomes.is_empty());
+
+    let header = L1BatchHeader::new(
+        L1BatchNumber(1),
+        0,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+    );
+    storage
+        .blocks_dal()
+        .insert_l1_batch(&header, &[], Default::default(), &[], &[])
+        .await
+        .unwrap();
+    storage
+        .blocks_dal()
+        .mark_miniblocks_as_executed_in_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    storage
+        .transactions_dal()
+        .mark_txs_as_executed_in_l1_batch(L1BatchNumber(1), &tx_results)
+        .await;
+
+    let outcomes = storage
+        .transactions_dal()
+        .get_execution_outcomes_for_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    assert_eq!(
+        outcomes,
+        [
+            StoredExecutionOutcome {
+                hash: tx_results[0].hash,
+                status: TxExecutionStatus::Success,
+                gas_used: 100,
+                refunded_gas: 10,
+            },
+            StoredExecutionOutcome {
+                hash: tx_results[1].hash,
+                status: TxExecutionStatus::Failure,
+                gas_used: 200,
+                refunded_gas: 0,
+            },
+        ]
+    );
+
+    let outcomes = storage
+        .transactions_dal()
+        .get_execution_outcomes_for_l1_batch(L1BatchNumber(2))
+        .await
+        .unwrap();
+    assert!(outcomes.is_empty());
+}
+
 fn create_circuits() -> Vec<(&'static str, String)> {
     vec![
         ("Main VM", "1_0_Main VM_BasicCircuits.bin".to_owned()),
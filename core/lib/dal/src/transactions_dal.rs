@@ -3,7 +3,9 @@ use std::{collections::HashMap, fmt, time::Duration};
 use anyhow::Context;
 use bigdecimal::BigDecimal;
 use itertools::Itertools;
+use micro_system_constants::PRIORITY_EXPIRATION;
 use micro_types::{
+    api::{priority_ops::PriorityOpStatus, TransactionRejectionCode},
     block::MiniblockExecutionData,
     fee::TransactionExecutionMetrics,
     get_nonce_key,
@@ -16,7 +18,11 @@ use micro_types::{
     PriorityOpId, Transaction, H256, PROTOCOL_UPGRADE_TX_TYPE, U256,
 };
 use micro_utils::{h256_to_u32, u256_to_big_decimal};
-use sqlx::{error, types::chrono::NaiveDateTime};
+use sqlx::{
+    error,
+    types::chrono::{DateTime, NaiveDateTime, Utc},
+    Row,
+};
 
 use crate::{
     instrument::InstrumentExt,
@@ -32,6 +38,9 @@ pub enum L2TxSubmissionResult {
     AlreadyExecuted,
     Duplicate,
     Proxied,
+    /// A pending transaction with the same initiator and nonce was not replaced because the new transaction
+    /// did not bump fees sufficiently.
+    ReplacementUnderpriced,
 }
 
 impl fmt::Display for L2TxSubmissionResult {
@@ -42,10 +51,20 @@ impl fmt::Display for L2TxSubmissionResult {
             Self::AlreadyExecuted => "already_executed",
             Self::Duplicate => "duplicate",
             Self::Proxied => "proxied",
+            Self::ReplacementUnderpriced => "replacement_underpriced",
         })
     }
 }
 
+/// Execution outcome of a transaction as persisted by the state keeper.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredExecutionOutcome {
+    pub hash: H256,
+    pub status: TxExecutionStatus,
+    pub gas_used: u64,
+    pub refunded_gas: u64,
+}
+
 #[derive(Debug)]
 pub struct TransactionsDal<'c, 'a> {
     pub(crate) storage: &'c mut StorageProcessor<'a>,
@@ -67,6 +86,7 @@ impl TransactionsDal<'_, '_> {
             let layer_2_tip_fee = u256_to_big_decimal(tx.common_data.layer_2_tip_fee);
             let sender = tx.common_data.sender.as_bytes();
             let serial_id = tx.serial_id().0 as i64;
+            let deadline_block = tx.common_data.deadline_block as i64;
             let gas_per_pubdata_limit = u256_to_big_decimal(tx.common_data.gas_per_pubdata_limit);
             let value = u256_to_big_decimal(tx.execute.value);
             let tx_format = tx.common_data.tx_format() as i32;
@@ -97,6 +117,7 @@ impl TransactionsDal<'_, '_> {
                     layer_2_tip_fee,
                     contract_address,
                     l1_block_number,
+                    l1_deadline_block,
                     value,
 
                     paymaster,
@@ -113,7 +134,7 @@ impl TransactionsDal<'_, '_> {
                 VALUES
                     (
                         $1, TRUE, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12,
-                        $13, $14, $15, $16, $17, $18, now(), now()
+                        $13, $14, $15, $16, $17, $18, $19, now(), now()
                     )
                 ON CONFLICT (hash) DO NOTHING
                 ",
@@ -128,6 +149,7 @@ impl TransactionsDal<'_, '_> {
                 layer_2_tip_fee,
                 contract_address,
                 l1_block_number.0 as i32,
+                deadline_block,
                 value,
                 empty_address.as_bytes(),
                 &[] as &[u8],
@@ -228,6 +250,28 @@ impl TransactionsDal<'_, '_> {
         &mut self,
         tx: L2Tx,
         exec_info: TransactionExecutionMetrics,
+    ) -> L2TxSubmissionResult {
+        self.insert_transaction_l2_inner(tx, exec_info, None).await
+    }
+
+    /// Same as [`Self::insert_transaction_l2()`], but a pending transaction with the same initiator and nonce
+    /// is only replaced if both `max_fee_per_gas` and `max_priority_fee_per_gas` are increased by at least
+    /// `min_fee_bump_percent`. The check is a part of the upsert, so concurrent replacements cannot bypass it.
+    pub async fn insert_transaction_l2_with_fee_bump(
+        &mut self,
+        tx: L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        min_fee_bump_percent: u64,
+    ) -> L2TxSubmissionResult {
+        self.insert_transaction_l2_inner(tx, exec_info, Some(min_fee_bump_percent))
+            .await
+    }
+
+    async fn insert_transaction_l2_inner(
+        &mut self,
+        tx: L2Tx,
+        exec_info: TransactionExecutionMetrics,
+        min_fee_bump_percent: Option<u64>,
     ) -> L2TxSubmissionResult {
         {
             let tx_hash = tx.hash();
@@ -261,6 +305,8 @@ impl TransactionsDal<'_, '_> {
             // Otherwise, if the subquery won't return NULL it means that there is already tx with such nonce and initiator_address in DB
             // and we can replace it WHERE clause conditions are met.
             // It is worth mentioning that if WHERE clause conditions are not met, None will be returned.
+            // Besides the transaction being already included into a miniblock, this happens if the fee bump
+            // required for the replacement (if specified) is insufficient.
             let query_result = sqlx::query!(
                 r#"
                 INSERT INTO transactions
@@ -313,8 +359,15 @@ impl TransactionsDal<'_, '_> {
                         received_at=$19,
                         created_at=now(),
                         updated_at=now(),
-                        error = NULL
+                        error = NULL,
+                        rejection_code = NULL
                     WHERE transactions.is_priority = FALSE AND transactions.miniblock_number IS NULL
+                        AND (
+                            $20::bigint IS NULL OR transactions.hash = $1 OR (
+                                $6 >= FLOOR(transactions.max_fee_per_gas * (100 + $20::bigint) / 100)
+                                AND $7 >= FLOOR(transactions.max_priority_fee_per_gas * (100 + $20::bigint) / 100)
+                            )
+                        )
                     RETURNING (SELECT hash FROM transactions WHERE transactions.initiator_address = $2 AND transactions.nonce = $3) IS NOT NULL as "is_replaced!"
                 "#,
                 tx_hash.as_bytes(),
@@ -335,7 +388,8 @@ impl TransactionsDal<'_, '_> {
                 exec_info.gas_used as i64,
                 (exec_info.initial_storage_writes + exec_info.repeated_storage_writes) as i32,
                 exec_info.contracts_used as i32,
-                received_at
+                received_at,
+                min_fee_bump_percent.map(|percent| percent as i64)
             )
                 .fetch_optional(self.storage.conn())
                 .await
@@ -345,6 +399,11 @@ impl TransactionsDal<'_, '_> {
                 Ok(option_query_result) => match option_query_result {
                     Some(true) => L2TxSubmissionResult::Replaced,
                     Some(false) => L2TxSubmissionResult::Added,
+                    None if min_fee_bump_percent.is_some()
+                        && self.has_pending_l2_tx(initiator_address, nonce).await =>
+                    {
+                        L2TxSubmissionResult::ReplacementUnderpriced
+                    }
                     None => L2TxSubmissionResult::AlreadyExecuted,
                 },
                 Err(err) => {
@@ -455,6 +514,8 @@ impl TransactionsDal<'_, '_> {
 
             let mut call_traces_tx_hashes = Vec::with_capacity(transactions.len());
             let mut bytea_call_traces = Vec::with_capacity(transactions.len());
+            let mut call_trace_addresses = Vec::new();
+            let mut call_trace_address_tx_hashes = Vec::new();
             transactions
                 .iter()
                 .enumerate()
@@ -478,6 +539,10 @@ impl TransactionsDal<'_, '_> {
                     };
 
                     if let Some(call_trace) = tx_res.call_trace() {
+                        for address in call_trace.participating_addresses() {
+                            call_trace_addresses.push(address.as_bytes().to_vec());
+                            call_trace_address_tx_hashes.push(hash.0.to_vec());
+                        }
                         bytea_call_traces.push(bincode::serialize(&call_trace).unwrap());
                         call_traces_tx_hashes.push(hash.0.to_vec());
                     }
@@ -725,27 +790,105 @@ impl TransactionsDal<'_, '_> {
                 .execute(transaction.conn())
                 .await
                 .unwrap();
+
+                // Index of addresses participating in call traces, which allows filtering traces by address.
+                sqlx::query(
+                    "INSERT INTO call_trace_addresses (address, miniblock_number, tx_hash) \
+                    SELECT u.address, $3, u.tx_hash \
+                    FROM UNNEST($1::bytea[], $2::bytea[]) AS u(address, tx_hash)",
+                )
+                .bind(&call_trace_addresses)
+                .bind(&call_trace_address_tx_hashes)
+                .bind(miniblock_number.0 as i64)
+                .instrument("insert_call_trace_addresses")
+                .report_latency()
+                .execute(transaction.conn())
+                .await
+                .unwrap();
             }
             transaction.commit().await.unwrap();
         }
     }
 
-    pub async fn mark_tx_as_rejected(&mut self, transaction_hash: H256, error: &str) {
-        {
-            // If the rejected tx has been replaced, it means that this tx hash does not exist in the database
-            // and we will update nothing.
-            // These txs don't affect the state, so we can just easily skip this update.
-            sqlx::query!(
-                "UPDATE transactions
-                    SET error = $1, updated_at = now()
-                    WHERE hash = $2",
-                error,
-                transaction_hash.0.to_vec()
-            )
-            .execute(self.storage.conn())
-            .await
-            .unwrap();
+    pub async fn mark_tx_as_rejected(
+        &mut self,
+        transaction_hash: H256,
+        code: TransactionRejectionCode,
+        error: &str,
+    ) {
+        // If the rejected tx has been replaced, it means that this tx hash does not exist in the database
+        // and we will update nothing.
+        // These txs don't affect the state, so we can just easily skip this update.
+        sqlx::query(
+            "UPDATE transactions \
+             SET error = $1, rejection_code = $2, updated_at = now() \
+             WHERE hash = $3",
+        )
+        .bind(error)
+        .bind(code.as_ref())
+        .bind(transaction_hash.as_bytes())
+        .instrument("mark_tx_as_rejected")
+        .with_arg("transaction_hash", &transaction_hash)
+        .execute(self.storage.conn())
+        .await
+        .unwrap();
+    }
+
+    /// Marks multiple transactions as rejected with a single query. Each rejection is specified
+    /// as a transaction hash together with a human-readable error.
+    pub async fn mark_txs_as_rejected(
+        &mut self,
+        rejections: &[(H256, String)],
+        code: TransactionRejectionCode,
+    ) -> sqlx::Result<()> {
+        if rejections.is_empty() {
+            return Ok(());
         }
+
+        let (hashes, errors): (Vec<_>, Vec<_>) = rejections
+            .iter()
+            .map(|(hash, error)| (hash.as_bytes().to_vec(), error.as_str()))
+            .unzip();
+        sqlx::query(
+            "UPDATE transactions \
+             SET error = data.error, rejection_code = $3, updated_at = now() \
+             FROM UNNEST($1::bytea[], $2::text[]) AS data(hash, error) \
+             WHERE transactions.hash = data.hash",
+        )
+        .bind(&hashes)
+        .bind(&errors)
+        .bind(code.as_ref())
+        .instrument("mark_txs_as_rejected")
+        .with_arg("rejections.len", &rejections.len())
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
+    }
+
+    /// Marks transactions evicted from the mempool as rejected, so that they are not loaded
+    /// into the mempool again.
+    pub async fn mark_txs_as_evicted(&mut self, transaction_hashes: &[H256]) -> sqlx::Result<()> {
+        if transaction_hashes.is_empty() {
+            return Ok(());
+        }
+
+        let hashes: Vec<_> = transaction_hashes
+            .iter()
+            .map(|hash| hash.as_bytes().to_vec())
+            .collect();
+        sqlx::query(
+            "UPDATE transactions \
+             SET error = $1, rejection_code = $2, in_mempool = FALSE, updated_at = now() \
+             WHERE hash = ANY($3) AND miniblock_number IS NULL",
+        )
+        .bind("rejected: evicted from the mempool")
+        .bind(TransactionRejectionCode::Evicted.as_ref())
+        .bind(&hashes)
+        .instrument("mark_txs_as_evicted")
+        .with_arg("transaction_hashes.len", &transaction_hashes.len())
+        .execute(self.storage.conn())
+        .await?;
+        Ok(())
     }
 
     pub async fn reset_transactions_state(&mut self, miniblock_number: MiniblockNumber) {
@@ -925,6 +1068,72 @@ impl TransactionsDal<'_, '_> {
         }
     }
 
+    /// Returns priority operations that are not executed on L1 yet (i.e., are not included into an L1 batch
+    /// executed on L1), ordered by their serial ID. Returns at most `limit` operations.
+    pub async fn get_unexecuted_priority_ops(
+        &mut self,
+        limit: usize,
+    ) -> sqlx::Result<Vec<PriorityOpStatus>> {
+        let rows = sqlx::query(
+            "SELECT transactions.priority_op_id, transactions.hash, transactions.l1_block_number, \
+                transactions.l1_deadline_block, transactions.received_at, transactions.miniblock_number, \
+                miniblocks.timestamp AS miniblock_timestamp, transactions.l1_batch_number \
+            FROM transactions \
+            LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number \
+            WHERE transactions.is_priority = TRUE AND transactions.priority_op_id IS NOT NULL \
+                AND (transactions.l1_batch_number IS NULL OR transactions.l1_batch_number > COALESCE( \
+                    (SELECT MAX(l1_batches.number) FROM l1_batches \
+                    JOIN eth_txs_history ON l1_batches.eth_execute_tx_id = eth_txs_history.eth_tx_id \
+                    WHERE eth_txs_history.confirmed_at IS NOT NULL), \
+                    -1 \
+                )) \
+            ORDER BY transactions.priority_op_id \
+            LIMIT $1",
+        )
+        .bind(limit as i64)
+        .instrument("get_unexecuted_priority_ops")
+        .with_arg("limit", &limit)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let l1_block_number = row
+                    .get::<Option<i32>, _>("l1_block_number")
+                    .map(|number| L1BlockNumber(number as u32));
+                // Operations stored before deadlines were persisted have their deadline derived
+                // from the L1 block number, same as the L1 contract does.
+                let deadline_block = row
+                    .get::<Option<i64>, _>("l1_deadline_block")
+                    .map(|block| block as u64)
+                    .or_else(|| {
+                        l1_block_number.map(|number| u64::from(number.0) + PRIORITY_EXPIRATION)
+                    });
+                let received_at: NaiveDateTime = row.get("received_at");
+                let included_at = row
+                    .get::<Option<i64>, _>("miniblock_timestamp")
+                    .and_then(|timestamp| NaiveDateTime::from_timestamp_opt(timestamp, 0))
+                    .map(|timestamp| DateTime::<Utc>::from_naive_utc_and_offset(timestamp, Utc));
+                PriorityOpStatus {
+                    serial_id: PriorityOpId(row.get::<i64, _>("priority_op_id") as u64),
+                    tx_hash: H256::from_slice(row.get("hash")),
+                    l1_block_number,
+                    deadline_block,
+                    received_at: DateTime::<Utc>::from_naive_utc_and_offset(received_at, Utc),
+                    miniblock_number: row
+                        .get::<Option<i64>, _>("miniblock_number")
+                        .map(|number| MiniblockNumber(number as u32)),
+                    included_at,
+                    l1_batch_number: row
+                        .get::<Option<i64>, _>("l1_batch_number")
+                        .map(|number| L1BatchNumber(number as u32)),
+                }
+            })
+            .collect())
+    }
+
     pub async fn next_priority_id(&mut self) -> PriorityOpId {
         {
             sqlx::query!(
@@ -1008,6 +1217,45 @@ impl TransactionsDal<'_, '_> {
         self.get_miniblocks_to_execute(transactions).await
     }
 
+    /// Returns execution outcomes of all transactions in the given L1 batch, in execution order.
+    pub async fn get_execution_outcomes_for_l1_batch(
+        &mut self,
+        l1_batch_number: L1BatchNumber,
+    ) -> sqlx::Result<Vec<StoredExecutionOutcome>> {
+        let rows = sqlx::query(
+            "SELECT hash, error, (execution_info->>'gas_used')::bigint AS gas_used, refunded_gas \
+             FROM transactions \
+             WHERE l1_batch_number = $1 \
+             ORDER BY miniblock_number, index_in_block",
+        )
+        .bind(l1_batch_number.0 as i64)
+        .instrument("get_execution_outcomes_for_l1_batch")
+        .with_arg("l1_batch_number", &l1_batch_number)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        let outcomes = rows
+            .into_iter()
+            .map(|row| {
+                let error: Option<String> = row.get("error");
+                let gas_used: Option<i64> = row.get("gas_used");
+                let refunded_gas: i64 = row.get("refunded_gas");
+                StoredExecutionOutcome {
+                    hash: H256::from_slice(row.get("hash")),
+                    status: if error.is_some() {
+                        TxExecutionStatus::Failure
+                    } else {
+                        TxExecutionStatus::Success
+                    },
+                    gas_used: gas_used.unwrap_or(0) as u64,
+                    refunded_gas: refunded_gas as u64,
+                }
+            })
+            .collect();
+        Ok(outcomes)
+    }
+
     async fn get_miniblocks_to_execute(
         &mut self,
         transactions: Vec<StorageTransaction>,
@@ -1140,14 +1388,58 @@ impl TransactionsDal<'_, '_> {
         .map(|tx| tx.into())
     }
 
-    pub async fn get_tx_memory(&mut self) -> u32 {
+    /// Checks whether there is a pending (i.e., not included into a miniblock) L2 transaction
+    /// with the specified initiator and nonce.
+    async fn has_pending_l2_tx(&mut self, initiator_address: Address, nonce: i64) -> bool {
+        sqlx::query(
+            "SELECT 1 FROM transactions \
+             WHERE initiator_address = $1 AND nonce = $2 \
+             AND is_priority = FALSE AND miniblock_number IS NULL",
+        )
+        .bind(initiator_address.as_bytes())
+        .bind(nonce)
+        .instrument("has_pending_l2_tx")
+        .with_arg("initiator_address", &initiator_address)
+        .with_arg("nonce", &nonce)
+        .fetch_optional(self.storage.conn())
+        .await
+        .unwrap()
+        .is_some()
+    }
+
+    /// Returns nonces of pending (i.e., not included into a miniblock and not rejected) L2 transactions
+    /// of the specified initiator, in the ascending order.
+    pub async fn get_pending_l2_tx_nonces(
+        &mut self,
+        initiator_address: Address,
+    ) -> sqlx::Result<Vec<Nonce>> {
+        let rows = sqlx::query(
+            "SELECT nonce FROM transactions \
+             WHERE initiator_address = $1 AND is_priority = FALSE \
+             AND miniblock_number IS NULL AND error IS NULL \
+             ORDER BY nonce",
+        )
+        .bind(initiator_address.as_bytes())
+        .instrument("get_pending_l2_tx_nonces")
+        .with_arg("initiator_address", &initiator_address)
+        .report_latency()
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| row.get::<Option<i64>, _>("nonce"))
+            .map(|nonce| Nonce(nonce as u32))
+            .collect())
+    }
+
+    pub async fn get_tx_memory(&mut self) -> sqlx::Result<u32> {
         let counts: i64 =
             sqlx::query!(r#"SELECT COUNT(*) as "count!" FROM transactions WHERE in_mempool=TRUE"#)
                 .fetch_one(self.storage.conn())
-                .await
-                .unwrap()
+                .await?
                 .count;
-        counts as u32
+        Ok(counts as u32)
     }
 
     pub async fn get_last_transactions_time(&mut self) -> i64 {
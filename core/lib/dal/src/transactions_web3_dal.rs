@@ -1,9 +1,19 @@
+use std::collections::HashMap;
+
 use micro_types::{
-    api, Address, L2ChainId, MiniblockNumber, Transaction, ACCOUNT_CODE_STORAGE_ADDRESS,
-    FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H160, H256, U256, U64,
+    api::{
+        self,
+        finality::{L1BatchLifecycle, L1BatchStage, TransactionFinality},
+        TransactionStatus,
+    },
+    Address, L1BatchNumber, L2ChainId, MiniblockNumber, Transaction, ACCOUNT_CODE_STORAGE_ADDRESS,
+    FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH, H256, U256,
+};
+use sqlx::{
+    postgres::PgRow,
+    types::chrono::{DateTime, NaiveDateTime, Utc},
+    Row,
 };
-use micro_utils::{bigdecimal_to_u256, h256_to_account_address};
-use sqlx::types::chrono::NaiveDateTime;
 
 use crate::{
     instrument::InstrumentExt,
@@ -12,7 +22,7 @@ use crate::{
         storage_event::StorageWeb3Log,
         storage_transaction::{
             extract_web3_transaction, web3_transaction_select_sql, StorageTransaction,
-            StorageTransactionDetails,
+            StorageTransactionDetails, StorageTransactionReceipt,
         },
     },
     SqlxError, StorageProcessor,
@@ -29,7 +39,8 @@ impl TransactionsWeb3Dal<'_, '_> {
         hash: H256,
     ) -> Result<Option<api::TransactionReceipt>, SqlxError> {
         {
-            let receipt = sqlx::query!(
+            let receipt = sqlx::query_as!(
+                StorageTransactionReceipt,
                 r#"
                 WITH sl AS (
                     SELECT * FROM storage_logs
@@ -68,61 +79,7 @@ impl TransactionsWeb3Dal<'_, '_> {
             .with_arg("hash", &hash)
             .fetch_optional(self.storage.conn())
             .await?
-            .map(|db_row| {
-                let status = match (db_row.block_number, db_row.error) {
-                    (_, Some(_)) => Some(U64::from(0)),
-                    (Some(_), None) => Some(U64::from(1)),
-                    // tx not executed yet
-                    _ => None,
-                };
-                let tx_type = db_row.tx_format.map(U64::from).unwrap_or_default();
-                let transaction_index = db_row.index_in_block.map(U64::from).unwrap_or_default();
-
-                let block_hash = db_row.block_hash.map(|bytes| H256::from_slice(&bytes));
-                api::TransactionReceipt {
-                    transaction_hash: H256::from_slice(&db_row.tx_hash),
-                    transaction_index,
-                    block_hash,
-                    block_number: db_row.block_number.map(U64::from),
-                    l1_batch_tx_index: db_row.l1_batch_tx_index.map(U64::from),
-                    l1_batch_number: db_row.l1_batch_number.map(U64::from),
-                    from: H160::from_slice(&db_row.initiator_address),
-                    to: db_row
-                        .transfer_to
-                        .or(db_row.execute_contract_address)
-                        .map(|addr| {
-                            serde_json::from_value::<Address>(addr)
-                                .expect("invalid address value in the database")
-                        })
-                        // For better compatibility with various clients, we never return null.
-                        .or_else(|| Some(Address::default())),
-                    cumulative_gas_used: Default::default(), // TODO: Should be actually calculated (SMA-1183).
-                    gas_used: {
-                        let refunded_gas: U256 = db_row.refunded_gas.into();
-                        db_row.gas_limit.map(|val| {
-                            let gas_limit = bigdecimal_to_u256(val);
-                            gas_limit - refunded_gas
-                        })
-                    },
-                    effective_gas_price: Some(
-                        db_row
-                            .effective_gas_price
-                            .map(bigdecimal_to_u256)
-                            .unwrap_or_default(),
-                    ),
-                    contract_address: db_row
-                        .contract_address
-                        .map(|addr| h256_to_account_address(&H256::from_slice(&addr))),
-                    logs: vec![],
-                    l2_to_l1_logs: vec![],
-                    status,
-                    root: block_hash,
-                    logs_bloom: Default::default(),
-                    // Even though the Rust SDK recommends us to supply "None" for legacy transactions
-                    // we always supply some number anyway to have the same behaviour as most popular RPCs
-                    transaction_type: Some(tx_type),
-                }
-            });
+            .map(api::TransactionReceipt::from);
             match receipt {
                 Some(mut receipt) => {
                     let logs: Vec<_> = sqlx::query_as!(
@@ -173,6 +130,105 @@ impl TransactionsWeb3Dal<'_, '_> {
         }
     }
 
+    /// Returns receipts for all transactions in the specified miniblock, ordered by the index in the miniblock.
+    pub async fn get_block_receipts(
+        &mut self,
+        block_number: MiniblockNumber,
+    ) -> Result<Vec<api::TransactionReceipt>, SqlxError> {
+        let mut receipts: Vec<api::TransactionReceipt> = sqlx::query_as(
+            "SELECT \
+                transactions.hash as tx_hash, \
+                transactions.index_in_block as index_in_block, \
+                transactions.l1_batch_tx_index as l1_batch_tx_index, \
+                transactions.miniblock_number as block_number, \
+                transactions.error as error, \
+                transactions.effective_gas_price as effective_gas_price, \
+                transactions.initiator_address as initiator_address, \
+                transactions.data->'to' as transfer_to, \
+                transactions.data->'contractAddress' as execute_contract_address, \
+                transactions.tx_format as tx_format, \
+                transactions.refunded_gas as refunded_gas, \
+                transactions.gas_limit as gas_limit, \
+                miniblocks.hash as block_hash, \
+                miniblocks.l1_batch_number as l1_batch_number, \
+                sl.key as contract_address \
+            FROM transactions \
+            INNER JOIN miniblocks ON miniblocks.number = transactions.miniblock_number \
+            LEFT JOIN LATERAL ( \
+                SELECT storage_logs.key, storage_logs.value FROM storage_logs \
+                WHERE storage_logs.address = $1 AND storage_logs.tx_hash = transactions.hash \
+                ORDER BY storage_logs.miniblock_number DESC, storage_logs.operation_number DESC \
+                LIMIT 1 \
+            ) sl ON sl.value != $3 \
+            WHERE transactions.miniblock_number = $2 \
+            ORDER BY transactions.index_in_block ASC",
+        )
+        .bind(ACCOUNT_CODE_STORAGE_ADDRESS.as_bytes())
+        .bind(block_number.0 as i64)
+        .bind(FAILED_CONTRACT_DEPLOYMENT_BYTECODE_HASH.as_bytes())
+        .instrument("get_block_receipts")
+        .with_arg("block_number", &block_number)
+        .fetch_all(self.storage.conn())
+        .await?
+        .into_iter()
+        .map(|row: StorageTransactionReceipt| row.into())
+        .collect();
+        let Some(first_receipt) = receipts.first() else {
+            return Ok(receipts);
+        };
+        let (block_hash, l1_batch_number) =
+            (first_receipt.block_hash, first_receipt.l1_batch_number);
+
+        let storage_logs: Vec<StorageWeb3Log> = sqlx::query_as(
+            "SELECT \
+                address, topic1, topic2, topic3, topic4, value, \
+                Null::bytea as block_hash, Null::bigint as l1_batch_number, \
+                miniblock_number, tx_hash, tx_index_in_block, \
+                event_index_in_block, event_index_in_tx \
+            FROM events \
+            WHERE miniblock_number = $1 \
+            ORDER BY event_index_in_block ASC",
+        )
+        .bind(block_number.0 as i64)
+        .instrument("get_block_receipts_events")
+        .with_arg("block_number", &block_number)
+        .fetch_all(self.storage.conn())
+        .await?;
+        let mut logs_by_tx = HashMap::<_, Vec<_>>::new();
+        for storage_log in storage_logs {
+            let mut log = api::Log::from(storage_log);
+            log.block_hash = block_hash;
+            log.l1_batch_number = l1_batch_number;
+            logs_by_tx
+                .entry(log.transaction_hash)
+                .or_default()
+                .push(log);
+        }
+
+        let storage_l2_to_l1_logs = self
+            .storage
+            .events_dal()
+            .l2_to_l1_logs_in_miniblock(block_number)
+            .await?;
+        let mut l2_to_l1_logs_by_tx = HashMap::<_, Vec<_>>::new();
+        for storage_l2_to_l1_log in storage_l2_to_l1_logs {
+            let mut l2_to_l1_log = api::L2ToL1Log::from(storage_l2_to_l1_log);
+            l2_to_l1_log.block_hash = block_hash;
+            l2_to_l1_log.l1_batch_number = l1_batch_number;
+            l2_to_l1_logs_by_tx
+                .entry(l2_to_l1_log.transaction_hash)
+                .or_default()
+                .push(l2_to_l1_log);
+        }
+
+        for receipt in &mut receipts {
+            let tx_hash = receipt.transaction_hash;
+            receipt.logs = logs_by_tx.remove(&Some(tx_hash)).unwrap_or_default();
+            receipt.l2_to_l1_logs = l2_to_l1_logs_by_tx.remove(&tx_hash).unwrap_or_default();
+        }
+        Ok(receipts)
+    }
+
     pub async fn get_transaction(
         &mut self,
         transaction_id: api::TransactionId,
@@ -250,12 +306,128 @@ impl TransactionsWeb3Dal<'_, '_> {
             .fetch_optional(self.storage.conn())
             .await?;
 
-            let tx = storage_tx_details.map(|tx_details| tx_details.into());
-
-            Ok(tx)
+            let Some(storage_tx_details) = storage_tx_details else {
+                return Ok(None);
+            };
+            let is_rejected =
+                storage_tx_details.error.is_some() && storage_tx_details.miniblock_number.is_none();
+            let mut tx: api::TransactionDetails = storage_tx_details.into();
+            if is_rejected {
+                tx.rejection = self.get_transaction_rejection(hash).await?;
+            }
+            Ok(Some(tx))
         }
     }
 
+    /// Returns the rejection reason for the specified transaction if it was rejected by the state keeper.
+    pub async fn get_transaction_rejection(
+        &mut self,
+        hash: H256,
+    ) -> Result<Option<api::TransactionRejection>, SqlxError> {
+        let row = sqlx::query(
+            "SELECT hash, rejection_code, error, updated_at \
+             FROM transactions \
+             WHERE hash = $1 AND rejection_code IS NOT NULL",
+        )
+        .bind(hash.as_bytes())
+        .instrument("get_transaction_rejection")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage.conn())
+        .await?;
+
+        Ok(row.and_then(|row| transaction_rejection_from_row(&row)))
+    }
+
+    /// Returns transactions rejected by the state keeper after the `(from_timestamp, from_hash)` cursor, ordered
+    /// by the rejection time and then by the transaction hash. The hash is necessary to paginate rejections sharing
+    /// the same timestamp, e.g. ones marked in a single DB transaction.
+    pub async fn get_rejected_transactions_after(
+        &mut self,
+        from_timestamp: NaiveDateTime,
+        from_hash: H256,
+        limit: usize,
+    ) -> Result<Vec<api::TransactionRejection>, SqlxError> {
+        let rows = sqlx::query(
+            "SELECT hash, rejection_code, error, updated_at \
+             FROM transactions \
+             WHERE rejection_code IS NOT NULL AND (updated_at, hash) > ($1, $2) \
+             ORDER BY updated_at, hash \
+             LIMIT $3",
+        )
+        .bind(from_timestamp)
+        .bind(from_hash.as_bytes())
+        .bind(limit as i64)
+        .instrument("get_rejected_transactions_after")
+        .with_arg("from_timestamp", &from_timestamp)
+        .with_arg("from_hash", &from_hash)
+        .with_arg("limit", &limit)
+        .fetch_all(self.storage.conn())
+        .await?;
+
+        Ok(rows
+            .iter()
+            .filter_map(transaction_rejection_from_row)
+            .collect())
+    }
+
+    /// Returns the full lifecycle of the specified transaction, including the L1 batch stage.
+    pub async fn get_transaction_finality(
+        &mut self,
+        hash: H256,
+    ) -> Result<Option<TransactionFinality>, SqlxError> {
+        let row = sqlx::query(
+            "SELECT transactions.received_at, transactions.error, \
+                transactions.miniblock_number, miniblocks.l1_batch_number \
+            FROM transactions \
+            LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number \
+            WHERE transactions.hash = $1",
+        )
+        .bind(hash.as_bytes())
+        .instrument("get_transaction_finality")
+        .with_arg("hash", &hash)
+        .fetch_optional(self.storage.conn())
+        .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let received_at: NaiveDateTime = row.get("received_at");
+        let error: Option<String> = row.get("error");
+        let miniblock_number = row
+            .get::<Option<i64>, _>("miniblock_number")
+            .map(|number| MiniblockNumber(number as u32));
+        let l1_batch = match row.get::<Option<i64>, _>("l1_batch_number") {
+            Some(number) => {
+                let number = L1BatchNumber(number as u32);
+                self.storage
+                    .blocks_web3_dal()
+                    .get_l1_batches_lifecycle(number..=number)
+                    .await?
+                    .pop()
+            }
+            None => None,
+        };
+        let l1_batch_stage = l1_batch.as_ref().map(L1BatchLifecycle::stage);
+
+        let status = if error.is_some() {
+            TransactionStatus::Failed
+        } else if l1_batch_stage == Some(L1BatchStage::Executed) {
+            TransactionStatus::Verified
+        } else if miniblock_number.is_some() {
+            TransactionStatus::Included
+        } else {
+            TransactionStatus::Pending
+        };
+        Ok(Some(TransactionFinality {
+            status,
+            received_at: DateTime::<Utc>::from_naive_utc_and_offset(received_at, Utc),
+            error,
+            miniblock_number,
+            l1_batch_stage,
+            l1_batch,
+        }))
+    }
+
     /// Returns hashes of txs which were received after `from_timestamp` and the time of receiving the last tx.
     pub async fn get_pending_txs_hashes_after(
         &mut self,
@@ -351,6 +523,86 @@ impl TransactionsWeb3Dal<'_, '_> {
 
         Ok(rows.into_iter().map(Into::into).collect())
     }
+
+    /// Returns transactions with the specified hashes. Unknown hashes are skipped; the returned transactions
+    /// are not ordered.
+    pub async fn get_transactions(
+        &mut self,
+        hashes: &[H256],
+        chain_id: L2ChainId,
+    ) -> Result<Vec<api::Transaction>, SqlxError> {
+        let query = format!(
+            "SELECT {} \
+             FROM transactions \
+             LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number \
+             WHERE transactions.hash = ANY($1)",
+            web3_transaction_select_sql()
+        );
+        let hashes_sql: Vec<_> = hashes.iter().map(|hash| hash.as_bytes().to_vec()).collect();
+        let rows = sqlx::query(&query)
+            .bind(&hashes_sql)
+            .instrument("get_web3_transactions")
+            .with_arg("hashes.len", &hashes.len())
+            .report_latency()
+            .fetch_all(self.storage.conn())
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| extract_web3_transaction(row, chain_id))
+            .collect())
+    }
+
+    /// Returns transactions initiated by the specified account and included into miniblocks,
+    /// starting from the most recent ones.
+    pub async fn get_account_transactions(
+        &mut self,
+        initiator: Address,
+        offset: usize,
+        limit: usize,
+        chain_id: L2ChainId,
+    ) -> Result<Vec<api::Transaction>, SqlxError> {
+        let query = format!(
+            "SELECT {} \
+             FROM transactions \
+             LEFT JOIN miniblocks ON miniblocks.number = transactions.miniblock_number \
+             WHERE transactions.initiator_address = $1 AND transactions.miniblock_number IS NOT NULL \
+             ORDER BY transactions.miniblock_number DESC, transactions.index_in_block DESC \
+             OFFSET $2 LIMIT $3",
+            web3_transaction_select_sql()
+        );
+        let rows = sqlx::query(&query)
+            .bind(initiator.as_bytes())
+            .bind(offset as i64)
+            .bind(limit as i64)
+            .instrument("get_account_transactions")
+            .with_arg("initiator", &initiator)
+            .with_arg("offset", &offset)
+            .with_arg("limit", &limit)
+            .report_latency()
+            .fetch_all(self.storage.conn())
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| extract_web3_transaction(row, chain_id))
+            .collect())
+    }
+}
+
+fn transaction_rejection_from_row(row: &PgRow) -> Option<api::TransactionRejection> {
+    let code: String = row.get("rejection_code");
+    let Ok(code) = code.parse() else {
+        tracing::warn!("Unknown transaction rejection code in DB: {code}");
+        return None;
+    };
+    let updated_at: NaiveDateTime = row.get("updated_at");
+    Some(api::TransactionRejection {
+        tx_hash: H256::from_slice(row.get("hash")),
+        code,
+        reason: row.get::<Option<String>, _>("error").unwrap_or_default(),
+        rejected_at: DateTime::<Utc>::from_naive_utc_and_offset(updated_at, Utc),
+    })
 }
 
 #[cfg(test)]
@@ -454,6 +706,55 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn getting_account_transactions() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        let initiator = tx.initiator_account();
+        prepare_transaction(&mut conn, tx).await;
+
+        let chain_id = L2ChainId::from(270);
+        let transactions = conn
+            .transactions_web3_dal()
+            .get_account_transactions(initiator, 0, 10, chain_id)
+            .await
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].hash, tx_hash);
+        assert_eq!(transactions[0].block_number, Some(1.into()));
+        let transactions = conn
+            .transactions_web3_dal()
+            .get_account_transactions(initiator, 1, 10, chain_id)
+            .await
+            .unwrap();
+        assert!(transactions.is_empty());
+        let transactions = conn
+            .transactions_web3_dal()
+            .get_account_transactions(Address::repeat_byte(1), 0, 10, chain_id)
+            .await
+            .unwrap();
+        assert!(transactions.is_empty());
+
+        let transactions = conn
+            .transactions_web3_dal()
+            .get_transactions(&[H256::repeat_byte(1), tx_hash], chain_id)
+            .await
+            .unwrap();
+        assert_eq!(transactions.len(), 1);
+        assert_eq!(transactions[0].hash, tx_hash);
+        let transactions = conn
+            .transactions_web3_dal()
+            .get_transactions(&[], chain_id)
+            .await
+            .unwrap();
+        assert!(transactions.is_empty());
+    }
+
     #[tokio::test]
     async fn getting_miniblock_transactions() {
         let connection_pool = ConnectionPool::test_pool().await;
@@ -480,4 +781,93 @@ mod tests {
         assert_eq!(raw_txs.len(), 1);
         assert_eq!(raw_txs[0].hash(), tx_hash);
     }
+
+    #[tokio::test]
+    async fn getting_block_receipts() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        conn.protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion::default())
+            .await;
+        let tx = mock_l2_transaction();
+        let tx_hash = tx.hash();
+        prepare_transaction(&mut conn, tx).await;
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_block_receipts(MiniblockNumber(0))
+            .await
+            .unwrap();
+        assert!(receipts.is_empty());
+
+        let receipts = conn
+            .transactions_web3_dal()
+            .get_block_receipts(MiniblockNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(receipts.len(), 1);
+        assert_eq!(receipts[0].transaction_hash, tx_hash);
+        assert_eq!(receipts[0].block_number, Some(1.into()));
+        assert_eq!(receipts[0].status, Some(1.into()));
+
+        let receipt = conn
+            .transactions_web3_dal()
+            .get_transaction_receipt(tx_hash)
+            .await
+            .unwrap()
+            .expect("no receipt");
+        assert_eq!(receipts[0], receipt);
+    }
+
+    #[tokio::test]
+    async fn paginating_rejected_transactions_with_same_timestamp() {
+        let connection_pool = ConnectionPool::test_pool().await;
+        let mut conn = connection_pool.access_storage().await.unwrap();
+        let txs = [
+            mock_l2_transaction(),
+            mock_l2_transaction(),
+            mock_l2_transaction(),
+        ];
+        for tx in &txs {
+            conn.transactions_dal()
+                .insert_transaction_l2(tx.clone(), TransactionExecutionMetrics::default())
+                .await;
+        }
+        // All transactions are rejected in a single query, so they share the rejection timestamp.
+        let rejections: Vec<_> = txs
+            .iter()
+            .map(|tx| (tx.hash(), "rejected: validation failed".to_owned()))
+            .collect();
+        conn.transactions_dal()
+            .mark_txs_as_rejected(&rejections, api::TransactionRejectionCode::ValidationFailed)
+            .await
+            .unwrap();
+
+        let first_page = conn
+            .transactions_web3_dal()
+            .get_rejected_transactions_after(NaiveDateTime::default(), H256::zero(), 2)
+            .await
+            .unwrap();
+        assert_eq!(first_page.len(), 2);
+        assert_eq!(first_page[0].rejected_at, first_page[1].rejected_at);
+        assert!(first_page[0].tx_hash < first_page[1].tx_hash);
+
+        let cursor = &first_page[1];
+        let second_page = conn
+            .transactions_web3_dal()
+            .get_rejected_transactions_after(cursor.rejected_at.naive_utc(), cursor.tx_hash, 2)
+            .await
+            .unwrap();
+        assert_eq!(second_page.len(), 1);
+
+        let mut hashes: Vec<_> = first_page
+            .iter()
+            .chain(&second_page)
+            .map(|rejection| rejection.tx_hash)
+            .collect();
+        let mut expected_hashes: Vec<_> = txs.iter().map(L2Tx::hash).collect();
+        hashes.sort_unstable();
+        expected_hashes.sort_unstable();
+        assert_eq!(hashes, expected_hashes);
+    }
 }
@@ -1,7 +1,8 @@
 use anyhow::Context as _;
 use micro_config::configs::{
     api::{
-        ContractVerificationApiConfig, HealthCheckConfig, MerkleTreeApiConfig, Web3JsonRpcConfig,
+        ClientRateLimitKey, ContractVerificationApiConfig, HealthCheckConfig, MerkleTreeApiConfig,
+        Web3JsonRpcConfig,
     },
     ApiConfig, PrometheusConfig,
 };
@@ -88,6 +89,18 @@ mod tests {
                 max_response_body_size_mb: Some(10),
                 websocket_requests_per_minute_limit: Some(10),
                 tree_api_url: None,
+                client_requests_per_second_limit: Some(100),
+                client_requests_burst_limit: Some(500),
+                client_rate_limit_key: Some(ClientRateLimitKey::ApiKey),
+                client_api_key_header: Some("x-micro-api-key".into()),
+                method_weights: Some(vec!["eth_call=5".into(), "eth_getLogs=20".into()]),
+                trusted_proxies: Some(vec![[10, 0, 0, 1].into(), [10, 0, 0, 2].into()]),
+                graphql_port: Some(3060),
+                graphql_max_complexity: Some(500),
+                graphql_max_depth: Some(8),
+                admin_http_addr: Some(([127, 0, 0, 1], 3053).into()),
+                txpool_namespace_enabled: Some(true),
+                prover_namespace_enabled: Some(true),
             },
             contract_verification: ContractVerificationApiConfig {
                 port: 3070,
@@ -134,6 +147,18 @@ mod tests {
             API_WEB3_JSON_RPC_FEE_HISTORY_LIMIT=100
             API_WEB3_JSON_RPC_MAX_BATCH_REQUEST_SIZE=200
             API_WEB3_JSON_RPC_WEBSOCKET_REQUESTS_PER_MINUTE_LIMIT=10
+            API_WEB3_JSON_RPC_CLIENT_REQUESTS_PER_SECOND_LIMIT=100
+            API_WEB3_JSON_RPC_CLIENT_REQUESTS_BURST_LIMIT=500
+            API_WEB3_JSON_RPC_CLIENT_RATE_LIMIT_KEY="api_key"
+            API_WEB3_JSON_RPC_CLIENT_API_KEY_HEADER="x-micro-api-key"
+            API_WEB3_JSON_RPC_METHOD_WEIGHTS="eth_call=5,eth_getLogs=20"
+            API_WEB3_JSON_RPC_TRUSTED_PROXIES="10.0.0.1,10.0.0.2"
+            API_WEB3_JSON_RPC_GRAPHQL_PORT=3060
+            API_WEB3_JSON_RPC_GRAPHQL_MAX_COMPLEXITY=500
+            API_WEB3_JSON_RPC_GRAPHQL_MAX_DEPTH=8
+            API_WEB3_JSON_RPC_ADMIN_HTTP_ADDR="127.0.0.1:3053"
+            API_WEB3_JSON_RPC_TXPOOL_NAMESPACE_ENABLED=true
+            API_WEB3_JSON_RPC_PROVER_NAMESPACE_ENABLED=true
             API_CONTRACT_VERIFICATION_PORT="3070"
             API_CONTRACT_VERIFICATION_URL="http://127.0.0.1:3070"
             API_CONTRACT_VERIFICATION_THREADS_PER_SERVER=128
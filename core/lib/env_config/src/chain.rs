@@ -52,6 +52,7 @@ impl FromEnv for MempoolConfig {
 #[cfg(test)]
 mod tests {
     use micro_basic_types::L2ChainId;
+    use micro_config::configs::chain::{MempoolEvictionPolicy, MempoolOrdering};
 
     use super::*;
     use crate::test_utils::{addr, EnvMutex};
@@ -86,6 +87,9 @@ mod tests {
                 virtual_blocks_per_miniblock: 1,
                 upload_witness_inputs_to_gcs: false,
                 enum_index_migration_chunk_size: Some(2_000),
+                prover_backlog_max_block_commit_deadline_ms: Some(10_000),
+                prover_backlog_threshold: Some(3),
+                prover_backlog_saturation: Some(30),
             },
             operations_manager: OperationsManagerConfig {
                 delay_interval: 100,
@@ -97,6 +101,12 @@ mod tests {
                 stuck_tx_timeout: 10,
                 remove_stuck_txs: true,
                 delay_interval: 100,
+                ordering: MempoolOrdering::PriorityFee,
+                replacement_fee_bump_percent: Some(15),
+                eviction_policy: MempoolEvictionPolicy::LowestFee,
+                max_pending_txs_per_account: Some(64),
+                max_nonce_gap_per_account: Some(16),
+                max_txs_per_ip_per_minute: Some(600),
             },
             circuit_breaker: CircuitBreakerConfig {
                 sync_interval_ms: 1000,
@@ -132,6 +142,9 @@ mod tests {
             CHAIN_STATE_KEEPER_SAVE_CALL_TRACES="false"
             CHAIN_STATE_KEEPER_UPLOAD_WITNESS_INPUTS_TO_GCS="false"
             CHAIN_STATE_KEEPER_ENUM_INDEX_MIGRATION_CHUNK_SIZE="2000"
+            CHAIN_STATE_KEEPER_PROVER_BACKLOG_MAX_BLOCK_COMMIT_DEADLINE_MS="10000"
+            CHAIN_STATE_KEEPER_PROVER_BACKLOG_THRESHOLD="3"
+            CHAIN_STATE_KEEPER_PROVER_BACKLOG_SATURATION="30"
             CHAIN_OPERATIONS_MANAGER_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_SYNC_INTERVAL_MS="10"
             CHAIN_MEMPOOL_SYNC_BATCH_SIZE="1000"
@@ -139,6 +152,12 @@ mod tests {
             CHAIN_MEMPOOL_REMOVE_STUCK_TXS="true"
             CHAIN_MEMPOOL_DELAY_INTERVAL="100"
             CHAIN_MEMPOOL_CAPACITY="1000000"
+            CHAIN_MEMPOOL_ORDERING="priority_fee"
+            CHAIN_MEMPOOL_REPLACEMENT_FEE_BUMP_PERCENT="15"
+            CHAIN_MEMPOOL_EVICTION_POLICY="lowest_fee"
+            CHAIN_MEMPOOL_MAX_PENDING_TXS_PER_ACCOUNT="64"
+            CHAIN_MEMPOOL_MAX_NONCE_GAP_PER_ACCOUNT="16"
+            CHAIN_MEMPOOL_MAX_TXS_PER_IP_PER_MINUTE="600"
             CHAIN_CIRCUIT_BREAKER_SYNC_INTERVAL_MS="1000"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_MAX_RETRY_NUMBER="5"
             CHAIN_CIRCUIT_BREAKER_HTTP_REQ_RETRY_INTERVAL_SEC="2"
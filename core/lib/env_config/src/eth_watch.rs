@@ -19,6 +19,7 @@ mod tests {
         ETHWatchConfig {
             confirmations_for_eth_event: Some(0),
             eth_node_poll_interval: 300,
+            priority_op_deadline_warning_blocks: Some(500),
         }
     }
 
@@ -28,6 +29,7 @@ mod tests {
         let config = r#"
             ETH_WATCH_CONFIRMATIONS_FOR_ETH_EVENT="0"
             ETH_WATCH_ETH_NODE_POLL_INTERVAL="300"
+            ETH_WATCH_PRIORITY_OP_DEADLINE_WARNING_BLOCKS="500"
         "#;
         lock.set_env(config);
 
@@ -10,6 +10,8 @@ impl FromEnv for HouseKeeperConfig {
 
 #[cfg(test)]
 mod tests {
+    use micro_config::configs::house_keeper::{BlobRetentionPolicy, L1BatchRetentionStage};
+
     use super::*;
     use crate::test_utils::EnvMutex;
 
@@ -30,6 +32,20 @@ mod tests {
             fri_prover_stats_reporting_interval_ms: 30_000,
             fri_proof_compressor_job_retrying_interval_ms: 30_000,
             fri_proof_compressor_stats_reporting_interval_ms: 30_000,
+            blobs_cleaning_interval_ms: Some(3_600_000),
+            blobs_cleaning_dry_run: true,
+            blobs_retention_policies: vec![
+                BlobRetentionPolicy {
+                    bucket: "proofs_fri".to_owned(),
+                    stage: L1BatchRetentionStage::Executed,
+                    retention_days: 7,
+                },
+                BlobRetentionPolicy {
+                    bucket: "prover_jobs_fri".to_owned(),
+                    stage: L1BatchRetentionStage::Proven,
+                    retention_days: 1,
+                },
+            ],
         }
     }
 
@@ -50,6 +66,9 @@ mod tests {
             HOUSE_KEEPER_FRI_PROVER_STATS_REPORTING_INTERVAL_MS="30000"
             HOUSE_KEEPER_FRI_PROOF_COMPRESSOR_STATS_REPORTING_INTERVAL_MS="30000"
             HOUSE_KEEPER_FRI_PROOF_COMPRESSOR_JOB_RETRYING_INTERVAL_MS="30000"
+            HOUSE_KEEPER_BLOBS_CLEANING_INTERVAL_MS="3600000"
+            HOUSE_KEEPER_BLOBS_CLEANING_DRY_RUN="true"
+            HOUSE_KEEPER_BLOBS_RETENTION_POLICIES="proofs_fri:executed:7,prover_jobs_fri:proven:1"
         "#;
         lock.set_env(config);
 
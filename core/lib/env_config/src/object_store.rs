@@ -40,6 +40,30 @@ impl FromEnv for SnapshotsObjectStoreConfig {
     }
 }
 
+/// Wrapper for `ObjectStoreConfig` that allows loading the config of the secondary store
+/// for the mirrored object store using `MIRROR_` prefix.
+#[derive(Debug)]
+pub struct MirrorObjectStoreConfig(pub ObjectStoreConfig);
+
+impl FromEnv for MirrorObjectStoreConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        let config = envy_load("mirror_object_store", "MIRROR_OBJECT_STORE_")?;
+        Ok(Self(config))
+    }
+}
+
+/// Wrapper for `ObjectStoreConfig` that allows loading the config of the secondary store
+/// for the mirrored prover object store using `PROVER_MIRROR_` prefix.
+#[derive(Debug)]
+pub struct ProverMirrorObjectStoreConfig(pub ObjectStoreConfig);
+
+impl FromEnv for ProverMirrorObjectStoreConfig {
+    fn from_env() -> anyhow::Result<Self> {
+        let config = envy_load("prover_mirror_object_store", "PROVER_MIRROR_OBJECT_STORE_")?;
+        Ok(Self(config))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use micro_config::{configs::object_store::ObjectStoreMode, ObjectStoreConfig};
@@ -118,4 +142,34 @@ mod tests {
         let actual = SnapshotsObjectStoreConfig::from_env().unwrap().0;
         assert_eq!(actual, expected_config("/snapshots_base_url"));
     }
+
+    #[test]
+    fn mirror_bucket_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            MIRROR_OBJECT_STORE_BUCKET_BASE_URL="/mirror_base_url"
+            MIRROR_OBJECT_STORE_MODE="FileBacked"
+            MIRROR_OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            MIRROR_OBJECT_STORE_CREDENTIAL_FILE_PATH="/path/to/credentials.json"
+            MIRROR_OBJECT_STORE_MAX_RETRIES="5"
+        "#;
+        lock.set_env(config);
+        let actual = MirrorObjectStoreConfig::from_env().unwrap().0;
+        assert_eq!(actual, expected_config("/mirror_base_url"));
+    }
+
+    #[test]
+    fn prover_mirror_bucket_config_from_env() {
+        let mut lock = MUTEX.lock();
+        let config = r#"
+            PROVER_MIRROR_OBJECT_STORE_BUCKET_BASE_URL="/prover_mirror_base_url"
+            PROVER_MIRROR_OBJECT_STORE_MODE="FileBacked"
+            PROVER_MIRROR_OBJECT_STORE_FILE_BACKED_BASE_PATH="artifacts"
+            PROVER_MIRROR_OBJECT_STORE_CREDENTIAL_FILE_PATH="/path/to/credentials.json"
+            PROVER_MIRROR_OBJECT_STORE_MAX_RETRIES="5"
+        "#;
+        lock.set_env(config);
+        let actual = ProverMirrorObjectStoreConfig::from_env().unwrap().0;
+        assert_eq!(actual, expected_config("/prover_mirror_base_url"));
+    }
 }
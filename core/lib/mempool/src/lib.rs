@@ -4,6 +4,9 @@ mod tests;
 mod types;
 
 pub use crate::{
-    mempool_store::{MempoolInfo, MempoolStore},
-    types::L2TxFilter,
+    mempool_store::{L2TransactionCounts, MempoolInfo, MempoolStore},
+    types::{
+        is_fee_bump_sufficient, AccountMempoolSnapshot, L2TxFilter, MempoolEvictionPolicy,
+        MempoolOrdering,
+    },
 };
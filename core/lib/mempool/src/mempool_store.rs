@@ -1,15 +1,24 @@
-use std::collections::{hash_map, BTreeSet, HashMap, HashSet};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{btree_map, BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet},
+};
 
 use micro_types::{
-    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction,
+    l1::L1Tx, l2::L2Tx, Address, ExecuteTransactionCommon, Nonce, PriorityOpId, Transaction, H256,
+    U256,
 };
 
-use crate::types::{AccountTransactions, L2TxFilter, MempoolScore};
+use crate::types::{
+    AccountMempoolSnapshot, AccountTransactions, L2TxFilter, MempoolEvictionPolicy,
+    MempoolOrdering, MempoolScore,
+};
 
 #[derive(Debug)]
 pub struct MempoolInfo {
     pub stashed_accounts: Vec<Address>,
     pub purged_accounts: Vec<Address>,
+    /// Hashes of L2 transactions evicted from the mempool according to its eviction policy.
+    pub evicted_transactions: Vec<H256>,
 }
 
 #[derive(Debug)]
@@ -19,12 +28,22 @@ pub struct MempoolStats {
     pub l2_priority_queue_size: usize,
 }
 
+/// Number of L2 transactions in the mempool split by whether they can be executed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct L2TransactionCounts {
+    /// Transactions with nonces contiguous from the account nonce.
+    pub pending: usize,
+    /// Transactions blocked by a nonce gap.
+    pub queued: usize,
+}
+
 #[derive(Debug)]
 pub struct MempoolStore {
     /// Pending L1 transactions
     l1_transactions: HashMap<PriorityOpId, L1Tx>,
-    /// Pending L2 transactions grouped by initiator address
-    l2_transactions_per_account: HashMap<Address, AccountTransactions>,
+    /// Pending L2 transactions grouped by initiator address. Ordered by address, so that snapshots
+    /// can be taken without sorting all accounts.
+    l2_transactions_per_account: BTreeMap<Address, AccountTransactions>,
     /// Global priority queue for L2 transactions. Used for scoring
     l2_priority_queue: BTreeSet<MempoolScore>,
     /// Next priority operation
@@ -33,21 +52,50 @@ pub struct MempoolStore {
     /// Number of L2 transactions in the mempool.
     size: u64,
     capacity: u64,
+    /// Ordering of L2 transactions from different accounts
+    ordering: MempoolOrdering,
+    /// Minimum fee increase (in percent) for a transaction to replace a pending one with the same nonce
+    min_replacement_fee_bump_percent: u64,
+    /// Policy for evicting L2 transactions once the capacity is reached
+    eviction_policy: MempoolEvictionPolicy,
 }
 
 impl MempoolStore {
     pub fn new(next_priority_id: PriorityOpId, capacity: u64) -> Self {
         Self {
             l1_transactions: HashMap::new(),
-            l2_transactions_per_account: HashMap::new(),
+            l2_transactions_per_account: BTreeMap::new(),
             l2_priority_queue: BTreeSet::new(),
             next_priority_id,
             stashed_accounts: vec![],
             size: 0,
             capacity,
+            ordering: MempoolOrdering::Fifo,
+            min_replacement_fee_bump_percent: 0,
+            eviction_policy: MempoolEvictionPolicy::Disabled,
         }
     }
 
+    /// Sets the ordering of L2 transactions from different accounts. FIFO ordering is used by default.
+    pub fn with_ordering(mut self, ordering: MempoolOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Sets the minimum fee increase (in percent) for a transaction to replace a pending transaction
+    /// with the same initiator and nonce. By default, replacements are not required to increase fees.
+    pub fn with_min_replacement_fee_bump(mut self, percent: u64) -> Self {
+        self.min_replacement_fee_bump_percent = percent;
+        self
+    }
+
+    /// Sets the policy for evicting L2 transactions once the mempool reaches its capacity.
+    /// By default, transactions are not evicted.
+    pub fn with_eviction_policy(mut self, policy: MempoolEvictionPolicy) -> Self {
+        self.eviction_policy = policy;
+        self
+    }
+
     /// Inserts batch of new transactions to mempool
     /// `initial_nonces` provides current committed nonce information to mempool
     /// variable is used only if account is not present in mempool yet and we have to bootstrap it
@@ -102,13 +150,14 @@ impl MempoolStore {
     ) {
         let account = transaction.initiator_account();
 
+        let min_fee_bump = self.min_replacement_fee_bump_percent;
         let metadata = match self.l2_transactions_per_account.entry(account) {
-            hash_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction),
-            hash_map::Entry::Vacant(entry) => {
+            btree_map::Entry::Occupied(mut txs) => txs.get_mut().insert(transaction, min_fee_bump),
+            btree_map::Entry::Vacant(entry) => {
                 let account_nonce = initial_nonces.get(&account).cloned().unwrap_or(Nonce(0));
                 entry
-                    .insert(AccountTransactions::new(account_nonce))
-                    .insert(transaction)
+                    .insert(AccountTransactions::new(account_nonce, self.ordering))
+                    .insert(transaction, min_fee_bump)
             }
         };
         if let Some(score) = metadata.previous_score {
@@ -141,19 +190,16 @@ impl MempoolStore {
 
         let mut removed = 0;
         // We want to fetch the next transaction that would match the fee requirements.
-        let tx_pointer = self
-            .l2_priority_queue
-            .iter()
-            .rfind(|el| el.matches_filter(filter))?
-            .clone();
+        let tx_pointer = self.find_next_l2_transaction(filter)?;
 
         // Stash all observed transactions that don't meet criteria
-        for stashed_pointer in self
-            .l2_priority_queue
-            .split_off(&tx_pointer)
+        let mut observed = self.l2_priority_queue.split_off(&tx_pointer);
+        observed.remove(&tx_pointer);
+        let (stashed, retained) = observed
             .into_iter()
-            .skip(1)
-        {
+            .partition::<Vec<_>, _>(|pointer| !pointer.matches_filter(filter));
+        self.l2_priority_queue.extend(retained);
+        for stashed_pointer in stashed {
             removed += self
                 .l2_transactions_per_account
                 .remove(&stashed_pointer.account)
@@ -179,6 +225,46 @@ impl MempoolStore {
         Some(transaction.into())
     }
 
+    /// Finds the score of the L2 transaction to be executed next among transactions matching the filter.
+    ///
+    /// With the FIFO ordering, this is the highest-ranked matching score. With the priority fee ordering,
+    /// transactions are ranked by their effective tip given the base fee from the filter; since the priority fee
+    /// in the score is an upper bound for the effective tip, only the top of the queue needs to be scanned.
+    fn find_next_l2_transaction(&self, filter: &L2TxFilter) -> Option<MempoolScore> {
+        let mut matching = self
+            .l2_priority_queue
+            .iter()
+            .rev()
+            .filter(|el| el.matches_filter(filter));
+        let mut best = matching.next()?;
+        if self.ordering == MempoolOrdering::Fifo {
+            return Some(best.clone());
+        }
+
+        let base_fee = filter.fee_per_gas;
+        let mut best_tip = best.effective_tip(base_fee);
+        for el in matching {
+            if el.priority_fee < best_tip {
+                break; // No remaining transaction can offer a higher effective tip
+            }
+            let tip = el.effective_tip(base_fee);
+            let is_better = match tip.cmp(&best_tip) {
+                Ordering::Greater => true,
+                Ordering::Less => false,
+                // Ties are broken in the same way as in the queue ordering.
+                Ordering::Equal => {
+                    (Reverse(el.received_at_ms), el.account)
+                        > (Reverse(best.received_at_ms), best.account)
+                }
+            };
+            if is_better {
+                best = el;
+                best_tip = tip;
+            }
+        }
+        Some(best.clone())
+    }
+
     /// When a state_keeper starts the block over after a rejected transaction,
     /// we have to rollback the nonces/ids in the mempool and
     /// reinsert the transactions from the block back into mempool.
@@ -206,9 +292,11 @@ impl MempoolStore {
     }
 
     pub fn get_mempool_info(&mut self) -> MempoolInfo {
+        let purged_accounts = self.gc();
         MempoolInfo {
             stashed_accounts: std::mem::take(&mut self.stashed_accounts),
-            purged_accounts: self.gc(),
+            purged_accounts,
+            evicted_transactions: self.evict(),
         }
     }
 
@@ -220,6 +308,48 @@ impl MempoolStore {
         }
     }
 
+    /// Returns a snapshot of L2 transactions of the specified account, or `None` if the account
+    /// has no transactions in the mempool. At most `limit` transactions with the lowest nonces are returned.
+    pub fn account_snapshot(
+        &self,
+        account: Address,
+        limit: usize,
+    ) -> Option<AccountMempoolSnapshot> {
+        self.l2_transactions_per_account
+            .get(&account)
+            .map(|txs| txs.snapshot(account, limit))
+    }
+
+    /// Returns snapshots of L2 transactions for all accounts in the mempool, ordered by account address.
+    /// At most `limit` transactions are returned in total; the snapshot of the last returned account
+    /// may be truncated.
+    pub fn snapshot(&self, limit: usize) -> Vec<AccountMempoolSnapshot> {
+        let mut tx_count = 0;
+        let mut snapshots = vec![];
+        for (&account, txs) in &self.l2_transactions_per_account {
+            if tx_count >= limit {
+                break;
+            }
+            let snapshot = txs.snapshot(account, limit - tx_count);
+            tx_count += snapshot.pending.len() + snapshot.queued.len();
+            snapshots.push(snapshot);
+        }
+        snapshots
+    }
+
+    /// Returns the number of executable and nonce-gapped L2 transactions in the mempool.
+    pub fn l2_transaction_counts(&self) -> L2TransactionCounts {
+        self.l2_transactions_per_account.values().fold(
+            L2TransactionCounts::default(),
+            |mut counts, txs| {
+                let pending_count = txs.pending_count();
+                counts.pending += pending_count;
+                counts.queued += txs.len() - pending_count;
+                counts
+            },
+        )
+    }
+
     fn gc(&mut self) -> Vec<Address> {
         if self.size >= self.capacity {
             let index: HashSet<_> = self
@@ -240,4 +370,69 @@ impl MempoolStore {
         }
         vec![]
     }
+    /// Evicts L2 transactions according to the eviction policy until the mempool size fits into its capacity.
+    /// Should be called after `gc()`, so that accounts blocked by nonce gaps are purged first.
+    fn evict(&mut self) -> Vec<H256> {
+        if self.size <= self.capacity || self.eviction_policy == MempoolEvictionPolicy::Disabled {
+            return vec![];
+        }
+
+        let policy = self.eviction_policy;
+        let mut candidates: BinaryHeap<_> = self
+            .l2_transactions_per_account
+            .iter()
+            .filter_map(|(&account, txs)| {
+                let candidate = EvictionCandidate::new(policy, account, txs.last()?);
+                Some(Reverse(candidate))
+            })
+            .collect();
+
+        let mut evicted = vec![];
+        while self.size > self.capacity {
+            let Some(Reverse(candidate)) = candidates.pop() else {
+                break;
+            };
+            let txs = self
+                .l2_transactions_per_account
+                .get_mut(&candidate.account)
+                .expect("mempool: dangling eviction candidate");
+            let (transaction, score) = txs
+                .remove_last()
+                .expect("mempool: eviction candidate without transactions");
+            if let Some(score) = score {
+                self.l2_priority_queue.remove(&score);
+            }
+            if let Some(next_candidate) = txs.last() {
+                let next_candidate =
+                    EvictionCandidate::new(policy, candidate.account, next_candidate);
+                candidates.push(Reverse(next_candidate));
+            }
+            self.size -= 1;
+            evicted.push(transaction.hash());
+        }
+        evicted
+    }
+}
+
+/// Transaction considered for eviction. Candidates are compared by their eviction priority;
+/// the candidate with the lowest priority is evicted first.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct EvictionCandidate {
+    priority: (U256, u64),
+    account: Address,
+}
+
+impl EvictionCandidate {
+    fn new(policy: MempoolEvictionPolicy, account: Address, transaction: &L2Tx) -> Self {
+        let received_at_ms = transaction.received_timestamp_ms;
+        let priority = match policy {
+            MempoolEvictionPolicy::Disabled => (U256::zero(), 0),
+            MempoolEvictionPolicy::LowestFee => (
+                transaction.common_data.fee.max_fee_per_gas,
+                u64::MAX - received_at_ms,
+            ),
+            MempoolEvictionPolicy::Oldest => (U256::zero(), received_at_ms),
+        };
+        Self { priority, account }
+    }
 }
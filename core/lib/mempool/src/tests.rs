@@ -11,8 +11,12 @@ use micro_types::{
     Address, Execute, ExecuteTransactionCommon, L1TxCommonData, Nonce, PriorityOpId, Transaction,
     H256, U256,
 };
+use rand::{rngs::StdRng, Rng, SeedableRng};
 
-use crate::{mempool_store::MempoolStore, types::L2TxFilter};
+use crate::{
+    mempool_store::MempoolStore,
+    types::{is_fee_bump_sufficient, L2TxFilter, MempoolEvictionPolicy, MempoolOrdering},
+};
 
 #[test]
 fn basic_flow() {
@@ -365,6 +369,324 @@ fn mempool_capacity() {
     );
 }
 
+#[test]
+fn mempool_capacity_without_eviction() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 2);
+    let account = Address::random();
+    let transactions = (0..3)
+        .map(|nonce| gen_l2_tx(account, Nonce(nonce)))
+        .collect();
+    mempool.insert(transactions, HashMap::new());
+
+    let info = mempool.get_mempool_info();
+    assert!(info.purged_accounts.is_empty());
+    assert!(info.evicted_transactions.is_empty());
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+}
+
+#[test]
+fn evicting_lowest_fee_transactions() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 3)
+        .with_eviction_policy(MempoolEvictionPolicy::LowestFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let now = unix_timestamp_ms();
+    let mut transactions = vec![
+        gen_l2_tx_with_fee(account0, Nonce(0), 1, now),
+        gen_l2_tx_with_fee(account0, Nonce(1), 100, now + 1),
+        gen_l2_tx_with_fee(account1, Nonce(0), 10, now + 2),
+        gen_l2_tx_with_fee(account1, Nonce(1), 5, now + 3),
+        gen_l2_tx_with_fee(account1, Nonce(2), 5, now + 4),
+    ];
+    let hashes = set_unique_hashes(&mut transactions);
+    mempool.insert(transactions, HashMap::new());
+
+    // The transaction of `account0` with the lowest fee cannot be evicted without introducing a nonce gap,
+    // so the tail transactions of `account1` are evicted instead, starting from the newest one.
+    let info = mempool.get_mempool_info();
+    assert_eq!(info.evicted_transactions, [hashes[4], hashes[3]]);
+    assert_eq!(mempool.stats().l2_transaction_count, 3);
+
+    let filter = L2TxFilter::default();
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 1));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(mempool.next_transaction(&filter), None);
+}
+
+#[test]
+fn evicting_oldest_transactions() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 1).with_eviction_policy(MempoolEvictionPolicy::Oldest);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let now = unix_timestamp_ms();
+    let mut transactions = vec![
+        gen_l2_tx_with_fee(account0, Nonce(0), 100, now),
+        gen_l2_tx_with_fee(account1, Nonce(0), 1, now + 1),
+    ];
+    let hashes = set_unique_hashes(&mut transactions);
+    mempool.insert(transactions, HashMap::new());
+
+    let info = mempool.get_mempool_info();
+    assert_eq!(info.evicted_transactions, [hashes[0]]);
+    let filter = L2TxFilter::default();
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(mempool.next_transaction(&filter), None);
+    assert!(!mempool.has_next(&filter));
+}
+
+#[test]
+fn priority_fee_ordering() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering(MempoolOrdering::PriorityFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account0, Nonce(0), 1, now),
+            // High fee of the second transaction shouldn't allow it to overtake the first one.
+            gen_l2_tx_with_fee(account0, Nonce(1), 100, now),
+            gen_l2_tx_with_fee(account1, Nonce(0), 10, now + 1),
+            gen_l2_tx_with_fee(account2, Nonce(0), 10, now + 2),
+        ],
+        HashMap::new(),
+    );
+
+    let filter = L2TxFilter::default();
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account2, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 1));
+    assert_eq!(mempool.next_transaction(&filter), None);
+}
+
+#[test]
+fn priority_fee_ordering_uses_effective_tip() {
+    let mut mempool =
+        MempoolStore::new(PriorityOpId(0), 100).with_ordering(MempoolOrdering::PriorityFee);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let account2 = Address::random();
+    let now = unix_timestamp_ms();
+    let transactions = vec![
+        // Effective tip is 5 since the max fee is close to the base fee.
+        gen_l2_tx_with_fees(account0, Nonce(0), 105, 100, now),
+        // Effective tip is 20.
+        gen_l2_tx_with_fees(account1, Nonce(0), 200, 20, now + 1),
+        // Effective tip is 5; loses to `account0` because it is received later.
+        gen_l2_tx_with_fees(account2, Nonce(0), 300, 5, now + 2),
+    ];
+    mempool.insert(transactions, HashMap::new());
+
+    let filter = L2TxFilter {
+        fee_per_gas: 100,
+        ..L2TxFilter::default()
+    };
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account2, 0));
+    assert_eq!(mempool.next_transaction(&filter), None);
+    assert!(mempool.get_mempool_info().stashed_accounts.is_empty());
+}
+
+#[test]
+fn fifo_ordering_ignores_fees() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::random();
+    let account1 = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account0, Nonce(0), 1, now),
+            gen_l2_tx_with_fee(account1, Nonce(0), 100, now + 1),
+        ],
+        HashMap::new(),
+    );
+
+    let filter = L2TxFilter::default();
+    assert_eq!(view(mempool.next_transaction(&filter)), (account0, 0));
+    assert_eq!(view(mempool.next_transaction(&filter)), (account1, 0));
+}
+
+#[test]
+fn replacement_requires_fee_bump() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100)
+        .with_ordering(MempoolOrdering::PriorityFee)
+        .with_min_replacement_fee_bump(10);
+    let account = Address::random();
+    let other_account = Address::random();
+    let now = unix_timestamp_ms();
+    mempool.insert(
+        vec![
+            gen_l2_tx_with_fee(account, Nonce(0), 100, now),
+            gen_l2_tx_with_fee(other_account, Nonce(0), 105, now),
+        ],
+        HashMap::new(),
+    );
+
+    // Underpriced replacement is ignored.
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), 109, now + 1)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_transaction_count, 2);
+    assert_eq!(mempool.stats().l2_priority_queue_size, 2);
+    let filter = L2TxFilter::default();
+    assert_eq!(
+        view_with_fee(mempool.next_transaction(&filter)),
+        (other_account, 0, 105)
+    );
+
+    // Sufficiently priced replacement is accepted and bumps the transaction in the priority queue.
+    mempool.insert(
+        vec![gen_l2_tx_with_fee(account, Nonce(0), 110, now + 2)],
+        HashMap::new(),
+    );
+    assert_eq!(mempool.stats().l2_priority_queue_size, 1);
+    assert_eq!(
+        view_with_fee(mempool.next_transaction(&filter)),
+        (account, 0, 110)
+    );
+    assert_eq!(mempool.next_transaction(&filter), None);
+}
+
+#[test]
+fn fee_bump_check() {
+    let fee = |max_fee_per_gas: u64, max_priority_fee_per_gas: u64| Fee {
+        max_fee_per_gas: max_fee_per_gas.into(),
+        max_priority_fee_per_gas: max_priority_fee_per_gas.into(),
+        ..Fee::default()
+    };
+
+    assert!(is_fee_bump_sufficient(&fee(100, 10), &fee(100, 10), 0));
+    assert!(!is_fee_bump_sufficient(&fee(100, 10), &fee(109, 11), 10));
+    assert!(!is_fee_bump_sufficient(&fee(100, 10), &fee(110, 10), 10));
+    assert!(is_fee_bump_sufficient(&fee(100, 10), &fee(110, 11), 10));
+    assert!(is_fee_bump_sufficient(&fee(0, 0), &fee(0, 0), 10));
+    let max_fee = fee(u64::MAX, u64::MAX);
+    assert!(!is_fee_bump_sufficient(&max_fee, &max_fee, 10));
+}
+
+/// Randomized test checking that the mempool never returns transactions with nonce gaps
+/// regardless of the ordering, replacements and rollbacks.
+#[test]
+fn no_nonce_gaps_with_random_operations() {
+    const ACCOUNT_COUNT: usize = 5;
+    const MAX_NONCE: u32 = 10;
+
+    for ordering in [MempoolOrdering::Fifo, MempoolOrdering::PriorityFee] {
+        for seed in 0..50 {
+            let mut rng = StdRng::seed_from_u64(seed);
+            let mut mempool = MempoolStore::new(PriorityOpId(0), 1_000)
+                .with_ordering(ordering)
+                .with_min_replacement_fee_bump(10);
+            let accounts: Vec<_> = (0..ACCOUNT_COUNT).map(|_| Address::random()).collect();
+            // Nonces that the state keeper expects for each account.
+            let mut expected_nonces: HashMap<_, _> =
+                accounts.iter().map(|&account| (account, 0_u32)).collect();
+            let mut last_executed = None;
+
+            for _ in 0..200 {
+                match rng.gen_range(0..10) {
+                    0..=4 => {
+                        let transactions = (0..rng.gen_range(1..5))
+                            .map(|_| {
+                                let account = accounts[rng.gen_range(0..ACCOUNT_COUNT)];
+                                let nonce = Nonce(rng.gen_range(0..MAX_NONCE));
+                                let fee = rng.gen_range(1..1_000);
+                                let received_at = rng.gen_range(0..1_000);
+                                gen_l2_tx_with_fee(account, nonce, fee, received_at)
+                            })
+                            .collect();
+                        mempool.insert(transactions, HashMap::new());
+                    }
+                    5..=8 => {
+                        let Some(tx) = mempool.next_transaction(&L2TxFilter::default()) else {
+                            continue;
+                        };
+                        let (account, nonce) = view(Some(tx.clone()));
+                        let expected_nonce = expected_nonces.get_mut(&account).unwrap();
+                        assert_eq!(nonce, *expected_nonce, "nonce gap for {account:?}");
+                        *expected_nonce += 1;
+                        last_executed = Some(tx);
+                    }
+                    _ => {
+                        // Roll back the last executed transaction, as the state keeper does on rejection.
+                        if let Some(tx) = last_executed.take() {
+                            let (account, nonce) = view(Some(tx.clone()));
+                            mempool.rollback(&tx);
+                            let expected_nonce = expected_nonces.get_mut(&account).unwrap();
+                            *expected_nonce = (*expected_nonce).min(nonce);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn account_snapshots() {
+    let mut mempool = MempoolStore::new(PriorityOpId(0), 100);
+    let account0 = Address::repeat_byte(0x11);
+    let account1 = Address::repeat_byte(0x22);
+    let transactions = vec![
+        gen_l2_tx(account0, Nonce(1)),
+        gen_l2_tx(account0, Nonce(0)),
+        gen_l2_tx(account0, Nonce(3)),
+        gen_l2_tx(account1, Nonce(5)),
+    ];
+    mempool.insert(transactions, HashMap::from([(account1, Nonce(4))]));
+
+    let snapshot = mempool.account_snapshot(account0, 100).unwrap();
+    assert_eq!(snapshot.nonce, Nonce(0));
+    let pending_nonces: Vec<_> = snapshot
+        .pending
+        .iter()
+        .map(|tx| tx.common_data.nonce)
+        .collect();
+    assert_eq!(pending_nonces, [Nonce(0), Nonce(1)]);
+    assert_eq!(snapshot.queued.len(), 1);
+    assert_eq!(snapshot.queued[0].common_data.nonce, Nonce(3));
+
+    let snapshot = mempool.account_snapshot(account1, 100).unwrap();
+    assert_eq!(snapshot.nonce, Nonce(4));
+    assert!(snapshot.pending.is_empty());
+    assert_eq!(snapshot.queued.len(), 1);
+    assert!(mempool.account_snapshot(Address::zero(), 100).is_none());
+
+    // Transactions with the lowest nonces are included into a capped snapshot.
+    let snapshot = mempool.account_snapshot(account0, 1).unwrap();
+    assert_eq!(snapshot.pending.len(), 1);
+    assert_eq!(snapshot.pending[0].common_data.nonce, Nonce(0));
+    assert!(snapshot.queued.is_empty());
+
+    let counts = mempool.l2_transaction_counts();
+    assert_eq!((counts.pending, counts.queued), (2, 2));
+    let snapshots = mempool.snapshot(100);
+    let accounts: Vec<_> = snapshots.iter().map(|snapshot| snapshot.account).collect();
+    assert_eq!(accounts, [account0, account1]);
+    let snapshots = mempool.snapshot(2);
+    assert_eq!(snapshots.len(), 1);
+    assert_eq!(snapshots[0].pending.len(), 2);
+    assert!(snapshots[0].queued.is_empty());
+    let snapshots = mempool.snapshot(4);
+    assert_eq!(snapshots.len(), 2);
+
+    // Executing a transaction shifts the account nonce.
+    assert_eq!(
+        view(mempool.next_transaction(&L2TxFilter::default())),
+        (account0, 0)
+    );
+    let snapshot = mempool.account_snapshot(account0, 100).unwrap();
+    assert_eq!(snapshot.nonce, Nonce(1));
+    assert_eq!(snapshot.pending.len(), 1);
+    assert_eq!(snapshot.queued.len(), 1);
+}
+
 fn gen_l2_tx(address: Address, nonce: Nonce) -> Transaction {
     gen_l2_tx_with_timestamp(address, nonce, unix_timestamp_ms())
 }
@@ -384,6 +706,55 @@ fn gen_l2_tx_with_timestamp(address: Address, nonce: Nonce, received_at_ms: u64)
     txn.into()
 }
 
+fn gen_l2_tx_with_fee(
+    address: Address,
+    nonce: Nonce,
+    fee_per_gas: u64,
+    received_at_ms: u64,
+) -> Transaction {
+    gen_l2_tx_with_fees(address, nonce, fee_per_gas, fee_per_gas, received_at_ms)
+}
+
+fn gen_l2_tx_with_fees(
+    address: Address,
+    nonce: Nonce,
+    max_fee_per_gas: u64,
+    max_priority_fee_per_gas: u64,
+    received_at_ms: u64,
+) -> Transaction {
+    let fee = Fee {
+        max_fee_per_gas: max_fee_per_gas.into(),
+        max_priority_fee_per_gas: max_priority_fee_per_gas.into(),
+        ..Fee::default()
+    };
+    let mut txn = L2Tx::new(
+        Address::default(),
+        Vec::new(),
+        nonce,
+        fee,
+        address,
+        U256::zero(),
+        None,
+        Default::default(),
+    );
+    txn.received_timestamp_ms = received_at_ms;
+    txn.into()
+}
+
+/// Assigns unique hashes to L2 transactions and returns them.
+fn set_unique_hashes(transactions: &mut [Transaction]) -> Vec<H256> {
+    let mut hashes = vec![];
+    for (i, tx) in transactions.iter_mut().enumerate() {
+        let ExecuteTransactionCommon::L2(data) = &mut tx.common_data else {
+            unreachable!("expected L2 transaction");
+        };
+        let hash = H256::from_low_u64_be(i as u64 + 1);
+        data.set_input(vec![], hash);
+        hashes.push(hash);
+    }
+    hashes
+}
+
 fn gen_l1_tx(priority_id: PriorityOpId) -> Transaction {
     let execute = Execute {
         contract_address: Address::repeat_byte(0x11),
@@ -422,6 +793,18 @@ fn view(transaction: Option<Transaction>) -> (Address, u32) {
     (tx.initiator_account(), tx.nonce().unwrap().0)
 }
 
+fn view_with_fee(transaction: Option<Transaction>) -> (Address, u32, u64) {
+    let tx = transaction.unwrap();
+    let ExecuteTransactionCommon::L2(data) = &tx.common_data else {
+        unreachable!("expected L2 transaction");
+    };
+    (
+        tx.initiator_account(),
+        data.nonce.0,
+        data.fee.max_fee_per_gas.as_u64(),
+    )
+}
+
 fn gen_transactions_for_filtering(input: Vec<(Address, Nonce, u64, u32)>) -> Vec<Transaction> {
     // Helper function to conveniently set `max_gas_per_pubdata_byte`.
     fn set_max_gas_per_pubdata_byte(tx: &mut Transaction, value: u32) {
@@ -1,38 +1,60 @@
-use std::{cmp::Ordering, collections::HashMap};
+use std::{cmp::Ordering, collections::BTreeMap};
 
 use micro_types::{fee::Fee, l2::L2Tx, Address, Nonce, Transaction, U256};
 
 /// Pending mempool transactions of account
 #[derive(Debug)]
 pub(crate) struct AccountTransactions {
-    /// transactions that belong to given account keyed by transaction nonce
-    transactions: HashMap<Nonce, L2Tx>,
+    /// transactions that belong to given account keyed by transaction nonce; ordered so that the transaction
+    /// with the highest nonce (the eviction candidate) can be accessed cheaply
+    transactions: BTreeMap<Nonce, L2Tx>,
     /// account nonce in mempool
     /// equals to committed nonce in db + number of transactions sent to state keeper
     nonce: Nonce,
+    ordering: MempoolOrdering,
 }
 
 impl AccountTransactions {
-    pub fn new(nonce: Nonce) -> Self {
+    pub fn new(nonce: Nonce, ordering: MempoolOrdering) -> Self {
         Self {
-            transactions: HashMap::new(),
+            transactions: BTreeMap::new(),
             nonce,
+            ordering,
         }
     }
 
-    /// Inserts new transaction for given account. Returns insertion metadata
-    pub fn insert(&mut self, transaction: L2Tx) -> InsertionMetadata {
+    /// Inserts new transaction for given account. Returns insertion metadata.
+    /// A pending transaction with the same nonce is only replaced if the new transaction
+    /// bumps fees by at least `min_fee_bump_percent`.
+    pub fn insert(&mut self, transaction: L2Tx, min_fee_bump_percent: u64) -> InsertionMetadata {
         let mut metadata = InsertionMetadata::default();
         let nonce = transaction.common_data.nonce;
         // skip insertion if transaction is old
         if nonce < self.nonce {
             return metadata;
         }
-        let new_score = Self::score_for_transaction(&transaction);
+        if let Some(pending) = self.transactions.get(&nonce) {
+            let is_same_tx = pending.hash() == transaction.hash();
+            let fee_bump_ok = is_fee_bump_sufficient(
+                &pending.common_data.fee,
+                &transaction.common_data.fee,
+                min_fee_bump_percent,
+            );
+            if !is_same_tx && !fee_bump_ok {
+                tracing::debug!(
+                    "Transaction {:?} is underpriced to replace pending transaction {:?}",
+                    transaction.hash(),
+                    pending.hash()
+                );
+                return metadata;
+            }
+        }
+
+        let new_score = self.score_for_transaction(&transaction);
         let previous_score = self
             .transactions
             .insert(nonce, transaction)
-            .map(|tx| Self::score_for_transaction(&tx));
+            .map(|tx| self.score_for_transaction(&tx));
         metadata.is_new = previous_score.is_none();
         if nonce == self.nonce {
             metadata.new_score = Some(new_score);
@@ -52,7 +74,7 @@ impl AccountTransactions {
         let score = self
             .transactions
             .get(&self.nonce)
-            .map(Self::score_for_transaction);
+            .map(|tx| self.score_for_transaction(tx));
         (transaction, score)
     }
 
@@ -65,28 +87,129 @@ impl AccountTransactions {
         self.nonce = self.nonce.min(tx_nonce);
         self.transactions
             .get(&(tx_nonce + 1))
-            .map(Self::score_for_transaction)
+            .map(|tx| self.score_for_transaction(tx))
     }
 
     pub fn len(&self) -> usize {
         self.transactions.len()
     }
 
-    fn score_for_transaction(transaction: &L2Tx) -> MempoolScore {
+    /// Returns the transaction with the highest nonce. This is the only transaction of the account
+    /// that can be removed without introducing a nonce gap.
+    pub fn last(&self) -> Option<&L2Tx> {
+        self.transactions.values().next_back()
+    }
+
+    /// Removes the transaction with the highest nonce. Returns the removed transaction and, if it was
+    /// the next transaction to be executed for the account, its score.
+    pub fn remove_last(&mut self) -> Option<(L2Tx, Option<MempoolScore>)> {
+        let (nonce, transaction) = self.transactions.pop_last()?;
+        let score = (nonce == self.nonce).then(|| self.score_for_transaction(&transaction));
+        Some((transaction, score))
+    }
+
+    /// Returns the number of executable transactions, i.e. ones with nonces contiguous from the current account nonce.
+    pub fn pending_count(&self) -> usize {
+        let mut expected_nonce = self.nonce;
+        let mut count = 0;
+        while self.transactions.contains_key(&expected_nonce) {
+            expected_nonce += 1;
+            count += 1;
+        }
+        count
+    }
+
+    /// Splits transactions of the account into executable ones (with nonces contiguous
+    /// from the current account nonce) and ones blocked by a nonce gap. At most `limit` transactions
+    /// with the lowest nonces are included into the snapshot.
+    pub fn snapshot(&self, account: Address, limit: usize) -> AccountMempoolSnapshot {
+        // All transactions have nonces not lower than the account nonce, so pending transactions go first.
+        let mut transactions = self.transactions.values().take(limit).cloned();
+        let pending = transactions.by_ref().take(self.pending_count()).collect();
+        let queued = transactions.collect();
+        AccountMempoolSnapshot {
+            account,
+            nonce: self.nonce,
+            pending,
+            queued,
+        }
+    }
+
+    fn score_for_transaction(&self, transaction: &L2Tx) -> MempoolScore {
+        let fee_data = transaction.common_data.fee.clone();
+        let priority_fee = match self.ordering {
+            MempoolOrdering::Fifo => U256::zero(),
+            MempoolOrdering::PriorityFee => fee_data
+                .max_priority_fee_per_gas
+                .min(fee_data.max_fee_per_gas),
+        };
         MempoolScore {
             account: transaction.initiator_account(),
             received_at_ms: transaction.received_timestamp_ms,
-            fee_data: transaction.common_data.fee.clone(),
+            priority_fee,
+            fee_data,
         }
     }
 }
 
-/// Mempool score of transaction. Used to prioritize L2 transactions in mempool
-/// Currently trivial ordering is used based on received at timestamp
+/// Snapshot of mempool transactions belonging to a single account.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountMempoolSnapshot {
+    pub account: Address,
+    /// Next nonce of the account expected by the mempool.
+    pub nonce: Nonce,
+    /// Transactions that can be executed in order, sorted by nonce.
+    pub pending: Vec<L2Tx>,
+    /// Transactions that cannot be executed because of a nonce gap, sorted by nonce.
+    pub queued: Vec<L2Tx>,
+}
+
+/// Policy for ordering L2 transactions from different accounts. Transactions from the same account
+/// are always returned in the nonce order, so the ordering never introduces nonce gaps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MempoolOrdering {
+    /// Transactions are ordered by the time they were received.
+    #[default]
+    Fifo,
+    /// Transactions offering a higher effective tip go first; ties are broken by the receive time.
+    /// The effective tip is `min(max_priority_fee_per_gas, max_fee_per_gas - base_fee)`, where the base fee
+    /// is the fee per gas requested by the state keeper.
+    PriorityFee,
+}
+
+/// Policy for evicting L2 transactions once the mempool reaches its capacity. Only the transaction
+/// with the highest nonce of an account may be evicted, so eviction never introduces nonce gaps.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MempoolEvictionPolicy {
+    /// Transactions are not evicted; only accounts blocked by nonce gaps are purged from the full mempool.
+    #[default]
+    Disabled,
+    /// Transactions with the lowest `max_fee_per_gas` are evicted first; ties are broken by evicting
+    /// the most recently received transaction.
+    LowestFee,
+    /// Transactions received earliest are evicted first.
+    Oldest,
+}
+
+/// Checks whether a pending transaction with `pending_fee` may be replaced by a transaction
+/// with `new_fee`. Both `max_fee_per_gas` and `max_priority_fee_per_gas` must be increased
+/// by at least `min_bump_percent`.
+pub fn is_fee_bump_sufficient(pending_fee: &Fee, new_fee: &Fee, min_bump_percent: u64) -> bool {
+    let bumped = |value: U256| value.saturating_mul(U256::from(100 + min_bump_percent)) / 100;
+    new_fee.max_fee_per_gas >= bumped(pending_fee.max_fee_per_gas)
+        && new_fee.max_priority_fee_per_gas >= bumped(pending_fee.max_priority_fee_per_gas)
+}
+
+/// Mempool score of transaction. Used to prioritize L2 transactions in mempool.
+/// Transactions are ordered by the priority fee (which is always zero for FIFO ordering),
+/// then by the received at timestamp.
 #[derive(Eq, PartialEq, Clone, Debug, Hash)]
 pub struct MempoolScore {
     pub account: Address,
     pub received_at_ms: u64,
+    /// Priority fee per gas offered by the transaction capped by its `max_fee_per_gas`; zero if the mempool
+    /// uses FIFO ordering. This is an upper bound for the effective tip of the transaction.
+    pub priority_fee: U256,
     // Not used for actual scoring, but state keeper would request
     // transactions that have acceptable fee values (so transactions
     // with fee too low would be ignored until prices go down).
@@ -99,10 +222,24 @@ impl MempoolScore {
         self.fee_data.max_fee_per_gas >= U256::from(filter.fee_per_gas)
             && self.fee_data.gas_per_pubdata_limit >= U256::from(filter.gas_per_pubdata)
     }
+
+    /// Returns the effective tip per gas of the transaction given the `base_fee` per gas, capped by
+    /// the priority fee from the score (i.e., zero for FIFO ordering).
+    pub fn effective_tip(&self, base_fee: u64) -> U256 {
+        let max_tip = self
+            .fee_data
+            .max_fee_per_gas
+            .saturating_sub(U256::from(base_fee));
+        self.priority_fee.min(max_tip)
+    }
 }
 
 impl Ord for MempoolScore {
     fn cmp(&self, other: &MempoolScore) -> Ordering {
+        match self.priority_fee.cmp(&other.priority_fee) {
+            Ordering::Equal => {}
+            ordering => return ordering,
+        }
         match self.received_at_ms.cmp(&other.received_at_ms).reverse() {
             Ordering::Equal => {}
             ordering => return ordering,
@@ -158,6 +295,7 @@ mod tests {
         let score = MempoolScore {
             account: Address::random(),
             received_at_ms: Default::default(), // Not important
+            priority_fee: Default::default(),   // Not important
             fee_data: Fee {
                 gas_limit: Default::default(), // Not important
                 max_fee_per_gas: U256::from(MAX_FEE_PER_GAS),
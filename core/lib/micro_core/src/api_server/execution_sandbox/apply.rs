@@ -30,6 +30,7 @@ use multivm::{
 };
 
 use super::{
+    storage::StorageWithOverrides,
     vm_metrics::{self, SandboxStage, SANDBOX_METRICS},
     BlockArgs, TxExecutionArgs, TxSharedArgs, VmPermit,
 };
@@ -43,7 +44,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
     tx: Transaction,
     block_args: BlockArgs,
     apply: impl FnOnce(
-        &mut VmInstance<StorageView<PostgresStorage<'_>>, HistoryDisabled>,
+        &mut VmInstance<StorageView<StorageWithOverrides<PostgresStorage<'_>>>, HistoryDisabled>,
         Transaction,
     ) -> T,
 ) -> T {
@@ -128,6 +129,7 @@ pub(super) fn apply_vm_in_sandbox<T>(
 
     let storage = PostgresStorage::new(rt_handle.clone(), connection, state_l2_block_number, false)
         .with_caches(shared_args.caches);
+    let storage = StorageWithOverrides::new(storage, execution_args.storage_overrides.clone());
     let mut storage_view = StorageView::new(storage);
 
     let storage_view_setup_started_at = Instant::now();
@@ -13,7 +13,7 @@ use multivm::{
 };
 use tracing::{span, Level};
 
-use super::{apply, vm_metrics, ApiTracer, BlockArgs, TxSharedArgs, VmPermit};
+use super::{apply, vm_metrics, ApiTracer, BlockArgs, StorageOverrides, TxSharedArgs, VmPermit};
 
 #[derive(Debug)]
 pub(crate) struct TxExecutionArgs {
@@ -22,6 +22,7 @@ pub(crate) struct TxExecutionArgs {
     pub added_balance: U256,
     pub enforced_base_fee: Option<u64>,
     pub missed_storage_invocation_limit: usize,
+    pub storage_overrides: StorageOverrides,
 }
 
 impl TxExecutionArgs {
@@ -32,12 +33,14 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee: Some(tx.common_data.fee.max_fee_per_gas.as_u64()),
             missed_storage_invocation_limit: usize::MAX,
+            storage_overrides: StorageOverrides::default(),
         }
     }
 
     fn for_eth_call(
         enforced_base_fee: u64,
         vm_execution_cache_misses_limit: Option<usize>,
+        storage_overrides: StorageOverrides,
     ) -> Self {
         let missed_storage_invocation_limit = vm_execution_cache_misses_limit.unwrap_or(usize::MAX);
         Self {
@@ -46,6 +49,19 @@ impl TxExecutionArgs {
             added_balance: U256::zero(),
             enforced_base_fee: Some(enforced_base_fee),
             missed_storage_invocation_limit,
+            storage_overrides,
+        }
+    }
+
+    /// Arguments for re-executing transactions from a sealed miniblock.
+    fn for_replay(enforced_base_fee: u64) -> Self {
+        Self {
+            execution_mode: TxExecutionMode::VerifyExecute,
+            enforced_nonce: None,
+            added_balance: U256::zero(),
+            enforced_base_fee: Some(enforced_base_fee),
+            missed_storage_invocation_limit: usize::MAX,
+            storage_overrides: StorageOverrides::default(),
         }
     }
 
@@ -53,6 +69,7 @@ impl TxExecutionArgs {
         vm_execution_cache_misses_limit: Option<usize>,
         tx: &Transaction,
         base_fee: u64,
+        storage_overrides: StorageOverrides,
     ) -> Self {
         let missed_storage_invocation_limit = vm_execution_cache_misses_limit.unwrap_or(usize::MAX);
         // For L2 transactions we need to explicitly put enough balance into the account of the users
@@ -69,10 +86,12 @@ impl TxExecutionArgs {
             enforced_nonce: tx.nonce(),
             added_balance,
             enforced_base_fee: Some(base_fee),
+            storage_overrides,
         }
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn execute_tx_eth_call(
     vm_permit: VmPermit,
     shared_args: TxSharedArgs,
@@ -80,20 +99,17 @@ pub(crate) async fn execute_tx_eth_call(
     mut tx: L2Tx,
     block_args: BlockArgs,
     vm_execution_cache_misses_limit: Option<usize>,
+    storage_overrides: StorageOverrides,
     custom_tracers: Vec<ApiTracer>,
 ) -> VmExecutionResultAndLogs {
     let enforced_base_fee = tx.common_data.fee.max_fee_per_gas.as_u64();
-    let execution_args =
-        TxExecutionArgs::for_eth_call(enforced_base_fee, vm_execution_cache_misses_limit);
-
-    if tx.common_data.signature.is_empty() {
-        tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
-    }
+    let execution_args = TxExecutionArgs::for_eth_call(
+        enforced_base_fee,
+        vm_execution_cache_misses_limit,
+        storage_overrides,
+    );
 
-    // Protection against infinite-loop eth_calls and alike:
-    // limiting the amount of gas the call can use.
-    // We can't use BLOCK_ERGS_LIMIT here since the VM itself has some overhead.
-    tx.common_data.fee.gas_limit = ETH_CALL_GAS_LIMIT.into();
+    prepare_eth_call_tx(&mut tx);
     let (vm_result, _) = execute_tx_in_sandbox(
         vm_permit,
         shared_args,
@@ -108,6 +124,134 @@ pub(crate) async fn execute_tx_eth_call(
     vm_result
 }
 
+fn prepare_eth_call_tx(tx: &mut L2Tx) {
+    if tx.common_data.signature.is_empty() {
+        tx.common_data.signature = PackedEthSignature::default().serialize_packed().into();
+    }
+
+    // Protection against infinite-loop eth_calls and alike:
+    // limiting the amount of gas the call can use.
+    // We can't use BLOCK_ERGS_LIMIT here since the VM itself has some overhead.
+    tx.common_data.fee.gas_limit = ETH_CALL_GAS_LIMIT.into();
+}
+
+/// Executes a bundle of calls in a single VM instance on top of the specified block, so that each call
+/// observes the changes made by the preceding ones. Each call is inspected with the tracers provided alongside it.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn execute_tx_bundle_eth_call(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    connection_pool: ConnectionPool,
+    txs: Vec<(L2Tx, Vec<ApiTracer>)>,
+    block_args: BlockArgs,
+    vm_execution_cache_misses_limit: Option<usize>,
+    storage_overrides: StorageOverrides,
+) -> Vec<VmExecutionResultAndLogs> {
+    // The base fee is shared by all calls, so we choose it so that it doesn't exceed the fee of any call.
+    let Some(enforced_base_fee) = txs
+        .iter()
+        .map(|(tx, _)| tx.common_data.fee.max_fee_per_gas.as_u64())
+        .min()
+    else {
+        return vec![];
+    };
+    let execution_args = TxExecutionArgs::for_eth_call(
+        enforced_base_fee,
+        vm_execution_cache_misses_limit,
+        storage_overrides,
+    );
+    let txs: Vec<_> = txs
+        .into_iter()
+        .map(|(mut tx, tracers)| {
+            prepare_eth_call_tx(&mut tx);
+            (Transaction::from(tx), tracers)
+        })
+        .collect();
+    let first_tx = txs[0].0.clone();
+
+    tokio::task::spawn_blocking(move || {
+        let span = span!(Level::DEBUG, "execute_bundle_in_sandbox").entered();
+        let result = apply::apply_vm_in_sandbox(
+            vm_permit,
+            shared_args,
+            &execution_args,
+            &connection_pool,
+            first_tx,
+            block_args,
+            |vm, _| {
+                txs.into_iter()
+                    .map(|(tx, tracers)| {
+                        vm.push_transaction(tx);
+                        let storage_invocation_tracer =
+                            StorageInvocations::new(execution_args.missed_storage_invocation_limit);
+                        let tracers: Vec<_> = tracers
+                            .into_iter()
+                            .map(|tracer| tracer.into_boxed())
+                            .chain([storage_invocation_tracer.into_tracer_pointer()])
+                            .collect();
+                        vm.inspect(tracers.into(), VmExecutionMode::OneTx)
+                    })
+                    .collect()
+            },
+        );
+        span.exit();
+        result
+    })
+    .await
+    .unwrap()
+}
+
+/// Re-executes transactions of a sealed miniblock in a single VM instance, so that each transaction
+/// observes the changes made by the preceding ones. Each transaction is inspected with the tracers
+/// provided alongside it.
+///
+/// `block_args` must point to the miniblock preceding the replayed one. Note that the transactions
+/// are executed in the context of that miniblock (e.g., its number and timestamp), so the results
+/// may differ from the original execution for transactions that depend on the block context.
+#[tracing::instrument(skip_all)]
+pub(crate) async fn replay_miniblock_transactions(
+    vm_permit: VmPermit,
+    shared_args: TxSharedArgs,
+    connection_pool: ConnectionPool,
+    block_args: BlockArgs,
+    enforced_base_fee: u64,
+    txs: Vec<(Transaction, Vec<ApiTracer>)>,
+) -> Vec<VmExecutionResultAndLogs> {
+    let Some((first_tx, _)) = txs.first() else {
+        return vec![];
+    };
+    let first_tx = first_tx.clone();
+    let execution_args = TxExecutionArgs::for_replay(enforced_base_fee);
+
+    tokio::task::spawn_blocking(move || {
+        let span = span!(Level::DEBUG, "replay_in_sandbox").entered();
+        let result = apply::apply_vm_in_sandbox(
+            vm_permit,
+            shared_args,
+            &execution_args,
+            &connection_pool,
+            first_tx,
+            block_args,
+            |vm, _| {
+                txs.into_iter()
+                    .map(|(tx, tracers)| {
+                        vm.push_transaction(tx);
+                        let tracers: Vec<_> = tracers
+                            .into_iter()
+                            .map(|tracer| tracer.into_boxed())
+                            .collect();
+                        vm.inspect(tracers.into(), VmExecutionMode::OneTx)
+                    })
+                    .collect()
+            },
+        );
+        span.exit();
+        result
+    })
+    .await
+    .unwrap()
+}
+
 #[tracing::instrument(skip_all)]
 pub(crate) async fn execute_tx_with_pending_state(
     vm_permit: VmPermit,
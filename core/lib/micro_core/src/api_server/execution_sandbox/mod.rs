@@ -11,7 +11,11 @@ use tokio::runtime::Handle;
 use self::vm_metrics::SandboxStage;
 pub(super) use self::{
     error::SandboxExecutionError,
-    execute::{execute_tx_eth_call, execute_tx_with_pending_state, TxExecutionArgs},
+    execute::{
+        execute_tx_bundle_eth_call, execute_tx_eth_call, execute_tx_with_pending_state,
+        replay_miniblock_transactions, TxExecutionArgs,
+    },
+    storage::{StateOverrideError, StorageOverrides},
     tracers::ApiTracer,
     vm_metrics::{SubmitTxStage, SANDBOX_METRICS},
 };
@@ -21,6 +25,7 @@ use super::tx_sender::MultiVMBaseSystemContracts;
 mod apply;
 mod error;
 mod execute;
+mod storage;
 mod tracers;
 mod validate;
 mod vm_metrics;
@@ -0,0 +1,308 @@
+//! VM storage overlay applying state overrides provided by API callers.
+
+use std::collections::{HashMap, HashSet};
+
+use micro_state::ReadStorage;
+use micro_types::{
+    api::state_override::StateOverride,
+    get_code_key, get_known_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, nonces_to_full_nonce, storage_key_for_eth_balance},
+    AccountTreeId, Address, StorageKey, StorageValue, H256, U256,
+};
+use micro_utils::{
+    bytecode::{hash_bytecode, validate_bytecode, InvalidBytecodeError},
+    h256_to_u256, u256_to_h256,
+};
+
+/// Errors that can occur when converting a [`StateOverride`] into [`StorageOverrides`].
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum StateOverrideError {
+    #[error("both `state` and `stateDiff` are specified for account {0:?}")]
+    StateAndStateDiff(Address),
+    #[error("nonce override for account {0:?} does not fit into 128 bits")]
+    NonceOverflow(Address),
+    #[error("invalid code override for account {0:?}: {1}")]
+    InvalidCode(Address, InvalidBytecodeError),
+}
+
+/// [`StateOverride`] converted to the storage representation used by the VM.
+#[derive(Debug, Clone, Default)]
+pub struct StorageOverrides {
+    slots: HashMap<StorageKey, StorageValue>,
+    /// Transaction nonces keyed by nonce storage keys. Unlike other slots, nonces are combined
+    /// with deployment nonces read from the underlying storage.
+    tx_nonces: HashMap<StorageKey, U256>,
+    factory_deps: HashMap<H256, Vec<u8>>,
+    /// Accounts with the entire storage overridden.
+    cleared_accounts: HashSet<AccountTreeId>,
+}
+
+impl StorageOverrides {
+    pub(crate) fn new(state_override: &StateOverride) -> Result<Self, StateOverrideError> {
+        let mut this = Self::default();
+        for (&address, account) in state_override.iter() {
+            if let Some(balance) = account.balance {
+                let balance_key = storage_key_for_eth_balance(&address);
+                this.slots.insert(balance_key, u256_to_h256(balance));
+            }
+
+            if let Some(nonce) = account.nonce {
+                if nonce > U256::from(u128::MAX) {
+                    return Err(StateOverrideError::NonceOverflow(address));
+                }
+                this.tx_nonces.insert(get_nonce_key(&address), nonce);
+            }
+
+            if let Some(code) = &account.code {
+                this.override_code(address, code.0.clone())?;
+            }
+
+            let account_id = AccountTreeId::new(address);
+            match (&account.state, &account.state_diff) {
+                (Some(_), Some(_)) => return Err(StateOverrideError::StateAndStateDiff(address)),
+                (Some(state), None) => {
+                    this.cleared_accounts.insert(account_id);
+                    this.override_slots(account_id, state);
+                }
+                (None, Some(state_diff)) => this.override_slots(account_id, state_diff),
+                (None, None) => { /* Storage is not overridden */ }
+            }
+        }
+        Ok(this)
+    }
+
+    fn override_code(&mut self, address: Address, code: Vec<u8>) -> Result<(), StateOverrideError> {
+        let code_key = get_code_key(&address);
+        if code.is_empty() {
+            self.slots.insert(code_key, H256::zero());
+            return Ok(());
+        }
+
+        validate_bytecode(&code).map_err(|err| StateOverrideError::InvalidCode(address, err))?;
+        let bytecode_hash = hash_bytecode(&code);
+        self.slots.insert(code_key, bytecode_hash);
+        // Mark the bytecode as known, as it would be after publishing it via `KnownCodesStorage`.
+        self.slots
+            .insert(get_known_code_key(&bytecode_hash), H256::from_low_u64_be(1));
+        self.factory_deps.insert(bytecode_hash, code);
+        Ok(())
+    }
+
+    fn override_slots(&mut self, account: AccountTreeId, slots: &HashMap<H256, H256>) {
+        for (&slot, &value) in slots {
+            self.slots.insert(StorageKey::new(account, slot), value);
+        }
+    }
+
+    /// Returns the overridden value of a storage slot, if any. Does not account for nonce overrides.
+    pub(crate) fn slot_value(&self, key: &StorageKey) -> Option<StorageValue> {
+        if let Some(value) = self.slots.get(key) {
+            Some(*value)
+        } else if self.cleared_accounts.contains(key.account()) {
+            Some(H256::zero())
+        } else {
+            None
+        }
+    }
+}
+
+/// [`ReadStorage`] implementation applying [`StorageOverrides`] on top of the wrapped storage.
+#[derive(Debug)]
+pub(super) struct StorageWithOverrides<S> {
+    storage: S,
+    overrides: StorageOverrides,
+}
+
+impl<S: ReadStorage> StorageWithOverrides<S> {
+    pub fn new(storage: S, overrides: StorageOverrides) -> Self {
+        Self { storage, overrides }
+    }
+}
+
+impl<S: ReadStorage> ReadStorage for StorageWithOverrides<S> {
+    fn read_value(&mut self, key: &StorageKey) -> StorageValue {
+        if let Some(&tx_nonce) = self.overrides.tx_nonces.get(key) {
+            let full_nonce = h256_to_u256(self.storage.read_value(key));
+            let (_, deployment_nonce) = decompose_full_nonce(full_nonce);
+            return u256_to_h256(nonces_to_full_nonce(tx_nonce, deployment_nonce));
+        }
+        self.overrides
+            .slot_value(key)
+            .unwrap_or_else(|| self.storage.read_value(key))
+    }
+
+    fn is_write_initial(&mut self, key: &StorageKey) -> bool {
+        // Overridden values are treated as if they were persisted: a slot overridden with a non-zero value
+        // was written before, while slots of accounts with the entire state overridden are not written yet
+        // unless overridden explicitly.
+        let is_overridden =
+            self.overrides.tx_nonces.contains_key(key) || self.overrides.slot_value(key).is_some();
+        if is_overridden && !self.read_value(key).is_zero() {
+            return false;
+        }
+        if self.overrides.cleared_accounts.contains(key.account()) {
+            return true;
+        }
+        self.storage.is_write_initial(key)
+    }
+
+    fn load_factory_dep(&mut self, hash: H256) -> Option<Vec<u8>> {
+        if let Some(code) = self.overrides.factory_deps.get(&hash) {
+            return Some(code.clone());
+        }
+        self.storage.load_factory_dep(hash)
+    }
+
+    fn get_enumeration_index(&mut self, key: &StorageKey) -> Option<u64> {
+        if self.is_write_initial(key) {
+            return None;
+        }
+        self.storage.get_enumeration_index(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use micro_state::InMemoryStorage;
+    use micro_types::{api::state_override::OverrideAccount, web3::types::Bytes};
+
+    use super::*;
+
+    fn state_override(address: Address, account: OverrideAccount) -> StateOverride {
+        StateOverride::new(HashMap::from([(address, account)]))
+    }
+
+    #[test]
+    fn applying_storage_overrides() {
+        let address = Address::repeat_byte(1);
+        let mut storage = InMemoryStorage::with_system_contracts(hash_bytecode);
+        let account = AccountTreeId::new(address);
+        let overridden_slot = StorageKey::new(account, H256::from_low_u64_be(1));
+        let intact_slot = StorageKey::new(account, H256::from_low_u64_be(2));
+        storage.set_value(overridden_slot, H256::repeat_byte(1));
+        storage.set_value(intact_slot, H256::repeat_byte(2));
+        let deployment_nonce = U256::from(3);
+        let full_nonce = nonces_to_full_nonce(U256::from(5), deployment_nonce);
+        storage.set_value(get_nonce_key(&address), u256_to_h256(full_nonce));
+
+        let code = vec![1_u8; 32];
+        let account_override = OverrideAccount {
+            balance: Some(U256::from(1_000)),
+            nonce: Some(U256::from(10)),
+            code: Some(Bytes(code.clone())),
+            state_diff: Some(HashMap::from([(
+                H256::from_low_u64_be(1),
+                H256::repeat_byte(0xff),
+            )])),
+            ..OverrideAccount::default()
+        };
+        let overrides = StorageOverrides::new(&state_override(address, account_override)).unwrap();
+        let mut storage = StorageWithOverrides::new(storage, overrides);
+
+        let balance = storage.read_value(&storage_key_for_eth_balance(&address));
+        assert_eq!(h256_to_u256(balance), U256::from(1_000));
+        let full_nonce = h256_to_u256(storage.read_value(&get_nonce_key(&address)));
+        assert_eq!(
+            decompose_full_nonce(full_nonce),
+            (U256::from(10), deployment_nonce)
+        );
+        assert_eq!(
+            storage.read_value(&overridden_slot),
+            H256::repeat_byte(0xff)
+        );
+        assert_eq!(storage.read_value(&intact_slot), H256::repeat_byte(2));
+
+        let bytecode_hash = hash_bytecode(&code);
+        assert_eq!(storage.read_value(&get_code_key(&address)), bytecode_hash);
+        assert!(storage.is_bytecode_known(&bytecode_hash));
+        assert_eq!(storage.load_factory_dep(bytecode_hash), Some(code));
+    }
+
+    #[test]
+    fn overriding_entire_account_state() {
+        let address = Address::repeat_byte(1);
+        let mut storage = InMemoryStorage::with_system_contracts(hash_bytecode);
+        let account = AccountTreeId::new(address);
+        let overridden_slot = StorageKey::new(account, H256::from_low_u64_be(1));
+        let cleared_slot = StorageKey::new(account, H256::from_low_u64_be(2));
+        storage.set_value(overridden_slot, H256::repeat_byte(1));
+        storage.set_value(cleared_slot, H256::repeat_byte(2));
+        let other_slot = StorageKey::new(AccountTreeId::new(Address::repeat_byte(2)), H256::zero());
+        storage.set_value(other_slot, H256::repeat_byte(3));
+
+        let account_override = OverrideAccount {
+            state: Some(HashMap::from([(
+                H256::from_low_u64_be(1),
+                H256::repeat_byte(0xff),
+            )])),
+            ..OverrideAccount::default()
+        };
+        let overrides = StorageOverrides::new(&state_override(address, account_override)).unwrap();
+        let mut storage = StorageWithOverrides::new(storage, overrides);
+
+        assert_eq!(
+            storage.read_value(&overridden_slot),
+            H256::repeat_byte(0xff)
+        );
+        assert_eq!(storage.read_value(&cleared_slot), H256::zero());
+        assert_eq!(storage.read_value(&other_slot), H256::repeat_byte(3));
+
+        assert!(!storage.is_write_initial(&overridden_slot));
+        assert!(storage.is_write_initial(&cleared_slot));
+        assert!(!storage.is_write_initial(&other_slot));
+    }
+
+    #[test]
+    fn initial_writes_with_storage_overrides() {
+        let address = Address::repeat_byte(1);
+        let storage = InMemoryStorage::with_system_contracts(hash_bytecode);
+        let account = AccountTreeId::new(address);
+        let new_slot = StorageKey::new(account, H256::from_low_u64_be(1));
+        let zeroed_slot = StorageKey::new(account, H256::from_low_u64_be(2));
+        let intact_slot = StorageKey::new(account, H256::from_low_u64_be(3));
+
+        let account_override = OverrideAccount {
+            balance: Some(U256::from(1_000)),
+            nonce: Some(U256::from(10)),
+            state_diff: Some(HashMap::from([
+                (H256::from_low_u64_be(1), H256::repeat_byte(0xff)),
+                (H256::from_low_u64_be(2), H256::zero()),
+            ])),
+            ..OverrideAccount::default()
+        };
+        let overrides = StorageOverrides::new(&state_override(address, account_override)).unwrap();
+        let mut storage = StorageWithOverrides::new(storage, overrides);
+
+        assert!(!storage.is_write_initial(&new_slot));
+        assert!(!storage.is_write_initial(&storage_key_for_eth_balance(&address)));
+        assert!(!storage.is_write_initial(&get_nonce_key(&address)));
+        assert!(storage.is_write_initial(&zeroed_slot));
+        assert!(storage.is_write_initial(&intact_slot));
+    }
+
+    #[test]
+    fn invalid_state_overrides() {
+        let address = Address::repeat_byte(1);
+        let account_override = OverrideAccount {
+            state: Some(HashMap::new()),
+            state_diff: Some(HashMap::new()),
+            ..OverrideAccount::default()
+        };
+        let err = StorageOverrides::new(&state_override(address, account_override)).unwrap_err();
+        assert!(matches!(err, StateOverrideError::StateAndStateDiff(_)));
+
+        let account_override = OverrideAccount {
+            code: Some(Bytes(vec![1; 64])),
+            ..OverrideAccount::default()
+        };
+        let err = StorageOverrides::new(&state_override(address, account_override)).unwrap_err();
+        assert!(matches!(err, StateOverrideError::InvalidCode(..)));
+
+        let account_override = OverrideAccount {
+            nonce: Some(U256::MAX),
+            ..OverrideAccount::default()
+        };
+        let err = StorageOverrides::new(&state_override(address, account_override)).unwrap_err();
+        assert!(matches!(err, StateOverrideError::NonceOverflow(_)));
+    }
+}
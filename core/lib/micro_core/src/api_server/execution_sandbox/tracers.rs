@@ -1,14 +1,27 @@
 use std::sync::Arc;
 
 use micro_state::WriteStorage;
-use micro_types::vm_trace::Call;
-use multivm::{tracers::CallTracer, vm_latest::HistoryMode, MultiVMTracer, MultiVmTracerPointer};
+use micro_types::vm_trace::{Call, FourByteTrace, PrestateTrace, StructLog};
+use multivm::{
+    tracers::{CallTracer, FourByteTracer, PrestateTracer, StructLogger, StructLoggerParams},
+    vm_latest::HistoryMode,
+    MultiVMTracer, MultiVmTracerPointer,
+};
 use once_cell::sync::OnceCell;
 
 /// Custom tracers supported by our API
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum ApiTracer {
     CallTracer(Arc<OnceCell<Vec<Call>>>),
+    PrestateTracer {
+        diff_mode: bool,
+        result: Arc<OnceCell<PrestateTrace>>,
+    },
+    FourByteTracer(Arc<OnceCell<FourByteTrace>>),
+    StructLogger {
+        params: StructLoggerParams,
+        result: Arc<OnceCell<Vec<StructLog>>>,
+    },
 }
 
 impl ApiTracer {
@@ -20,6 +33,13 @@ impl ApiTracer {
     ) -> MultiVmTracerPointer<S, H> {
         match self {
             ApiTracer::CallTracer(tracer) => CallTracer::new(tracer.clone()).into_tracer_pointer(),
+            ApiTracer::PrestateTracer { diff_mode, result } => {
+                PrestateTracer::new(diff_mode, result).into_tracer_pointer()
+            }
+            ApiTracer::FourByteTracer(result) => FourByteTracer::new(result).into_tracer_pointer(),
+            ApiTracer::StructLogger { params, result } => {
+                StructLogger::new(params, result).into_tracer_pointer()
+            }
         }
     }
 }
@@ -0,0 +1,72 @@
+//! Data loaders batching Postgres queries issued by GraphQL resolvers. Without batching, resolving a list
+//! of entities (e.g., transactions in a block) would issue a separate query for each entity.
+
+use std::collections::HashMap;
+
+use async_graphql::{dataloader::Loader, Error};
+use micro_dal::ConnectionPool;
+use micro_types::{api, L2ChainId, MiniblockNumber, H256};
+
+use super::schema::internal_error;
+
+/// Loads blocks together with hashes of their transactions by block numbers.
+#[derive(Debug)]
+pub(super) struct BlockLoader {
+    pub pool: ConnectionPool,
+}
+
+#[async_trait::async_trait]
+impl Loader<MiniblockNumber> for BlockLoader {
+    type Value = api::Block<api::TransactionVariant>;
+    type Error = Error;
+
+    async fn load(
+        &self,
+        numbers: &[MiniblockNumber],
+    ) -> Result<HashMap<MiniblockNumber, Self::Value>, Self::Error> {
+        let mut storage = self
+            .pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(internal_error)?;
+        let blocks = storage
+            .blocks_web3_dal()
+            .get_blocks(numbers)
+            .await
+            .map_err(internal_error)?;
+        Ok(blocks
+            .into_iter()
+            .map(|block| (MiniblockNumber(block.number.as_u32()), block))
+            .collect())
+    }
+}
+
+/// Loads transactions by their hashes.
+#[derive(Debug)]
+pub(super) struct TransactionLoader {
+    pub pool: ConnectionPool,
+    pub chain_id: L2ChainId,
+}
+
+#[async_trait::async_trait]
+impl Loader<H256> for TransactionLoader {
+    type Value = api::Transaction;
+    type Error = Error;
+
+    async fn load(&self, hashes: &[H256]) -> Result<HashMap<H256, Self::Value>, Self::Error> {
+        let mut storage = self
+            .pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(internal_error)?;
+        let transactions = storage
+            .transactions_web3_dal()
+            .get_transactions(hashes, self.chain_id)
+            .await
+            .map_err(internal_error)?;
+        Ok(transactions
+            .into_iter()
+            .map(|transaction| (transaction.hash, transaction))
+            .collect())
+    }
+}
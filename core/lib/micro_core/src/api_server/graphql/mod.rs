@@ -0,0 +1,152 @@
+//! Read-only GraphQL API over blocks, transactions and logs. The schema follows [EIP-1767]
+//! and extends it with zkSync-specific fields, such as L1 batch numbers and hashes of L1 transactions
+//! committing, proving and executing batches.
+//!
+//! [EIP-1767]: https://eips.ethereum.org/EIPS/eip-1767
+
+use std::{fmt, future::Future, net::SocketAddr, pin::Pin};
+
+use anyhow::Context as _;
+use async_graphql::{dataloader::DataLoader, EmptyMutation, EmptySubscription, Schema};
+use axum::{extract::State, routing, Json, Router};
+use micro_config::configs::api::Web3JsonRpcConfig;
+use micro_dal::ConnectionPool;
+use micro_types::L2ChainId;
+use tokio::sync::watch;
+
+use self::{
+    loaders::{BlockLoader, TransactionLoader},
+    schema::{GraphQlState, QueryRoot},
+};
+
+mod loaders;
+mod scalars;
+mod schema;
+#[cfg(test)]
+mod tests;
+
+type ApiSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// GraphQL API served over HTTP on a dedicated port.
+#[derive(Clone)]
+pub struct GraphQlApi {
+    schema: ApiSchema,
+}
+
+impl fmt::Debug for GraphQlApi {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.debug_struct("GraphQlApi").finish_non_exhaustive()
+    }
+}
+
+impl GraphQlApi {
+    /// Creates the API. Query complexity and depth limits, as well as the maximum number of entities
+    /// returned by list fields, are taken from the provided config.
+    pub fn new(pool: ConnectionPool, config: &Web3JsonRpcConfig, chain_id: L2ChainId) -> Self {
+        let block_loader = BlockLoader { pool: pool.clone() };
+        let transaction_loader = TransactionLoader {
+            pool: pool.clone(),
+            chain_id,
+        };
+        let state = GraphQlState {
+            pool,
+            chain_id,
+            entities_limit: config.req_entities_limit(),
+        };
+        let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+            .data(state)
+            .data(DataLoader::new(block_loader, tokio::spawn))
+            .data(DataLoader::new(transaction_loader, tokio::spawn))
+            .limit_complexity(config.graphql_max_complexity())
+            .limit_depth(config.graphql_max_depth())
+            .finish();
+        Self { schema }
+    }
+
+    async fn execute(&self, request: async_graphql::Request) -> async_graphql::Response {
+        self.schema.execute(request).await
+    }
+
+    async fn query_handler(
+        State(this): State<Self>,
+        Json(request): Json<async_graphql::Request>,
+    ) -> Json<async_graphql::Response> {
+        Json(this.execute(request).await)
+    }
+
+    fn create_server(
+        self,
+        bind_address: &SocketAddr,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<GraphQlServer> {
+        tracing::debug!("Starting GraphQL API server on {bind_address}");
+
+        let app = Router::new()
+            .route("/graphql", routing::post(Self::query_handler))
+            .with_state(self);
+
+        let server = axum::Server::try_bind(bind_address)
+            .with_context(|| format!("Failed binding GraphQL API server to {bind_address}"))?
+            .serve(app.into_make_service());
+        let local_addr = server.local_addr();
+        let server_future = async move {
+            server
+                .with_graceful_shutdown(async move {
+                    if stop_receiver.changed().await.is_err() {
+                        tracing::warn!(
+                            "Stop signal sender for GraphQL API server was dropped without sending a signal"
+                        );
+                    }
+                    tracing::info!("Stop signal received, GraphQL API server is shutting down");
+                })
+                .await
+                .context("GraphQL API server failed")?;
+
+            tracing::info!("GraphQL API server shut down");
+            Ok(())
+        };
+
+        Ok(GraphQlServer {
+            local_addr,
+            server_future: Box::pin(server_future),
+        })
+    }
+
+    /// Runs the HTTP server for the API.
+    pub async fn run_server(
+        self,
+        bind_address: SocketAddr,
+        stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        self.create_server(&bind_address, stop_receiver)?
+            .run()
+            .await
+    }
+}
+
+/// `axum`-powered HTTP server for the GraphQL API.
+#[must_use = "Server must be `run()`"]
+struct GraphQlServer {
+    local_addr: SocketAddr,
+    server_future: Pin<Box<dyn Future<Output = anyhow::Result<()>> + Send>>,
+}
+
+impl fmt::Debug for GraphQlServer {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter
+            .debug_struct("GraphQlServer")
+            .field("local_addr", &self.local_addr)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GraphQlServer {
+    #[cfg(test)]
+    fn local_addr(&self) -> &SocketAddr {
+        &self.local_addr
+    }
+
+    async fn run(self) -> anyhow::Result<()> {
+        self.server_future.await
+    }
+}
@@ -0,0 +1,96 @@
+//! Custom scalars defined by the EIP-1767 schema.
+
+use async_graphql::scalar;
+use micro_types::{web3::types::Bytes as RawBytes, H160, H256, U256};
+use serde::{Deserialize, Serialize};
+
+/// 32-byte binary string.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct Bytes32(pub H256);
+
+scalar!(
+    Bytes32,
+    "Bytes32",
+    "32 byte binary string, represented as 0x-prefixed hexadecimal."
+);
+
+/// 20-byte account address.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct Address(pub H160);
+
+scalar!(
+    Address,
+    "Address",
+    "Address is a 20 byte Ethereum address, represented as 0x-prefixed hexadecimal."
+);
+
+/// Arbitrary-length binary string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct Bytes(pub RawBytes);
+
+scalar!(
+    Bytes,
+    "Bytes",
+    "An arbitrary length binary string, represented as 0x-prefixed hexadecimal. \
+     An empty byte string is represented as '0x'."
+);
+
+/// 256-bit unsigned integer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct BigInt(pub U256);
+
+scalar!(
+    BigInt,
+    "BigInt",
+    "BigInt is a large integer, represented as 0x-prefixed hexadecimal."
+);
+
+/// 64-bit unsigned integer.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub(super) struct Long(pub u64);
+
+scalar!(
+    Long,
+    "Long",
+    "Long is a 64 bit unsigned integer, represented as a decimal number."
+);
+
+impl From<H256> for Bytes32 {
+    fn from(value: H256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<H160> for Address {
+    fn from(value: H160) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    fn from(value: Vec<u8>) -> Self {
+        Self(RawBytes(value))
+    }
+}
+
+impl From<U256> for BigInt {
+    fn from(value: U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<U256> for Long {
+    /// Saturates values that do not fit into 64 bits.
+    fn from(value: U256) -> Self {
+        Self(if value > U256::from(u64::MAX) {
+            u64::MAX
+        } else {
+            value.as_u64()
+        })
+    }
+}
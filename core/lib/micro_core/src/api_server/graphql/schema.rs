@@ -0,0 +1,867 @@
+//! GraphQL schema following EIP-1767, extended with zkSync-specific fields.
+
+use std::fmt;
+
+use async_graphql::{dataloader::DataLoader, Context, Enum, Error, InputObject, Object, Result};
+use micro_dal::{ConnectionPool, StorageProcessor};
+use micro_types::{
+    api::{
+        self,
+        finality::{L1BatchLifecycle, L1BatchStage},
+        BlockId, BlockNumber, GetLogsFilter, TransactionVariant,
+    },
+    AccountTreeId, L1BatchNumber, L2ChainId, MiniblockNumber, H160, H256, L2_ETH_TOKEN_ADDRESS,
+};
+use tokio::sync::OnceCell;
+
+use super::{
+    loaders::{BlockLoader, TransactionLoader},
+    scalars::{Address, BigInt, Bytes, Bytes32, Long},
+};
+
+/// Complexity multiplier for list fields. Used together with the complexity limit of the schema
+/// to bound the amount of work performed for a single query.
+const LIST_COMPLEXITY: usize = 10;
+/// Maximum number of blocks returned by the `blocks` query.
+const MAX_BLOCKS_PER_QUERY: u64 = 100;
+/// Default number of transactions returned by the `Account.transactions` field.
+const DEFAULT_ACCOUNT_TRANSACTIONS: u32 = 20;
+/// Maximum number of topic positions in a log filter.
+const MAX_TOPIC_POSITIONS: usize = 4;
+
+/// State shared by all GraphQL resolvers.
+#[derive(Debug, Clone)]
+pub(super) struct GraphQlState {
+    pub pool: ConnectionPool,
+    pub chain_id: L2ChainId,
+    /// Maximum number of entities (e.g., logs or account transactions) returned by a single field.
+    pub entities_limit: usize,
+}
+
+impl GraphQlState {
+    async fn storage(&self) -> Result<StorageProcessor<'_>> {
+        self.pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn sealed_miniblock_number(&self) -> Result<MiniblockNumber> {
+        let mut storage = self.storage().await?;
+        storage
+            .blocks_web3_dal()
+            .get_sealed_miniblock_number()
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn load_block(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<api::Block<TransactionVariant>>> {
+        let mut storage = self.storage().await?;
+        storage
+            .blocks_web3_dal()
+            .get_block_by_web3_block_id(block_id, false, self.chain_id)
+            .await
+            .map_err(internal_error)
+    }
+
+    async fn load_logs(&self, filter: GetLogsFilter) -> Result<Vec<Log>> {
+        let limit = self.entities_limit;
+        let mut storage = self.storage().await?;
+        let logs = storage
+            .events_web3_dal()
+            .get_logs(filter, limit + 1)
+            .await
+            .map_err(internal_error)?;
+        if logs.len() > limit {
+            let message = format!("Query returns more than {limit} logs; narrow down the filter");
+            return Err(Error::new(message));
+        }
+        Ok(logs.into_iter().map(Log).collect())
+    }
+
+    async fn load_l1_batch(&self, number: L1BatchNumber) -> Result<Option<L1Batch>> {
+        let mut storage = self.storage().await?;
+        let lifecycle = storage
+            .blocks_web3_dal()
+            .get_l1_batches_lifecycle(number..=number)
+            .await
+            .map_err(internal_error)?;
+        Ok(lifecycle.into_iter().next().map(L1Batch))
+    }
+}
+
+fn state<'a>(ctx: &Context<'a>) -> &'a GraphQlState {
+    ctx.data_unchecked()
+}
+
+async fn block_by_number(ctx: &Context<'_>, number: MiniblockNumber) -> Result<Option<Block>> {
+    let loader: &DataLoader<BlockLoader> = ctx.data_unchecked();
+    Ok(loader.load_one(number).await?.map(Block))
+}
+
+async fn transaction_by_hash(ctx: &Context<'_>, hash: H256) -> Result<Option<Transaction>> {
+    let loader: &DataLoader<TransactionLoader> = ctx.data_unchecked();
+    Ok(loader.load_one(hash).await?.map(Transaction::new))
+}
+
+/// Complexity of the `blocks` query, which is proportional to the number of requested blocks.
+fn blocks_complexity(from: Long, to: Option<Long>, child_complexity: usize) -> usize {
+    let count = to.map_or(MAX_BLOCKS_PER_QUERY, |to| {
+        to.0.saturating_sub(from.0)
+            .saturating_add(1)
+            .min(MAX_BLOCKS_PER_QUERY)
+    });
+    (count as usize).saturating_mul(child_complexity)
+}
+
+pub(super) fn internal_error(err: impl fmt::Display) -> Error {
+    tracing::error!("Internal error in GraphQL API: {err}");
+    Error::new("Internal error")
+}
+
+fn block_id(number: u64) -> BlockId {
+    BlockId::Number(BlockNumber::Number(number.into()))
+}
+
+fn miniblock_number(number: Long) -> Result<MiniblockNumber> {
+    let number = u32::try_from(number.0).map_err(|_| Error::new("block number is too large"))?;
+    Ok(MiniblockNumber(number))
+}
+
+/// Converts EIP-1767 topics (a list of alternatives for each topic position, with an empty list
+/// matching any topic) to the DAL format with 1-based topic positions.
+fn topics_filter(topics: Option<Vec<Vec<Bytes32>>>) -> Result<Vec<(u32, Vec<H256>)>> {
+    let topics = topics.unwrap_or_default();
+    if topics.len() > MAX_TOPIC_POSITIONS {
+        let message = format!("at most {MAX_TOPIC_POSITIONS} topic positions are supported");
+        return Err(Error::new(message));
+    }
+    Ok(topics
+        .into_iter()
+        .enumerate()
+        .filter(|(_, alternatives)| !alternatives.is_empty())
+        .map(|(idx, alternatives)| {
+            let alternatives = alternatives.into_iter().map(|topic| topic.0).collect();
+            (idx as u32 + 1, alternatives)
+        })
+        .collect())
+}
+
+fn addresses_filter(addresses: Option<Vec<Address>>) -> Vec<H160> {
+    addresses
+        .unwrap_or_default()
+        .into_iter()
+        .map(|address| address.0)
+        .collect()
+}
+
+/// Filter for logs in a range of blocks.
+#[derive(Debug, InputObject)]
+pub(super) struct FilterCriteria {
+    /// First block to search for logs. Defaults to the latest sealed block.
+    from_block: Option<Long>,
+    /// Last block (inclusive) to search for logs. Defaults to the latest sealed block.
+    to_block: Option<Long>,
+    /// Addresses of contracts that emitted logs. An empty list matches any address.
+    addresses: Option<Vec<Address>>,
+    /// Topic alternatives for each topic position. An empty list at a certain position matches any topic.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Filter for logs in a single block.
+#[derive(Debug, InputObject)]
+pub(super) struct BlockFilterCriteria {
+    /// Addresses of contracts that emitted logs. An empty list matches any address.
+    addresses: Option<Vec<Address>>,
+    /// Topic alternatives for each topic position. An empty list at a certain position matches any topic.
+    topics: Option<Vec<Vec<Bytes32>>>,
+}
+
+/// Root of all GraphQL queries.
+#[derive(Debug)]
+pub(super) struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// Fetches a block by number or hash. If neither is specified, returns the latest sealed block.
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        number: Option<Long>,
+        hash: Option<Bytes32>,
+    ) -> Result<Option<Block>> {
+        let block_id = match (number, hash) {
+            (Some(_), Some(_)) => {
+                return Err(Error::new(
+                    "only one of `number` and `hash` may be specified",
+                ));
+            }
+            (Some(number), None) => block_id(number.0),
+            (None, Some(hash)) => BlockId::Hash(hash.0),
+            (None, None) => BlockId::Number(BlockNumber::Latest),
+        };
+        let block = state(ctx).load_block(block_id).await?;
+        Ok(block.map(Block))
+    }
+
+    /// Fetches blocks in the inclusive range `[from, to]`. If `to` is not specified, fetches blocks
+    /// up to the latest sealed one.
+    #[graphql(complexity = "blocks_complexity(from, to, child_complexity)")]
+    async fn blocks(&self, ctx: &Context<'_>, from: Long, to: Option<Long>) -> Result<Vec<Block>> {
+        let state = state(ctx);
+        let to = match to {
+            Some(to) => to.0,
+            None => state.sealed_miniblock_number().await?.0.into(),
+        };
+        if to < from.0 {
+            return Ok(vec![]);
+        }
+        if to - from.0 >= MAX_BLOCKS_PER_QUERY {
+            let message = format!("at most {MAX_BLOCKS_PER_QUERY} blocks may be requested at once");
+            return Err(Error::new(message));
+        }
+
+        let numbers = (from.0..=to)
+            .map(|number| miniblock_number(Long(number)))
+            .collect::<Result<Vec<_>>>()?;
+        let mut storage = state.storage().await?;
+        let blocks = storage
+            .blocks_web3_dal()
+            .get_blocks(&numbers)
+            .await
+            .map_err(internal_error)?;
+        Ok(blocks.into_iter().map(Block).collect())
+    }
+
+    /// Fetches a transaction by its hash.
+    async fn transaction(&self, ctx: &Context<'_>, hash: Bytes32) -> Result<Option<Transaction>> {
+        transaction_by_hash(ctx, hash.0).await
+    }
+
+    /// Fetches logs matching the filter. Fails if the number of matching logs exceeds the configured limit.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>, filter: FilterCriteria) -> Result<Vec<Log>> {
+        let state = state(ctx);
+        let latest = if filter.from_block.is_none() || filter.to_block.is_none() {
+            Some(state.sealed_miniblock_number().await?)
+        } else {
+            None
+        };
+        let from_block = match filter.from_block {
+            Some(number) => miniblock_number(number)?,
+            None => latest.unwrap(),
+        };
+        let to_block = match filter.to_block {
+            Some(number) => miniblock_number(number)?,
+            None => latest.unwrap(),
+        };
+
+        let filter = GetLogsFilter {
+            from_block,
+            to_block,
+            addresses: addresses_filter(filter.addresses),
+            topics: topics_filter(filter.topics)?,
+        };
+        state.load_logs(filter).await
+    }
+
+    /// Returns the chain ID of the network.
+    #[graphql(name = "chainID")]
+    async fn chain_id(&self, ctx: &Context<'_>) -> BigInt {
+        BigInt(state(ctx).chain_id.as_u64().into())
+    }
+
+    /// Fetches an account state as of the specified block, or the latest sealed block if not specified.
+    /// This field is zkSync-specific.
+    async fn account(&self, address: Address, block: Option<Long>) -> Result<Account> {
+        let block = block.map(miniblock_number).transpose()?;
+        Ok(Account::new(address.0, block))
+    }
+
+    /// Fetches an L1 batch by its number. This field is zkSync-specific.
+    async fn l1_batch(&self, ctx: &Context<'_>, number: Long) -> Result<Option<L1Batch>> {
+        let Ok(number) = u32::try_from(number.0) else {
+            return Ok(None);
+        };
+        state(ctx).load_l1_batch(L1BatchNumber(number)).await
+    }
+}
+
+/// Block (aka miniblock) of the L2 chain.
+#[derive(Debug)]
+pub(super) struct Block(api::Block<TransactionVariant>);
+
+impl Block {
+    fn number(&self) -> u64 {
+        self.0.number.as_u64()
+    }
+
+    fn transaction_hashes(&self) -> impl Iterator<Item = H256> + '_ {
+        self.0.transactions.iter().map(|tx| match tx {
+            TransactionVariant::Hash(hash) => *hash,
+            TransactionVariant::Full(tx) => tx.hash,
+        })
+    }
+}
+
+#[Object]
+impl Block {
+    /// Block number.
+    #[graphql(name = "number")]
+    async fn number_field(&self) -> Long {
+        Long(self.number())
+    }
+
+    /// Block hash.
+    async fn hash(&self) -> Bytes32 {
+        self.0.hash.into()
+    }
+
+    /// Parent block, or `null` for the genesis block.
+    async fn parent(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let Some(parent_number) = self.number().checked_sub(1) else {
+            return Ok(None);
+        };
+        block_by_number(ctx, miniblock_number(Long(parent_number))?).await
+    }
+
+    async fn nonce(&self) -> Bytes {
+        self.0.nonce.as_bytes().to_vec().into()
+    }
+
+    async fn transactions_root(&self) -> Bytes32 {
+        self.0.transactions_root.into()
+    }
+
+    /// Number of transactions in the block.
+    async fn transaction_count(&self) -> Long {
+        Long(self.0.transactions.len() as u64)
+    }
+
+    async fn state_root(&self) -> Bytes32 {
+        self.0.state_root.into()
+    }
+
+    async fn receipts_root(&self) -> Bytes32 {
+        self.0.receipts_root.into()
+    }
+
+    /// Account that produced the block, as of this block or the specified block.
+    async fn miner(&self, block: Option<Long>) -> Result<Account> {
+        let block = block.unwrap_or(Long(self.number()));
+        Ok(Account::new(self.0.author, Some(miniblock_number(block)?)))
+    }
+
+    async fn extra_data(&self) -> Bytes {
+        Bytes(self.0.extra_data.clone())
+    }
+
+    async fn gas_limit(&self) -> Long {
+        self.0.gas_limit.into()
+    }
+
+    async fn gas_used(&self) -> Long {
+        self.0.gas_used.into()
+    }
+
+    async fn base_fee_per_gas(&self) -> BigInt {
+        self.0.base_fee_per_gas.into()
+    }
+
+    /// UNIX timestamp of the block.
+    async fn timestamp(&self) -> Long {
+        self.0.timestamp.into()
+    }
+
+    async fn logs_bloom(&self) -> Bytes {
+        self.0.logs_bloom.as_bytes().to_vec().into()
+    }
+
+    async fn mix_hash(&self) -> Bytes32 {
+        self.0.mix_hash.into()
+    }
+
+    async fn difficulty(&self) -> BigInt {
+        self.0.difficulty.into()
+    }
+
+    async fn total_difficulty(&self) -> BigInt {
+        self.0.total_difficulty.into()
+    }
+
+    /// Number of ommers; always 0.
+    async fn ommer_count(&self) -> i32 {
+        self.0.uncles.len() as i32
+    }
+
+    async fn ommer_hash(&self) -> Bytes32 {
+        self.0.uncles_hash.into()
+    }
+
+    /// Transactions in the block, in the order of their execution.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn transactions(&self, ctx: &Context<'_>) -> Result<Vec<Transaction>> {
+        let hashes: Vec<_> = self.transaction_hashes().collect();
+        let loader: &DataLoader<TransactionLoader> = ctx.data_unchecked();
+        let mut transactions = loader.load_many(hashes.iter().copied()).await?;
+        let transactions = hashes
+            .iter()
+            .filter_map(|hash| transactions.remove(hash))
+            .map(Transaction::new);
+        Ok(transactions.collect())
+    }
+
+    /// Transaction with the specified index in the block.
+    async fn transaction_at(&self, ctx: &Context<'_>, index: i32) -> Result<Option<Transaction>> {
+        let Ok(index) = usize::try_from(index) else {
+            return Ok(None);
+        };
+        let Some(hash) = self.transaction_hashes().nth(index) else {
+            return Ok(None);
+        };
+        transaction_by_hash(ctx, hash).await
+    }
+
+    /// Logs emitted in the block that match the filter.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>, filter: BlockFilterCriteria) -> Result<Vec<Log>> {
+        let number = miniblock_number(Long(self.number()))?;
+        let filter = GetLogsFilter {
+            from_block: number,
+            to_block: number,
+            addresses: addresses_filter(filter.addresses),
+            topics: topics_filter(filter.topics)?,
+        };
+        state(ctx).load_logs(filter).await
+    }
+
+    /// Account state as of this block.
+    async fn account(&self, address: Address) -> Result<Account> {
+        let number = miniblock_number(Long(self.number()))?;
+        Ok(Account::new(address.0, Some(number)))
+    }
+
+    /// Number of the L1 batch containing the block, or `null` if the batch is not sealed yet.
+    /// This field is zkSync-specific.
+    async fn l1_batch_number(&self) -> Option<Long> {
+        self.0.l1_batch_number.map(|number| Long(number.as_u64()))
+    }
+
+    /// Timestamp of the L1 batch containing the block. This field is zkSync-specific.
+    async fn l1_batch_timestamp(&self) -> Option<Long> {
+        self.0.l1_batch_timestamp.map(Long::from)
+    }
+
+    /// L1 batch containing the block. This field is zkSync-specific.
+    async fn l1_batch(&self, ctx: &Context<'_>) -> Result<Option<L1Batch>> {
+        let Some(number) = self.0.l1_batch_number else {
+            return Ok(None);
+        };
+        state(ctx)
+            .load_l1_batch(L1BatchNumber(number.as_u32()))
+            .await
+    }
+}
+
+/// L2 transaction. The transaction receipt is loaded lazily and only once, when a receipt field
+/// is requested for the first time.
+#[derive(Debug)]
+pub(super) struct Transaction {
+    transaction: api::Transaction,
+    receipt: OnceCell<Option<api::TransactionReceipt>>,
+}
+
+impl Transaction {
+    fn new(transaction: api::Transaction) -> Self {
+        Self {
+            transaction,
+            receipt: OnceCell::new(),
+        }
+    }
+
+    async fn receipt(&self, ctx: &Context<'_>) -> Result<Option<&api::TransactionReceipt>> {
+        let receipt = self
+            .receipt
+            .get_or_try_init(|| async {
+                let mut storage = state(ctx).storage().await?;
+                storage
+                    .transactions_web3_dal()
+                    .get_transaction_receipt(self.transaction.hash)
+                    .await
+                    .map_err(internal_error)
+            })
+            .await?;
+        Ok(receipt.as_ref())
+    }
+
+    fn block_number(&self) -> Option<MiniblockNumber> {
+        let number = self.transaction.block_number?;
+        Some(MiniblockNumber(number.as_u32()))
+    }
+}
+
+#[Object]
+impl Transaction {
+    async fn hash(&self) -> Bytes32 {
+        self.transaction.hash.into()
+    }
+
+    async fn nonce(&self) -> Long {
+        self.transaction.nonce.into()
+    }
+
+    /// Index of the transaction in its block, or `null` if the transaction is pending.
+    async fn index(&self) -> Option<i32> {
+        let index = self.transaction.transaction_index?;
+        Some(index.as_u32() as i32)
+    }
+
+    /// Initiator of the transaction, as of the specified block or the latest sealed block.
+    async fn from(&self, block: Option<Long>) -> Result<Account> {
+        let block = block.map(miniblock_number).transpose()?;
+        let from = self.transaction.from.unwrap_or_default();
+        Ok(Account::new(from, block))
+    }
+
+    /// Recipient of the transaction, or `null` for contract deployments.
+    async fn to(&self, block: Option<Long>) -> Result<Option<Account>> {
+        let block = block.map(miniblock_number).transpose()?;
+        Ok(self.transaction.to.map(|to| Account::new(to, block)))
+    }
+
+    async fn value(&self) -> BigInt {
+        self.transaction.value.into()
+    }
+
+    async fn gas_price(&self) -> BigInt {
+        self.transaction.gas_price.unwrap_or_default().into()
+    }
+
+    async fn max_fee_per_gas(&self) -> Option<BigInt> {
+        self.transaction.max_fee_per_gas.map(BigInt)
+    }
+
+    async fn max_priority_fee_per_gas(&self) -> Option<BigInt> {
+        self.transaction.max_priority_fee_per_gas.map(BigInt)
+    }
+
+    /// Gas limit of the transaction.
+    async fn gas(&self) -> Long {
+        self.transaction.gas.into()
+    }
+
+    async fn input_data(&self) -> Bytes {
+        Bytes(self.transaction.input.clone())
+    }
+
+    /// Block containing the transaction, or `null` if the transaction is pending.
+    async fn block(&self, ctx: &Context<'_>) -> Result<Option<Block>> {
+        let Some(number) = self.block_number() else {
+            return Ok(None);
+        };
+        block_by_number(ctx, number).await
+    }
+
+    /// Execution status: 1 for success, 0 for failure, or `null` if the transaction is pending.
+    async fn status(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        let receipt = self.receipt(ctx).await?;
+        Ok(receipt
+            .and_then(|receipt| receipt.status)
+            .map(|status| Long(status.as_u64())))
+    }
+
+    async fn gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        let receipt = self.receipt(ctx).await?;
+        Ok(receipt.and_then(|receipt| receipt.gas_used).map(Long::from))
+    }
+
+    async fn cumulative_gas_used(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        let receipt = self.receipt(ctx).await?;
+        Ok(receipt.map(|receipt| receipt.cumulative_gas_used.into()))
+    }
+
+    async fn effective_gas_price(&self, ctx: &Context<'_>) -> Result<Option<BigInt>> {
+        let receipt = self.receipt(ctx).await?;
+        Ok(receipt
+            .and_then(|receipt| receipt.effective_gas_price)
+            .map(BigInt))
+    }
+
+    /// Contract deployed by the transaction, if any.
+    async fn created_contract(
+        &self,
+        ctx: &Context<'_>,
+        block: Option<Long>,
+    ) -> Result<Option<Account>> {
+        let block = block.map(miniblock_number).transpose()?;
+        let receipt = self.receipt(ctx).await?;
+        let contract_address = receipt.and_then(|receipt| receipt.contract_address);
+        Ok(contract_address.map(|address| Account::new(address, block)))
+    }
+
+    /// Logs emitted by the transaction, or `null` if the transaction is pending.
+    #[graphql(complexity = "LIST_COMPLEXITY * child_complexity")]
+    async fn logs(&self, ctx: &Context<'_>) -> Result<Option<Vec<Log>>> {
+        let receipt = self.receipt(ctx).await?;
+        Ok(receipt.map(|receipt| receipt.logs.iter().cloned().map(Log).collect()))
+    }
+
+    async fn r(&self) -> BigInt {
+        self.transaction.r.unwrap_or_default().into()
+    }
+
+    async fn s(&self) -> BigInt {
+        self.transaction.s.unwrap_or_default().into()
+    }
+
+    async fn v(&self) -> BigInt {
+        let v = self.transaction.v.unwrap_or_default();
+        BigInt(v.as_u64().into())
+    }
+
+    /// EIP-2718 transaction type.
+    #[graphql(name = "type")]
+    async fn transaction_type(&self) -> Option<i32> {
+        let tx_type = self.transaction.transaction_type?;
+        Some(tx_type.as_u32() as i32)
+    }
+
+    /// Number of the L1 batch containing the transaction, or `null` if the batch is not sealed yet.
+    /// This field is zkSync-specific.
+    async fn l1_batch_number(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        let receipt = self.receipt(ctx).await?;
+        let number = receipt.and_then(|receipt| receipt.l1_batch_number);
+        Ok(number.map(|number| Long(number.as_u64())))
+    }
+
+    /// Index of the transaction in its L1 batch. This field is zkSync-specific.
+    async fn l1_batch_tx_index(&self, ctx: &Context<'_>) -> Result<Option<Long>> {
+        let receipt = self.receipt(ctx).await?;
+        let index = receipt.and_then(|receipt| receipt.l1_batch_tx_index);
+        Ok(index.map(|index| Long(index.as_u64())))
+    }
+
+    /// L1 batch containing the transaction. This field is zkSync-specific.
+    async fn l1_batch(&self, ctx: &Context<'_>) -> Result<Option<L1Batch>> {
+        let receipt = self.receipt(ctx).await?;
+        let Some(number) = receipt.and_then(|receipt| receipt.l1_batch_number) else {
+            return Ok(None);
+        };
+        state(ctx)
+            .load_l1_batch(L1BatchNumber(number.as_u32()))
+            .await
+    }
+}
+
+/// Log emitted by a transaction.
+#[derive(Debug)]
+pub(super) struct Log(api::Log);
+
+#[Object]
+impl Log {
+    /// Index of the log in its block.
+    async fn index(&self) -> i32 {
+        self.0.log_index.map_or(0, |index| index.low_u32() as i32)
+    }
+
+    /// Account that emitted the log, as of the specified block or the latest sealed block.
+    async fn account(&self, block: Option<Long>) -> Result<Account> {
+        let block = block.map(miniblock_number).transpose()?;
+        Ok(Account::new(self.0.address, block))
+    }
+
+    async fn topics(&self) -> Vec<Bytes32> {
+        self.0.topics.iter().copied().map(Bytes32).collect()
+    }
+
+    async fn data(&self) -> Bytes {
+        Bytes(self.0.data.clone())
+    }
+
+    /// Transaction that emitted the log.
+    async fn transaction(&self, ctx: &Context<'_>) -> Result<Option<Transaction>> {
+        let Some(hash) = self.0.transaction_hash else {
+            return Ok(None);
+        };
+        transaction_by_hash(ctx, hash).await
+    }
+
+    /// Number of the L1 batch containing the log. This field is zkSync-specific.
+    async fn l1_batch_number(&self) -> Option<Long> {
+        self.0.l1_batch_number.map(|number| Long(number.as_u64()))
+    }
+}
+
+/// Account state as of a certain block.
+#[derive(Debug)]
+pub(super) struct Account {
+    address: H160,
+    /// If not specified, the state is taken as of the latest sealed block.
+    block: Option<MiniblockNumber>,
+}
+
+impl Account {
+    fn new(address: H160, block: Option<MiniblockNumber>) -> Self {
+        Self { address, block }
+    }
+
+    async fn block_number(&self, state: &GraphQlState) -> Result<MiniblockNumber> {
+        match self.block {
+            Some(number) => Ok(number),
+            None => state.sealed_miniblock_number().await,
+        }
+    }
+}
+
+#[Object]
+impl Account {
+    async fn address(&self) -> Address {
+        self.address.into()
+    }
+
+    /// Balance of the account in the base token.
+    async fn balance(&self, ctx: &Context<'_>) -> Result<BigInt> {
+        let state = state(ctx);
+        let block_number = self.block_number(state).await?;
+        let mut storage = state.storage().await?;
+        let balance = storage
+            .storage_web3_dal()
+            .standard_token_historical_balance(
+                AccountTreeId::new(L2_ETH_TOKEN_ADDRESS),
+                AccountTreeId::new(self.address),
+                block_number,
+            )
+            .await
+            .map_err(internal_error)?;
+        Ok(balance.into())
+    }
+
+    /// Nonce of the account.
+    async fn transaction_count(&self, ctx: &Context<'_>) -> Result<Long> {
+        let state = state(ctx);
+        let block_number = self.block_number(state).await?;
+        let mut storage = state.storage().await?;
+        let nonce = storage
+            .storage_web3_dal()
+            .get_address_historical_nonce(self.address, block_number)
+            .await
+            .map_err(internal_error)?;
+        Ok(nonce.into())
+    }
+
+    /// Bytecode of the contract deployed at the account address; empty for non-contract accounts.
+    async fn code(&self, ctx: &Context<'_>) -> Result<Bytes> {
+        let state = state(ctx);
+        let block_number = self.block_number(state).await?;
+        let mut storage = state.storage().await?;
+        let code = storage
+            .storage_web3_dal()
+            .get_contract_code_unchecked(self.address, block_number)
+            .await
+            .map_err(internal_error)?;
+        Ok(code.unwrap_or_default().into())
+    }
+
+    /// Included transactions initiated by the account, starting from the most recent ones. Unlike
+    /// other account fields, this list does not depend on the block the account state is taken at.
+    /// At most `first` transactions are returned (20 by default); this number is additionally capped
+    /// by the server. This field is zkSync-specific.
+    #[graphql(complexity = "(first as usize).saturating_mul(child_complexity)")]
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] offset: u32,
+        #[graphql(default = DEFAULT_ACCOUNT_TRANSACTIONS)] first: u32,
+    ) -> Result<Vec<Transaction>> {
+        let state = state(ctx);
+        let limit = (first as usize).min(state.entities_limit);
+        let mut storage = state.storage().await?;
+        let transactions = storage
+            .transactions_web3_dal()
+            .get_account_transactions(self.address, offset as usize, limit, state.chain_id)
+            .await
+            .map_err(internal_error)?;
+        Ok(transactions.into_iter().map(Transaction::new).collect())
+    }
+}
+
+/// Lifecycle stage of an L1 batch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Enum)]
+pub(super) enum L1BatchStatus {
+    /// The batch is sealed by the state keeper.
+    Sealed,
+    /// The commit transaction for the batch is confirmed on L1.
+    Committed,
+    /// The prove transaction for the batch is confirmed on L1.
+    Proven,
+    /// The execute transaction for the batch is confirmed on L1.
+    Executed,
+}
+
+impl From<L1BatchStage> for L1BatchStatus {
+    fn from(stage: L1BatchStage) -> Self {
+        match stage {
+            L1BatchStage::Sealed => Self::Sealed,
+            L1BatchStage::Committed => Self::Committed,
+            L1BatchStage::Proven => Self::Proven,
+            L1BatchStage::Executed => Self::Executed,
+        }
+    }
+}
+
+/// L1 batch together with its L1 lifecycle. This type is zkSync-specific.
+#[derive(Debug)]
+pub(super) struct L1Batch(L1BatchLifecycle);
+
+#[Object]
+impl L1Batch {
+    async fn number(&self) -> Long {
+        Long(self.0.number.0.into())
+    }
+
+    async fn timestamp(&self) -> Long {
+        Long(self.0.timestamp)
+    }
+
+    /// Root hash of the Merkle tree after the batch, or `null` if not computed yet.
+    async fn root_hash(&self) -> Option<Bytes32> {
+        self.0.root_hash.map(Bytes32)
+    }
+
+    /// The latest lifecycle stage reached by the batch.
+    async fn status(&self) -> L1BatchStatus {
+        self.0.stage().into()
+    }
+
+    async fn commit_tx_hash(&self) -> Option<Bytes32> {
+        self.0.commit_tx_hash.map(Bytes32)
+    }
+
+    /// UNIX timestamp at which the commit transaction was confirmed on L1.
+    async fn committed_at(&self) -> Option<Long> {
+        self.0
+            .committed_at
+            .map(|time| Long(time.timestamp() as u64))
+    }
+
+    async fn prove_tx_hash(&self) -> Option<Bytes32> {
+        self.0.prove_tx_hash.map(Bytes32)
+    }
+
+    /// UNIX timestamp at which the prove transaction was confirmed on L1.
+    async fn proven_at(&self) -> Option<Long> {
+        self.0.proven_at.map(|time| Long(time.timestamp() as u64))
+    }
+
+    async fn execute_tx_hash(&self) -> Option<Bytes32> {
+        self.0.execute_tx_hash.map(Bytes32)
+    }
+
+    /// UNIX timestamp at which the execute transaction was confirmed on L1.
+    async fn executed_at(&self) -> Option<Long> {
+        self.0.executed_at.map(|time| Long(time.timestamp() as u64))
+    }
+}
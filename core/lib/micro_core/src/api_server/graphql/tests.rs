@@ -0,0 +1,154 @@
+//! Tests for the GraphQL API.
+
+use std::net::Ipv4Addr;
+
+use micro_config::configs::chain::NetworkConfig;
+use micro_types::{Address, H256};
+use serde_json::json;
+
+use super::*;
+use crate::genesis::{ensure_genesis_state, GenesisParams};
+
+async fn prepare_api() -> GraphQlApi {
+    let pool = ConnectionPool::test_pool().await;
+    let network_config = NetworkConfig::for_tests();
+    let mut storage = pool.access_storage().await.unwrap();
+    if storage.blocks_dal().is_genesis_needed().await.unwrap() {
+        ensure_genesis_state(
+            &mut storage,
+            network_config.micro_network_id,
+            &GenesisParams::mock(),
+        )
+        .await
+        .unwrap();
+    }
+    drop(storage);
+
+    let mut config = Web3JsonRpcConfig::for_tests();
+    config.graphql_max_complexity = Some(100);
+    GraphQlApi::new(pool, &config, network_config.micro_network_id)
+}
+
+#[tokio::test]
+async fn querying_genesis_block() {
+    let api = prepare_api().await;
+    let query = "{ block(number: 0) { number parent { number } transactionCount l1BatchNumber l1Batch { number } } }";
+    let response = api.execute(query.into()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data,
+        json!({
+            "block": {
+                "number": 0,
+                "parent": null,
+                "transactionCount": 0,
+                "l1BatchNumber": 0,
+                "l1Batch": { "number": 0 },
+            }
+        })
+    );
+}
+
+#[tokio::test]
+async fn querying_block_ranges_and_transactions() {
+    let api = prepare_api().await;
+    let query = "{ blocks(from: 0, to: 5) { number parent { number } transactions { hash } } }";
+    let response = api.execute(query.into()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data,
+        json!({
+            "blocks": [{ "number": 0, "parent": null, "transactions": [] }],
+        })
+    );
+
+    let hash = format!("{:?}", H256::repeat_byte(1));
+    let query = format!(
+        "{{ transaction(hash: \"{hash}\") {{ hash }} block(number: 0) {{ transactionAt(index: 0) {{ hash }} }} }}"
+    );
+    let response = api.execute(query.as_str().into()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+    let data = response.data.into_json().unwrap();
+    assert_eq!(
+        data,
+        json!({ "transaction": null, "block": { "transactionAt": null } })
+    );
+}
+
+#[tokio::test]
+async fn query_limits() {
+    let api = prepare_api().await;
+
+    let query = "{ blocks(from: 0, to: 1000) { number } }";
+    let response = api.execute(query.into()).await;
+    assert_eq!(response.errors.len(), 1);
+
+    let query = "{ block { transactions { logs { transaction { logs { index } } } } } }";
+    let response = api.execute(query.into()).await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(
+        response.errors[0].message.contains("complex"),
+        "{:?}",
+        response.errors
+    );
+
+    // Complexity of list fields depends on the number of requested entities.
+    let query = "{ blocks(from: 0, to: 99) { number hash } }";
+    let response = api.execute(query.into()).await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(
+        response.errors[0].message.contains("complex"),
+        "{:?}",
+        response.errors
+    );
+    let address = format!("{:?}", Address::repeat_byte(1));
+    let query =
+        format!("{{ account(address: \"{address}\") {{ transactions(first: 200) {{ hash }} }} }}");
+    let response = api.execute(query.as_str().into()).await;
+    assert_eq!(response.errors.len(), 1);
+    assert!(
+        response.errors[0].message.contains("complex"),
+        "{:?}",
+        response.errors
+    );
+    let query =
+        format!("{{ account(address: \"{address}\") {{ transactions(first: 5) {{ hash }} }} }}");
+    let response = api.execute(query.as_str().into()).await;
+    assert!(response.errors.is_empty(), "{:?}", response.errors);
+
+    let query = "{ logs(filter: { topics: [[], [], [], [], []] }) { index } }";
+    let response = api.execute(query.into()).await;
+    assert_eq!(response.errors.len(), 1);
+}
+
+#[tokio::test]
+async fn graphql_server_basics() {
+    let api = prepare_api().await;
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let server = api
+        .create_server(&(Ipv4Addr::LOCALHOST, 0).into(), stop_receiver)
+        .unwrap();
+    let local_addr = *server.local_addr();
+    let server_task = tokio::spawn(server.run());
+
+    let response: serde_json::Value = reqwest::Client::new()
+        .post(format!("http://{local_addr}/graphql"))
+        .json(&json!({ "query": "{ chainID }" }))
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    let chain_id = NetworkConfig::for_tests().micro_network_id.as_u64();
+    assert_eq!(
+        response,
+        json!({ "data": { "chainID": format!("{chain_id:#x}") } })
+    );
+
+    stop_sender.send_replace(true);
+    server_task.await.unwrap().unwrap();
+}
@@ -2,6 +2,7 @@
 
 pub mod contract_verification;
 pub mod execution_sandbox;
+pub mod graphql;
 pub mod healthcheck;
 pub mod tree;
 pub mod tx_sender;
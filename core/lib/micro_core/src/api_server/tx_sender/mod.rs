@@ -1,16 +1,20 @@
 //! Helper module to submit transactions into the micro Network.
 
-use std::{cmp, num::NonZeroU32, sync::Arc, time::Instant};
+use std::{cmp, net::IpAddr, num::NonZeroU32, sync::Arc, time::Instant};
 
 use governor::{
-    clock::MonotonicClock,
+    clock::{DefaultClock, MonotonicClock},
     middleware::NoOpMiddleware,
-    state::{InMemoryState, NotKeyed},
+    state::{keyed::DefaultKeyedStateStore, InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
-use micro_config::configs::{api::Web3JsonRpcConfig, chain::StateKeeperConfig};
+use micro_config::configs::{
+    api::Web3JsonRpcConfig,
+    chain::{MempoolConfig, StateKeeperConfig},
+};
 use micro_contracts::BaseSystemContracts;
 use micro_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool};
+use micro_mempool::L2TxFilter;
 use micro_state::PostgresStorageCaches;
 use micro_types::{
     fee::{Fee, TransactionExecutionMetrics},
@@ -37,15 +41,20 @@ pub(super) use self::{proxy::TxProxy, result::SubmitTxError};
 use crate::{
     api_server::{
         execution_sandbox::{
-            adjust_l1_gas_price_for_tx, execute_tx_eth_call, execute_tx_with_pending_state,
-            get_pubdata_for_factory_deps, BlockArgs, SubmitTxStage, TxExecutionArgs, TxSharedArgs,
-            VmConcurrencyLimiter, VmPermit, SANDBOX_METRICS,
+            adjust_l1_gas_price_for_tx, execute_tx_bundle_eth_call, execute_tx_eth_call,
+            execute_tx_with_pending_state, get_pubdata_for_factory_deps, ApiTracer, BlockArgs,
+            StorageOverrides, SubmitTxStage, TxExecutionArgs, TxSharedArgs, VmConcurrencyLimiter,
+            VmPermit, SANDBOX_METRICS,
         },
         tx_sender::result::ApiCallResult,
     },
+    deny_list::DenyList,
     l1_gas_price::L1GasPriceProvider,
     metrics::{TxStage, APP_METRICS},
-    state_keeper::seal_criteria::{ConditionalSealer, SealData},
+    state_keeper::{
+        l2_tx_filter,
+        seal_criteria::{ConditionalSealer, SealData},
+    },
 };
 
 mod proxy;
@@ -54,6 +63,11 @@ mod result;
 /// Type alias for the rate limiter implementation.
 type TxSenderRateLimiter =
     RateLimiter<NotKeyed, InMemoryState, MonotonicClock, NoOpMiddleware<Instant>>;
+/// Type alias for the rate limiter keyed by the client IP address.
+type TxSenderIpRateLimiter = RateLimiter<IpAddr, DefaultKeyedStateStore<IpAddr>, DefaultClock>;
+
+/// Number of client IP addresses tracked by the per-IP rate limiter, after which inactive addresses are purged.
+const MAX_TRACKED_CLIENT_IPS: usize = 100_000;
 
 #[derive(Debug, Clone)]
 pub struct MultiVMBaseSystemContracts {
@@ -152,6 +166,8 @@ pub struct TxSenderBuilder {
     /// Actual state keeper configuration, required for tx verification.
     /// If not set, transactions would not be checked against seal criteria.
     state_keeper_config: Option<StateKeeperConfig>,
+    /// Deny-list of transaction initiators. If not set, transactions are not checked against the deny-list.
+    deny_list: Option<DenyList>,
 }
 
 impl TxSenderBuilder {
@@ -163,6 +179,7 @@ impl TxSenderBuilder {
             rate_limiter: None,
             proxy: None,
             state_keeper_config: None,
+            deny_list: None,
         }
     }
 
@@ -192,6 +209,11 @@ impl TxSenderBuilder {
         self
     }
 
+    pub fn with_deny_list(mut self, deny_list: DenyList) -> Self {
+        self.deny_list = Some(deny_list);
+        self
+    }
+
     pub async fn build<G: L1GasPriceProvider>(
         self,
         l1_gas_price_source: Arc<G>,
@@ -203,6 +225,11 @@ impl TxSenderBuilder {
             self.master_connection_pool.is_some() || self.proxy.is_some(),
             "Either master connection pool or proxy must be set"
         );
+        let ip_rate_limiter = self.config.max_txs_per_ip_per_minute.map(|limit| {
+            let limit =
+                NonZeroU32::new(limit).expect("`max_txs_per_ip_per_minute` must be positive");
+            RateLimiter::keyed(Quota::per_minute(limit))
+        });
 
         TxSender(Arc::new(TxSenderInner {
             sender_config: self.config,
@@ -211,8 +238,10 @@ impl TxSenderBuilder {
             l1_gas_price_source,
             api_contracts,
             rate_limiter: self.rate_limiter,
+            ip_rate_limiter,
             proxy: self.proxy,
             state_keeper_config: self.state_keeper_config,
+            deny_list: self.deny_list,
             vm_concurrency_limiter,
             storage_caches,
         }))
@@ -233,12 +262,22 @@ pub struct TxSenderConfig {
     pub vm_execution_cache_misses_limit: Option<usize>,
     pub validation_computational_gas_limit: u32,
     pub chain_id: L2ChainId,
+    /// Minimum fee increase (in percent) for a transaction to replace a pending transaction
+    /// with the same initiator and nonce.
+    pub replacement_fee_bump_percent: u64,
+    /// Maximum number of pending transactions of a single initiator.
+    pub max_pending_txs_per_account: Option<u32>,
+    /// Maximum distance between the transaction nonce and the next nonce after pending transactions of the initiator.
+    pub max_nonce_gap_per_account: Option<u32>,
+    /// Maximum number of transactions submitted from a single IP address per minute.
+    pub max_txs_per_ip_per_minute: Option<u32>,
 }
 
 impl TxSenderConfig {
     pub fn new(
         state_keeper_config: &StateKeeperConfig,
         web3_json_config: &Web3JsonRpcConfig,
+        mempool_config: &MempoolConfig,
         chain_id: L2ChainId,
     ) -> Self {
         Self {
@@ -251,6 +290,10 @@ impl TxSenderConfig {
             validation_computational_gas_limit: state_keeper_config
                 .validation_computational_gas_limit,
             chain_id,
+            replacement_fee_bump_percent: mempool_config.replacement_fee_bump_percent(),
+            max_pending_txs_per_account: mempool_config.max_pending_txs_per_account,
+            max_nonce_gap_per_account: mempool_config.max_nonce_gap_per_account,
+            max_txs_per_ip_per_minute: mempool_config.max_txs_per_ip_per_minute,
         }
     }
 }
@@ -264,12 +307,16 @@ pub struct TxSenderInner<G> {
     pub(super) api_contracts: ApiContracts,
     /// Optional rate limiter that will limit the amount of transactions per second sent from a single entity.
     rate_limiter: Option<TxSenderRateLimiter>,
+    /// Optional rate limiter for transactions submitted from a single IP address.
+    ip_rate_limiter: Option<TxSenderIpRateLimiter>,
     /// Optional transaction proxy to be used for transaction submission.
     pub(super) proxy: Option<TxProxy>,
     /// An up-to-date version of the state keeper config.
     /// This field may be omitted on the external node, since the configuration may change unexpectedly.
     /// If this field is set to `None`, `TxSender` will assume that any transaction is executable.
     state_keeper_config: Option<StateKeeperConfig>,
+    /// Optional deny-list of transaction initiators.
+    deny_list: Option<DenyList>,
     /// Used to limit the amount of VMs that can be executed simultaneously.
     pub(super) vm_concurrency_limiter: Arc<VmConcurrencyLimiter>,
     // Caches used in VM execution.
@@ -302,13 +349,22 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         self.0.storage_caches.clone()
     }
 
+    /// Submits a transaction to the mempool. `client_ip` is the IP address of the client submitting
+    /// the transaction, if known; it's used to apply per-IP submission limits.
     #[tracing::instrument(skip(self, tx))]
-    pub async fn submit_tx(&self, tx: L2Tx) -> Result<L2TxSubmissionResult, SubmitTxError> {
+    pub async fn submit_tx(
+        &self,
+        tx: L2Tx,
+        client_ip: Option<IpAddr>,
+    ) -> Result<L2TxSubmissionResult, SubmitTxError> {
         if let Some(rate_limiter) = &self.0.rate_limiter {
             if rate_limiter.check().is_err() {
                 return Err(SubmitTxError::RateLimitExceeded);
             }
         }
+        if let (Some(ip_rate_limiter), Some(client_ip)) = (&self.0.ip_rate_limiter, client_ip) {
+            check_ip_rate_limit(ip_rate_limiter, client_ip)?;
+        }
 
         let stage_latency = SANDBOX_METRICS.submit_tx[&SubmitTxStage::Validate].start();
         self.validate_tx(&tx).await?;
@@ -374,9 +430,14 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             );
         }
 
+        let expected_nonce = self.get_expected_nonce(&tx).await;
+        self.validate_account_limits(&tx, expected_nonce).await?;
+
         let nonce = tx.common_data.nonce.0;
         let hash = tx.hash();
-        let expected_nonce = self.get_expected_nonce(&tx).await;
+        // The fee bump required to replace a pending transaction is enforced by the DB upsert, so that
+        // concurrent replacements cannot bypass it.
+        let min_fee_bump = self.0.sender_config.replacement_fee_bump_percent;
         let submission_res_handle = self
             .0
             .master_connection_pool
@@ -386,7 +447,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             .await
             .unwrap()
             .transactions_dal()
-            .insert_transaction_l2(tx, tx_metrics)
+            .insert_transaction_l2_with_fee_bump(tx, tx_metrics, min_fee_bump)
             .await;
 
         APP_METRICS.processed_txs[&TxStage::Mempool(submission_res_handle)].inc();
@@ -398,6 +459,10 @@ impl<G: L1GasPriceProvider> TxSender<G> {
                 nonce,
             )),
             L2TxSubmissionResult::Duplicate => Err(SubmitTxError::IncorrectTx(TxDuplication(hash))),
+            L2TxSubmissionResult::ReplacementUnderpriced => {
+                tracing::info!("Submitted tx {hash:?} is underpriced to replace pending tx");
+                Err(SubmitTxError::ReplacementUnderpriced(min_fee_bump))
+            }
             _ => {
                 SANDBOX_METRICS.submit_tx[&SubmitTxStage::DbInsert]
                     .observe(stage_started_at.elapsed());
@@ -422,6 +487,17 @@ impl<G: L1GasPriceProvider> TxSender<G> {
     }
 
     async fn validate_tx(&self, tx: &L2Tx) -> Result<(), SubmitTxError> {
+        if let Some(deny_list) = &self.0.deny_list {
+            let initiator = tx.initiator_account();
+            if deny_list.contains(&initiator) {
+                tracing::info!(
+                    "Submitted Tx {:?} is rejected because its initiator {initiator:?} is on the deny-list",
+                    tx.hash()
+                );
+                return Err(SubmitTxError::DeniedAddress(initiator));
+            }
+        }
+
         let max_gas = U256::from(u32::MAX);
         if tx.common_data.fee.gas_limit > max_gas
             || tx.common_data.fee.gas_per_pubdata_limit > max_gas
@@ -511,6 +587,42 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         }
     }
 
+    /// Checks per-initiator limits on the number of pending transactions and the nonce gap.
+    async fn validate_account_limits(
+        &self,
+        tx: &L2Tx,
+        expected_nonce: Nonce,
+    ) -> Result<(), SubmitTxError> {
+        let config = &self.0.sender_config;
+        if config.max_pending_txs_per_account.is_none()
+            && config.max_nonce_gap_per_account.is_none()
+        {
+            return Ok(());
+        }
+
+        let pending_nonces = self
+            .0
+            .master_connection_pool
+            .as_ref()
+            .unwrap() // Checked by the caller
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .transactions_dal()
+            .get_pending_l2_tx_nonces(tx.initiator_account())
+            .await
+            .unwrap();
+        let result = check_account_limits(config, expected_nonce, &pending_nonces, tx.nonce());
+        if let Err(err) = &result {
+            tracing::info!(
+                "Submitted tx {:?} violates limits for initiator {:?}: {err}",
+                tx.hash(),
+                tx.initiator_account()
+            );
+        }
+        result
+    }
+
     async fn get_expected_nonce(&self, tx: &L2Tx) -> Nonce {
         let mut connection = self
             .0
@@ -541,7 +653,12 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             return Ok(());
         }
 
-        let balance = self.get_balance(&tx.common_data.initiator_address).await;
+        let balance = self
+            .get_balance(
+                &tx.common_data.initiator_address,
+                &StorageOverrides::default(),
+            )
+            .await;
 
         // Estimate the minimum fee price user will agree to.
         let gas_price = cmp::min(
@@ -563,8 +680,15 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         }
     }
 
-    async fn get_balance(&self, initiator_address: &H160) -> U256 {
+    async fn get_balance(
+        &self,
+        initiator_address: &H160,
+        storage_overrides: &StorageOverrides,
+    ) -> U256 {
         let eth_balance_key = storage_key_for_eth_balance(initiator_address);
+        if let Some(balance) = storage_overrides.slot_value(&eth_balance_key) {
+            return h256_to_u256(balance);
+        }
 
         let balance = self
             .0
@@ -591,6 +715,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         tx_gas_limit: u32,
         l1_gas_price: u64,
         base_fee: u64,
+        storage_overrides: &StorageOverrides,
     ) -> (VmExecutionResultAndLogs, TransactionExecutionMetrics) {
         let gas_limit_with_overhead = tx_gas_limit
             + derive_overhead(
@@ -622,8 +747,12 @@ impl<G: L1GasPriceProvider> TxSender<G> {
 
         let shared_args = self.shared_args_for_gas_estimate(l1_gas_price);
         let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
-        let execution_args =
-            TxExecutionArgs::for_gas_estimate(vm_execution_cache_misses_limit, &tx, base_fee);
+        let execution_args = TxExecutionArgs::for_gas_estimate(
+            vm_execution_cache_misses_limit,
+            &tx,
+            base_fee,
+            storage_overrides.clone(),
+        );
         let (exec_result, tx_metrics) = execute_tx_with_pending_state(
             vm_permit,
             shared_args,
@@ -655,8 +784,10 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         mut tx: Transaction,
         estimated_fee_scale_factor: f64,
         acceptable_overestimation: u32,
+        storage_overrides: Option<StorageOverrides>,
     ) -> Result<Fee, SubmitTxError> {
         let estimation_started_at = Instant::now();
+        let storage_overrides = storage_overrides.unwrap_or_default();
         let l1_gas_price = {
             let effective_gas_price = self.0.l1_gas_price_source.estimate_effective_gas_price();
             let current_l1_gas_price =
@@ -692,20 +823,26 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         // if the default account does not have enough funds
         // for transferring tx.value, without taking into account the fee,
         // there is no sense to estimate the fee
-        let account_code_hash = self
-            .0
-            .replica_connection_pool
-            .access_storage_tagged("api")
-            .await
-            .unwrap()
-            .storage_dal()
-            .get_by_key(&hashed_key)
-            .await
-            .unwrap_or_default();
+        let account_code_hash = if let Some(code_hash) = storage_overrides.slot_value(&hashed_key) {
+            code_hash
+        } else {
+            self.0
+                .replica_connection_pool
+                .access_storage_tagged("api")
+                .await
+                .unwrap()
+                .storage_dal()
+                .get_by_key(&hashed_key)
+                .await
+                .unwrap_or_default()
+        };
 
         if !tx.is_l1()
             && account_code_hash == H256::zero()
-            && tx.execute.value > self.get_balance(&tx.initiator_account()).await
+            && tx.execute.value
+                > self
+                    .get_balance(&tx.initiator_account(), &storage_overrides)
+                    .await
         {
             tracing::info!(
                 "fee estimation failed on validation step.
@@ -782,6 +919,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
                     try_gas_limit,
                     l1_gas_price,
                     base_fee,
+                    &storage_overrides,
                 )
                 .await;
 
@@ -819,6 +957,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
                 suggested_gas_limit,
                 l1_gas_price,
                 base_fee,
+                &storage_overrides,
             )
             .await;
 
@@ -855,6 +994,7 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         &self,
         block_args: BlockArgs,
         tx: L2Tx,
+        storage_overrides: Option<StorageOverrides>,
     ) -> Result<Vec<u8>, SubmitTxError> {
         let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
         let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
@@ -867,12 +1007,37 @@ impl<G: L1GasPriceProvider> TxSender<G> {
             tx,
             block_args,
             vm_execution_cache_misses_limit,
+            storage_overrides.unwrap_or_default(),
             vec![],
         )
         .await
         .into_api_call_result()
     }
 
+    /// Executes a bundle of calls on top of the specified block, so that each call observes
+    /// the changes made by the preceding ones.
+    pub(crate) async fn simulate_bundle(
+        &self,
+        block_args: BlockArgs,
+        txs: Vec<(L2Tx, Vec<ApiTracer>)>,
+        storage_overrides: Option<StorageOverrides>,
+    ) -> Result<Vec<VmExecutionResultAndLogs>, SubmitTxError> {
+        let vm_permit = self.0.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(SubmitTxError::ServerShuttingDown)?;
+
+        let vm_execution_cache_misses_limit = self.0.sender_config.vm_execution_cache_misses_limit;
+        Ok(execute_tx_bundle_eth_call(
+            vm_permit,
+            self.shared_args(),
+            self.0.replica_connection_pool.clone(),
+            txs,
+            block_args,
+            vm_execution_cache_misses_limit,
+            storage_overrides.unwrap_or_default(),
+        )
+        .await)
+    }
+
     pub fn gas_price(&self) -> u64 {
         let gas_price = self.0.l1_gas_price_source.estimate_effective_gas_price();
         let l1_gas_price = (gas_price as f64 * self.0.sender_config.gas_price_scale_factor).round();
@@ -883,6 +1048,19 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         base_fee
     }
 
+    /// Returns the filter applied by the state keeper when picking L2 transactions from the mempool.
+    pub(crate) fn l2_tx_filter(&self) -> L2TxFilter {
+        l2_tx_filter(
+            self.0.l1_gas_price_source.as_ref(),
+            self.0.sender_config.fair_l2_gas_price,
+        )
+    }
+
+    /// Returns the deny-list checked for submitted transactions, if any.
+    pub(crate) fn deny_list(&self) -> Option<&DenyList> {
+        self.0.deny_list.as_ref()
+    }
+
     fn ensure_tx_executable(
         &self,
         transaction: Transaction,
@@ -923,3 +1101,119 @@ impl<G: L1GasPriceProvider> TxSender<G> {
         Ok(())
     }
 }
+
+/// Checks that the client IP address hasn't exceeded its transaction submission limit.
+fn check_ip_rate_limit(
+    ip_rate_limiter: &TxSenderIpRateLimiter,
+    client_ip: IpAddr,
+) -> Result<(), SubmitTxError> {
+    if ip_rate_limiter.len() > MAX_TRACKED_CLIENT_IPS {
+        ip_rate_limiter.retain_recent();
+    }
+    if ip_rate_limiter.check_key(&client_ip).is_err() {
+        tracing::info!(
+            "Transaction submitted from {client_ip} is rejected by the per-IP rate limit"
+        );
+        return Err(SubmitTxError::IpRateLimitExceeded(client_ip));
+    }
+    Ok(())
+}
+
+/// Checks per-initiator limits for a transaction with the specified `nonce`. `pending_nonces` are the nonces
+/// of pending transactions of the initiator sorted in the ascending order, and `expected_nonce` is the committed
+/// nonce of the initiator.
+fn check_account_limits(
+    config: &TxSenderConfig,
+    expected_nonce: Nonce,
+    pending_nonces: &[Nonce],
+    nonce: Nonce,
+) -> Result<(), SubmitTxError> {
+    if let Some(max_pending_txs) = config.max_pending_txs_per_account {
+        // A transaction replacing a pending one doesn't increase the number of pending transactions.
+        let is_replacement = pending_nonces.contains(&nonce);
+        if !is_replacement && pending_nonces.len() >= max_pending_txs as usize {
+            return Err(SubmitTxError::TooManyPendingTransactions(max_pending_txs));
+        }
+    }
+
+    if let Some(max_nonce_gap) = config.max_nonce_gap_per_account {
+        // Find the next nonce after pending transactions that can be executed without gaps.
+        let mut next_nonce = expected_nonce;
+        for &pending_nonce in pending_nonces {
+            if pending_nonce == next_nonce {
+                next_nonce += 1;
+            } else if pending_nonce > next_nonce {
+                break;
+            }
+        }
+        let max_nonce = next_nonce.0.saturating_add(max_nonce_gap);
+        if nonce.0 > max_nonce {
+            return Err(SubmitTxError::NonceIsTooHigh(
+                expected_nonce.0,
+                max_nonce,
+                nonce.0,
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_matches::assert_matches;
+
+    use super::*;
+
+    fn config(max_pending_txs: Option<u32>, max_nonce_gap: Option<u32>) -> TxSenderConfig {
+        let mut config = TxSenderConfig::new(
+            &StateKeeperConfig::for_tests(),
+            &Web3JsonRpcConfig::for_tests(),
+            &MempoolConfig::for_tests(),
+            L2ChainId::default(),
+        );
+        config.max_pending_txs_per_account = max_pending_txs;
+        config.max_nonce_gap_per_account = max_nonce_gap;
+        config
+    }
+
+    #[test]
+    fn limiting_pending_txs_per_account() {
+        let config = config(Some(2), None);
+        check_account_limits(&config, Nonce(0), &[], Nonce(5)).unwrap();
+        check_account_limits(&config, Nonce(0), &[Nonce(0)], Nonce(1)).unwrap();
+
+        let pending_nonces = [Nonce(0), Nonce(1)];
+        let err = check_account_limits(&config, Nonce(0), &pending_nonces, Nonce(2)).unwrap_err();
+        assert_matches!(err, SubmitTxError::TooManyPendingTransactions(2));
+        // Replacing a pending transaction is allowed.
+        check_account_limits(&config, Nonce(0), &pending_nonces, Nonce(1)).unwrap();
+    }
+
+    #[test]
+    fn limiting_nonce_gap_per_account() {
+        let config = config(None, Some(1));
+        check_account_limits(&config, Nonce(3), &[], Nonce(3)).unwrap();
+        check_account_limits(&config, Nonce(3), &[], Nonce(4)).unwrap();
+        let err = check_account_limits(&config, Nonce(3), &[], Nonce(5)).unwrap_err();
+        assert_matches!(err, SubmitTxError::NonceIsTooHigh(3, 4, 5));
+
+        // Pending transactions with contiguous nonces shift the allowed range.
+        let pending_nonces = [Nonce(3), Nonce(4), Nonce(7)];
+        check_account_limits(&config, Nonce(3), &pending_nonces, Nonce(6)).unwrap();
+        let err = check_account_limits(&config, Nonce(3), &pending_nonces, Nonce(8)).unwrap_err();
+        assert_matches!(err, SubmitTxError::NonceIsTooHigh(3, 6, 8));
+    }
+
+    #[test]
+    fn limiting_txs_per_ip() {
+        let limiter = RateLimiter::keyed(Quota::per_minute(NonZeroU32::new(2).unwrap()));
+        let client_ip = IpAddr::from([10, 0, 0, 1]);
+        let other_client_ip = IpAddr::from([10, 0, 0, 2]);
+
+        check_ip_rate_limit(&limiter, client_ip).unwrap();
+        check_ip_rate_limit(&limiter, client_ip).unwrap();
+        let err = check_ip_rate_limit(&limiter, client_ip).unwrap_err();
+        assert_matches!(err, SubmitTxError::IpRateLimitExceeded(ip) if ip == client_ip);
+        check_ip_rate_limit(&limiter, other_client_ip).unwrap();
+    }
+}
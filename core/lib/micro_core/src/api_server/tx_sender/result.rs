@@ -1,4 +1,6 @@
-use micro_types::{l2::error::TxCheckError, U256};
+use std::net::IpAddr;
+
+use micro_types::{l2::error::TxCheckError, Address, U256};
 use multivm::{
     interface::{ExecutionResult, VmExecutionResultAndLogs},
     tracers::validator::ValidationError,
@@ -25,6 +27,12 @@ pub enum SubmitTxError {
     Unexecutable(String),
     #[error("too many transactions")]
     RateLimitExceeded,
+    /// IpRateLimitExceeded is returned if too many transactions were submitted from the client IP address.
+    #[error("too many transactions submitted from {0}")]
+    IpRateLimitExceeded(IpAddr),
+    /// TooManyPendingTransactions is returned if the transaction initiator has too many pending transactions.
+    #[error("too many pending transactions for the account. at most {0} are allowed")]
+    TooManyPendingTransactions(u32),
     #[error("server shutting down")]
     ServerShuttingDown,
     #[error("failed to include transaction in the system. reason: {0}")]
@@ -66,6 +74,13 @@ pub enum SubmitTxError {
     /// than required to start the invocation.
     #[error("intrinsic gas too low")]
     IntrinsicGas,
+    /// ReplacementUnderpriced is returned if the transaction replaces a pending transaction
+    /// with the same nonce without a sufficient fee increase.
+    #[error("replacement transaction underpriced. fees must be increased by at least {0}%")]
+    ReplacementUnderpriced(u64),
+    /// DeniedAddress is returned if the transaction initiator is on the deny-list.
+    #[error("account {0:?} is not allowed to submit transactions")]
+    DeniedAddress(Address),
     /// Error returned from main node
     #[error("{0}")]
     ProxyError(#[from] micro_web3_decl::jsonrpsee::core::Error),
@@ -82,6 +97,8 @@ impl SubmitTxError {
             Self::GasLimitIsTooBig => "gas-limit-is-too-big",
             Self::Unexecutable(_) => "unexecutable",
             Self::RateLimitExceeded => "rate-limit-exceeded",
+            Self::IpRateLimitExceeded(_) => "ip-rate-limit-exceeded",
+            Self::TooManyPendingTransactions(_) => "too-many-pending-transactions",
             Self::ServerShuttingDown => "shutting-down",
             Self::BootloaderFailure(_) => "bootloader-failure",
             Self::ValidationFailed(_) => "validation-failed",
@@ -98,6 +115,8 @@ impl SubmitTxError {
             Self::FeePerPubdataByteTooHigh => "pubdata-price-limit-too-high",
             Self::InsufficientFundsForTransfer => "insufficient-funds-for-transfer",
             Self::IntrinsicGas => "intrinsic-gas",
+            Self::ReplacementUnderpriced(_) => "replacement-underpriced",
+            Self::DeniedAddress(_) => "denied-address",
             Self::ProxyError(_) => "proxy-error",
         }
     }
@@ -1,4 +1,4 @@
-use std::{future::Future, num::NonZeroU32, sync::Arc};
+use std::{future::Future, net::IpAddr, num::NonZeroU32, slice, sync::Arc};
 
 use futures::{future, FutureExt};
 use governor::{
@@ -8,19 +8,30 @@ use governor::{
     Quota, RateLimiter,
 };
 use jsonrpc_core::{
+    error::ErrorCode,
     middleware::{self, Middleware},
-    Error, FutureResponse, Request, Response, Version,
+    Call, Error, FutureResponse, Id, Output, Request, Response, Version,
 };
 use jsonrpc_pubsub::Session;
 use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
 
+use crate::api_server::web3::rate_limiter::{
+    with_client_ip, ClientKey, ClientRateLimiter, RATE_LIMITED_ERROR_CODE,
+    RATE_LIMITED_ERROR_MESSAGE,
+};
+
 /// Configures the rate limiting for the WebSocket API.
 /// Rate limiting is applied per active connection, e.g. a single connected user may not send more than X requests
 /// per minute.
+///
+/// Additionally, the metadata can hold the client identity used for per-client rate limiting
+/// (see [`ClientRateLimiter`]) and the client IP address exposed to RPC method handlers.
 #[derive(Debug, Clone)]
 pub struct RateLimitMetadata<T> {
     meta: T,
     rate_limiter: Option<Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>>,
+    client: Option<ClientKey>,
+    client_ip: Option<IpAddr>,
 }
 
 impl<T> RateLimitMetadata<T> {
@@ -35,7 +46,22 @@ impl<T> RateLimitMetadata<T> {
             None
         };
 
-        Self { meta, rate_limiter }
+        Self {
+            meta,
+            rate_limiter,
+            client: None,
+            client_ip: None,
+        }
+    }
+
+    pub(crate) fn with_client(mut self, client: ClientKey) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    pub(crate) fn with_client_ip(mut self, client_ip: Option<IpAddr>) -> Self {
+        self.client_ip = client_ip;
+        self
     }
 }
 
@@ -50,6 +76,7 @@ impl<T: jsonrpc_pubsub::PubSubMetadata> jsonrpc_pubsub::PubSubMetadata for RateL
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "transport", rename_all = "snake_case")]
 pub(crate) enum Transport {
+    Http,
     Ws,
 }
 
@@ -68,16 +95,18 @@ struct LimitMiddlewareMetrics {
 #[vise::register]
 static METRICS: vise::Global<LimitMiddlewareMetrics> = vise::Global::new();
 
-/// Middleware that implements limiting for WebSocket connections:
-/// - Limits the number of requests per minute for a single connection.
+/// Middleware that implements limiting for JSON-RPC requests:
+/// - Limits the number of requests per minute for a single WebSocket connection.
+/// - Limits the total weight of called methods for a single client, if per-client rate limiting is enabled.
 /// - Limits the maximum size of the batch requests.
 ///
-/// Rate limiting data is stored in the metadata of the connection, while the maximum batch size is stored in the
-/// middleware itself.
+/// Rate limiting data and the client identity are stored in the request / connection metadata, while the client
+/// rate limiter and the maximum batch size are stored in the middleware itself.
 #[derive(Debug)]
 pub(crate) struct LimitMiddleware {
     transport: Transport,
     max_batch_size: Option<usize>,
+    client_limiter: Option<ClientRateLimiter>,
 }
 
 impl LimitMiddleware {
@@ -85,6 +114,32 @@ impl LimitMiddleware {
         Self {
             transport,
             max_batch_size,
+            client_limiter: None,
+        }
+    }
+
+    pub fn with_client_limiter(mut self, client_limiter: Option<ClientRateLimiter>) -> Self {
+        self.client_limiter = client_limiter;
+        self
+    }
+
+    fn rate_limited_response(request: &Request) -> Response {
+        let error = Error {
+            code: ErrorCode::ServerError(RATE_LIMITED_ERROR_CODE),
+            message: RATE_LIMITED_ERROR_MESSAGE.to_owned(),
+            data: None,
+        };
+        let output = |call: &Call| {
+            let id = match call {
+                Call::MethodCall(call) => call.id.clone(),
+                Call::Notification(_) | Call::Invalid { .. } => Id::Null,
+            };
+            Output::from(Err(error.clone()), id, Some(Version::V2))
+        };
+
+        match request {
+            Request::Single(call) => Response::Single(output(call)),
+            Request::Batch(calls) => Response::Batch(calls.iter().map(output).collect()),
         }
     }
 }
@@ -117,13 +172,24 @@ impl<T: jsonrpc_core::Metadata> Middleware<RateLimitMetadata<T>> for LimitMiddle
             // Note: if required, we can extract data on rate limiting from the error.
             if rate_limiter.check_n(num_requests).is_err() {
                 METRICS.rate_limited[&self.transport].inc();
-                let err = Error {
-                    code: jsonrpc_core::error::ErrorCode::ServerError(429),
-                    message: "Too many requests".to_string(),
-                    data: None,
-                };
+                let response = Self::rate_limited_response(&request);
+                return future::ready(Some(response)).boxed().left_future();
+            }
+        }
 
-                let response = Response::from(err, Some(Version::V2));
+        // Check per-client limits. As with the per-connection limits, the entire batch is either admitted or rejected.
+        if let (Some(client_limiter), Some(client)) = (&self.client_limiter, &meta.client) {
+            let calls = match &request {
+                Request::Single(call) => slice::from_ref(call),
+                Request::Batch(calls) => calls.as_slice(),
+            };
+            let methods = calls.iter().filter_map(|call| match call {
+                Call::MethodCall(call) => Some(call.method.as_str()),
+                Call::Notification(notification) => Some(notification.method.as_str()),
+                Call::Invalid { .. } => None,
+            });
+            if client_limiter.check(client, methods).is_err() {
+                let response = Self::rate_limited_response(&request);
                 return future::ready(Some(response)).boxed().left_future();
             }
         }
@@ -132,14 +198,17 @@ impl<T: jsonrpc_core::Metadata> Middleware<RateLimitMetadata<T>> for LimitMiddle
         if let Request::Batch(batch) = &request {
             METRICS.size[&self.transport].observe(batch.len());
 
-            if Some(batch.len()) > self.max_batch_size {
+            if matches!(self.max_batch_size, Some(max_batch_size) if batch.len() > max_batch_size) {
                 METRICS.rejected[&self.transport].inc();
                 let response = Response::from(Error::invalid_request(), Some(Version::V2));
                 return future::ready(Some(response)).boxed().left_future();
             }
         }
 
-        // Proceed with the request.
-        next(request, meta).right_future()
+        // Proceed with the request, making the client IP address available to method handlers.
+        let client_ip = meta.client_ip;
+        with_client_ip(client_ip, next(request, meta))
+            .boxed()
+            .left_future()
     }
 }
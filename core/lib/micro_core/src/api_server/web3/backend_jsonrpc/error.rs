@@ -16,12 +16,19 @@ pub fn into_jsrpc_error(err: Web3Error) -> Error {
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFeeParams(_)
+            | Web3Error::InvalidStateOverride(_)
+            | Web3Error::BundleTooLarge(_)
+            | Web3Error::TooManyProofKeys(_)
             | Web3Error::LogsLimitExceeded(_, _, _)
-            | Web3Error::InvalidFilterBlockHash => ErrorCode::InvalidParams,
+            | Web3Error::TraceLimitExceeded(_, _, _)
+            | Web3Error::InvalidFilterBlockHash
+            | Web3Error::UpgradeActivationAfterL1(_) => ErrorCode::InvalidParams,
             Web3Error::SubmitTransactionError(_, _) | Web3Error::SerializationError(_) => 3.into(),
             Web3Error::PubSubTimeout => 4.into(),
-            Web3Error::RequestTimeout => 5.into(),
-            Web3Error::TreeApiUnavailable => 6.into(),
+            Web3Error::RequestTimeout | Web3Error::TxInclusionTimeout(_) => 5.into(),
+            Web3Error::TreeApiUnavailable
+            | Web3Error::MempoolUnavailable
+            | Web3Error::PriorityOpsMonitorUnavailable => 6.into(),
         },
         message: match err {
             Web3Error::SubmitTransactionError(_, _) => err.to_string(),
@@ -0,0 +1,99 @@
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_derive::rpc;
+use micro_types::{
+    deny_list::{DenyListAuditRecord, DenyListEntry},
+    l2::prover::Paging,
+    Address, L1BatchNumber,
+};
+
+use crate::{
+    api_server::web3::{backend_jsonrpc::error::into_jsrpc_error, AdminNamespace},
+    l1_gas_price::L1GasPriceProvider,
+};
+
+#[rpc]
+pub trait AdminNamespaceT {
+    #[rpc(name = "admin_getDenyList")]
+    fn get_deny_list(&self) -> BoxFuture<Result<Vec<DenyListEntry>>>;
+
+    #[rpc(name = "admin_addToDenyList")]
+    fn add_to_deny_list(&self, address: Address, reason: String) -> BoxFuture<Result<()>>;
+
+    #[rpc(name = "admin_removeFromDenyList")]
+    fn remove_from_deny_list(&self, address: Address, reason: String) -> BoxFuture<Result<bool>>;
+
+    #[rpc(name = "admin_getDenyListAuditLog")]
+    fn get_deny_list_audit_log(
+        &self,
+        address: Option<Address>,
+        paging: Option<Paging>,
+    ) -> BoxFuture<Result<Vec<DenyListAuditRecord>>>;
+
+    #[rpc(name = "admin_scheduleProtocolUpgrade")]
+    fn schedule_protocol_upgrade(
+        &self,
+        protocol_version: u16,
+        activation_timestamp: Option<u64>,
+        activation_l1_batch_number: Option<L1BatchNumber>,
+    ) -> BoxFuture<Result<bool>>;
+}
+
+impl<G: L1GasPriceProvider + Send + Sync + 'static> AdminNamespaceT for AdminNamespace<G> {
+    fn get_deny_list(&self) -> BoxFuture<Result<Vec<DenyListEntry>>> {
+        let self_ = self.clone();
+        Box::pin(async move { self_.get_deny_list_impl().await.map_err(into_jsrpc_error) })
+    }
+
+    fn add_to_deny_list(&self, address: Address, reason: String) -> BoxFuture<Result<()>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .add_to_deny_list_impl(address, reason)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn remove_from_deny_list(&self, address: Address, reason: String) -> BoxFuture<Result<bool>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .remove_from_deny_list_impl(address, reason)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn get_deny_list_audit_log(
+        &self,
+        address: Option<Address>,
+        paging: Option<Paging>,
+    ) -> BoxFuture<Result<Vec<DenyListAuditRecord>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_deny_list_audit_log_impl(address, paging)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn schedule_protocol_upgrade(
+        &self,
+        protocol_version: u16,
+        activation_timestamp: Option<u64>,
+        activation_l1_batch_number: Option<L1BatchNumber>,
+    ) -> BoxFuture<Result<bool>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .schedule_protocol_upgrade_impl(
+                    protocol_version,
+                    activation_timestamp,
+                    activation_l1_batch_number,
+                )
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+}
@@ -1,7 +1,7 @@
 use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
 use micro_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{BlockId, BlockNumber, DebugTrace, ResultDebugCall, TracerConfig},
     transaction_request::CallRequest,
     H256,
 };
@@ -17,14 +17,14 @@ pub trait DebugNamespaceT {
         &self,
         block: BlockNumber,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>>;
+    ) -> BoxFuture<Result<Vec<ResultDebugCall<DebugTrace>>>>;
 
     #[rpc(name = "debug_traceBlockByHash")]
     fn trace_block_by_hash(
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>>;
+    ) -> BoxFuture<Result<Vec<ResultDebugCall<DebugTrace>>>>;
 
     #[rpc(name = "debug_traceCall")]
     fn trace_call(
@@ -32,14 +32,14 @@ pub trait DebugNamespaceT {
         request: CallRequest,
         block: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<DebugCall>>;
+    ) -> BoxFuture<Result<DebugTrace>>;
 
     #[rpc(name = "debug_traceTransaction")]
     fn trace_transaction(
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Option<DebugCall>>>;
+    ) -> BoxFuture<Result<Option<DebugTrace>>>;
 }
 
 impl DebugNamespaceT for DebugNamespace {
@@ -47,7 +47,7 @@ impl DebugNamespaceT for DebugNamespace {
         &self,
         block: BlockNumber,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>> {
+    ) -> BoxFuture<Result<Vec<ResultDebugCall<DebugTrace>>>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
@@ -61,7 +61,7 @@ impl DebugNamespaceT for DebugNamespace {
         &self,
         hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Vec<ResultDebugCall>>> {
+    ) -> BoxFuture<Result<Vec<ResultDebugCall<DebugTrace>>>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
@@ -76,7 +76,7 @@ impl DebugNamespaceT for DebugNamespace {
         request: CallRequest,
         block: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<DebugCall>> {
+    ) -> BoxFuture<Result<DebugTrace>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
@@ -90,8 +90,13 @@ impl DebugNamespaceT for DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> BoxFuture<Result<Option<DebugCall>>> {
+    ) -> BoxFuture<Result<Option<DebugTrace>>> {
         let self_ = self.clone();
-        Box::pin(async move { Ok(self_.debug_trace_transaction_impl(tx_hash, options).await) })
+        Box::pin(async move {
+            self_
+                .debug_trace_transaction_impl(tx_hash, options)
+                .await
+                .map_err(into_jsrpc_error)
+        })
     }
 }
@@ -2,8 +2,8 @@ use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
 use micro_types::{
     api::{
-        BlockId, BlockIdVariant, BlockNumber, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        state_override::StateOverride, BlockId, BlockIdVariant, BlockNumber, Transaction,
+        TransactionId, TransactionReceipt, TransactionVariant,
     },
     transaction_request::CallRequest,
     web3::types::{FeeHistory, Index, SyncState},
@@ -25,13 +25,19 @@ pub trait EthNamespaceT {
     fn chain_id(&self) -> BoxFuture<Result<U64>>;
 
     #[rpc(name = "eth_call")]
-    fn call(&self, req: CallRequest, block: Option<BlockIdVariant>) -> BoxFuture<Result<Bytes>>;
+    fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> BoxFuture<Result<Bytes>>;
 
     #[rpc(name = "eth_estimateGas")]
     fn estimate_gas(
         &self,
         req: CallRequest,
         _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> BoxFuture<Result<U256>>;
 
     #[rpc(name = "eth_gasPrice")]
@@ -130,6 +136,12 @@ pub trait EthNamespaceT {
     #[rpc(name = "eth_getTransactionReceipt")]
     fn get_transaction_receipt(&self, hash: H256) -> BoxFuture<Result<Option<TransactionReceipt>>>;
 
+    #[rpc(name = "eth_getBlockReceipts")]
+    fn get_block_receipts(
+        &self,
+        block: BlockIdVariant,
+    ) -> BoxFuture<Result<Option<Vec<TransactionReceipt>>>>;
+
     #[rpc(name = "eth_protocolVersion")]
     fn protocol_version(&self) -> BoxFuture<Result<String>>;
 
@@ -188,11 +200,16 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> EthNamespaceT for EthNamespa
         Box::pin(async move { Ok(self_.chain_id_impl()) })
     }
 
-    fn call(&self, req: CallRequest, block: Option<BlockIdVariant>) -> BoxFuture<Result<Bytes>> {
+    fn call(
+        &self,
+        req: CallRequest,
+        block: Option<BlockIdVariant>,
+        state_override: Option<StateOverride>,
+    ) -> BoxFuture<Result<Bytes>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
-                .call_impl(req, block.map(Into::into))
+                .call_impl(req, block.map(Into::into), state_override)
                 .await
                 .map_err(into_jsrpc_error)
         })
@@ -202,11 +219,12 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> EthNamespaceT for EthNamespa
         &self,
         req: CallRequest,
         block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> BoxFuture<Result<U256>> {
         let self_ = self.clone();
         Box::pin(async move {
             self_
-                .estimate_gas_impl(req, block)
+                .estimate_gas_impl(req, block, state_override)
                 .await
                 .map_err(into_jsrpc_error)
         })
@@ -431,6 +449,19 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> EthNamespaceT for EthNamespa
         })
     }
 
+    fn get_block_receipts(
+        &self,
+        block: BlockIdVariant,
+    ) -> BoxFuture<Result<Option<Vec<TransactionReceipt>>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_block_receipts_impl(block.into())
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
     fn protocol_version(&self) -> BoxFuture<Result<String>> {
         let self_ = self.clone();
         Box::pin(async move { Ok(self_.protocol_version()) })
@@ -1,7 +1,11 @@
+pub mod admin;
 pub mod debug;
 pub mod en;
 pub mod eth;
 pub mod net;
+pub mod prover;
+pub mod trace;
+pub mod txpool;
 pub mod web3;
 pub mod zks;
 
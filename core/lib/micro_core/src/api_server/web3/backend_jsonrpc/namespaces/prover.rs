@@ -0,0 +1,84 @@
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_derive::rpc;
+use micro_types::{
+    l2::prover::{Assignment, DepositInfo, Paging},
+    Address, L1BatchNumber,
+};
+
+use crate::{
+    api_server::web3::{backend_jsonrpc::error::into_jsrpc_error, ProverNamespace},
+    l1_gas_price::L1GasPriceProvider,
+};
+
+#[rpc]
+pub trait ProverNamespaceT {
+    #[rpc(name = "prover_getAssignment")]
+    fn get_assignment(&self, l1_batch_number: L1BatchNumber) -> BoxFuture<Result<Vec<Assignment>>>;
+
+    #[rpc(name = "prover_getAssignmentsByProver")]
+    fn get_assignments_by_prover(
+        &self,
+        prover: Address,
+        paging: Option<Paging>,
+    ) -> BoxFuture<Result<Vec<Assignment>>>;
+
+    #[rpc(name = "prover_getDepositInfo")]
+    fn get_deposit_info(&self, prover: Address) -> BoxFuture<Result<DepositInfo>>;
+
+    #[rpc(name = "prover_getPenalties")]
+    fn get_penalties(
+        &self,
+        prover: Option<Address>,
+        paging: Option<Paging>,
+    ) -> BoxFuture<Result<Vec<Assignment>>>;
+}
+
+impl<G: L1GasPriceProvider + Send + Sync + 'static> ProverNamespaceT for ProverNamespace<G> {
+    fn get_assignment(&self, l1_batch_number: L1BatchNumber) -> BoxFuture<Result<Vec<Assignment>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_assignment_impl(l1_batch_number)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn get_assignments_by_prover(
+        &self,
+        prover: Address,
+        paging: Option<Paging>,
+    ) -> BoxFuture<Result<Vec<Assignment>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_assignments_by_prover_impl(prover, paging)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn get_deposit_info(&self, prover: Address) -> BoxFuture<Result<DepositInfo>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_deposit_info_impl(prover)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn get_penalties(
+        &self,
+        prover: Option<Address>,
+        paging: Option<Paging>,
+    ) -> BoxFuture<Result<Vec<Assignment>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_penalties_impl(prover, paging)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+}
@@ -0,0 +1,81 @@
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_derive::rpc;
+use micro_types::{
+    api::{
+        trace::{Trace, TraceFilter, TraceResultType, TraceResults},
+        BlockId, BlockNumber,
+    },
+    transaction_request::CallRequest,
+    H256,
+};
+
+use crate::api_server::web3::{
+    backend_jsonrpc::error::into_jsrpc_error, namespaces::TraceNamespace,
+};
+
+#[rpc]
+pub trait TraceNamespaceT {
+    #[rpc(name = "trace_block")]
+    fn trace_block(&self, block: BlockNumber) -> BoxFuture<Result<Vec<Trace>>>;
+
+    #[rpc(name = "trace_transaction")]
+    fn trace_transaction(&self, tx_hash: H256) -> BoxFuture<Result<Option<Vec<Trace>>>>;
+
+    #[rpc(name = "trace_filter")]
+    fn trace_filter(&self, filter: TraceFilter) -> BoxFuture<Result<Vec<Trace>>>;
+
+    #[rpc(name = "trace_call")]
+    fn trace_call(
+        &self,
+        request: CallRequest,
+        trace_types: Vec<TraceResultType>,
+        block: Option<BlockId>,
+    ) -> BoxFuture<Result<TraceResults>>;
+}
+
+impl TraceNamespaceT for TraceNamespace {
+    fn trace_block(&self, block: BlockNumber) -> BoxFuture<Result<Vec<Trace>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .trace_block_impl(block)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn trace_transaction(&self, tx_hash: H256) -> BoxFuture<Result<Option<Vec<Trace>>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .trace_transaction_impl(tx_hash)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn trace_filter(&self, filter: TraceFilter) -> BoxFuture<Result<Vec<Trace>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .trace_filter_impl(filter)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn trace_call(
+        &self,
+        request: CallRequest,
+        trace_types: Vec<TraceResultType>,
+        block: Option<BlockId>,
+    ) -> BoxFuture<Result<TraceResults>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .trace_call_impl(request, trace_types, block)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+}
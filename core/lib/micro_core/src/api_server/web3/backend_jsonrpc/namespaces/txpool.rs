@@ -0,0 +1,44 @@
+use jsonrpc_core::Result;
+use jsonrpc_derive::rpc;
+use micro_types::{
+    api::txpool::{TxPoolAccountContent, TxPoolContent, TxPoolStatus, TxPoolTransaction},
+    Address,
+};
+
+use crate::{
+    api_server::web3::{backend_jsonrpc::error::into_jsrpc_error, TxPoolNamespace},
+    l1_gas_price::L1GasPriceProvider,
+};
+
+#[rpc]
+pub trait TxPoolNamespaceT {
+    #[rpc(name = "txpool_status")]
+    fn status(&self) -> Result<TxPoolStatus>;
+
+    #[rpc(name = "txpool_content")]
+    fn content(&self) -> Result<TxPoolContent<TxPoolTransaction>>;
+
+    #[rpc(name = "txpool_contentFrom")]
+    fn content_from(&self, address: Address) -> Result<TxPoolAccountContent<TxPoolTransaction>>;
+
+    #[rpc(name = "txpool_inspect")]
+    fn inspect(&self) -> Result<TxPoolContent<String>>;
+}
+
+impl<G: L1GasPriceProvider + Send + Sync + 'static> TxPoolNamespaceT for TxPoolNamespace<G> {
+    fn status(&self) -> Result<TxPoolStatus> {
+        self.status_impl().map_err(into_jsrpc_error)
+    }
+
+    fn content(&self) -> Result<TxPoolContent<TxPoolTransaction>> {
+        self.content_impl().map_err(into_jsrpc_error)
+    }
+
+    fn content_from(&self, address: Address) -> Result<TxPoolAccountContent<TxPoolTransaction>> {
+        self.content_from_impl(address).map_err(into_jsrpc_error)
+    }
+
+    fn inspect(&self) -> Result<TxPoolContent<String>> {
+        self.inspect_impl().map_err(into_jsrpc_error)
+    }
+}
@@ -5,15 +5,21 @@ use jsonrpc_core::{BoxFuture, Result};
 use jsonrpc_derive::rpc;
 use micro_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        bundle::{SimulateBundleOptions, SimulatedCall},
+        finality::TransactionFinality,
+        priority_ops::PriorityOpsReport,
+        protocol_upgrades::ProtocolUpgradesReport,
+        AccountStorageKeys, BatchedProof, BlockDetails, BlockIdVariant, BridgeAddresses,
+        L1BatchDetails, L2ToL1LogProof, LogCursor, LogsPage, Proof, ProtocolVersion,
+        TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     statistics_info::StatiticsInfo,
     transaction_request::CallRequest,
+    web3::types::Bytes,
     Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
 };
-use micro_web3_decl::types::Token;
+use micro_web3_decl::types::{Filter, Token};
 
 use crate::{
     l1_gas_price::L1GasPriceProvider,
@@ -83,6 +89,25 @@ pub trait ZksNamespaceT {
     #[rpc(name = "zks_getTransactionDetails")]
     fn get_transaction_details(&self, hash: H256) -> BoxFuture<Result<Option<TransactionDetails>>>;
 
+    #[rpc(name = "zks_getTransactionFinality")]
+    fn get_transaction_finality(
+        &self,
+        hash: H256,
+    ) -> BoxFuture<Result<Option<TransactionFinality>>>;
+
+    #[rpc(name = "zks_getPriorityOpsStatus")]
+    fn get_priority_ops_status(&self) -> BoxFuture<Result<Option<PriorityOpsReport>>>;
+
+    #[rpc(name = "zks_getProtocolUpgradesStatus")]
+    fn get_protocol_upgrades_status(&self) -> BoxFuture<Result<ProtocolUpgradesReport>>;
+
+    #[rpc(name = "zks_sendRawTransactionWithDetailedOutput")]
+    fn send_raw_transaction_with_detailed_output(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<u64>,
+    ) -> BoxFuture<Result<TransactionDetailedResult>>;
+
     #[rpc(name = "zks_getRawBlockTransactions")]
     fn get_raw_block_transactions(
         &self,
@@ -115,8 +140,31 @@ pub trait ZksNamespaceT {
         l1_batch_number: L1BatchNumber,
     ) -> BoxFuture<Result<Proof>>;
 
+    #[rpc(name = "zks_getProofs")]
+    fn get_proofs(
+        &self,
+        accounts: Vec<AccountStorageKeys>,
+        l1_batch_number: L1BatchNumber,
+    ) -> BoxFuture<Result<BatchedProof>>;
+
     #[rpc(name = "zks_getStatistics")]
     fn get_statistics_info(&self) -> BoxFuture<Result<StatiticsInfo>>;
+
+    #[rpc(name = "zks_getLogsWithCursor")]
+    fn get_logs_with_cursor(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> BoxFuture<Result<LogsPage>>;
+
+    #[rpc(name = "zks_simulateBundle")]
+    fn simulate_bundle(
+        &self,
+        calls: Vec<CallRequest>,
+        block: Option<BlockIdVariant>,
+        options: Option<SimulateBundleOptions>,
+    ) -> BoxFuture<Result<Vec<SimulatedCall>>>;
 }
 
 impl<G: L1GasPriceProvider + Send + Sync + 'static> ZksNamespaceT for ZksNamespace<G> {
@@ -261,6 +309,52 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> ZksNamespaceT for ZksNamespa
         })
     }
 
+    fn get_transaction_finality(
+        &self,
+        hash: H256,
+    ) -> BoxFuture<Result<Option<TransactionFinality>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_transaction_finality_impl(hash)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn get_priority_ops_status(&self) -> BoxFuture<Result<Option<PriorityOpsReport>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_priority_ops_status_impl()
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn get_protocol_upgrades_status(&self) -> BoxFuture<Result<ProtocolUpgradesReport>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_protocol_upgrades_status_impl()
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn send_raw_transaction_with_detailed_output(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<u64>,
+    ) -> BoxFuture<Result<TransactionDetailedResult>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .send_raw_transaction_with_detailed_output_impl(tx_bytes, timeout_ms)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
     fn get_raw_block_transactions(
         &self,
         block_number: MiniblockNumber,
@@ -320,8 +414,52 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> ZksNamespaceT for ZksNamespa
         })
     }
 
+    fn get_proofs(
+        &self,
+        accounts: Vec<AccountStorageKeys>,
+        l1_batch_number: L1BatchNumber,
+    ) -> BoxFuture<Result<BatchedProof>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_batched_proofs_impl(accounts, l1_batch_number)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
     fn get_statistics_info(&self) -> BoxFuture<Result<StatiticsInfo>> {
         let self_ = self.clone();
         Box::pin(async move { Ok(self_.get_statistics_info_impl().await) })
     }
+
+    fn get_logs_with_cursor(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> BoxFuture<Result<LogsPage>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .get_logs_with_cursor_impl(filter, cursor, limit)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
+
+    fn simulate_bundle(
+        &self,
+        calls: Vec<CallRequest>,
+        block: Option<BlockIdVariant>,
+        options: Option<SimulateBundleOptions>,
+    ) -> BoxFuture<Result<Vec<SimulatedCall>>> {
+        let self_ = self.clone();
+        Box::pin(async move {
+            self_
+                .simulate_bundle_impl(calls, block.map(Into::into), options)
+                .await
+                .map_err(into_jsrpc_error)
+        })
+    }
 }
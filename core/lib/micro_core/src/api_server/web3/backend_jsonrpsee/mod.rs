@@ -10,6 +10,7 @@ use micro_web3_decl::{
 };
 
 pub mod namespaces;
+pub(crate) mod rate_limit_middleware;
 
 pub fn from_std_error(e: impl Error) -> ErrorObjectOwned {
     ErrorObjectOwned::owned(ErrorCode::InternalError.code(), e.to_string(), Some(()))
@@ -26,12 +27,19 @@ pub fn into_jsrpc_error(err: Web3Error) -> ErrorObjectOwned {
             | Web3Error::TooManyTopics
             | Web3Error::FilterNotFound
             | Web3Error::InvalidFeeParams(_)
+            | Web3Error::InvalidStateOverride(_)
+            | Web3Error::BundleTooLarge(_)
+            | Web3Error::TooManyProofKeys(_)
             | Web3Error::InvalidFilterBlockHash
-            | Web3Error::LogsLimitExceeded(_, _, _) => ErrorCode::InvalidParams.code(),
+            | Web3Error::LogsLimitExceeded(_, _, _)
+            | Web3Error::TraceLimitExceeded(_, _, _)
+            | Web3Error::UpgradeActivationAfterL1(_) => ErrorCode::InvalidParams.code(),
             Web3Error::SubmitTransactionError(_, _) | Web3Error::SerializationError(_) => 3,
             Web3Error::PubSubTimeout => 4,
-            Web3Error::RequestTimeout => 5,
-            Web3Error::TreeApiUnavailable => 6,
+            Web3Error::RequestTimeout | Web3Error::TxInclusionTimeout(_) => 5,
+            Web3Error::TreeApiUnavailable
+            | Web3Error::MempoolUnavailable
+            | Web3Error::PriorityOpsMonitorUnavailable => 6,
         },
         match err {
             Web3Error::SubmitTransactionError(ref message, _) => message.clone(),
@@ -0,0 +1,56 @@
+use async_trait::async_trait;
+use micro_types::{
+    deny_list::{DenyListAuditRecord, DenyListEntry},
+    l2::prover::Paging,
+    Address, L1BatchNumber,
+};
+use micro_web3_decl::{jsonrpsee::core::RpcResult, namespaces::AdminNamespaceServer};
+
+use crate::{
+    api_server::web3::{backend_jsonrpsee::into_jsrpc_error, namespaces::AdminNamespace},
+    l1_gas_price::L1GasPriceProvider,
+};
+
+#[async_trait]
+impl<G: L1GasPriceProvider + Send + Sync + 'static> AdminNamespaceServer for AdminNamespace<G> {
+    async fn get_deny_list(&self) -> RpcResult<Vec<DenyListEntry>> {
+        self.get_deny_list_impl().await.map_err(into_jsrpc_error)
+    }
+
+    async fn add_to_deny_list(&self, address: Address, reason: String) -> RpcResult<()> {
+        self.add_to_deny_list_impl(address, reason)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn remove_from_deny_list(&self, address: Address, reason: String) -> RpcResult<bool> {
+        self.remove_from_deny_list_impl(address, reason)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn get_deny_list_audit_log(
+        &self,
+        address: Option<Address>,
+        paging: Option<Paging>,
+    ) -> RpcResult<Vec<DenyListAuditRecord>> {
+        self.get_deny_list_audit_log_impl(address, paging)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn schedule_protocol_upgrade(
+        &self,
+        protocol_version: u16,
+        activation_timestamp: Option<u64>,
+        activation_l1_batch_number: Option<L1BatchNumber>,
+    ) -> RpcResult<bool> {
+        self.schedule_protocol_upgrade_impl(
+            protocol_version,
+            activation_timestamp,
+            activation_l1_batch_number,
+        )
+        .await
+        .map_err(into_jsrpc_error)
+    }
+}
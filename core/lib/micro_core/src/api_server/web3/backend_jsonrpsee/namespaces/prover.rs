@@ -0,0 +1,46 @@
+use async_trait::async_trait;
+use micro_types::{
+    l2::prover::{Assignment, DepositInfo, Paging},
+    Address, L1BatchNumber,
+};
+use micro_web3_decl::{jsonrpsee::core::RpcResult, namespaces::ProverNamespaceServer};
+
+use crate::{
+    api_server::web3::{backend_jsonrpsee::into_jsrpc_error, namespaces::ProverNamespace},
+    l1_gas_price::L1GasPriceProvider,
+};
+
+#[async_trait]
+impl<G: L1GasPriceProvider + Send + Sync + 'static> ProverNamespaceServer for ProverNamespace<G> {
+    async fn get_assignment(&self, l1_batch_number: L1BatchNumber) -> RpcResult<Vec<Assignment>> {
+        self.get_assignment_impl(l1_batch_number)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn get_assignments_by_prover(
+        &self,
+        prover: Address,
+        paging: Option<Paging>,
+    ) -> RpcResult<Vec<Assignment>> {
+        self.get_assignments_by_prover_impl(prover, paging)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn get_deposit_info(&self, prover: Address) -> RpcResult<DepositInfo> {
+        self.get_deposit_info_impl(prover)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn get_penalties(
+        &self,
+        prover: Option<Address>,
+        paging: Option<Paging>,
+    ) -> RpcResult<Vec<Assignment>> {
+        self.get_penalties_impl(prover, paging)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+}
@@ -0,0 +1,44 @@
+use micro_types::{
+    api::{
+        trace::{Trace, TraceFilter, TraceResultType, TraceResults},
+        BlockId, BlockNumber,
+    },
+    transaction_request::CallRequest,
+    H256,
+};
+use micro_web3_decl::{
+    jsonrpsee::core::{async_trait, RpcResult},
+    namespaces::trace::TraceNamespaceServer,
+};
+
+use crate::api_server::web3::{backend_jsonrpsee::into_jsrpc_error, namespaces::TraceNamespace};
+
+#[async_trait]
+impl TraceNamespaceServer for TraceNamespace {
+    async fn trace_block(&self, block: BlockNumber) -> RpcResult<Vec<Trace>> {
+        self.trace_block_impl(block).await.map_err(into_jsrpc_error)
+    }
+
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Option<Vec<Trace>>> {
+        self.trace_transaction_impl(tx_hash)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<Trace>> {
+        self.trace_filter_impl(filter)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn trace_call(
+        &self,
+        request: CallRequest,
+        trace_types: Vec<TraceResultType>,
+        block: Option<BlockId>,
+    ) -> RpcResult<TraceResults> {
+        self.trace_call_impl(request, trace_types, block)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+}
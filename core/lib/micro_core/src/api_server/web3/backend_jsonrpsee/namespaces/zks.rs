@@ -3,18 +3,24 @@ use std::collections::HashMap;
 use bigdecimal::BigDecimal;
 use micro_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        bundle::{SimulateBundleOptions, SimulatedCall},
+        finality::TransactionFinality,
+        priority_ops::PriorityOpsReport,
+        protocol_upgrades::ProtocolUpgradesReport,
+        AccountStorageKeys, BatchedProof, BlockDetails, BlockIdVariant, BridgeAddresses,
+        L1BatchDetails, L2ToL1LogProof, LogCursor, LogsPage, Proof, ProtocolVersion,
+        TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     statistics_info::StatiticsInfo,
     transaction_request::CallRequest,
+    web3::types::Bytes,
     Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
 };
 use micro_web3_decl::{
     jsonrpsee::core::{async_trait, RpcResult},
     namespaces::zks::ZksNamespaceServer,
-    types::Token,
+    types::{Filter, Token},
 };
 
 use crate::{
@@ -120,6 +126,33 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> ZksNamespaceServer for ZksNa
             .map_err(into_jsrpc_error)
     }
 
+    async fn get_transaction_finality(&self, hash: H256) -> RpcResult<Option<TransactionFinality>> {
+        self.get_transaction_finality_impl(hash)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn get_priority_ops_status(&self) -> RpcResult<Option<PriorityOpsReport>> {
+        self.get_priority_ops_status_impl()
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn get_protocol_upgrades_status(&self) -> RpcResult<ProtocolUpgradesReport> {
+        self.get_protocol_upgrades_status_impl()
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn send_raw_transaction_with_detailed_output(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<u64>,
+    ) -> RpcResult<TransactionDetailedResult> {
+        self.send_raw_transaction_with_detailed_output_impl(tx_bytes, timeout_ms)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn get_raw_block_transactions(
         &self,
         block_number: MiniblockNumber,
@@ -164,7 +197,39 @@ impl<G: L1GasPriceProvider + Send + Sync + 'static> ZksNamespaceServer for ZksNa
             .map_err(into_jsrpc_error)
     }
 
+    async fn get_proofs(
+        &self,
+        accounts: Vec<AccountStorageKeys>,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<BatchedProof> {
+        self.get_batched_proofs_impl(accounts, l1_batch_number)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
     async fn get_statistics_info(&self) -> RpcResult<StatiticsInfo> {
         Ok(self.get_statistics_info_impl().await)
     }
+
+    async fn get_logs_with_cursor(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> RpcResult<LogsPage> {
+        self.get_logs_with_cursor_impl(filter, cursor, limit)
+            .await
+            .map_err(into_jsrpc_error)
+    }
+
+    async fn simulate_bundle(
+        &self,
+        calls: Vec<CallRequest>,
+        block: Option<BlockIdVariant>,
+        options: Option<SimulateBundleOptions>,
+    ) -> RpcResult<Vec<SimulatedCall>> {
+        self.simulate_bundle_impl(calls, block.map(Into::into), options)
+            .await
+            .map_err(into_jsrpc_error)
+    }
 }
@@ -0,0 +1,260 @@
+//! `tower` middleware implementing per-client rate limiting for the `jsonrpsee` HTTP server.
+//! `jsonrpsee` doesn't provide RPC-level middleware, so the middleware parses request bodies on its own
+//! in order to get the called methods. Since bodies are read before `jsonrpsee` gets to them, the middleware
+//! enforces the request body size limit itself.
+//!
+//! Also contains middleware exposing the client IP address to RPC method handlers.
+
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use jsonrpc_http_server::hyper::{self, body::HttpBody as _, header, Body, StatusCode};
+use micro_web3_decl::jsonrpsee::types::error::{OVERSIZED_REQUEST_CODE, OVERSIZED_REQUEST_MSG};
+use serde::Deserialize;
+use serde_json::json;
+use tower::{Layer, Service};
+
+use crate::api_server::web3::rate_limiter::{
+    current_client_ip, with_client_ip, ClientIpResolver, ClientRateLimiter,
+    RATE_LIMITED_ERROR_CODE, RATE_LIMITED_ERROR_MESSAGE,
+};
+
+/// Minimal representation of a JSON-RPC call sufficient for rate limiting.
+#[derive(Debug, Deserialize)]
+struct RawCall {
+    method: String,
+    #[serde(default)]
+    id: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawRequest {
+    Single(RawCall),
+    Batch(Vec<RawCall>),
+}
+
+impl RawRequest {
+    fn calls(&self) -> &[RawCall] {
+        match self {
+            Self::Single(call) => std::slice::from_ref(call),
+            Self::Batch(calls) => calls,
+        }
+    }
+
+    fn rate_limited_response(&self) -> hyper::Response<Body> {
+        let error = |call: &RawCall| {
+            json!({
+                "jsonrpc": "2.0",
+                "error": {
+                    "code": RATE_LIMITED_ERROR_CODE,
+                    "message": RATE_LIMITED_ERROR_MESSAGE,
+                },
+                "id": call.id,
+            })
+        };
+        let body = match self {
+            Self::Single(call) => error(call),
+            Self::Batch(calls) => calls.iter().map(error).collect(),
+        };
+
+        hyper::Response::builder()
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body.to_string()))
+            .unwrap()
+    }
+}
+
+/// Reads a request body. Returns `None` if the body is larger than `max_size` bytes; in this case, the body
+/// is not read completely.
+async fn read_body(mut body: Body, max_size: usize) -> Result<Option<Vec<u8>>, hyper::Error> {
+    // The lower bound of the size hint is set from the `Content-Length` header, if it's present.
+    let expected_size = body.size_hint().lower();
+    if expected_size > max_size as u64 {
+        return Ok(None);
+    }
+
+    let mut bytes = Vec::with_capacity(expected_size as usize);
+    while let Some(chunk) = body.data().await {
+        let chunk = chunk?;
+        if bytes.len() + chunk.len() > max_size {
+            return Ok(None);
+        }
+        bytes.extend_from_slice(&chunk);
+    }
+    Ok(Some(bytes))
+}
+
+/// Mirrors the response returned by `jsonrpsee` for oversized requests.
+fn too_large_response(max_size: usize) -> hyper::Response<Body> {
+    let body = json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": OVERSIZED_REQUEST_CODE,
+            "message": OVERSIZED_REQUEST_MSG,
+            "data": format!("Exceeded max limit of {max_size}"),
+        },
+        "id": null,
+    });
+    hyper::Response::builder()
+        .status(StatusCode::PAYLOAD_TOO_LARGE)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap()
+}
+
+/// Layer applying [`ClientRateLimiter`] to HTTP requests.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientRateLimitLayer {
+    limiter: ClientRateLimiter,
+    max_request_body_size: usize,
+}
+
+impl ClientRateLimitLayer {
+    pub fn new(limiter: ClientRateLimiter, max_request_body_size: usize) -> Self {
+        Self {
+            limiter,
+            max_request_body_size,
+        }
+    }
+}
+
+impl<S> Layer<S> for ClientRateLimitLayer {
+    type Service = ClientRateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientRateLimitService {
+            inner,
+            limiter: self.limiter.clone(),
+            max_request_body_size: self.max_request_body_size,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ClientRateLimitService<S> {
+    inner: S,
+    limiter: ClientRateLimiter,
+    max_request_body_size: usize,
+}
+
+impl<S> Service<hyper::Request<Body>> for ClientRateLimitService<S>
+where
+    S: Service<hyper::Request<Body>, Response = hyper::Response<Body>> + Clone + Send + 'static,
+    S::Error: From<hyper::Error> + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = hyper::Response<Body>;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::Request<Body>) -> Self::Future {
+        // The inner service is ready, while its clone may be not; hence, we use the ready service
+        // and leave the clone in its place.
+        let inner_clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, inner_clone);
+        let limiter = self.limiter.clone();
+        let max_request_body_size = self.max_request_body_size;
+
+        Box::pin(async move {
+            // The client IP address is set by `ClientIpLayer`, which wraps this layer.
+            let client = limiter.client_key(current_client_ip(), request.headers());
+            let (parts, body) = request.into_parts();
+            let Some(body) = read_body(body, max_request_body_size).await? else {
+                return Ok(too_large_response(max_request_body_size));
+            };
+
+            // Requests that cannot be parsed are passed through; `jsonrpsee` will respond to them with an error.
+            if let Ok(raw_request) = serde_json::from_slice::<RawRequest>(&body) {
+                let methods = raw_request.calls().iter().map(|call| call.method.as_str());
+                if limiter.check(&client, methods).is_err() {
+                    return Ok(raw_request.rate_limited_response());
+                }
+            }
+            inner
+                .call(hyper::Request::from_parts(parts, Body::from(body)))
+                .await
+        })
+    }
+}
+
+/// Layer making the client IP address available to RPC method handlers, so that they can apply per-IP limits.
+/// Only applicable to HTTP servers, since `jsonrpsee` handles WebSocket requests outside of the middleware.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientIpLayer {
+    resolver: ClientIpResolver,
+}
+
+impl ClientIpLayer {
+    pub fn new(resolver: ClientIpResolver) -> Self {
+        Self { resolver }
+    }
+}
+
+impl<S> Layer<S> for ClientIpLayer {
+    type Service = ClientIpService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ClientIpService {
+            inner,
+            resolver: self.resolver.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ClientIpService<S> {
+    inner: S,
+    resolver: ClientIpResolver,
+}
+
+impl<S> Service<hyper::Request<Body>> for ClientIpService<S>
+where
+    S: Service<hyper::Request<Body>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: hyper::Request<Body>) -> Self::Future {
+        let client_ip = self.resolver.client_ip(request.headers());
+        Box::pin(with_client_ip(client_ip, self.inner.call(request)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn reading_body_with_size_limit() {
+        let body = read_body(Body::from(vec![1_u8; 10]), 10).await.unwrap();
+        assert_eq!(body.unwrap(), [1; 10]);
+        let body = read_body(Body::from(vec![1_u8; 10]), 9).await.unwrap();
+        assert!(body.is_none());
+
+        // Chunked bodies don't have a size hint.
+        let chunks = || {
+            let (mut sender, body) = Body::channel();
+            tokio::spawn(async move {
+                for chunk in [[1_u8; 4], [2; 4], [3; 4]] {
+                    sender.send_data(chunk.to_vec().into()).await.ok();
+                }
+            });
+            body
+        };
+        let body = read_body(chunks(), 12).await.unwrap();
+        assert_eq!(body.unwrap().len(), 12);
+        let body = read_body(chunks(), 10).await.unwrap();
+        assert!(body.is_none());
+    }
+}
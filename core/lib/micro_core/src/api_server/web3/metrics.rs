@@ -15,7 +15,7 @@ use super::{ApiTransport, TypedFilter};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "scheme", rename_all = "UPPERCASE")]
-pub(super) enum ApiTransportLabel {
+pub(crate) enum ApiTransportLabel {
     Http,
     Ws,
 }
@@ -179,6 +179,18 @@ pub(super) enum SubscriptionType {
     Blocks,
     Txs,
     Logs,
+    L1Batches,
+    RejectedTxs,
+}
+
+/// Source of data for pubsub notifications.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "source", rename_all = "snake_case")]
+pub(super) enum NotificationSource {
+    /// Polling Postgres.
+    Poll,
+    /// In-process notifications from the state keeper.
+    Push,
 }
 
 #[derive(Debug, Metrics)]
@@ -190,6 +202,15 @@ pub(super) struct PubSubMetrics {
     pub notify_subscribers_latency: Family<SubscriptionType, Histogram<Duration>>,
     pub notify: Family<SubscriptionType, Counter>,
     pub active_subscribers: Family<SubscriptionType, Gauge>,
+    /// Latency between committing a miniblock to Postgres and receiving an in-process notification about it.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub push_latency: Family<SubscriptionType, Histogram<Duration>>,
+    /// Number of times a notifier had to poll Postgres because it missed in-process notifications.
+    pub push_fallbacks: Family<SubscriptionType, Counter>,
+    /// Lag between the miniblock timestamp and notifying `newHeads` subscribers, grouped by the notification source.
+    /// Miniblock timestamps have a 1-second resolution, so this metric is mostly useful to compare sources.
+    #[metrics(buckets = Buckets::LATENCIES)]
+    pub block_notification_lag: Family<NotificationSource, Histogram<Duration>>,
 }
 
 #[vise::register]
@@ -1,4 +1,9 @@
-use std::{net::SocketAddr, sync::Arc, time::Duration};
+use std::{
+    cell::Cell,
+    net::{IpAddr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
 
 use anyhow::Context as _;
 use chrono::NaiveDateTime;
@@ -6,6 +11,7 @@ use futures::future;
 use jsonrpc_core::MetaIoHandler;
 use jsonrpc_http_server::hyper;
 use jsonrpc_pubsub::PubSubHandler;
+use jsonrpc_ws_server::ws;
 use micro_dal::{ConnectionPool, StorageProcessor};
 use micro_health_check::{HealthStatus, HealthUpdater, ReactiveHealthCheck};
 use micro_types::{api, MiniblockNumber};
@@ -16,8 +22,9 @@ use micro_web3_decl::{
         RpcModule,
     },
     namespaces::{
-        DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer, NetNamespaceServer,
-        SnapshotsNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
+        AdminNamespaceServer, DebugNamespaceServer, EnNamespaceServer, EthNamespaceServer,
+        NetNamespaceServer, ProverNamespaceServer, SnapshotsNamespaceServer, TraceNamespaceServer,
+        TxPoolNamespaceServer, Web3NamespaceServer, ZksNamespaceServer,
     },
     types::Filter,
 };
@@ -33,25 +40,31 @@ use self::{
         batch_limiter_middleware::{LimitMiddleware, Transport},
         error::internal_error,
         namespaces::{
-            debug::DebugNamespaceT, en::EnNamespaceT, eth::EthNamespaceT, net::NetNamespaceT,
-            web3::Web3NamespaceT, zks::ZksNamespaceT,
+            admin::AdminNamespaceT, debug::DebugNamespaceT, en::EnNamespaceT, eth::EthNamespaceT,
+            net::NetNamespaceT, prover::ProverNamespaceT, trace::TraceNamespaceT,
+            txpool::TxPoolNamespaceT, web3::Web3NamespaceT, zks::ZksNamespaceT,
         },
         pub_sub::Web3PubSub,
     },
-    metrics::API_METRICS,
+    backend_jsonrpsee::rate_limit_middleware::{ClientIpLayer, ClientRateLimitLayer},
+    metrics::{ApiTransportLabel, API_METRICS},
     namespaces::{
-        DebugNamespace, EnNamespace, EthNamespace, NetNamespace, SnapshotsNamespace, Web3Namespace,
-        ZksNamespace,
+        AdminNamespace, DebugNamespace, EnNamespace, EthNamespace, NetNamespace, ProverNamespace,
+        SnapshotsNamespace, TraceNamespace, TxPoolNamespace, Web3Namespace, ZksNamespace,
     },
     pubsub::{EthSubscribe, PubSubEvent},
+    rate_limiter::{ClientIpResolver, ClientKey, ClientRateLimiter, ClientRateLimits},
     state::{Filters, InternalApiConfig, RpcState, SealedMiniblockNumber},
+    statistics::StatisticsCache,
 };
 use crate::{
     api_server::{
         execution_sandbox::VmConcurrencyBarrier, tree::TreeApiHttpClient, tx_sender::TxSender,
         web3::backend_jsonrpc::batch_limiter_middleware::RateLimitMetadata,
     },
+    eth_watch::PriorityOpsReportReceiver,
     l1_gas_price::L1GasPriceProvider,
+    state_keeper::{MempoolGuard, MiniblockNotifications},
     sync_layer::SyncState,
 };
 
@@ -60,12 +73,24 @@ pub mod backend_jsonrpsee;
 mod metrics;
 pub mod namespaces;
 mod pubsub;
+pub mod rate_limiter;
 pub mod state;
+pub mod statistics;
 #[cfg(test)]
 pub(crate) mod tests;
 
 /// Timeout for graceful shutdown logic within API servers.
 const GRACEFUL_SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(5);
+/// Maximum request body size for `jsonrpsee` servers (equals to the `jsonrpsee` default).
+/// Also enforced by the rate-limiting middleware, which reads request bodies before `jsonrpsee`.
+const MAX_REQUEST_BODY_SIZE: u32 = 10 * 1024 * 1024;
+
+thread_local! {
+    /// Client IP address of the WebSocket connection being opened. `jsonrpc_ws_server` runs the request middleware
+    /// and the metadata extractor for the handshake request synchronously on the same thread, so the address
+    /// is passed from the former to the latter via this variable.
+    static WS_CLIENT_IP: Cell<Option<IpAddr>> = Cell::new(None);
+}
 
 /// Represents all kinds of `Filter`.
 #[derive(Debug, Clone)]
@@ -101,6 +126,10 @@ pub enum Namespace {
     En,
     Pubsub,
     Snapshots,
+    Trace,
+    Prover,
+    TxPool,
+    Admin,
 }
 
 impl Namespace {
@@ -136,6 +165,8 @@ pub struct ApiBuilder<G> {
     batch_request_size_limit: Option<usize>,
     response_body_size_limit: Option<usize>,
     websocket_requests_per_minute_limit: Option<u32>,
+    client_rate_limits: Option<ClientRateLimits>,
+    client_ip_resolver: ClientIpResolver,
     sync_state: Option<SyncState>,
     threads: Option<usize>,
     vm_concurrency_limit: Option<usize>,
@@ -143,6 +174,11 @@ pub struct ApiBuilder<G> {
     namespaces: Option<Vec<Namespace>>,
     tree_api_url: Option<String>,
     pub_sub_events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
+    miniblock_notifications: Option<MiniblockNotifications>,
+    mempool: Option<MempoolGuard>,
+    priority_ops_report: Option<PriorityOpsReportReceiver>,
+    statistics: Option<StatisticsCache>,
+    health_check_name: Option<&'static str>,
 }
 
 impl<G> ApiBuilder<G> {
@@ -160,6 +196,8 @@ impl<G> ApiBuilder<G> {
             batch_request_size_limit: None,
             response_body_size_limit: None,
             websocket_requests_per_minute_limit: None,
+            client_rate_limits: None,
+            client_ip_resolver: ClientIpResolver::default(),
             threads: None,
             vm_concurrency_limit: None,
             polling_interval: None,
@@ -167,6 +205,11 @@ impl<G> ApiBuilder<G> {
             config,
             tree_api_url: None,
             pub_sub_events_sender: None,
+            miniblock_notifications: None,
+            mempool: None,
+            priority_ops_report: None,
+            statistics: None,
+            health_check_name: None,
         }
     }
 
@@ -187,6 +230,20 @@ impl<G> ApiBuilder<G> {
         self
     }
 
+    /// Configures the HTTP transport bound to the specified address. Unlike [`Self::http()`], this allows
+    /// to restrict the server to a certain interface (e.g., loopback for internal-only servers).
+    pub fn http_addr(mut self, addr: SocketAddr) -> Self {
+        self.transport = Some(ApiTransport::Http(addr));
+        self
+    }
+
+    /// Overrides the name of the server health check (`http_api` or `ws_api` by default depending
+    /// on the transport). Must be set if several servers with the same transport run in a single app.
+    pub fn with_health_check_name(mut self, name: &'static str) -> Self {
+        self.health_check_name = Some(name);
+        self
+    }
+
     /// Configures a dedicated DB pool to be used for updating the latest miniblock information
     /// in a background task. If not called, the main pool will be used. If the API server is under high load,
     /// it may make sense to supply a single-connection pool to reduce pool contention with the API methods.
@@ -233,6 +290,20 @@ impl<G> ApiBuilder<G> {
         self
     }
 
+    /// Enables per-client rate limiting with the specified limits. If `None` is provided, per-client
+    /// rate limiting is disabled.
+    pub fn with_client_rate_limits(mut self, client_rate_limits: Option<ClientRateLimits>) -> Self {
+        self.client_rate_limits = client_rate_limits;
+        self
+    }
+
+    /// Sets IP addresses of reverse proxies whose forwarding headers are trusted when determining client IP addresses.
+    /// If no proxies are set, client IP addresses are unknown, and per-IP limits are not applied.
+    pub fn with_trusted_proxies(mut self, trusted_proxies: Vec<IpAddr>) -> Self {
+        self.client_ip_resolver = ClientIpResolver::new(trusted_proxies);
+        self
+    }
+
     pub fn with_sync_state(mut self, sync_state: SyncState) -> Self {
         self.sync_state = Some(sync_state);
         self
@@ -248,6 +319,36 @@ impl<G> ApiBuilder<G> {
         self
     }
 
+    /// Enables in-process notifications about sealed miniblocks for pubsub subscriptions and for waiting
+    /// on transaction inclusion in `zks_sendRawTransactionWithDetailedOutput`. Should only be used
+    /// if the API server runs in the same process as the state keeper. Postgres is still polled
+    /// as a fallback.
+    pub fn with_miniblock_notifications(mut self, notifications: MiniblockNotifications) -> Self {
+        self.miniblock_notifications = Some(notifications);
+        self
+    }
+
+    /// Provides access to the in-process mempool for the `txpool` namespace. Should only be used
+    /// if the API server runs in the same process as the state keeper.
+    pub(crate) fn with_mempool(mut self, mempool: MempoolGuard) -> Self {
+        self.mempool = Some(mempool);
+        self
+    }
+
+    /// Provides access to reports of the in-process priority ops monitor. Should only be used
+    /// if the API server runs in the same process as the Ethereum watcher.
+    pub(crate) fn with_priority_ops_report(mut self, report: PriorityOpsReportReceiver) -> Self {
+        self.priority_ops_report = Some(report);
+        self
+    }
+
+    /// Shares network statistics aggregated for another API server, so that they are not aggregated
+    /// separately for each server. If not called, the server will spawn its own aggregator.
+    pub fn with_statistics(mut self, statistics: StatisticsCache) -> Self {
+        self.statistics = Some(statistics);
+        self
+    }
+
     pub fn with_vm_concurrency_limit(mut self, vm_concurrency_limit: usize) -> Self {
         self.vm_concurrency_limit = Some(vm_concurrency_limit);
         self
@@ -282,6 +383,12 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             SealedMiniblockNumber::new(self.last_miniblock_pool, SEALED_MINIBLOCK_UPDATE_INTERVAL);
         // The update tasks takes care of its termination, so we don't need to retain its handle.
         tokio::spawn(update_task);
+        let statistics = self.statistics.unwrap_or_else(|| {
+            let (statistics, update_task) =
+                StatisticsCache::new(self.pool.clone(), StatisticsCache::DEFAULT_UPDATE_INTERVAL);
+            tokio::spawn(update_task);
+            statistics
+        });
 
         RpcState {
             installed_filters: Arc::new(Mutex::new(Filters::new(self.filters_limit))),
@@ -290,6 +397,10 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             sync_state: self.sync_state,
             api_config: self.config,
             last_sealed_miniblock,
+            statistics,
+            miniblock_notifications: self.miniblock_notifications,
+            mempool: self.mempool,
+            priority_ops_report: self.priority_ops_report,
             tree_api: self
                 .tree_api_url
                 .map(|url| TreeApiHttpClient::new(url.as_str())),
@@ -327,6 +438,22 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             rpc.merge(DebugNamespace::new(rpc_state.clone()).await.into_rpc())
                 .expect("Can't merge debug namespace");
         }
+        if namespaces.contains(&Namespace::Trace) {
+            rpc.merge(TraceNamespace::new(rpc_state.clone()).await.into_rpc())
+                .expect("Can't merge trace namespace");
+        }
+        if namespaces.contains(&Namespace::Prover) {
+            rpc.merge(ProverNamespace::new(rpc_state.clone()).into_rpc())
+                .expect("Can't merge prover namespace");
+        }
+        if namespaces.contains(&Namespace::TxPool) {
+            rpc.merge(TxPoolNamespace::new(rpc_state.clone()).into_rpc())
+                .expect("Can't merge txpool namespace");
+        }
+        if namespaces.contains(&Namespace::Admin) {
+            rpc.merge(AdminNamespace::new(rpc_state.clone()).into_rpc())
+                .expect("Can't merge admin namespace");
+        }
         if namespaces.contains(&Namespace::Snapshots) {
             rpc.merge(SnapshotsNamespace::new(rpc_state).into_rpc())
                 .expect("Can't merge snapshots namespace");
@@ -344,7 +471,7 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
 
         if self.namespaces.is_none() {
             tracing::warn!(
-                "debug_  and snapshots_ API namespace will be disabled by default in ApiBuilder"
+                "debug_, trace_ and snapshots_ API namespaces will be disabled by default in ApiBuilder"
             );
             self.namespaces = Some(Namespace::DEFAULT.to_vec());
         }
@@ -399,7 +526,8 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             tracing::info!("`response_body_size_limit` is not supported for `jsonrpc` backend, this value is ignored");
         }
 
-        let (health_check, health_updater) = ReactiveHealthCheck::new("http_api");
+        let health_check_name = self.health_check_name.unwrap_or("http_api");
+        let (health_check, health_updater) = ReactiveHealthCheck::new(health_check_name);
         let vm_barrier = self.vm_barrier.take().unwrap();
         // ^ `unwrap()` is safe by construction
 
@@ -409,16 +537,33 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             .worker_threads(self.threads.unwrap())
             .build()
             .context("Failed creating Tokio runtime for `jsonrpc` API backend")?;
-        let mut io_handler: MetaIoHandler<()> = MetaIoHandler::default();
+        let client_limiter = self.build_client_limiter(ApiTransportLabel::Http, &runtime);
+        let limit_middleware =
+            LimitMiddleware::new(Transport::Http, None).with_client_limiter(client_limiter.clone());
+        let mut io_handler: MetaIoHandler<RateLimitMetadata<()>, _> =
+            MetaIoHandler::with_middleware(limit_middleware);
         self.extend_jsonrpc_methods(&mut io_handler).await;
 
+        let client_ip_resolver = self.client_ip_resolver.clone();
         let (local_addr_sender, local_addr) = oneshot::channel();
         let server_task = tokio::task::spawn_blocking(move || {
-            let server = jsonrpc_http_server::ServerBuilder::new(io_handler)
-                .threads(1)
-                .event_loop_executor(runtime.handle().clone())
-                .start_http(&addr)
-                .context("jsonrpc_http::Server::start_http")?;
+            let server = jsonrpc_http_server::ServerBuilder::with_meta_extractor(
+                io_handler,
+                move |request: &hyper::Request<hyper::Body>| {
+                    let client_ip = client_ip_resolver.client_ip(request.headers());
+                    let metadata = RateLimitMetadata::new(None, ()).with_client_ip(client_ip);
+                    match &client_limiter {
+                        Some(limiter) => {
+                            metadata.with_client(limiter.client_key(client_ip, request.headers()))
+                        }
+                        None => metadata,
+                    }
+                },
+            )
+            .threads(1)
+            .event_loop_executor(runtime.handle().clone())
+            .start_http(&addr)
+            .context("jsonrpc_http::Server::start_http")?;
             local_addr_sender.send(*server.address()).ok();
 
             let close_handle = server.close_handle();
@@ -461,6 +606,19 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         })
     }
 
+    /// Creates a per-client rate limiter if per-client rate limits are configured. Inactive clients
+    /// are periodically purged from the limiter state by a task spawned on the provided runtime.
+    fn build_client_limiter(
+        &mut self,
+        transport: ApiTransportLabel,
+        runtime: &tokio::runtime::Runtime,
+    ) -> Option<ClientRateLimiter> {
+        let limits = self.client_rate_limits.take()?;
+        let limiter = ClientRateLimiter::new(limits, transport);
+        runtime.spawn(limiter.clone().run_retention());
+        Some(limiter)
+    }
+
     async fn wait_for_vm(vm_barrier: VmConcurrencyBarrier, transport: &str) {
         let wait_for_vm =
             tokio::time::timeout(GRACEFUL_SHUTDOWN_TIMEOUT, vm_barrier.wait_until_stopped());
@@ -498,9 +656,22 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             io.extend_with(NetNamespace::new(micro_network_id).to_delegate());
         }
         if namespaces.contains(&Namespace::Debug) {
-            let debug_ns = DebugNamespace::new(rpc_state).await;
+            let debug_ns = DebugNamespace::new(rpc_state.clone()).await;
             io.extend_with(debug_ns.to_delegate());
         }
+        if namespaces.contains(&Namespace::Prover) {
+            io.extend_with(ProverNamespace::new(rpc_state.clone()).to_delegate());
+        }
+        if namespaces.contains(&Namespace::TxPool) {
+            io.extend_with(TxPoolNamespace::new(rpc_state.clone()).to_delegate());
+        }
+        if namespaces.contains(&Namespace::Admin) {
+            io.extend_with(AdminNamespace::new(rpc_state.clone()).to_delegate());
+        }
+        if namespaces.contains(&Namespace::Trace) {
+            let trace_ns = TraceNamespace::new(rpc_state).await;
+            io.extend_with(trace_ns.to_delegate());
+        }
     }
 
     async fn build_jsonrpc_ws(
@@ -512,17 +683,21 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             tracing::info!("`response_body_size_limit` is not supported for `jsonrpc` backend, this value is ignored");
         }
 
-        let (health_check, health_updater) = ReactiveHealthCheck::new("ws_api");
+        let health_check_name = self.health_check_name.unwrap_or("ws_api");
+        let (health_check, health_updater) = ReactiveHealthCheck::new(health_check_name);
         let websocket_requests_per_second_limit = self.websocket_requests_per_minute_limit;
-        let batch_limiter_middleware =
-            LimitMiddleware::new(Transport::Ws, self.batch_request_size_limit);
-
         let runtime = tokio::runtime::Builder::new_multi_thread()
             .enable_all()
             .thread_name("jsonrpc-ws-worker")
             .worker_threads(self.threads.unwrap())
             .build()
             .context("Failed creating Tokio runtime for `jsonrpc-ws` API backend")?;
+        // HTTP headers are not available for WebSocket requests, so clients are identified by their connection.
+        let client_limiter = self.build_client_limiter(ApiTransportLabel::Ws, &runtime);
+        let has_client_limiter = client_limiter.is_some();
+        let batch_limiter_middleware =
+            LimitMiddleware::new(Transport::Ws, self.batch_request_size_limit)
+                .with_client_limiter(client_limiter);
         let max_connections = self.subscriptions_limit.unwrap_or(usize::MAX);
         let vm_barrier = self.vm_barrier.take().unwrap();
 
@@ -548,21 +723,38 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             tasks.extend(pub_sub.spawn_notifiers(
                 self.pool.clone(),
                 polling_interval,
+                self.miniblock_notifications.as_ref(),
                 stop_receiver.clone(),
             ));
             io_handler.extend_with(pub_sub.to_delegate());
         }
         self.extend_jsonrpc_methods(&mut io_handler).await;
 
+        let client_ip_resolver = self.client_ip_resolver.clone();
+        let client_ip_middleware = move |request: &ws::Request| -> Option<ws::Response> {
+            let client_ip =
+                client_ip_resolver.resolve(|name| std::str::from_utf8(request.header(name)?).ok());
+            WS_CLIENT_IP.with(|cell| cell.set(client_ip));
+            None
+        };
         let (local_addr_sender, local_addr) = oneshot::channel();
         let server_task = tokio::task::spawn_blocking(move || {
             let server = jsonrpc_ws_server::ServerBuilder::with_meta_extractor(
                 io_handler,
                 move |context: &jsonrpc_ws_server::RequestContext| {
                     let session = Arc::new(jsonrpc_pubsub::Session::new(context.sender()));
-                    RateLimitMetadata::new(websocket_requests_per_second_limit, session)
+                    let client_ip = WS_CLIENT_IP.with(Cell::take);
+                    let metadata =
+                        RateLimitMetadata::new(websocket_requests_per_second_limit, session)
+                            .with_client_ip(client_ip);
+                    if has_client_limiter {
+                        metadata.with_client(ClientKey::Connection(context.session_id))
+                    } else {
+                        metadata
+                    }
                 },
             )
+            .request_middleware(client_ip_middleware)
             .event_loop_executor(runtime.handle().clone())
             .max_connections(max_connections)
             .session_stats(TrackOpenWsConnections)
@@ -628,12 +820,18 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             if self.websocket_requests_per_minute_limit.is_some() {
                 tracing::info!("`websocket_requests_per_second_limit` is not supported for `jsonrpsee` backend, this value is ignored");
             }
+            // `jsonrpsee` handles WebSocket messages outside of the `tower` middleware, so the limits cannot be enforced.
+            anyhow::ensure!(
+                self.client_rate_limits.is_none(),
+                "Per-client rate limits are not supported for WebSocket `jsonrpsee` backend; use `jsonrpc` backend instead"
+            );
         }
 
-        let (runtime_thread_name, health_check_name) = match transport {
+        let (runtime_thread_name, default_health_check_name) = match transport {
             ApiTransport::Http(_) => ("jsonrpsee-http-worker", "http_api"),
             ApiTransport::WebSocket(_) => ("jsonrpsee-ws-worker", "ws_api"),
         };
+        let health_check_name = self.health_check_name.unwrap_or(default_health_check_name);
         let (health_check, health_updater) = ReactiveHealthCheck::new(health_check_name);
         let vm_barrier = self.vm_barrier.take().unwrap();
         let batch_request_config = if let Some(limit) = self.batch_request_size_limit {
@@ -654,6 +852,8 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             .with_context(|| {
                 format!("Failed creating Tokio runtime for {health_check_name} jsonrpsee server")
             })?;
+        let client_limiter = self.build_client_limiter((&transport).into(), &runtime);
+        let client_ip_resolver = self.client_ip_resolver.clone();
         let rpc = self.build_rpc_module().await;
 
         // Start the server in a separate tokio runtime from a dedicated thread.
@@ -668,6 +868,8 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
                 vm_barrier,
                 batch_request_config,
                 response_body_size_limit,
+                client_limiter,
+                client_ip_resolver,
             ));
             runtime.shutdown_timeout(GRACEFUL_SHUTDOWN_TIMEOUT);
             res
@@ -701,6 +903,8 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         vm_barrier: VmConcurrencyBarrier,
         batch_request_config: BatchRequestConfig,
         response_body_size_limit: u32,
+        client_limiter: Option<ClientRateLimiter>,
+        client_ip_resolver: ClientIpResolver,
     ) -> anyhow::Result<()> {
         let (transport_str, is_http, addr) = match transport {
             ApiTransport::Http(addr) => ("HTTP", true, addr),
@@ -726,9 +930,14 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
             }),
         );
         // Assemble server middleware.
-        let middleware = tower::ServiceBuilder::new()
-            .layer(in_flight_requests)
-            .option_layer(cors);
+        let middleware =
+            tower::ServiceBuilder::new()
+                .layer(in_flight_requests)
+                .option_layer(cors)
+                .option_layer(is_http.then(|| ClientIpLayer::new(client_ip_resolver)))
+                .option_layer(client_limiter.map(|limiter| {
+                    ClientRateLimitLayer::new(limiter, MAX_REQUEST_BODY_SIZE as usize)
+                }));
 
         let server_builder = if is_http {
             ServerBuilder::default().http_only().max_connections(5_000)
@@ -739,6 +948,7 @@ impl<G: 'static + Send + Sync + L1GasPriceProvider> ApiBuilder<G> {
         let server = server_builder
             .set_batch_request_config(batch_request_config)
             .set_middleware(middleware)
+            .max_request_body_size(MAX_REQUEST_BODY_SIZE)
             .max_response_body_size(response_body_size_limit)
             .build(addr)
             .await
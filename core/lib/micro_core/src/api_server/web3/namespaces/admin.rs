@@ -0,0 +1,198 @@
+use micro_dal::{protocol_upgrade_schedules_dal::UpgradeActivationUpdate, StorageProcessor};
+use micro_types::{
+    deny_list::{DenyListAuditRecord, DenyListEntry},
+    l2::prover::Paging,
+    Address, L1BatchNumber,
+};
+use micro_web3_decl::error::Web3Error;
+
+use crate::{
+    api_server::web3::{
+        backend_jsonrpc::error::internal_error, metrics::API_METRICS, state::RpcState,
+    },
+    l1_gas_price::L1GasPriceProvider,
+};
+
+/// Administrative namespace used to manage the deny-list and protocol upgrade activation. Must only be
+/// exposed on servers not reachable from the public network.
+#[derive(Debug)]
+pub struct AdminNamespace<G> {
+    state: RpcState<G>,
+}
+
+impl<G> Clone for AdminNamespace<G> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<G: L1GasPriceProvider> AdminNamespace<G> {
+    pub fn new(state: RpcState<G>) -> Self {
+        Self { state }
+    }
+
+    /// Acquires a connection to the master database; deny-list changes must not be written to a replica.
+    async fn access_master_storage(
+        &self,
+        method_name: &'static str,
+    ) -> Result<StorageProcessor<'_>, Web3Error> {
+        let pool = self
+            .state
+            .tx_sender
+            .0
+            .master_connection_pool
+            .as_ref()
+            .ok_or_else(|| internal_error(method_name, "master connection pool is not set"))?;
+        pool.access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(method_name, err))
+    }
+
+    /// Reloads the deny-list used by the transaction sender, so that changes take effect immediately
+    /// for this server. Other components pick up changes on the next periodic reload.
+    async fn reload_deny_list(
+        &self,
+        method_name: &'static str,
+        storage: &mut StorageProcessor<'_>,
+    ) -> Result<(), Web3Error> {
+        if let Some(deny_list) = self.state.tx_sender.deny_list() {
+            deny_list
+                .reload(storage)
+                .await
+                .map_err(|err| internal_error(method_name, err))?;
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_deny_list_impl(&self) -> Result<Vec<DenyListEntry>, Web3Error> {
+        const METHOD_NAME: &str = "admin_getDenyList";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let entries = storage
+            .deny_list_dal()
+            .get_entries()
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        method_latency.observe();
+        Ok(entries)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn add_to_deny_list_impl(
+        &self,
+        address: Address,
+        reason: String,
+    ) -> Result<(), Web3Error> {
+        const METHOD_NAME: &str = "admin_addToDenyList";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut storage = self.access_master_storage(METHOD_NAME).await?;
+        storage
+            .deny_list_dal()
+            .add_address(address, &reason)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        tracing::info!("Added {address:?} to the deny-list: {reason}");
+        self.reload_deny_list(METHOD_NAME, &mut storage).await?;
+        method_latency.observe();
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn remove_from_deny_list_impl(
+        &self,
+        address: Address,
+        reason: String,
+    ) -> Result<bool, Web3Error> {
+        const METHOD_NAME: &str = "admin_removeFromDenyList";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut storage = self.access_master_storage(METHOD_NAME).await?;
+        let removed = storage
+            .deny_list_dal()
+            .remove_address(address, &reason)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        if removed {
+            tracing::info!("Removed {address:?} from the deny-list: {reason}");
+            self.reload_deny_list(METHOD_NAME, &mut storage).await?;
+        }
+        method_latency.observe();
+        Ok(removed)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_deny_list_audit_log_impl(
+        &self,
+        address: Option<Address>,
+        paging: Option<Paging>,
+    ) -> Result<Vec<DenyListAuditRecord>, Web3Error> {
+        const METHOD_NAME: &str = "admin_getDenyListAuditLog";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let paging = paging.unwrap_or_default();
+        let max_limit = self.state.api_config.req_entities_limit;
+        let limit = paging.limit.map_or(max_limit, |limit| limit.min(max_limit));
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let records = storage
+            .deny_list_dal()
+            .get_audit_log(address, paging.offset, limit)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        method_latency.observe();
+        Ok(records)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn schedule_protocol_upgrade_impl(
+        &self,
+        protocol_version: u16,
+        activation_timestamp: Option<u64>,
+        activation_l1_batch_number: Option<L1BatchNumber>,
+    ) -> Result<bool, Web3Error> {
+        const METHOD_NAME: &str = "admin_scheduleProtocolUpgrade";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut storage = self.access_master_storage(METHOD_NAME).await?;
+        let update = storage
+            .protocol_upgrade_schedules_dal()
+            .set_activation(
+                protocol_version,
+                activation_timestamp,
+                activation_l1_batch_number,
+            )
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let updated = match update {
+            UpgradeActivationUpdate::Updated => {
+                tracing::info!(
+                    "Scheduled protocol upgrade to version {protocol_version}: activation timestamp \
+                     {activation_timestamp:?}, activation L1 batch {activation_l1_batch_number:?}"
+                );
+                true
+            }
+            UpgradeActivationUpdate::NotPending => false,
+            UpgradeActivationUpdate::AfterL1Activation {
+                l1_activation_timestamp,
+            } => {
+                return Err(Web3Error::UpgradeActivationAfterL1(l1_activation_timestamp));
+            }
+        };
+        method_latency.observe();
+        Ok(updated)
+    }
+}
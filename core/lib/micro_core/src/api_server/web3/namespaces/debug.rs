@@ -1,22 +1,31 @@
-use std::sync::Arc;
+use std::{collections::BTreeMap, sync::Arc};
 
-use micro_dal::ConnectionPool;
+use micro_dal::{ConnectionPool, StorageProcessor};
 use micro_state::PostgresStorageCaches;
 use micro_types::{
-    api::{BlockId, BlockNumber, DebugCall, ResultDebugCall, TracerConfig},
+    api::{
+        BlockId, BlockNumber, DebugCall, DebugStructLogs, DebugTrace, ResultDebugCall,
+        StructLoggerConfig, SupportedTracers, TracerConfig,
+    },
     l2::L2Tx,
     transaction_request::CallRequest,
-    vm_trace::Call,
-    AccountTreeId, L2ChainId, H256, USED_BOOTLOADER_MEMORY_BYTES,
+    vm_trace::{Call, FourByteTrace, PrestateTrace},
+    web3::types::Bytes,
+    AccountTreeId, L2ChainId, MiniblockNumber, Transaction, H256, USED_BOOTLOADER_MEMORY_BYTES,
 };
 use micro_web3_decl::error::Web3Error;
-use multivm::{interface::ExecutionResult, vm_latest::constants::BLOCK_GAS_LIMIT};
+use multivm::{
+    interface::{ExecutionResult, VmExecutionResultAndLogs},
+    tracers::StructLoggerParams,
+    vm_latest::constants::BLOCK_GAS_LIMIT,
+};
 use once_cell::sync::OnceCell;
 
 use crate::{
     api_server::{
         execution_sandbox::{
-            execute_tx_eth_call, ApiTracer, BlockArgs, TxSharedArgs, VmConcurrencyLimiter,
+            execute_tx_eth_call, replay_miniblock_transactions, ApiTracer, BlockArgs,
+            StorageOverrides, TxSharedArgs, VmConcurrencyLimiter,
         },
         tx_sender::ApiContracts,
         web3::{
@@ -63,38 +72,48 @@ impl DebugNamespace {
         &self,
         block_id: BlockId,
         options: Option<TracerConfig>,
-    ) -> Result<Vec<ResultDebugCall>, Web3Error> {
+    ) -> Result<Vec<ResultDebugCall<DebugTrace>>, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_block";
 
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let options = options.unwrap_or_default();
         let mut connection = self
             .connection_pool
             .access_storage_tagged("api")
             .await
             .unwrap();
         let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
-        let call_trace = connection
-            .blocks_web3_dal()
-            .get_trace_for_miniblock(block_number)
-            .await
-            .unwrap();
-        let call_trace = call_trace
+
+        let traces = match options.tracer {
+            SupportedTracers::CallTracer | SupportedTracers::FourByteTracer => {
+                let call_traces = connection
+                    .blocks_web3_dal()
+                    .get_trace_for_miniblock(block_number)
+                    .await
+                    .unwrap();
+                call_traces
+                    .into_iter()
+                    .map(|call_trace| stored_trace(call_trace, &options))
+                    .collect()
+            }
+            SupportedTracers::PrestateTracer | SupportedTracers::StructLogger => {
+                let txs = connection
+                    .transactions_web3_dal()
+                    .get_raw_miniblock_transactions(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                self.replay_miniblock(connection, block_number, txs, 0, &options)
+                    .await?
+            }
+        };
+        let traces = traces
             .into_iter()
-            .map(|call_trace| {
-                let mut result: DebugCall = call_trace.into();
-                if only_top_call {
-                    result.calls = vec![];
-                }
-                ResultDebugCall { result }
-            })
+            .map(|result| ResultDebugCall { result })
             .collect();
 
         let block_diff = self.last_sealed_miniblock.diff(block_number);
         method_latency.observe(block_diff);
-        Ok(call_trace)
+        Ok(traces)
     }
 
     #[tracing::instrument(skip(self))]
@@ -102,25 +121,49 @@ impl DebugNamespace {
         &self,
         tx_hash: H256,
         options: Option<TracerConfig>,
-    ) -> Option<DebugCall> {
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
-        let call_trace = self
+    ) -> Result<Option<DebugTrace>, Web3Error> {
+        const METHOD_NAME: &str = "debug_trace_transaction";
+
+        let options = options.unwrap_or_default();
+        let mut connection = self
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
-            .transactions_dal()
-            .get_call_trace(tx_hash)
-            .await;
-        call_trace.map(|call_trace| {
-            let mut result: DebugCall = call_trace.into();
-            if only_top_call {
-                result.calls = vec![];
+            .unwrap();
+
+        match options.tracer {
+            SupportedTracers::CallTracer | SupportedTracers::FourByteTracer => {
+                let call_trace = connection.transactions_dal().get_call_trace(tx_hash).await;
+                Ok(call_trace.map(|call_trace| stored_trace(call_trace, &options)))
+            }
+            SupportedTracers::PrestateTracer | SupportedTracers::StructLogger => {
+                let receipt = connection
+                    .transactions_web3_dal()
+                    .get_transaction_receipt(tx_hash)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                let Some(block_number) = receipt.and_then(|receipt| receipt.block_number) else {
+                    return Ok(None);
+                };
+                let block_number = MiniblockNumber(block_number.as_u32());
+
+                let mut txs = connection
+                    .transactions_web3_dal()
+                    .get_raw_miniblock_transactions(block_number)
+                    .await
+                    .map_err(|err| internal_error(METHOD_NAME, err))?;
+                let Some(tx_position) = txs.iter().position(|tx| tx.hash() == tx_hash) else {
+                    return Ok(None);
+                };
+                // Transactions following the traced one don't influence its execution.
+                txs.truncate(tx_position + 1);
+
+                let mut traces = self
+                    .replay_miniblock(connection, block_number, txs, tx_position, &options)
+                    .await?;
+                Ok(traces.pop())
             }
-            result
-        })
+        }
     }
 
     #[tracing::instrument(skip(self, request, block_id))]
@@ -129,14 +172,19 @@ impl DebugNamespace {
         request: CallRequest,
         block_id: Option<BlockId>,
         options: Option<TracerConfig>,
-    ) -> Result<DebugCall, Web3Error> {
+    ) -> Result<DebugTrace, Web3Error> {
         const METHOD_NAME: &str = "debug_trace_call";
 
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
-        let only_top_call = options
-            .map(|options| options.tracer_config.only_top_call)
-            .unwrap_or(false);
+        let options = options.unwrap_or_default();
+        let storage_overrides = options
+            .state_overrides
+            .as_ref()
+            .map(StorageOverrides::new)
+            .transpose()
+            .map_err(|err| Web3Error::InvalidStateOverride(err.to_string()))?
+            .unwrap_or_default();
 
         let mut connection = self
             .connection_pool
@@ -145,34 +193,48 @@ impl DebugNamespace {
             .unwrap();
         let block_args = BlockArgs::new(&mut connection, block_id)
             .await
-            .map_err(|err| internal_error("debug_trace_call", err))?
+            .map_err(|err| internal_error(METHOD_NAME, err))?
             .ok_or(Web3Error::NoBlock)?;
         drop(connection);
 
-        let tx = L2Tx::from_request(request.into(), USED_BOOTLOADER_MEMORY_BYTES)?;
+        let trace = match api_tracer(&options) {
+            ApiTracer::CallTracer(_) => {
+                let only_top_call = options.tracer_config.call.only_top_call;
+                self.trace_call_tree(request, block_args, storage_overrides, only_top_call)
+                    .await?
+                    .into()
+            }
+            tracer => {
+                let (_, result) = self
+                    .execute_call(request, block_args, storage_overrides, vec![tracer.clone()])
+                    .await?;
+                traced_result(METHOD_NAME, tracer, &result)?
+            }
+        };
 
-        let shared_args = self.shared_args();
-        let vm_permit = self.vm_concurrency_limiter.acquire().await;
-        let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
+        let block_diff = self.last_sealed_miniblock.diff_with_block_args(&block_args);
+        method_latency.observe(block_diff);
+        Ok(trace)
+    }
 
-        // We don't need properly trace if we only need top call
+    /// Executes a call on top of the specified block and returns its call tree.
+    pub(super) async fn trace_call_tree(
+        &self,
+        request: CallRequest,
+        block_args: BlockArgs,
+        storage_overrides: StorageOverrides,
+        only_top_call: bool,
+    ) -> Result<Call, Web3Error> {
         let call_tracer_result = Arc::new(OnceCell::default());
+        // We don't need properly trace if we only need top call
         let custom_tracers = if only_top_call {
             vec![]
         } else {
             vec![ApiTracer::CallTracer(call_tracer_result.clone())]
         };
-
-        let result = execute_tx_eth_call(
-            vm_permit,
-            shared_args,
-            self.connection_pool.clone(),
-            tx.clone(),
-            block_args,
-            self.vm_execution_cache_misses_limit,
-            custom_tracers,
-        )
-        .await;
+        let (tx, result) = self
+            .execute_call(request, block_args, storage_overrides, custom_tracers)
+            .await?;
 
         let (output, revert_reason) = match result.result {
             ExecutionResult::Success { output, .. } => (output, None),
@@ -190,7 +252,7 @@ impl DebugNamespace {
             .unwrap()
             .take()
             .unwrap_or_default();
-        let call = Call::new_high_level(
+        Ok(Call::new_high_level(
             tx.common_data.fee.gas_limit.as_u32(),
             result.statistics.gas_used,
             tx.execute.value,
@@ -198,11 +260,110 @@ impl DebugNamespace {
             output,
             revert_reason,
             trace,
-        );
+        ))
+    }
 
-        let block_diff = self.last_sealed_miniblock.diff_with_block_args(&block_args);
-        method_latency.observe(block_diff);
-        Ok(call.into())
+    async fn execute_call(
+        &self,
+        request: CallRequest,
+        block_args: BlockArgs,
+        storage_overrides: StorageOverrides,
+        custom_tracers: Vec<ApiTracer>,
+    ) -> Result<(L2Tx, VmExecutionResultAndLogs), Web3Error> {
+        let tx = L2Tx::from_request(request.into(), USED_BOOTLOADER_MEMORY_BYTES)?;
+
+        let shared_args = self.shared_args();
+        let vm_permit = self.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
+
+        let result = execute_tx_eth_call(
+            vm_permit,
+            shared_args,
+            self.connection_pool.clone(),
+            tx.clone(),
+            block_args,
+            self.vm_execution_cache_misses_limit,
+            storage_overrides,
+            custom_tracers,
+        )
+        .await;
+        Ok((tx, result))
+    }
+
+    /// Re-executes transactions from a sealed miniblock on top of the state after the previous miniblock,
+    /// tracing transactions starting from `first_traced_tx`. Returns traces for the traced transactions.
+    async fn replay_miniblock(
+        &self,
+        mut connection: StorageProcessor<'_>,
+        block_number: MiniblockNumber,
+        txs: Vec<Transaction>,
+        first_traced_tx: usize,
+        options: &TracerConfig,
+    ) -> Result<Vec<DebugTrace>, Web3Error> {
+        const METHOD_NAME: &str = "debug_replay_miniblock";
+
+        if txs.is_empty() {
+            return Ok(vec![]);
+        }
+        // The genesis miniblock has no preceding state to replay transactions on top of.
+        let prev_block_number = block_number
+            .0
+            .checked_sub(1)
+            .ok_or(Web3Error::NotImplemented)?;
+        let header = connection
+            .blocks_dal()
+            .get_miniblock_header(block_number)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .ok_or(Web3Error::NoBlock)?;
+        let block_args = BlockArgs::new(
+            &mut connection,
+            BlockId::Number(BlockNumber::Number(prev_block_number.into())),
+        )
+        .await
+        .map_err(|err| internal_error(METHOD_NAME, err))?
+        .ok_or(Web3Error::NoBlock)?;
+        drop(connection);
+
+        let mut tracers = vec![];
+        let txs = txs
+            .into_iter()
+            .enumerate()
+            .map(|(i, tx)| {
+                if i < first_traced_tx {
+                    return (tx, vec![]);
+                }
+                let tracer = api_tracer(options);
+                tracers.push(tracer.clone());
+                (tx, vec![tracer])
+            })
+            .collect();
+
+        let shared_args = TxSharedArgs {
+            l1_gas_price: header.l1_gas_price,
+            fair_l2_gas_price: header.l2_fair_gas_price,
+            // Unlike `eth_call` contracts, these contracts mimic the real transaction execution.
+            base_system_contracts: self.api_contracts.estimate_gas.clone(),
+            ..self.shared_args()
+        };
+        let vm_permit = self.vm_concurrency_limiter.acquire().await;
+        let vm_permit = vm_permit.ok_or(Web3Error::InternalError)?;
+        let results = replay_miniblock_transactions(
+            vm_permit,
+            shared_args,
+            self.connection_pool.clone(),
+            block_args,
+            header.base_fee_per_gas,
+            txs,
+        )
+        .await;
+
+        let traced_results = results.iter().skip(first_traced_tx);
+        tracers
+            .into_iter()
+            .zip(traced_results)
+            .map(|(tracer, result)| traced_result(METHOD_NAME, tracer, result))
+            .collect()
     }
 
     fn shared_args(&self) -> TxSharedArgs {
@@ -217,3 +378,82 @@ impl DebugNamespace {
         }
     }
 }
+
+/// Creates a tracer for the tracer type selected in `options`.
+fn api_tracer(options: &TracerConfig) -> ApiTracer {
+    match options.tracer {
+        SupportedTracers::CallTracer => ApiTracer::CallTracer(Arc::default()),
+        SupportedTracers::PrestateTracer => ApiTracer::PrestateTracer {
+            diff_mode: options.tracer_config.prestate.diff_mode,
+            result: Arc::default(),
+        },
+        SupportedTracers::FourByteTracer => ApiTracer::FourByteTracer(Arc::default()),
+        SupportedTracers::StructLogger => ApiTracer::StructLogger {
+            params: struct_logger_params(&options.struct_logger_config),
+            result: Arc::default(),
+        },
+    }
+}
+
+fn struct_logger_params(config: &StructLoggerConfig) -> StructLoggerParams {
+    StructLoggerParams {
+        enable_registers: !config.disable_stack,
+        enable_storage: !config.disable_storage,
+        limit: (config.limit > 0).then_some(config.limit),
+    }
+}
+
+/// Converts a call trace persisted for a transaction into the trace for the selected tracer.
+/// Only applicable to tracers whose output can be derived from the call trace.
+fn stored_trace(call_trace: Call, options: &TracerConfig) -> DebugTrace {
+    if options.tracer == SupportedTracers::FourByteTracer {
+        return DebugTrace::FourByteTrace(FourByteTrace::from_call(&call_trace));
+    }
+
+    let mut result: DebugCall = call_trace.into();
+    if options.tracer_config.call.only_top_call {
+        result.calls = vec![];
+    }
+    DebugTrace::CallTrace(result)
+}
+
+/// Extracts the trace collected by a tracer during VM execution. Call traces are assembled separately
+/// since they include the top-level call data not available to the tracer, so they are rejected here.
+fn traced_result(
+    method_name: &'static str,
+    tracer: ApiTracer,
+    result: &VmExecutionResultAndLogs,
+) -> Result<DebugTrace, Web3Error> {
+    Ok(match tracer {
+        ApiTracer::CallTracer(_) => {
+            return Err(internal_error(
+                method_name,
+                "call traces cannot be extracted from a VM execution result",
+            ));
+        }
+        ApiTracer::PrestateTracer { result: trace, .. } => DebugTrace::PrestateTrace(
+            trace
+                .get()
+                .cloned()
+                .unwrap_or_else(|| PrestateTrace::Prestate(BTreeMap::new())),
+        ),
+        ApiTracer::FourByteTracer(trace) => {
+            DebugTrace::FourByteTrace(trace.get().cloned().unwrap_or_default())
+        }
+        ApiTracer::StructLogger {
+            result: struct_logs,
+            ..
+        } => {
+            let return_value = match &result.result {
+                ExecutionResult::Success { output } => Bytes(output.clone()),
+                ExecutionResult::Revert { .. } | ExecutionResult::Halt { .. } => Bytes::default(),
+            };
+            DebugTrace::StructLogs(DebugStructLogs {
+                gas: result.statistics.gas_used.into(),
+                failed: result.result.is_failed(),
+                return_value,
+                struct_logs: struct_logs.get().cloned().unwrap_or_default(),
+            })
+        }
+    })
+}
@@ -1,7 +1,7 @@
 use micro_types::{
     api::{
-        BlockId, BlockNumber, GetLogsFilter, Transaction, TransactionId, TransactionReceipt,
-        TransactionVariant,
+        state_override::StateOverride, BlockId, BlockNumber, GetLogsFilter, Transaction,
+        TransactionId, TransactionReceipt, TransactionVariant,
     },
     l2::{L2Tx, TransactionType},
     transaction_request::CallRequest,
@@ -19,7 +19,7 @@ use micro_web3_decl::{
 
 use crate::{
     api_server::{
-        execution_sandbox::BlockArgs,
+        execution_sandbox::{BlockArgs, StorageOverrides},
         web3::{
             backend_jsonrpc::error::internal_error,
             metrics::{BlockCallObserver, API_METRICS},
@@ -34,6 +34,38 @@ use crate::{
 pub const EVENT_TOPIC_NUMBER_LIMIT: usize = 4;
 pub const PROTOCOL_VERSION: &str = "zks/1";
 
+/// Converts a Web3 filter into a DAL filter for the specified resolved block range.
+pub(crate) fn get_logs_filter(
+    filter: &Filter,
+    from_block: MiniblockNumber,
+    to_block: MiniblockNumber,
+) -> Result<GetLogsFilter, Web3Error> {
+    let addresses = if let Some(addresses) = &filter.address {
+        addresses.0.clone()
+    } else {
+        vec![]
+    };
+    let topics = if let Some(topics) = &filter.topics {
+        if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
+            return Err(Web3Error::TooManyTopics);
+        }
+        let topics_by_idx = topics
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, topics)| Some((idx as u32 + 1, topics.as_ref()?.0.clone())));
+        topics_by_idx.collect::<Vec<_>>()
+    } else {
+        vec![]
+    };
+
+    Ok(GetLogsFilter {
+        from_block,
+        to_block,
+        addresses,
+        topics,
+    })
+}
+
 #[derive(Debug)]
 pub struct EthNamespace<G> {
     state: RpcState<G>,
@@ -73,14 +105,20 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         block_number
     }
 
-    #[tracing::instrument(skip(self, request, block_id))]
+    #[tracing::instrument(skip(self, request, block_id, state_override))]
     pub async fn call_impl(
         &self,
         request: CallRequest,
         block_id: Option<BlockId>,
+        state_override: Option<StateOverride>,
     ) -> Result<Bytes, Web3Error> {
         const METHOD_NAME: &str = "call";
 
+        let storage_overrides = state_override
+            .as_ref()
+            .map(StorageOverrides::new)
+            .transpose()
+            .map_err(|err| Web3Error::InvalidStateOverride(err.to_string()))?;
         let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
         let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
         let mut connection = self
@@ -97,7 +135,11 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
 
         let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
 
-        let call_result = self.state.tx_sender.eth_call(block_args, tx).await;
+        let call_result = self
+            .state
+            .tx_sender
+            .eth_call(block_args, tx, storage_overrides)
+            .await;
         let res_bytes = call_result
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
 
@@ -109,16 +151,31 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         Ok(res_bytes.into())
     }
 
-    #[tracing::instrument(skip(self, request, _block))]
+    #[tracing::instrument(skip(self, request, _block, state_override))]
     pub async fn estimate_gas_impl(
         &self,
         request: CallRequest,
         _block: Option<BlockNumber>,
+        state_override: Option<StateOverride>,
     ) -> Result<U256, Web3Error> {
         const METHOD_NAME: &str = "estimate_gas";
 
         let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let storage_overrides = state_override
+            .as_ref()
+            .map(StorageOverrides::new)
+            .transpose()
+            .map_err(|err| Web3Error::InvalidStateOverride(err.to_string()))?;
         let mut request_with_gas_per_pubdata_overridden = request;
+        if request_with_gas_per_pubdata_overridden.nonce.is_none() {
+            // If the sender nonce is overridden, it should be used instead of the stored one.
+            let from = request_with_gas_per_pubdata_overridden
+                .from
+                .unwrap_or_default();
+            request_with_gas_per_pubdata_overridden.nonce = state_override
+                .as_ref()
+                .and_then(|state_override| state_override.get(&from)?.nonce);
+        }
         self.state
             .set_nonce_for_call_request(&mut request_with_gas_per_pubdata_overridden)
             .await?;
@@ -158,7 +215,12 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation)
+            .get_txs_fee_in_wei(
+                tx.into(),
+                scale_factor,
+                acceptable_overestimation,
+                storage_overrides,
+            )
             .await
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
 
@@ -537,6 +599,39 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         receipt
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn get_block_receipts_impl(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<Vec<TransactionReceipt>>, Web3Error> {
+        const METHOD_NAME: &str = "get_block_receipts";
+
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let block_number = storage
+            .blocks_web3_dal()
+            .resolve_block_id(block_id)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let Some(block_number) = block_number else {
+            method_latency.observe_without_diff();
+            return Ok(None);
+        };
+
+        let receipts = storage
+            .transactions_web3_dal()
+            .get_block_receipts(block_number)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        self.report_latency_with_block_id(method_latency, block_number);
+        Ok(Some(receipts))
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn new_block_filter_impl(&self) -> Result<U256, Web3Error> {
         const METHOD_NAME: &str = "new_block_filter";
@@ -659,16 +754,7 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
         const METHOD_NAME: &str = "send_raw_transaction";
 
         let method_latency = API_METRICS.start_call(METHOD_NAME);
-        let (mut tx, hash) = self.state.parse_transaction_bytes(&tx_bytes.0)?;
-        tx.set_input(tx_bytes.0, hash);
-
-        let submit_result = self.state.tx_sender.submit_tx(tx).await;
-        let submit_result = submit_result.map(|_| hash).map_err(|err| {
-            tracing::debug!("Send raw transaction error: {err}");
-            API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
-            Web3Error::SubmitTransactionError(err.to_string(), err.data())
-        });
-
+        let submit_result = self.state.submit_raw_transaction(tx_bytes.0).await;
         method_latency.observe();
         submit_result
     }
@@ -803,23 +889,6 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
             }
 
             TypedFilter::Events(filter, from_block) => {
-                let addresses = if let Some(addresses) = &filter.address {
-                    addresses.0.clone()
-                } else {
-                    vec![]
-                };
-                let topics = if let Some(topics) = &filter.topics {
-                    if topics.len() > EVENT_TOPIC_NUMBER_LIMIT {
-                        return Err(Web3Error::TooManyTopics);
-                    }
-                    let topics_by_idx = topics.iter().enumerate().filter_map(|(idx, topics)| {
-                        Some((idx as u32 + 1, topics.as_ref()?.0.clone()))
-                    });
-                    topics_by_idx.collect::<Vec<_>>()
-                } else {
-                    vec![]
-                };
-
                 let mut to_block = self
                     .state
                     .resolve_filter_block_number(filter.to_block)
@@ -833,12 +902,7 @@ impl<G: L1GasPriceProvider> EthNamespace<G> {
                     );
                 }
 
-                let get_logs_filter = GetLogsFilter {
-                    from_block: *from_block,
-                    to_block,
-                    addresses,
-                    topics,
-                };
+                let get_logs_filter = get_logs_filter(filter, *from_block, to_block)?;
 
                 let mut storage = self
                     .state
@@ -0,0 +1,214 @@
+use micro_contracts::{sys_deposit_contract, sys_white_list_contract};
+use micro_system_constants::{DEPOSIT_ADDRESS, WHITE_LIST_ADDRESS};
+use micro_types::{
+    api::{BlockId, BlockNumber},
+    ethabi::{Function, Token},
+    l2::prover::{Assignment, DepositInfo, DepositStatus, Paging},
+    transaction_request::CallRequest,
+    Address, L1BatchNumber, U256,
+};
+use micro_web3_decl::error::Web3Error;
+
+use crate::{
+    api_server::web3::{
+        backend_jsonrpc::error::internal_error, metrics::API_METRICS, namespaces::EthNamespace,
+        state::RpcState,
+    },
+    l1_gas_price::L1GasPriceProvider,
+};
+
+#[derive(Debug)]
+pub struct ProverNamespace<G> {
+    state: RpcState<G>,
+    eth: EthNamespace<G>,
+}
+
+impl<G> Clone for ProverNamespace<G> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+            eth: self.eth.clone(),
+        }
+    }
+}
+
+impl<G: L1GasPriceProvider> ProverNamespace<G> {
+    pub fn new(state: RpcState<G>) -> Self {
+        Self {
+            eth: EthNamespace::new(state.clone()),
+            state,
+        }
+    }
+
+    /// Resolves paging parameters, capping the limit by the configured entities limit.
+    fn resolve_paging(&self, paging: Option<Paging>) -> (usize, usize) {
+        let paging = paging.unwrap_or_default();
+        let max_limit = self.state.api_config.req_entities_limit;
+        let limit = paging.limit.map_or(max_limit, |limit| limit.min(max_limit));
+        (paging.offset, limit)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_assignment_impl(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<Vec<Assignment>, Web3Error> {
+        const METHOD_NAME: &str = "get_assignment";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let assignments = storage
+            .assignments_dal()
+            .get_assignments_for_l1_batch(l1_batch_number)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        method_latency.observe();
+        Ok(assignments)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_assignments_by_prover_impl(
+        &self,
+        prover: Address,
+        paging: Option<Paging>,
+    ) -> Result<Vec<Assignment>, Web3Error> {
+        const METHOD_NAME: &str = "get_assignments_by_prover";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let (offset, limit) = self.resolve_paging(paging);
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let assignments = storage
+            .assignments_dal()
+            .get_prover_assignments(prover, offset, limit)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        method_latency.observe();
+        Ok(assignments)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_penalties_impl(
+        &self,
+        prover: Option<Address>,
+        paging: Option<Paging>,
+    ) -> Result<Vec<Assignment>, Web3Error> {
+        const METHOD_NAME: &str = "get_penalties";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let (offset, limit) = self.resolve_paging(paging);
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let penalties = storage
+            .assignments_dal()
+            .get_penalties(prover, offset, limit)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        method_latency.observe();
+        Ok(penalties)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_deposit_info_impl(&self, prover: Address) -> Result<DepositInfo, Web3Error> {
+        const METHOD_NAME: &str = "get_deposit_info";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let white_list_contract = sys_white_list_contract();
+        let deposit_contract = sys_deposit_contract();
+        let white_list_function = white_list_contract
+            .function("whiteList")
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let main_token_function = deposit_contract
+            .function("mainToken")
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let deposit_info_function = deposit_contract
+            .function("getProverTokenDepositInfo")
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+
+        let in_white_list = self
+            .call_contract(
+                METHOD_NAME,
+                white_list_function,
+                WHITE_LIST_ADDRESS,
+                &[Token::Address(prover)],
+            )
+            .await?
+            .into_iter()
+            .next()
+            .and_then(Token::into_bool)
+            .ok_or_else(|| internal_error(METHOD_NAME, "unexpected `whiteList` output"))?;
+        let token = self
+            .call_contract(METHOD_NAME, main_token_function, DEPOSIT_ADDRESS, &[])
+            .await?
+            .into_iter()
+            .next()
+            .and_then(Token::into_address)
+            .ok_or_else(|| internal_error(METHOD_NAME, "unexpected `mainToken` output"))?;
+
+        let deposit_info = self
+            .call_contract(
+                METHOD_NAME,
+                deposit_info_function,
+                DEPOSIT_ADDRESS,
+                &[Token::Address(prover), Token::Address(token)],
+            )
+            .await?
+            .into_iter()
+            .next()
+            .and_then(Token::into_tuple)
+            .ok_or_else(|| internal_error(METHOD_NAME, "unexpected deposit info output"))?;
+        // The deposit info struct has `(status, applyTime, amount, depositTime)` layout.
+        let fields: Option<Vec<_>> = deposit_info.into_iter().map(Token::into_uint).collect();
+        let [status, apply_time, amount, deposit_time] = fields
+            .and_then(|fields| <[U256; 4]>::try_from(fields).ok())
+            .ok_or_else(|| internal_error(METHOD_NAME, "unexpected deposit info layout"))?;
+        let status = DepositStatus::from_code(status);
+
+        method_latency.observe();
+        Ok(DepositInfo {
+            prover,
+            in_white_list,
+            token,
+            status,
+            amount,
+            deposit_time,
+            apply_time,
+        })
+    }
+
+    /// Calls a view function of a system contract on the latest miniblock and decodes its output.
+    async fn call_contract(
+        &self,
+        method_name: &'static str,
+        function: &Function,
+        contract_address: Address,
+        args: &[Token],
+    ) -> Result<Vec<Token>, Web3Error> {
+        let data = function
+            .encode_input(args)
+            .map_err(|err| internal_error(method_name, err))?;
+        let request = CallRequest {
+            to: Some(contract_address),
+            data: Some(data.into()),
+            ..CallRequest::default()
+        };
+        let block_id = BlockId::Number(BlockNumber::Latest);
+        let output = self.eth.call_impl(request, Some(block_id), None).await?;
+        function
+            .decode_output(&output.0)
+            .map_err(|err| internal_error(method_name, err))
+    }
+}
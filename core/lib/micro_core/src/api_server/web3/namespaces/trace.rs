@@ -0,0 +1,204 @@
+use micro_dal::ConnectionPool;
+use micro_types::{
+    api::{
+        trace::{Trace, TraceFilter, TraceResultType, TraceResults},
+        BlockId, BlockNumber,
+    },
+    transaction_request::CallRequest,
+    web3::types::Bytes,
+    H256,
+};
+use micro_web3_decl::error::Web3Error;
+
+use crate::{
+    api_server::{
+        execution_sandbox::{BlockArgs, StorageOverrides},
+        web3::{
+            backend_jsonrpc::error::internal_error,
+            metrics::API_METRICS,
+            namespaces::DebugNamespace,
+            resolve_block,
+            state::{RpcState, SealedMiniblockNumber},
+        },
+    },
+    l1_gas_price::L1GasPriceProvider,
+};
+
+/// Parity-style `trace` namespace. Traces of executed transactions are built from call traces persisted
+/// by the state keeper, so the namespace should only be enabled if call traces are saved.
+#[derive(Debug, Clone)]
+pub struct TraceNamespace {
+    connection_pool: ConnectionPool,
+    req_entities_limit: usize,
+    last_sealed_miniblock: SealedMiniblockNumber,
+    debug: DebugNamespace,
+}
+
+impl TraceNamespace {
+    pub async fn new<G: L1GasPriceProvider>(state: RpcState<G>) -> Self {
+        Self {
+            connection_pool: state.connection_pool.clone(),
+            req_entities_limit: state.api_config.req_entities_limit,
+            last_sealed_miniblock: state.last_sealed_miniblock.clone(),
+            debug: DebugNamespace::new(state).await,
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn trace_block_impl(&self, block: BlockNumber) -> Result<Vec<Trace>, Web3Error> {
+        const METHOD_NAME: &str = "trace_block";
+
+        let block_id = BlockId::Number(block);
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut connection = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let block_number = resolve_block(&mut connection, block_id, METHOD_NAME).await?;
+        let call_traces = connection
+            .blocks_web3_dal()
+            .get_call_traces_in_range(block_number..=block_number, &[], None)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let traces = call_traces
+            .into_iter()
+            .flat_map(|(location, call_trace)| Trace::flatten(call_trace, Some(location)))
+            .collect();
+
+        let block_diff = self.last_sealed_miniblock.diff(block_number);
+        method_latency.observe(block_diff);
+        Ok(traces)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn trace_transaction_impl(
+        &self,
+        tx_hash: H256,
+    ) -> Result<Option<Vec<Trace>>, Web3Error> {
+        const METHOD_NAME: &str = "trace_transaction";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let call_trace = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .blocks_web3_dal()
+            .get_call_trace_with_location(tx_hash)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let traces =
+            call_trace.map(|(location, call_trace)| Trace::flatten(call_trace, Some(location)));
+
+        method_latency.observe();
+        Ok(traces)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn trace_filter_impl(&self, filter: TraceFilter) -> Result<Vec<Trace>, Web3Error> {
+        const METHOD_NAME: &str = "trace_filter";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut connection = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let from_block = filter.from_block.unwrap_or(BlockNumber::Latest);
+        let from_block =
+            resolve_block(&mut connection, BlockId::Number(from_block), METHOD_NAME).await?;
+        let to_block = filter.to_block.unwrap_or(BlockNumber::Latest);
+        let to_block =
+            resolve_block(&mut connection, BlockId::Number(to_block), METHOD_NAME).await?;
+        if from_block > to_block {
+            method_latency.observe();
+            return Ok(vec![]);
+        }
+
+        // At most `limit` transactions are scanned. Fetch one extra transaction to check whether
+        // the range contains more transactions than that.
+        let limit = self.req_entities_limit;
+        let mut call_traces = connection
+            .blocks_web3_dal()
+            .get_call_traces_in_range(from_block..=to_block, &filter.addresses(), Some(limit + 1))
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let first_excess_location = if call_traces.len() > limit {
+            call_traces.pop().map(|(location, _)| location)
+        } else {
+            None
+        };
+
+        // `after` and `count` are applied before checking the limit, so that paginated requests
+        // over large block ranges succeed as long as the requested page fits into scanned transactions.
+        let count = filter.count.unwrap_or(usize::MAX);
+        let traces: Vec<_> = call_traces
+            .into_iter()
+            .flat_map(|(location, call_trace)| Trace::flatten(call_trace, Some(location)))
+            .filter(|trace| filter.matches(trace))
+            .skip(filter.after.unwrap_or(0))
+            .take(count)
+            .collect();
+        if let Some(location) = first_excess_location {
+            if traces.len() < count {
+                // Suggest a block range ending before the block with the first transaction over the limit.
+                let suggested_to_block =
+                    location.block_number.0.saturating_sub(1).max(from_block.0);
+                return Err(Web3Error::TraceLimitExceeded(
+                    limit,
+                    from_block.0,
+                    suggested_to_block,
+                ));
+            }
+        }
+
+        method_latency.observe();
+        Ok(traces)
+    }
+
+    #[tracing::instrument(skip(self, request, block_id))]
+    pub async fn trace_call_impl(
+        &self,
+        request: CallRequest,
+        trace_types: Vec<TraceResultType>,
+        block_id: Option<BlockId>,
+    ) -> Result<TraceResults, Web3Error> {
+        const METHOD_NAME: &str = "trace_call";
+
+        if trace_types
+            .iter()
+            .any(|&trace_type| trace_type != TraceResultType::Trace)
+        {
+            return Err(Web3Error::NotImplemented);
+        }
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut connection = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap();
+        let block_args = BlockArgs::new(&mut connection, block_id)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .ok_or(Web3Error::NoBlock)?;
+        drop(connection);
+
+        let call = self
+            .debug
+            .trace_call_tree(request, block_args, StorageOverrides::default(), false)
+            .await?;
+        let output = Bytes(call.output.clone());
+        let trace = if trace_types.is_empty() {
+            vec![]
+        } else {
+            Trace::flatten(call, None)
+        };
+
+        let block_diff = self.last_sealed_miniblock.diff_with_block_args(&block_args);
+        method_latency.observe(block_diff);
+        Ok(TraceResults { output, trace })
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+
+use micro_mempool::{AccountMempoolSnapshot, L2TxFilter};
+use micro_types::{
+    api::txpool::{
+        TxPoolAccountContent, TxPoolBlockReason, TxPoolContent, TxPoolStatus, TxPoolTransaction,
+    },
+    l2::L2Tx,
+    Address, U256,
+};
+use micro_web3_decl::error::Web3Error;
+
+use crate::{
+    api_server::web3::{metrics::API_METRICS, state::RpcState},
+    l1_gas_price::L1GasPriceProvider,
+    state_keeper::MempoolGuard,
+};
+
+#[derive(Debug)]
+pub struct TxPoolNamespace<G> {
+    state: RpcState<G>,
+}
+
+impl<G> Clone for TxPoolNamespace<G> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<G: L1GasPriceProvider> TxPoolNamespace<G> {
+    pub fn new(state: RpcState<G>) -> Self {
+        Self { state }
+    }
+
+    fn mempool(&self) -> Result<&MempoolGuard, Web3Error> {
+        self.state
+            .mempool
+            .as_ref()
+            .ok_or(Web3Error::MempoolUnavailable)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn status_impl(&self) -> Result<TxPoolStatus, Web3Error> {
+        const METHOD_NAME: &str = "txpool_status";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let counts = self.mempool()?.l2_transaction_counts();
+        method_latency.observe();
+        Ok(TxPoolStatus {
+            pending: (counts.pending as u64).into(),
+            queued: (counts.queued as u64).into(),
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn content_impl(&self) -> Result<TxPoolContent<TxPoolTransaction>, Web3Error> {
+        const METHOD_NAME: &str = "txpool_content";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let content = self.content(|tx| tx)?;
+        method_latency.observe();
+        Ok(content)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn content_from_impl(
+        &self,
+        address: Address,
+    ) -> Result<TxPoolAccountContent<TxPoolTransaction>, Web3Error> {
+        const METHOD_NAME: &str = "txpool_contentFrom";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let snapshot = self
+            .mempool()?
+            .account_snapshot(address, self.state.api_config.req_entities_limit);
+        let content = snapshot.map_or_else(TxPoolAccountContent::default, |snapshot| {
+            let filter = self.state.tx_sender.l2_tx_filter();
+            let (pending, queued) = describe_snapshot(&snapshot, &filter);
+            TxPoolAccountContent { pending, queued }
+        });
+        method_latency.observe();
+        Ok(content)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn inspect_impl(&self) -> Result<TxPoolContent<String>, Web3Error> {
+        const METHOD_NAME: &str = "txpool_inspect";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let content = self.content(|tx| {
+            format!(
+                "{:?}: {} wei + {} gas × {} wei",
+                tx.to, tx.value, tx.gas, tx.max_fee_per_gas
+            )
+        })?;
+        method_latency.observe();
+        Ok(content)
+    }
+
+    /// Collects the mempool content. The number of returned transactions is capped by the entities limit
+    /// from the API config; transactions with the highest nonces of the last returned account may be omitted.
+    fn content<T>(
+        &self,
+        mut map_fn: impl FnMut(TxPoolTransaction) -> T,
+    ) -> Result<TxPoolContent<T>, Web3Error> {
+        let snapshots = self
+            .mempool()?
+            .snapshot(self.state.api_config.req_entities_limit);
+        let filter = self.state.tx_sender.l2_tx_filter();
+
+        let mut content = TxPoolContent::default();
+        for snapshot in &snapshots {
+            let (pending, queued) = describe_snapshot(snapshot, &filter);
+            let mut map_txs = |txs: BTreeMap<u32, TxPoolTransaction>| -> BTreeMap<u32, T> {
+                txs.into_iter()
+                    .map(|(nonce, tx)| (nonce, map_fn(tx)))
+                    .collect()
+            };
+            if !pending.is_empty() {
+                content.pending.insert(snapshot.account, map_txs(pending));
+            }
+            if !queued.is_empty() {
+                content.queued.insert(snapshot.account, map_txs(queued));
+            }
+        }
+        Ok(content)
+    }
+}
+
+type TxsByNonce = BTreeMap<u32, TxPoolTransaction>;
+
+/// Converts a mempool snapshot for an account into pending and queued transactions keyed by nonce,
+/// annotating each transaction with the reason it's not picked by the state keeper (if any).
+fn describe_snapshot(
+    snapshot: &AccountMempoolSnapshot,
+    filter: &L2TxFilter,
+) -> (TxsByNonce, TxsByNonce) {
+    let pending = snapshot
+        .pending
+        .iter()
+        .map(|tx| {
+            let mut api_tx = TxPoolTransaction::from(tx);
+            api_tx.blocked_by = filter_block_reason(tx, filter);
+            (tx.nonce().0, api_tx)
+        })
+        .collect();
+
+    // The first missing nonce is the nonce following the last pending transaction.
+    let expected_nonce = snapshot.nonce.0 + snapshot.pending.len() as u32;
+    let queued = snapshot
+        .queued
+        .iter()
+        .map(|tx| {
+            let mut api_tx = TxPoolTransaction::from(tx);
+            api_tx.blocked_by = Some(TxPoolBlockReason::NonceGap {
+                expected_nonce: expected_nonce.into(),
+            });
+            (tx.nonce().0, api_tx)
+        })
+        .collect();
+    (pending, queued)
+}
+
+/// Mirrors the fee checks performed by the mempool when the state keeper requests the next transaction.
+fn filter_block_reason(tx: &L2Tx, filter: &L2TxFilter) -> Option<TxPoolBlockReason> {
+    let fee = &tx.common_data.fee;
+    let required_fee_per_gas = U256::from(filter.fee_per_gas);
+    let required_gas_per_pubdata = U256::from(filter.gas_per_pubdata);
+    if fee.max_fee_per_gas < required_fee_per_gas {
+        Some(TxPoolBlockReason::FeeTooLow {
+            max_fee_per_gas: fee.max_fee_per_gas,
+            required_fee_per_gas,
+        })
+    } else if fee.gas_per_pubdata_limit < required_gas_per_pubdata {
+        Some(TxPoolBlockReason::GasPerPubdataTooLow {
+            gas_per_pubdata_limit: fee.gas_per_pubdata_limit,
+            required_gas_per_pubdata,
+        })
+    } else {
+        None
+    }
+}
@@ -1,12 +1,23 @@
-use std::{collections::HashMap, convert::TryInto, ops::Add};
+use std::{
+    collections::HashMap,
+    convert::TryInto,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use bigdecimal::{BigDecimal, Zero};
 use micro_dal::StorageProcessor;
 use micro_mini_merkle_tree::MiniMerkleTree;
 use micro_types::{
     api::{
-        BlockDetails, BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Proof,
-        ProtocolVersion, StorageProof, TransactionDetails,
+        bundle::{SimulateBundleOptions, SimulatedCall, SimulatedCallStatus, StorageDiff},
+        finality::TransactionFinality,
+        priority_ops::PriorityOpsReport,
+        protocol_upgrades::ProtocolUpgradesReport,
+        AccountStorageKeys, ApiStorageLog, BatchedProof, BlockDetails, BlockId, BlockNumber,
+        BridgeAddresses, GetLogsFilter, L1BatchDetails, L2ToL1LogProof, Log, LogCursor, LogsPage,
+        Proof, ProtocolVersion, StorageProof, TransactionDetailedResult, TransactionDetails,
+        TransactionReceipt,
     },
     fee::Fee,
     l1::L1Tx,
@@ -15,6 +26,8 @@ use micro_types::{
     statistics_info::StatiticsInfo,
     tokens::ETHEREUM_ADDRESS,
     transaction_request::CallRequest,
+    vm_trace::Call,
+    web3::types::{Bytes, Index},
     AccountTreeId, L1BatchNumber, MiniblockNumber, StorageKey, Transaction, L1_MESSENGER_ADDRESS,
     L2_ETH_TOKEN_ADDRESS, MAX_GAS_PER_PUBDATA_BYTE, REQUIRED_L1_TO_L2_GAS_PER_PUBDATA_BYTE, U256,
     U64,
@@ -22,17 +35,38 @@ use micro_types::{
 use micro_utils::{address_to_h256, ratio_to_big_decimal_normalized};
 use micro_web3_decl::{
     error::Web3Error,
-    types::{Address, Token, H256},
+    types::{Address, Filter, Token, H256},
 };
+use multivm::interface::{ExecutionResult, VmExecutionResultAndLogs};
+use once_cell::sync::OnceCell;
+use tokio::sync::broadcast;
 
 use crate::{
     api_server::{
+        execution_sandbox::{ApiTracer, BlockArgs, StorageOverrides},
         tree::TreeApiClient,
-        web3::{backend_jsonrpc::error::internal_error, metrics::API_METRICS, RpcState},
+        web3::{
+            backend_jsonrpc::error::internal_error, metrics::API_METRICS,
+            namespaces::eth::get_logs_filter, RpcState,
+        },
     },
     l1_gas_price::L1GasPriceProvider,
+    state_keeper::{load_protocol_upgrades_report, MiniblockNotifications, SealedMiniblock},
 };
 
+/// Maximum number of calls in a bundle accepted by `zks_simulateBundle`.
+const MAX_BUNDLE_SIZE: usize = 64;
+/// Default time to wait for transaction inclusion in `zks_sendRawTransactionWithDetailedOutput`.
+const DEFAULT_TX_INCLUSION_TIMEOUT: Duration = Duration::from_secs(10);
+/// Upper bound for the inclusion timeout requested by callers.
+const MAX_TX_INCLUSION_TIMEOUT: Duration = Duration::from_secs(30);
+/// Interval to re-check Postgres if no miniblock notifications were received; guards against missed notifications.
+const TX_INCLUSION_FALLBACK_POLL_INTERVAL: Duration = Duration::from_secs(1);
+/// Initial interval between requests of transaction details from the main node on the external node.
+const MAIN_NODE_TX_DETAILS_MIN_INTERVAL: Duration = Duration::from_millis(200);
+/// Upper bound for the (exponentially growing) interval between transaction details requests to the main node.
+const MAIN_NODE_TX_DETAILS_MAX_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Debug)]
 pub struct ZksNamespace<G> {
     pub state: RpcState<G>,
@@ -115,7 +149,7 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
         let fee = self
             .state
             .tx_sender
-            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation)
+            .get_txs_fee_in_wei(tx, scale_factor, acceptable_overestimation, None)
             .await
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
 
@@ -543,6 +577,201 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
         tx_details
     }
 
+    #[tracing::instrument(skip(self))]
+    pub async fn get_transaction_finality_impl(
+        &self,
+        hash: H256,
+    ) -> Result<Option<TransactionFinality>, Web3Error> {
+        const METHOD_NAME: &str = "get_transaction_finality";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let finality = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .transactions_web3_dal()
+            .get_transaction_finality(hash)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err));
+
+        method_latency.observe();
+        finality
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub fn get_priority_ops_status_impl(&self) -> Result<Option<PriorityOpsReport>, Web3Error> {
+        const METHOD_NAME: &str = "get_priority_ops_status";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let report = self
+            .state
+            .priority_ops_report
+            .as_ref()
+            .ok_or(Web3Error::PriorityOpsMonitorUnavailable)?
+            .borrow()
+            .clone();
+        method_latency.observe();
+        Ok(report)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_protocol_upgrades_status_impl(
+        &self,
+    ) -> Result<ProtocolUpgradesReport, Web3Error> {
+        const METHOD_NAME: &str = "get_protocol_upgrades_status";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let mut storage = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let report = load_protocol_upgrades_report(&mut storage)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        method_latency.observe();
+        Ok(report)
+    }
+
+    #[tracing::instrument(skip(self, tx_bytes))]
+    pub async fn send_raw_transaction_with_detailed_output_impl(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<u64>,
+    ) -> Result<TransactionDetailedResult, Web3Error> {
+        const METHOD_NAME: &str = "send_raw_transaction_with_detailed_output";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let timeout = timeout_ms
+            .map_or(DEFAULT_TX_INCLUSION_TIMEOUT, Duration::from_millis)
+            .min(MAX_TX_INCLUSION_TIMEOUT);
+        // Subscribe before submitting the transaction, so that the notification about its inclusion cannot be missed.
+        let miniblock_receiver = self
+            .state
+            .miniblock_notifications
+            .as_ref()
+            .map(MiniblockNotifications::subscribe);
+        let tx_hash = self.state.submit_raw_transaction(tx_bytes.0).await?;
+
+        let wait_result = tokio::time::timeout(
+            timeout,
+            self.wait_for_inclusion(tx_hash, miniblock_receiver),
+        )
+        .await;
+        let receipt = wait_result.map_err(|_| Web3Error::TxInclusionTimeout(tx_hash))??;
+        let block_number = receipt
+            .block_number
+            .expect("receipt for included transaction has no block number");
+        let storage_logs = self
+            .state
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .unwrap()
+            .storage_logs_dal()
+            .get_tx_storage_logs(MiniblockNumber(block_number.as_u32()), tx_hash)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        let storage_logs = storage_logs
+            .into_iter()
+            .map(|log| ApiStorageLog {
+                address: *log.key.address(),
+                key: *log.key.key(),
+                written_value: log.value,
+            })
+            .collect();
+
+        method_latency.observe();
+        Ok(TransactionDetailedResult {
+            transaction_hash: tx_hash,
+            events: receipt.logs.clone(),
+            receipt,
+            storage_logs,
+        })
+    }
+
+    /// Waits until the transaction is included in a sealed miniblock and returns its receipt. Postgres is re-checked
+    /// after each miniblock notification, since transactions rejected by the state keeper produce no notifications.
+    /// If miniblock notifications are not available, the last sealed miniblock number shared by all API handlers
+    /// is watched instead.
+    async fn wait_for_inclusion(
+        &self,
+        tx_hash: H256,
+        mut miniblock_receiver: Option<broadcast::Receiver<Arc<SealedMiniblock>>>,
+    ) -> Result<TransactionReceipt, Web3Error> {
+        const METHOD_NAME: &str = "wait_for_inclusion";
+
+        let mut sealed_miniblock_receiver = self.state.last_sealed_miniblock.subscribe();
+        let mut main_node_request_interval = MAIN_NODE_TX_DETAILS_MIN_INTERVAL;
+        let mut next_main_node_request = Instant::now();
+        loop {
+            let receipt = self
+                .state
+                .connection_pool
+                .access_storage_tagged("api")
+                .await
+                .unwrap()
+                .transactions_web3_dal()
+                .get_transaction_receipt(tx_hash)
+                .await
+                .map_err(|err| internal_error(METHOD_NAME, err))?;
+            if let Some(receipt) = receipt {
+                if receipt.block_number.is_some() {
+                    return Ok(receipt);
+                } else if receipt.status == Some(U64::zero()) {
+                    let message = format!("Transaction {tx_hash:?} was rejected by the sequencer");
+                    return Err(Web3Error::SubmitTransactionError(message, vec![]));
+                }
+            }
+            let proxy = self.state.tx_sender.0.proxy.as_ref();
+            if let Some(proxy) = proxy.filter(|_| Instant::now() >= next_main_node_request) {
+                // We're running an external node: rejected transactions are never synced back to us,
+                // so the rejection must be queried from the main node directly. Requests are backed off
+                // exponentially so that waiting clients don't flood the main node.
+                next_main_node_request = Instant::now() + main_node_request_interval;
+                main_node_request_interval =
+                    (main_node_request_interval * 2).min(MAIN_NODE_TX_DETAILS_MAX_INTERVAL);
+                let details = match proxy.request_tx_details(tx_hash).await {
+                    Ok(details) => details,
+                    Err(err) => {
+                        // Not fatal: the transaction can still be synced back to us before the timeout.
+                        tracing::warn!(
+                            "Failed requesting details for tx {tx_hash:?} from the main node: {err}"
+                        );
+                        None
+                    }
+                };
+                if let Some(rejection) = details.and_then(|details| details.rejection) {
+                    let message = format!(
+                        "Transaction {tx_hash:?} was rejected by the sequencer: {}",
+                        rejection.reason
+                    );
+                    return Err(Web3Error::SubmitTransactionError(message, vec![]));
+                }
+            }
+
+            let Some(receiver) = &mut miniblock_receiver else {
+                tokio::select! {
+                    _ = tokio::time::sleep(TX_INCLUSION_FALLBACK_POLL_INTERVAL) => { /* re-check Postgres */ }
+                    _ = sealed_miniblock_receiver.changed() => { /* re-check Postgres */ }
+                }
+                continue;
+            };
+            tokio::select! {
+                _ = tokio::time::sleep(TX_INCLUSION_FALLBACK_POLL_INTERVAL) => { /* re-check Postgres */ }
+                recv_result = receiver.recv() => {
+                    if let Err(broadcast::error::RecvError::Closed) = recv_result {
+                        tracing::warn!("Miniblock notifications channel is closed; switching to polling");
+                        miniblock_receiver = None;
+                    }
+                }
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self))]
     pub async fn get_l1_batch_details_impl(
         &self,
@@ -650,14 +879,23 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
             .map(|key| StorageKey::new(AccountTreeId::new(address), *key).hashed_key_u256())
             .collect();
 
-        let storage_proof = self
+        let entries = self
             .state
             .tree_api
             .as_ref()
             .ok_or(Web3Error::TreeApiUnavailable)?
             .get_proofs(l1_batch_number, hashed_keys)
             .await
-            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        if entries.len() != keys.len() {
+            let err = format!(
+                "tree API returned {} proofs for {} requested keys",
+                entries.len(),
+                keys.len()
+            );
+            return Err(internal_error(METHOD_NAME, err));
+        }
+        let storage_proof = entries
             .into_iter()
             .zip(keys)
             .map(|(proof, key)| StorageProof {
@@ -674,64 +912,294 @@ impl<G: L1GasPriceProvider> ZksNamespace<G> {
         })
     }
 
-    #[tracing::instrument(skip(self))]
-    pub async fn get_statistics_info_impl(&self) -> StatiticsInfo {
-        let endpoint_name = "get_statistics_info_impl";
-        let mut l2_block_id = self
+    #[tracing::instrument(skip(self, accounts))]
+    pub async fn get_batched_proofs_impl(
+        &self,
+        accounts: Vec<AccountStorageKeys>,
+        l1_batch_number: L1BatchNumber,
+    ) -> Result<BatchedProof, Web3Error> {
+        const METHOD_NAME: &str = "get_batched_proofs";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let key_count: usize = accounts.iter().map(|account| account.keys.len()).sum();
+        let max_key_count = self.state.api_config.req_entities_limit;
+        if key_count > max_key_count {
+            return Err(Web3Error::TooManyProofKeys(max_key_count));
+        }
+        let tree_api = self
+            .state
+            .tree_api
+            .as_ref()
+            .ok_or(Web3Error::TreeApiUnavailable)?;
+
+        let root_hash = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
             .unwrap()
-            .blocks_web3_dal()
-            .get_sealed_miniblock_number()
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
             .await
-            .map(|n| U64::from(n.0))
-            .map_err(|err| internal_error(endpoint_name, err))
-            .unwrap();
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .ok_or(Web3Error::NoBlock)?;
+
+        let hashed_keys = accounts
+            .iter()
+            .flat_map(|account| {
+                let account_id = AccountTreeId::new(account.address);
+                account
+                    .keys
+                    .iter()
+                    .map(move |&key| StorageKey::new(account_id, key).hashed_key_u256())
+            })
+            .collect();
+        let entries = tree_api
+            .get_proofs(l1_batch_number, hashed_keys)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
+        // Otherwise, zipping keys with entries below would silently drop proofs or attribute them to wrong keys.
+        if entries.len() != key_count {
+            let err = format!(
+                "tree API returned {} proofs for {key_count} requested keys",
+                entries.len()
+            );
+            return Err(internal_error(METHOD_NAME, err));
+        }
+        let mut entries = entries.into_iter();
+        // Entries are returned in the same order as the requested keys, so we split them by accounts.
+        let proofs = accounts
+            .into_iter()
+            .map(|account| {
+                let storage_proof = account
+                    .keys
+                    .into_iter()
+                    .zip(&mut entries)
+                    .map(|(key, proof)| StorageProof {
+                        key,
+                        proof: proof.merkle_path,
+                        value: proof.value,
+                        index: proof.index,
+                    })
+                    .collect();
+                Proof {
+                    address: account.address,
+                    storage_proof,
+                }
+            })
+            .collect();
+
+        method_latency.observe();
+        Ok(BatchedProof {
+            l1_batch_number,
+            root_hash,
+            proofs,
+        })
+    }
+
+    #[tracing::instrument(skip(self, filter))]
+    pub async fn get_logs_with_cursor_impl(
+        &self,
+        mut filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> Result<LogsPage, Web3Error> {
+        const METHOD_NAME: &str = "get_logs_with_cursor";
 
-        let l2_verify_block_id = self
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        self.state.resolve_filter_block_hash(&mut filter).await?;
+        let (from_block, to_block) = self.state.resolve_filter_block_range(&filter).await?;
+        // Do not return logs from blocks that are not sealed yet, so that pagination is deterministic.
+        let latest_block = self
+            .state
+            .resolve_filter_block_number(Some(BlockNumber::Latest))
+            .await?;
+        let to_block = to_block.min(latest_block);
+        let get_logs_filter = get_logs_filter(&filter, from_block, to_block)?;
+
+        // `clamp()` panics if `max_limit` is less than the lower bound, so a misconfigured zero limit is bumped to 1.
+        let max_limit = self.state.api_config.req_entities_limit.max(1);
+        let limit = limit.unwrap_or(max_limit).clamp(1, max_limit);
+        // Fetch one extra log to check whether there are more logs after the page.
+        let mut logs = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
             .unwrap()
-            .blocks_web3_dal()
-            .get_sealed_prove_miniblock_number()
+            .events_web3_dal()
+            .get_logs_after(get_logs_filter, cursor, limit + 1)
             .await
-            .map(|n| U64::from(n.0))
-            .map_err(|err| internal_error(endpoint_name, err))
-            .unwrap();
+            .map_err(|err| internal_error(METHOD_NAME, err))?;
 
-        let tx_mempool = self
+        let next_cursor = if logs.len() > limit {
+            logs.truncate(limit);
+            logs.last().map(|log| LogCursor {
+                block_number: MiniblockNumber(log.block_number.unwrap().as_u32()),
+                log_index: log.log_index.unwrap().as_u32(),
+            })
+        } else {
+            None
+        };
+
+        method_latency.observe();
+        Ok(LogsPage { logs, next_cursor })
+    }
+
+    #[tracing::instrument(skip(self, requests, options))]
+    pub async fn simulate_bundle_impl(
+        &self,
+        requests: Vec<CallRequest>,
+        block_id: Option<BlockId>,
+        options: Option<SimulateBundleOptions>,
+    ) -> Result<Vec<SimulatedCall>, Web3Error> {
+        const METHOD_NAME: &str = "simulate_bundle";
+
+        if requests.len() > MAX_BUNDLE_SIZE {
+            return Err(Web3Error::BundleTooLarge(MAX_BUNDLE_SIZE));
+        }
+        let options = options.unwrap_or_default();
+        let storage_overrides = options
+            .state_overrides
+            .as_ref()
+            .map(StorageOverrides::new)
+            .transpose()
+            .map_err(|err| Web3Error::InvalidStateOverride(err.to_string()))?;
+
+        let block_id = block_id.unwrap_or(BlockId::Number(BlockNumber::Pending));
+        let method_latency = API_METRICS.start_block_call(METHOD_NAME, block_id);
+        let mut connection = self
             .state
             .connection_pool
             .access_storage_tagged("api")
             .await
-            .unwrap()
-            .transactions_dal()
-            .get_tx_memory()
-            .await;
-        let un_verified_blocks = l2_block_id.saturating_sub(l2_verify_block_id);
+            .unwrap();
+        let block_args = BlockArgs::new(&mut connection, block_id)
+            .await
+            .map_err(|err| internal_error(METHOD_NAME, err))?
+            .ok_or(Web3Error::NoBlock)?;
+        drop(connection);
+
+        let mut txs = Vec::with_capacity(requests.len());
+        let mut call_traces = Vec::with_capacity(requests.len());
+        for request in requests {
+            let tx = L2Tx::from_request(request.into(), self.state.api_config.max_tx_size)?;
+            let (tracers, call_trace) = if options.with_call_traces {
+                let call_trace = Arc::new(OnceCell::default());
+                (
+                    vec![ApiTracer::CallTracer(call_trace.clone())],
+                    Some(call_trace),
+                )
+            } else {
+                (vec![], None)
+            };
+            txs.push((tx, tracers));
+            call_traces.push(call_trace);
+        }
+        let calls: Vec<_> = txs.iter().map(|(tx, _)| tx.clone()).collect();
 
-        let latest_proof_time = self
+        let results = self
             .state
-            .connection_pool
-            .access_storage_tagged("api")
+            .tx_sender
+            .simulate_bundle(block_args, txs, storage_overrides)
             .await
-            .unwrap()
-            .eth_sender_dal()
-            .get_latest_proof_time()
-            .await;
+            .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()))?;
+        let simulated_calls = results
+            .into_iter()
+            .zip(calls.into_iter().zip(call_traces))
+            .enumerate()
+            .map(|(i, (result, (tx, call_trace)))| {
+                let call_trace = call_trace.map(|trace| trace.get().cloned().unwrap_or_default());
+                simulated_call(i, tx, result, call_trace)
+            })
+            .collect();
 
-        l2_block_id = l2_block_id.add(1);
+        let block_diff = self
+            .state
+            .last_sealed_miniblock
+            .diff_with_block_args(&block_args);
+        method_latency.observe(block_diff);
+        Ok(simulated_calls)
+    }
 
-        StatiticsInfo {
-            tx_mempool: tx_mempool,
-            last_verified_block: l2_verify_block_id,
-            next_block_id: l2_block_id,
-            un_verified_blocks: un_verified_blocks,
-            latest_proof_time: latest_proof_time,
+    #[tracing::instrument(skip(self))]
+    pub async fn get_statistics_info_impl(&self) -> StatiticsInfo {
+        const METHOD_NAME: &str = "get_statistics_info";
+
+        let method_latency = API_METRICS.start_call(METHOD_NAME);
+        let stats = StatiticsInfo::clone(&self.state.statistics.get());
+        method_latency.observe();
+        stats
+    }
+}
+
+fn simulated_call(
+    index: usize,
+    tx: L2Tx,
+    result: VmExecutionResultAndLogs,
+    call_trace: Option<Vec<Call>>,
+) -> SimulatedCall {
+    let (status, return_data, error, revert_reason) = match result.result {
+        ExecutionResult::Success { output } => (SimulatedCallStatus::Success, output, None, None),
+        ExecutionResult::Revert { output } => {
+            let reason = output.to_string();
+            (
+                SimulatedCallStatus::Reverted,
+                output.encoded_data(),
+                Some(reason.clone()),
+                Some(reason),
+            )
         }
+        ExecutionResult::Halt { reason } => (
+            SimulatedCallStatus::Halted,
+            vec![],
+            Some(reason.to_string()),
+            None,
+        ),
+    };
+
+    let logs = result
+        .logs
+        .events
+        .into_iter()
+        .enumerate()
+        .map(|(log_index, event)| Log {
+            address: event.address,
+            topics: event.indexed_topics,
+            data: Bytes(event.value),
+            block_hash: None,
+            block_number: None,
+            l1_batch_number: None,
+            transaction_hash: None,
+            transaction_index: Some(Index::from(index)),
+            log_index: None,
+            transaction_log_index: Some(log_index.into()),
+            log_type: None,
+            removed: None,
+        })
+        .collect();
+    let state_diff = StorageDiff::from_storage_logs(&result.logs.storage_logs);
+
+    let trace = call_trace.map(|calls| {
+        Call::new_high_level(
+            tx.common_data.fee.gas_limit.as_u32(),
+            result.statistics.gas_used,
+            tx.execute.value,
+            tx.execute.calldata,
+            return_data.clone(),
+            revert_reason,
+            calls,
+        )
+        .into()
+    });
+
+    SimulatedCall {
+        status,
+        return_data: Bytes(return_data),
+        error,
+        gas_used: result.statistics.gas_used.into(),
+        logs,
+        state_diff,
+        trace,
     }
 }
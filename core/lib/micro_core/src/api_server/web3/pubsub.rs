@@ -1,26 +1,40 @@
 //! (Largely) backend-agnostic logic for dealing with Web3 subscriptions.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use anyhow::Context as _;
 use jsonrpc_core::error::{Error, ErrorCode};
 use jsonrpc_pubsub::{typed, SubscriptionId};
 use micro_dal::ConnectionPool;
-use micro_types::{MiniblockNumber, H128, H256};
+use micro_types::{
+    api::{
+        finality::{L1BatchStage, L1BatchStatusUpdate},
+        TransactionRejection,
+    },
+    L1BatchNumber, MiniblockNumber, H128, H256,
+};
 use micro_web3_decl::types::{BlockHeader, Log, PubSubFilter, PubSubResult};
 use tokio::{
-    sync::{mpsc, watch, RwLock},
+    sync::{broadcast, mpsc, watch, RwLock},
     task::JoinHandle,
-    time::{interval, Duration},
+    time::{interval, Duration, Interval},
 };
 
 use super::{
-    metrics::{SubscriptionType, PUB_SUB_METRICS},
+    metrics::{NotificationSource, SubscriptionType, PUB_SUB_METRICS},
     namespaces::eth::EVENT_TOPIC_NUMBER_LIMIT,
 };
+use crate::state_keeper::{MiniblockNotifications, SealedMiniblock};
 
 pub(super) type SubscriptionMap<T> = Arc<RwLock<HashMap<SubscriptionId, T>>>;
 
+/// Maximum number of rejected transactions loaded from Postgres during a single notifier iteration.
+const REJECTED_TXS_BATCH_LIMIT: usize = 1_000;
+
 /// Events emitted by the subscription logic. Only used in WebSocket server tests so far.
 #[derive(Debug)]
 pub(super) enum PubSubEvent {
@@ -28,16 +42,93 @@ pub(super) enum PubSubEvent {
     NotifyIterationFinished(SubscriptionType),
 }
 
+/// Next action of a notifier.
+#[derive(Debug)]
+enum NotifierIteration {
+    /// Poll Postgres for new data.
+    Poll,
+    /// Notify subscribers about a miniblock received via in-process notifications.
+    Pushed(Arc<SealedMiniblock>),
+    /// Do nothing; subscribers were already notified about the pushed miniblock.
+    Skip,
+}
+
 /// Manager of notifications for a certain type of subscriptions.
+///
+/// If in-process miniblock notifications are available (i.e., the API server runs in the same process
+/// as the state keeper), the notifier uses them to notify subscribers as soon as a miniblock is sealed.
+/// Polling is still used as a fallback for miniblocks that were missed by the in-process path
+/// (e.g., fictive miniblocks or miniblocks skipped by a lagging receiver).
 #[derive(Debug)]
 struct PubSubNotifier<V> {
     subscribers: SubscriptionMap<V>,
     connection_pool: ConnectionPool,
     polling_interval: Duration,
+    miniblock_receiver: Option<broadcast::Receiver<Arc<SealedMiniblock>>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
 impl<V: Clone> PubSubNotifier<V> {
+    /// Waits for either the polling timer or an in-process miniblock notification.
+    async fn wait_for_next_iteration(
+        &mut self,
+        timer: &mut Interval,
+        sub_type: SubscriptionType,
+    ) -> Option<Arc<SealedMiniblock>> {
+        let Some(receiver) = &mut self.miniblock_receiver else {
+            timer.tick().await;
+            return None;
+        };
+
+        tokio::select! {
+            _ = timer.tick() => None,
+            recv_result = receiver.recv() => match recv_result {
+                Ok(miniblock) => {
+                    PUB_SUB_METRICS.push_latency[&sub_type].observe(miniblock.sealed_at.elapsed());
+                    // Postpone polling since the notifier has just received fresh data.
+                    timer.reset();
+                    Some(miniblock)
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped_count)) => {
+                    tracing::info!(
+                        "{sub_type:?} notifier skipped {skipped_count} miniblock notifications; \
+                         falling back to polling"
+                    );
+                    PUB_SUB_METRICS.push_fallbacks[&sub_type].inc();
+                    None
+                }
+                Err(broadcast::error::RecvError::Closed) => {
+                    tracing::warn!(
+                        "Miniblock notifications channel is closed; {sub_type:?} notifier switches to polling"
+                    );
+                    self.miniblock_receiver = None;
+                    None
+                }
+            },
+        }
+    }
+
+    /// Determines the next action for notifiers tracking the last notified miniblock.
+    async fn next_miniblock_iteration(
+        &mut self,
+        timer: &mut Interval,
+        last_block_number: MiniblockNumber,
+        sub_type: SubscriptionType,
+    ) -> NotifierIteration {
+        let Some(miniblock) = self.wait_for_next_iteration(timer, sub_type).await else {
+            return NotifierIteration::Poll;
+        };
+        if miniblock.number <= last_block_number {
+            NotifierIteration::Skip
+        } else if miniblock.number == last_block_number + 1 {
+            NotifierIteration::Pushed(miniblock)
+        } else {
+            // Some miniblocks were not delivered via in-process notifications.
+            PUB_SUB_METRICS.push_fallbacks[&sub_type].inc();
+            NotifierIteration::Poll
+        }
+    }
+
     async fn sealed_miniblock_number(&self) -> anyhow::Result<MiniblockNumber> {
         self.connection_pool
             .access_storage_tagged("api")
@@ -61,7 +152,7 @@ impl<V: Clone> PubSubNotifier<V> {
 }
 
 impl PubSubNotifier<typed::Sink<PubSubResult>> {
-    async fn notify_blocks(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_blocks(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut last_block_number = self.sealed_miniblock_number().await?;
         let mut timer = interval(self.polling_interval);
         loop {
@@ -69,11 +160,23 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
                 tracing::info!("Stop signal received, pubsub_block_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
 
-            let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Blocks].start();
-            let new_blocks = self.new_blocks(last_block_number).await?;
-            db_latency.observe();
+            let iteration = self
+                .next_miniblock_iteration(&mut timer, last_block_number, SubscriptionType::Blocks)
+                .await;
+            let (new_blocks, source) = match iteration {
+                NotifierIteration::Poll => {
+                    let db_latency =
+                        PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Blocks].start();
+                    let new_blocks = self.new_blocks(last_block_number).await?;
+                    db_latency.observe();
+                    (new_blocks, NotificationSource::Poll)
+                }
+                NotifierIteration::Pushed(miniblock) => {
+                    (vec![miniblock.header.clone()], NotificationSource::Push)
+                }
+                NotifierIteration::Skip => (vec![], NotificationSource::Push),
+            };
 
             if let Some(last_block) = new_blocks.last() {
                 last_block_number = MiniblockNumber(last_block.number.unwrap().as_u32());
@@ -90,6 +193,7 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
                     }
                 }
                 notify_latency.observe();
+                Self::observe_block_lag(&new_blocks, source);
             }
             self.emit_event(PubSubEvent::NotifyIterationFinished(
                 SubscriptionType::Blocks,
@@ -98,6 +202,18 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
         Ok(())
     }
 
+    fn observe_block_lag(blocks: &[BlockHeader], source: NotificationSource) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .expect("incorrect system time");
+        for block in blocks {
+            let timestamp = Duration::from_secs(block.timestamp.as_u64());
+            if let Some(lag) = now.checked_sub(timestamp) {
+                PUB_SUB_METRICS.block_notification_lag[&source].observe(lag);
+            }
+        }
+    }
+
     async fn new_blocks(
         &self,
         last_block_number: MiniblockNumber,
@@ -112,7 +228,7 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
             .with_context(|| format!("get_block_headers_after({last_block_number})"))
     }
 
-    async fn notify_txs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_txs(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut last_time = chrono::Utc::now().naive_utc();
         let mut timer = interval(self.polling_interval);
         loop {
@@ -120,7 +236,15 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
                 tracing::info!("Stop signal received, pubsub_tx_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
+            // Pending transactions are not produced by the state keeper, so in-process notifications
+            // are only used to poll Postgres early if a sealed miniblock contains transactions.
+            let pushed_miniblock = self
+                .wait_for_next_iteration(&mut timer, SubscriptionType::Txs)
+                .await;
+            if pushed_miniblock.map_or(false, |miniblock| miniblock.tx_hashes.is_empty()) {
+                self.emit_event(PubSubEvent::NotifyIterationFinished(SubscriptionType::Txs));
+                continue;
+            }
 
             let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Txs].start();
             let (new_txs, new_last_time) = self.new_txs(last_time).await?;
@@ -160,10 +284,192 @@ impl PubSubNotifier<typed::Sink<PubSubResult>> {
             .await
             .context("get_pending_txs_hashes_after()")
     }
+
+    /// Notifies subscribers about L1 batches reaching new lifecycle stages. Unlike other notifiers,
+    /// this one only polls Postgres since most stage transitions are caused by L1 transactions.
+    async fn notify_l1_batches(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        let mut last_stages = self.l1_batch_stages().await?;
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, pubsub_l1_batch_notifier is shutting down");
+                break;
+            }
+            timer.tick().await;
+
+            let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::L1Batches].start();
+            let stages = self.l1_batch_stages().await?;
+            let updates = self.new_l1_batch_updates(last_stages, stages).await?;
+            db_latency.observe();
+            last_stages = stages;
+
+            if !updates.is_empty() {
+                let notify_latency = PUB_SUB_METRICS.notify_subscribers_latency
+                    [&SubscriptionType::L1Batches]
+                    .start();
+                for sink in self.current_subscribers().await {
+                    for update in updates.iter().cloned() {
+                        if sink
+                            .notify(Ok(PubSubResult::L1BatchStatus(update)))
+                            .is_err()
+                        {
+                            // Subscriber disconnected.
+                            break;
+                        }
+                        PUB_SUB_METRICS.notify[&SubscriptionType::L1Batches].inc();
+                    }
+                }
+                notify_latency.observe();
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::L1Batches,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the last L1 batch number reaching each stage, in the order of [`L1BatchStage::ALL`].
+    async fn l1_batch_stages(&self) -> anyhow::Result<[Option<L1BatchNumber>; 4]> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?;
+        let mut blocks_dal = storage.blocks_dal();
+        let sealed = blocks_dal
+            .get_sealed_l1_batch_number()
+            .await
+            .context("get_sealed_l1_batch_number()")?;
+        let committed = blocks_dal
+            .get_number_of_last_l1_batch_committed_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_committed_on_eth()")?;
+        let proven = blocks_dal
+            .get_number_of_last_l1_batch_proven_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_proven_on_eth()")?;
+        let executed = blocks_dal
+            .get_number_of_last_l1_batch_executed_on_eth()
+            .await
+            .context("get_number_of_last_l1_batch_executed_on_eth()")?;
+        Ok([Some(sealed), committed, proven, executed])
+    }
+
+    /// Loads updates for L1 batches that have reached new stages since `last_stages`. Updates are ordered
+    /// by the batch number and then by the stage.
+    async fn new_l1_batch_updates(
+        &self,
+        last_stages: [Option<L1BatchNumber>; 4],
+        stages: [Option<L1BatchNumber>; 4],
+    ) -> anyhow::Result<Vec<L1BatchStatusUpdate>> {
+        let changed_ranges = last_stages
+            .into_iter()
+            .zip(stages)
+            .filter_map(|(last, current)| {
+                let current = current.filter(|&current| Some(current) > last)?;
+                let first = last.map_or(L1BatchNumber(0), |last| last + 1);
+                Some((first, current))
+            });
+        let Some((first, last)) =
+            changed_ranges.reduce(|(first, last), (other_first, other_last)| {
+                (first.min(other_first), last.max(other_last))
+            })
+        else {
+            return Ok(vec![]);
+        };
+
+        let batches = self
+            .connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?
+            .blocks_web3_dal()
+            .get_l1_batches_lifecycle(first..=last)
+            .await
+            .with_context(|| format!("get_l1_batches_lifecycle({first}..={last})"))?;
+
+        let mut updates = vec![];
+        for batch in batches {
+            let number = Some(batch.number);
+            for (i, stage) in L1BatchStage::ALL.into_iter().enumerate() {
+                if number > last_stages[i] && number <= stages[i] {
+                    updates.push(L1BatchStatusUpdate {
+                        stage,
+                        batch: batch.clone(),
+                    });
+                }
+            }
+        }
+        Ok(updates)
+    }
+
+    /// Notifies subscribers about transactions rejected by the state keeper. Rejections are not included
+    /// into miniblocks, so this notifier only polls Postgres.
+    async fn notify_rejected_txs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        // Rejections are paginated by their timestamp and then by the transaction hash, since multiple
+        // transactions can be rejected at the same time.
+        let mut last_time = chrono::Utc::now().naive_utc();
+        let mut last_hash = H256::zero();
+        let mut timer = interval(self.polling_interval);
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!(
+                    "Stop signal received, pubsub_rejected_tx_notifier is shutting down"
+                );
+                break;
+            }
+            timer.tick().await;
+
+            let db_latency =
+                PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::RejectedTxs].start();
+            let rejections = self.new_rejected_txs(last_time, last_hash).await?;
+            db_latency.observe();
+
+            if let Some(last_rejection) = rejections.last() {
+                last_time = last_rejection.rejected_at.naive_utc();
+                last_hash = last_rejection.tx_hash;
+                let notify_latency = PUB_SUB_METRICS.notify_subscribers_latency
+                    [&SubscriptionType::RejectedTxs]
+                    .start();
+                for sink in self.current_subscribers().await {
+                    for rejection in rejections.iter().cloned() {
+                        if sink
+                            .notify(Ok(PubSubResult::RejectedTransaction(rejection)))
+                            .is_err()
+                        {
+                            // Subscriber disconnected.
+                            break;
+                        }
+                        PUB_SUB_METRICS.notify[&SubscriptionType::RejectedTxs].inc();
+                    }
+                }
+                notify_latency.observe();
+            }
+            self.emit_event(PubSubEvent::NotifyIterationFinished(
+                SubscriptionType::RejectedTxs,
+            ));
+        }
+        Ok(())
+    }
+
+    async fn new_rejected_txs(
+        &self,
+        last_time: chrono::NaiveDateTime,
+        last_hash: H256,
+    ) -> anyhow::Result<Vec<TransactionRejection>> {
+        self.connection_pool
+            .access_storage_tagged("api")
+            .await
+            .context("access_storage_tagged")?
+            .transactions_web3_dal()
+            .get_rejected_transactions_after(last_time, last_hash, REJECTED_TXS_BATCH_LIMIT)
+            .await
+            .context("get_rejected_transactions_after()")
+    }
 }
 
 impl PubSubNotifier<(typed::Sink<PubSubResult>, PubSubFilter)> {
-    async fn notify_logs(self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+    async fn notify_logs(mut self, stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
         let mut last_block_number = self.sealed_miniblock_number().await?;
         let mut timer = interval(self.polling_interval);
         loop {
@@ -171,14 +477,40 @@ impl PubSubNotifier<(typed::Sink<PubSubResult>, PubSubFilter)> {
                 tracing::info!("Stop signal received, pubsub_logs_notifier is shutting down");
                 break;
             }
-            timer.tick().await;
 
-            let db_latency = PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
-            let new_logs = self.new_logs(last_block_number).await?;
-            db_latency.observe();
+            let iteration = self
+                .next_miniblock_iteration(&mut timer, last_block_number, SubscriptionType::Logs)
+                .await;
+            let new_logs = match iteration {
+                NotifierIteration::Poll => {
+                    let db_latency =
+                        PUB_SUB_METRICS.db_poll_latency[&SubscriptionType::Logs].start();
+                    // Miniblocks without logs are only accounted for if in-process notifications are used;
+                    // otherwise, the next pushed miniblock would be considered out of order.
+                    let sealed_miniblock_number = if self.miniblock_receiver.is_some() {
+                        Some(self.sealed_miniblock_number().await?)
+                    } else {
+                        None
+                    };
+                    let new_logs = self.new_logs(last_block_number).await?;
+                    db_latency.observe();
+                    if let Some(number) = sealed_miniblock_number {
+                        last_block_number = last_block_number.max(number);
+                    }
+                    new_logs
+                }
+                NotifierIteration::Pushed(miniblock) => {
+                    // Miniblocks without logs must still be accounted for.
+                    last_block_number = miniblock.number;
+                    miniblock.logs.clone()
+                }
+                NotifierIteration::Skip => vec![],
+            };
 
             if let Some(last_log) = new_logs.last() {
-                last_block_number = MiniblockNumber(last_log.block_number.unwrap().as_u32());
+                let last_log_block_number =
+                    MiniblockNumber(last_log.block_number.unwrap().as_u32());
+                last_block_number = last_block_number.max(last_log_block_number);
                 let notify_latency =
                     PUB_SUB_METRICS.notify_subscribers_latency[&SubscriptionType::Logs].start();
 
@@ -220,6 +552,8 @@ pub(super) struct EthSubscribe {
     active_block_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
     active_tx_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
     active_log_subs: SubscriptionMap<(typed::Sink<PubSubResult>, PubSubFilter)>,
+    active_l1_batch_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
+    active_rejected_tx_subs: SubscriptionMap<typed::Sink<PubSubResult>>,
     events_sender: Option<mpsc::UnboundedSender<PubSubEvent>>,
 }
 
@@ -230,6 +564,8 @@ impl EthSubscribe {
             active_block_subs: SubscriptionMap::default(),
             active_tx_subs: SubscriptionMap::default(),
             active_log_subs: SubscriptionMap::default(),
+            active_l1_batch_subs: SubscriptionMap::default(),
+            active_rejected_tx_subs: SubscriptionMap::default(),
             events_sender: None,
         }
     }
@@ -306,6 +642,22 @@ impl EthSubscribe {
                     }
                 }
             }
+            "l1Batches" => {
+                let mut l1_batch_subs = self.active_l1_batch_subs.write().await;
+                let Ok((sink, id)) = Self::assign_id(subscriber) else {
+                    return;
+                };
+                l1_batch_subs.insert(id, sink);
+                Some(SubscriptionType::L1Batches)
+            }
+            "rejectedTransactions" => {
+                let mut rejected_tx_subs = self.active_rejected_tx_subs.write().await;
+                let Ok((sink, id)) = Self::assign_id(subscriber) else {
+                    return;
+                };
+                rejected_tx_subs.insert(id, sink);
+                Some(SubscriptionType::RejectedTxs)
+            }
             "syncing" => {
                 let Ok((sink, _id)) = Self::assign_id(subscriber) else {
                     return;
@@ -335,6 +687,22 @@ impl EthSubscribe {
             Some(SubscriptionType::Txs)
         } else if self.active_log_subs.write().await.remove(&id).is_some() {
             Some(SubscriptionType::Logs)
+        } else if self
+            .active_l1_batch_subs
+            .write()
+            .await
+            .remove(&id)
+            .is_some()
+        {
+            Some(SubscriptionType::L1Batches)
+        } else if self
+            .active_rejected_tx_subs
+            .write()
+            .await
+            .remove(&id)
+            .is_some()
+        {
+            Some(SubscriptionType::RejectedTxs)
         } else {
             None
         };
@@ -350,18 +718,21 @@ impl EthSubscribe {
         }
     }
 
-    /// Spawns notifier tasks. This should be called once per instance.
+    /// Spawns notifier tasks. This should be called once per instance. If `miniblock_notifications`
+    /// are provided, notifiers will use them in addition to polling Postgres.
     pub fn spawn_notifiers(
         &self,
         connection_pool: ConnectionPool,
         polling_interval: Duration,
+        miniblock_notifications: Option<&MiniblockNotifications>,
         stop_receiver: watch::Receiver<bool>,
     ) -> Vec<JoinHandle<anyhow::Result<()>>> {
-        let mut notifier_tasks = Vec::with_capacity(3);
+        let mut notifier_tasks = Vec::with_capacity(5);
         let notifier = PubSubNotifier {
             subscribers: self.active_block_subs.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            miniblock_receiver: miniblock_notifications.map(MiniblockNotifications::subscribe),
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_blocks(stop_receiver.clone()));
@@ -371,6 +742,7 @@ impl EthSubscribe {
             subscribers: self.active_tx_subs.clone(),
             connection_pool: connection_pool.clone(),
             polling_interval,
+            miniblock_receiver: miniblock_notifications.map(MiniblockNotifications::subscribe),
             events_sender: self.events_sender.clone(),
         };
         let notifier_task = tokio::spawn(notifier.notify_txs(stop_receiver.clone()));
@@ -378,11 +750,32 @@ impl EthSubscribe {
 
         let notifier = PubSubNotifier {
             subscribers: self.active_log_subs.clone(),
+            connection_pool: connection_pool.clone(),
+            polling_interval,
+            miniblock_receiver: miniblock_notifications.map(MiniblockNotifications::subscribe),
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            subscribers: self.active_l1_batch_subs.clone(),
+            connection_pool: connection_pool.clone(),
+            polling_interval,
+            miniblock_receiver: None,
+            events_sender: self.events_sender.clone(),
+        };
+        let notifier_task = tokio::spawn(notifier.notify_l1_batches(stop_receiver.clone()));
+        notifier_tasks.push(notifier_task);
+
+        let notifier = PubSubNotifier {
+            subscribers: self.active_rejected_tx_subs.clone(),
             connection_pool,
             polling_interval,
+            miniblock_receiver: None,
             events_sender: self.events_sender.clone(),
         };
-        let notifier_task = tokio::spawn(notifier.notify_logs(stop_receiver));
+        let notifier_task = tokio::spawn(notifier.notify_rejected_txs(stop_receiver));
 
         notifier_tasks.push(notifier_task);
         notifier_tasks
@@ -0,0 +1,445 @@
+//! Per-client rate limiting for Web3 API servers.
+
+use std::{
+    collections::{HashMap, HashSet},
+    future::Future,
+    net::IpAddr,
+    num::NonZeroU32,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use governor::{clock::DefaultClock, state::keyed::DefaultKeyedStateStore, Quota, RateLimiter};
+use jsonrpc_http_server::hyper::{header::HeaderName, HeaderMap};
+pub use micro_config::configs::api::ClientRateLimitKey;
+use micro_config::configs::api::Web3JsonRpcConfig;
+use vise::{Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Metrics};
+
+use super::metrics::ApiTransportLabel;
+
+/// JSON-RPC error code returned to rate-limited clients ("limit exceeded" as per EIP-1474).
+pub(crate) const RATE_LIMITED_ERROR_CODE: i64 = -32_005;
+pub(crate) const RATE_LIMITED_ERROR_MESSAGE: &str = "Rate limit exceeded";
+
+/// Interval between purging inactive clients from the rate limiter state.
+const RETAIN_INTERVAL: Duration = Duration::from_secs(60);
+
+tokio::task_local! {
+    /// IP address of the client whose request is being handled, if known.
+    static CLIENT_IP: Option<IpAddr>;
+}
+
+/// Runs the future with the client IP address available to RPC method handlers via [`current_client_ip()`].
+pub(crate) async fn with_client_ip<F: Future>(client_ip: Option<IpAddr>, future: F) -> F::Output {
+    CLIENT_IP.scope(client_ip, future).await
+}
+
+/// Returns the IP address of the client whose request is being handled. Returns `None` if the address is unknown
+/// (e.g., if the server is not configured to trust any reverse proxies).
+pub(crate) fn current_client_ip() -> Option<IpAddr> {
+    CLIENT_IP.try_with(|&client_ip| client_ip).ok().flatten()
+}
+
+/// Determines client IP addresses from the headers set by trusted reverse proxies.
+///
+/// The API servers don't expose socket addresses of their peers, so forwarding headers are the only source
+/// of client addresses. They are only honored if trusted proxies are configured; in this case, the servers
+/// must not be reachable bypassing the proxies.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ClientIpResolver {
+    trusted_proxies: Arc<HashSet<IpAddr>>,
+}
+
+impl ClientIpResolver {
+    pub fn new(trusted_proxies: impl IntoIterator<Item = IpAddr>) -> Self {
+        Self {
+            trusted_proxies: Arc::new(trusted_proxies.into_iter().collect()),
+        }
+    }
+
+    /// Determines the client IP address based on HTTP request headers.
+    pub fn client_ip(&self, headers: &HeaderMap) -> Option<IpAddr> {
+        self.resolve(|name| headers.get(name)?.to_str().ok())
+    }
+
+    /// Determines the client IP address using the provided header getter.
+    pub fn resolve<'a>(&self, header: impl Fn(&str) -> Option<&'a str>) -> Option<IpAddr> {
+        if self.trusted_proxies.is_empty() {
+            return None;
+        }
+
+        // Each proxy appends the address of its peer to `X-Forwarded-For`, so entries to the left
+        // of the rightmost untrusted entry may be forged by the client.
+        if let Some(forwarded_for) = header("x-forwarded-for") {
+            let mut leftmost_ip = None;
+            for entry in forwarded_for.rsplit(',') {
+                let ip: IpAddr = entry.trim().parse().ok()?;
+                if !self.trusted_proxies.contains(&ip) {
+                    return Some(ip);
+                }
+                leftmost_ip = Some(ip);
+            }
+            return leftmost_ip;
+        }
+        header("x-real-ip")?.trim().parse().ok()
+    }
+}
+
+/// Default weights for methods that are more expensive than a typical DB lookup, either because
+/// they execute the VM or because they can scan large block ranges. Other methods have weight 1.
+const DEFAULT_METHOD_WEIGHTS: &[(&str, u32)] = &[
+    ("eth_call", 10),
+    ("eth_estimateGas", 20),
+    ("eth_sendRawTransaction", 10),
+    ("eth_getLogs", 10),
+    ("eth_getFilterLogs", 10),
+    ("eth_getBlockReceipts", 5),
+    ("eth_feeHistory", 5),
+    ("zks_estimateFee", 20),
+    ("zks_estimateGasL1ToL2", 20),
+    ("zks_getProof", 5),
+    ("zks_getProofs", 10),
+    ("zks_getLogsWithCursor", 10),
+    ("zks_sendRawTransactionWithDetailedOutput", 10),
+    ("zks_simulateBundle", 40),
+    ("debug_traceCall", 20),
+    ("debug_traceTransaction", 5),
+    ("debug_traceBlockByNumber", 20),
+    ("debug_traceBlockByHash", 20),
+    ("trace_call", 20),
+    ("trace_block", 10),
+    ("trace_filter", 20),
+    ("prover_getDepositInfo", 30),
+    ("txpool_content", 10),
+    ("txpool_inspect", 5),
+    ("txpool_status", 5),
+    ("txpool_contentFrom", 2),
+];
+
+/// Weights of Web3 methods used in per-client rate limiting.
+#[derive(Debug, Clone)]
+pub struct MethodWeights(HashMap<String, NonZeroU32>);
+
+impl Default for MethodWeights {
+    fn default() -> Self {
+        let weights = DEFAULT_METHOD_WEIGHTS
+            .iter()
+            .map(|&(method, weight)| (method.to_owned(), NonZeroU32::new(weight).unwrap()));
+        Self(weights.collect())
+    }
+}
+
+impl MethodWeights {
+    /// Creates weights by applying the provided overrides in the `method=weight` format to the default weights.
+    pub fn with_overrides(overrides: &[String]) -> anyhow::Result<Self> {
+        let mut this = Self::default();
+        for method_override in overrides {
+            let (method, weight) = method_override.split_once('=').with_context(|| {
+                format!("method weight `{method_override}` is not in the `method=weight` format")
+            })?;
+            let weight: u32 = weight
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid weight for method `{method}`"))?;
+            let weight = NonZeroU32::new(weight)
+                .with_context(|| format!("weight for method `{method}` must be positive"))?;
+            this.0.insert(method.trim().to_owned(), weight);
+        }
+        Ok(this)
+    }
+
+    fn get(&self, method: &str) -> NonZeroU32 {
+        self.0.get(method).copied().unwrap_or(NonZeroU32::MIN)
+    }
+}
+
+/// Configuration of per-client rate limits for an API server.
+#[derive(Debug, Clone)]
+pub struct ClientRateLimits {
+    /// Sustained number of request weight units per second for a single client.
+    pub requests_per_second: NonZeroU32,
+    /// Maximum number of request weight units a single client may spend in a burst.
+    pub burst: NonZeroU32,
+    /// Source of client identity.
+    pub key: ClientRateLimitKey,
+    /// Name of the HTTP header containing the client API key.
+    pub api_key_header: String,
+    pub method_weights: MethodWeights,
+}
+
+impl ClientRateLimits {
+    /// Creates limits based on the main node API config. Returns `None` if per-client rate limiting is disabled.
+    pub fn from_config(config: &Web3JsonRpcConfig) -> anyhow::Result<Option<Self>> {
+        let Some(requests_per_second) = config.client_requests_per_second_limit else {
+            return Ok(None);
+        };
+        let requests_per_second = NonZeroU32::new(requests_per_second)
+            .context("`client_requests_per_second_limit` must be positive")?;
+        let burst = config
+            .client_requests_burst_limit()
+            .and_then(NonZeroU32::new)
+            .context("`client_requests_burst_limit` must be positive")?;
+        Ok(Some(Self {
+            requests_per_second,
+            burst,
+            key: config.client_rate_limit_key(),
+            api_key_header: config.client_api_key_header().to_owned(),
+            method_weights: MethodWeights::with_overrides(config.method_weights())?,
+        }))
+    }
+}
+
+/// Client identity used as the rate limiter key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) enum ClientKey {
+    Ip(IpAddr),
+    ApiKey(String),
+    /// WebSocket connection. Used for the `jsonrpc` WebSocket server, which doesn't provide access
+    /// to HTTP headers of the connection.
+    Connection(u64),
+    /// Catch-all key for clients that cannot be identified.
+    Anonymous,
+}
+
+impl ClientKey {
+    fn kind(&self) -> ClientKind {
+        match self {
+            Self::Ip(_) => ClientKind::Ip,
+            Self::ApiKey(_) => ClientKind::ApiKey,
+            Self::Connection(_) => ClientKind::Connection,
+            Self::Anonymous => ClientKind::Anonymous,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue)]
+#[metrics(rename_all = "snake_case")]
+enum ClientKind {
+    Ip,
+    ApiKey,
+    Connection,
+    Anonymous,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelSet)]
+struct ClientLabels {
+    scheme: ApiTransportLabel,
+    client: ClientKind,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "api_web3_rate_limiter")]
+struct RateLimiterMetrics {
+    /// Number of requests (batch requests are counted as a single request) checked by the limiter.
+    requests: Family<ClientLabels, Counter>,
+    /// Number of requests rejected by the limiter.
+    rejected: Family<ClientLabels, Counter>,
+    /// Total weight of requests admitted by the limiter.
+    admitted_weight: Family<ClientLabels, Counter>,
+    /// Number of clients tracked by the limiter.
+    tracked_clients: Family<ApiTransportLabel, Gauge<usize>>,
+}
+
+#[vise::register]
+static METRICS: vise::Global<RateLimiterMetrics> = vise::Global::new();
+
+/// Error returned by [`ClientRateLimiter`] if a request is rate-limited.
+#[derive(Debug)]
+pub(crate) struct RateLimited;
+
+/// Token bucket rate limiter keyed by the client identity. The cost of a request is the total weight
+/// of the called methods.
+#[derive(Debug, Clone)]
+pub(crate) struct ClientRateLimiter {
+    inner: Arc<RateLimiter<ClientKey, DefaultKeyedStateStore<ClientKey>, DefaultClock>>,
+    method_weights: Arc<MethodWeights>,
+    key: ClientRateLimitKey,
+    api_key_header: Option<HeaderName>,
+    transport: ApiTransportLabel,
+}
+
+impl ClientRateLimiter {
+    pub fn new(limits: ClientRateLimits, transport: ApiTransportLabel) -> Self {
+        let quota = Quota::per_second(limits.requests_per_second).allow_burst(limits.burst);
+        let api_key_header = match HeaderName::try_from(limits.api_key_header.as_str()) {
+            Ok(header) => Some(header),
+            Err(err) => {
+                tracing::warn!(
+                    "Invalid API key header `{}`: {err}; API keys will be ignored",
+                    limits.api_key_header
+                );
+                None
+            }
+        };
+        Self {
+            inner: Arc::new(RateLimiter::keyed(quota)),
+            method_weights: Arc::new(limits.method_weights),
+            key: limits.key,
+            api_key_header,
+            transport,
+        }
+    }
+
+    /// Identifies the client based on its IP address and HTTP headers of its request.
+    pub fn client_key(&self, client_ip: Option<IpAddr>, headers: &HeaderMap) -> ClientKey {
+        let key = match self.key {
+            ClientRateLimitKey::Ip => client_ip.map(ClientKey::Ip),
+            ClientRateLimitKey::ApiKey => self
+                .api_key_header
+                .as_ref()
+                .and_then(|header| headers.get(header)?.to_str().ok())
+                .map(|api_key| ClientKey::ApiKey(api_key.to_owned())),
+        };
+        key.unwrap_or(ClientKey::Anonymous)
+    }
+
+    /// Checks whether a request calling the specified methods is admitted for the client.
+    pub fn check<'a>(
+        &self,
+        key: &ClientKey,
+        methods: impl IntoIterator<Item = &'a str>,
+    ) -> Result<(), RateLimited> {
+        let weight = methods
+            .into_iter()
+            .fold(0_u32, |acc, method| {
+                acc.saturating_add(self.method_weights.get(method).get())
+            })
+            .max(1);
+        let weight = NonZeroU32::new(weight).unwrap();
+
+        let labels = ClientLabels {
+            scheme: self.transport,
+            client: key.kind(),
+        };
+        METRICS.requests[&labels].inc();
+        if self.inner.check_key_n(key, weight).is_err() {
+            METRICS.rejected[&labels].inc();
+            return Err(RateLimited);
+        }
+        METRICS.admitted_weight[&labels].inc_by(weight.get().into());
+        Ok(())
+    }
+
+    /// Periodically purges inactive clients from the limiter state. The returned future never resolves;
+    /// it should be spawned on the server runtime.
+    pub async fn run_retention(self) {
+        let mut interval = tokio::time::interval(RETAIN_INTERVAL);
+        loop {
+            interval.tick().await;
+            self.inner.retain_recent();
+            self.inner.shrink_to_fit();
+            METRICS.tracked_clients[&self.transport].set(self.inner.len());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpc_http_server::hyper::header::HeaderValue;
+
+    use super::*;
+
+    fn limiter(key: ClientRateLimitKey) -> ClientRateLimiter {
+        let limits = ClientRateLimits {
+            requests_per_second: NonZeroU32::new(1).unwrap(),
+            burst: NonZeroU32::new(30).unwrap(),
+            key,
+            api_key_header: "x-api-key".to_owned(),
+            method_weights: MethodWeights::with_overrides(&["eth_getLogs = 15".to_owned()])
+                .unwrap(),
+        };
+        ClientRateLimiter::new(limits, ApiTransportLabel::Http)
+    }
+
+    #[test]
+    fn parsing_method_weights() {
+        let weights = MethodWeights::with_overrides(&["eth_call=3".to_owned()]).unwrap();
+        assert_eq!(weights.get("eth_call").get(), 3);
+        assert_eq!(weights.get("eth_estimateGas").get(), 20);
+        assert_eq!(weights.get("zks_simulateBundle").get(), 40);
+        assert_eq!(weights.get("eth_blockNumber").get(), 1);
+
+        for invalid_override in ["eth_call", "eth_call=0", "eth_call=-1"] {
+            MethodWeights::with_overrides(&[invalid_override.to_owned()]).unwrap_err();
+        }
+    }
+
+    #[test]
+    fn identifying_clients() {
+        let ip_limiter = limiter(ClientRateLimitKey::Ip);
+        let api_key_limiter = limiter(ClientRateLimitKey::ApiKey);
+        let mut headers = HeaderMap::new();
+        assert_eq!(ip_limiter.client_key(None, &headers), ClientKey::Anonymous);
+        assert_eq!(
+            api_key_limiter.client_key(None, &headers),
+            ClientKey::Anonymous
+        );
+
+        let client_ip = IpAddr::from([10, 0, 0, 1]);
+        assert_eq!(
+            ip_limiter.client_key(Some(client_ip), &headers),
+            ClientKey::Ip(client_ip)
+        );
+        headers.insert("x-api-key", HeaderValue::from_static("secret"));
+        assert_eq!(
+            api_key_limiter.client_key(Some(client_ip), &headers),
+            ClientKey::ApiKey("secret".to_owned())
+        );
+    }
+
+    #[test]
+    fn resolving_client_ips() {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-real-ip", HeaderValue::from_static("10.0.0.2"));
+        assert_eq!(ClientIpResolver::default().client_ip(&headers), None);
+
+        let proxy_ip = IpAddr::from([192, 168, 0, 1]);
+        let resolver = ClientIpResolver::new([proxy_ip]);
+        assert_eq!(resolver.client_ip(&HeaderMap::new()), None);
+        assert_eq!(resolver.client_ip(&headers), Some([10, 0, 0, 2].into()));
+
+        // The client may prepend arbitrary entries to `X-Forwarded-For`; they must be ignored.
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("1.2.3.4, 10.0.0.1, 192.168.0.1"),
+        );
+        assert_eq!(resolver.client_ip(&headers), Some([10, 0, 0, 1].into()));
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("192.168.0.1, 192.168.0.1"),
+        );
+        assert_eq!(resolver.client_ip(&headers), Some(proxy_ip));
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("10.0.0.1, garbage, 192.168.0.1"),
+        );
+        assert_eq!(resolver.client_ip(&headers), None);
+    }
+
+    #[test]
+    fn limiting_requests_by_weight() {
+        let limiter = limiter(ClientRateLimitKey::Ip);
+        let client = ClientKey::Ip([10, 0, 0, 1].into());
+        let other_client = ClientKey::Ip([10, 0, 0, 2].into());
+
+        // 2 * 10 (`eth_call`) + 1 (`eth_blockNumber`) = 21 weight units out of 30
+        limiter
+            .check(&client, ["eth_call", "eth_call", "eth_blockNumber"])
+            .unwrap();
+        limiter.check(&client, ["eth_getLogs"]).unwrap_err();
+        limiter.check(&other_client, ["eth_getLogs"]).unwrap();
+        for _ in 0..9 {
+            limiter.check(&client, ["eth_chainId"]).unwrap();
+        }
+        limiter.check(&client, ["eth_chainId"]).unwrap_err();
+    }
+    #[tokio::test]
+    async fn client_ip_is_scoped_to_future() {
+        assert_eq!(current_client_ip(), None);
+        let client_ip = Some([10, 0, 0, 1].into());
+        let scoped_ip = with_client_ip(client_ip, async { current_client_ip() }).await;
+        assert_eq!(scoped_ip, client_ip);
+        assert_eq!(current_client_ip(), None);
+    }
+}
@@ -15,17 +15,26 @@ use micro_types::{
     MiniblockNumber, H256, U256, U64,
 };
 use micro_web3_decl::{error::Web3Error, types::Filter};
-use tokio::sync::Mutex;
+use tokio::sync::{watch, Mutex};
 use vise::GaugeGuard;
 
-use super::metrics::{FilterType, FILTER_METRICS};
+use super::{
+    metrics::{FilterType, API_METRICS, FILTER_METRICS},
+    rate_limiter,
+};
 use crate::{
     api_server::{
         execution_sandbox::BlockArgs,
         tree::TreeApiHttpClient,
         tx_sender::TxSender,
-        web3::{backend_jsonrpc::error::internal_error, resolve_block, TypedFilter},
+        web3::{
+            backend_jsonrpc::error::internal_error, resolve_block, statistics::StatisticsCache,
+            TypedFilter,
+        },
     },
+    eth_watch::PriorityOpsReportReceiver,
+    l1_gas_price::L1GasPriceProvider,
+    state_keeper::{MempoolGuard, MiniblockNotifications},
     sync_layer::SyncState,
 };
 
@@ -78,9 +87,12 @@ impl InternalApiConfig {
 ///
 /// The information may be temporarily outdated and thus should only be used where this is OK
 /// (e.g., for metrics reporting). The value is updated by [`Self::diff()`] and [`Self::diff_with_block_args()`]
-/// and on an interval specified when creating an instance.
+/// and on an interval specified when creating an instance. Updates can be awaited via [`Self::subscribe()`].
 #[derive(Debug, Clone)]
-pub(crate) struct SealedMiniblockNumber(Arc<AtomicU32>);
+pub(crate) struct SealedMiniblockNumber {
+    number: Arc<AtomicU32>,
+    updates_sender: Arc<watch::Sender<MiniblockNumber>>,
+}
 
 impl SealedMiniblockNumber {
     /// Creates a handle to the last sealed miniblock number together with a task that will update
@@ -89,11 +101,14 @@ impl SealedMiniblockNumber {
         connection_pool: ConnectionPool,
         update_interval: Duration,
     ) -> (Self, impl Future<Output = ()> + Send) {
-        let this = Self(Arc::default());
+        let this = Self {
+            number: Arc::default(),
+            updates_sender: Arc::new(watch::channel(MiniblockNumber(0)).0),
+        };
         let number_updater = this.clone();
         let update_task = async move {
             loop {
-                if Arc::strong_count(&number_updater.0) == 1 {
+                if Arc::strong_count(&number_updater.number) == 1 {
                     // The `sealed_miniblock_number` was dropped; there's no sense continuing updates.
                     tracing::debug!("Stopping latest sealed miniblock updates");
                     break;
@@ -127,11 +142,26 @@ impl SealedMiniblockNumber {
     /// Returns the last sealed miniblock number after the update.
     fn update(&self, maybe_newer_miniblock_number: MiniblockNumber) -> MiniblockNumber {
         let prev_value = self
-            .0
+            .number
             .fetch_max(maybe_newer_miniblock_number.0, Ordering::Relaxed);
+        if maybe_newer_miniblock_number.0 > prev_value {
+            self.updates_sender.send_if_modified(|number| {
+                let is_newer = maybe_newer_miniblock_number > *number;
+                if is_newer {
+                    *number = maybe_newer_miniblock_number;
+                }
+                is_newer
+            });
+        }
         MiniblockNumber(prev_value).max(maybe_newer_miniblock_number)
     }
 
+    /// Subscribes to updates of the last sealed miniblock number. All subscribers share
+    /// the single update task, so waiting on the returned receiver does not put additional load on Postgres.
+    pub fn subscribe(&self) -> watch::Receiver<MiniblockNumber> {
+        self.updates_sender.subscribe()
+    }
+
     pub fn diff(&self, miniblock_number: MiniblockNumber) -> u32 {
         let sealed_miniblock_number = self.update(miniblock_number);
         sealed_miniblock_number.0.saturating_sub(miniblock_number.0)
@@ -161,6 +191,10 @@ pub struct RpcState<E> {
     pub sync_state: Option<SyncState>,
     pub(super) api_config: InternalApiConfig,
     pub(super) last_sealed_miniblock: SealedMiniblockNumber,
+    pub(super) statistics: StatisticsCache,
+    pub(super) miniblock_notifications: Option<MiniblockNotifications>,
+    pub(super) mempool: Option<MempoolGuard>,
+    pub(super) priority_ops_report: Option<PriorityOpsReportReceiver>,
 }
 
 // Custom implementation is required due to generic param:
@@ -176,6 +210,10 @@ impl<E> Clone for RpcState<E> {
             sync_state: self.sync_state.clone(),
             api_config: self.api_config.clone(),
             last_sealed_miniblock: self.last_sealed_miniblock.clone(),
+            statistics: self.statistics.clone(),
+            miniblock_notifications: self.miniblock_notifications.clone(),
+            mempool: self.mempool.clone(),
+            priority_ops_report: self.priority_ops_report.clone(),
         }
     }
 }
@@ -313,6 +351,25 @@ impl<E> RpcState<E> {
     }
 }
 
+impl<E: L1GasPriceProvider> RpcState<E> {
+    /// Parses a raw transaction and submits it via [`TxSender`], returning the transaction hash.
+    pub(crate) async fn submit_raw_transaction(
+        &self,
+        tx_bytes: Vec<u8>,
+    ) -> Result<H256, Web3Error> {
+        let (mut tx, hash) = self.parse_transaction_bytes(&tx_bytes)?;
+        tx.set_input(tx_bytes, hash);
+
+        let client_ip = rate_limiter::current_client_ip();
+        let submit_result = self.tx_sender.submit_tx(tx, client_ip).await;
+        submit_result.map(|_| hash).map_err(|err| {
+            tracing::debug!("Send raw transaction error: {err}");
+            API_METRICS.submit_tx_error[&err.prom_error_code()].inc();
+            Web3Error::SubmitTransactionError(err.to_string(), err.data())
+        })
+    }
+}
+
 /// Contains mapping from index to `Filter`x with optional location.
 #[derive(Debug)]
 pub(crate) struct Filters(LruCache<U256, InstalledFilter>);
@@ -0,0 +1,130 @@
+//! Background aggregation of network statistics returned by `zks_getStatisticsInfo`.
+
+use std::{
+    future::Future,
+    sync::{Arc, RwLock},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::Context as _;
+use micro_dal::ConnectionPool;
+use micro_types::{
+    statistics_info::{StatiticsInfo, ThroughputWindow},
+    U64,
+};
+
+/// Windows over which transaction throughput is measured.
+const THROUGHPUT_WINDOWS: [Duration; 3] = [
+    Duration::from_secs(60),
+    Duration::from_secs(600),
+    Duration::from_secs(3_600),
+];
+/// Window over which prover assignment statistics are aggregated.
+const ASSIGNMENTS_WINDOW: Duration = Duration::from_secs(86_400);
+
+/// Cached network statistics periodically updated by a background task, so that serving
+/// `zks_getStatisticsInfo` doesn't require accessing Postgres.
+#[derive(Debug, Clone)]
+pub struct StatisticsCache(Arc<RwLock<Arc<StatiticsInfo>>>);
+
+impl StatisticsCache {
+    /// Statistics are informational, so they are allowed to lag significantly behind the chain state.
+    pub const DEFAULT_UPDATE_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Creates a cache together with a task that will update it on a schedule. Until the first update
+    /// completes, the cache contains default statistics with zero `updated_at` timestamp.
+    pub fn new(
+        connection_pool: ConnectionPool,
+        update_interval: Duration,
+    ) -> (Self, impl Future<Output = ()> + Send) {
+        let this = Self(Arc::default());
+        let cache_updater = this.clone();
+        let update_task = async move {
+            loop {
+                if Arc::strong_count(&cache_updater.0) == 1 {
+                    // The cache was dropped; there's no sense continuing updates.
+                    tracing::debug!("Stopping statistics updates");
+                    break;
+                }
+
+                match aggregate_statistics(&connection_pool).await {
+                    Ok(stats) => {
+                        *cache_updater.0.write().expect("statistics lock poisoned") =
+                            Arc::new(stats);
+                    }
+                    Err(err) => tracing::warn!("Failed aggregating network statistics: {err:#}"),
+                }
+                tokio::time::sleep(update_interval).await;
+            }
+        };
+
+        (this, update_task)
+    }
+
+    pub fn get(&self) -> Arc<StatiticsInfo> {
+        self.0.read().expect("statistics lock poisoned").clone()
+    }
+}
+
+async fn aggregate_statistics(pool: &ConnectionPool) -> anyhow::Result<StatiticsInfo> {
+    let mut storage = pool.access_storage_tagged("api").await?;
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .context("invalid system time")?
+        .as_secs();
+
+    let sealed_miniblock = storage
+        .blocks_web3_dal()
+        .get_sealed_miniblock_number()
+        .await
+        .context("get_sealed_miniblock_number()")?;
+    let verified_miniblock = storage
+        .blocks_web3_dal()
+        .get_sealed_prove_miniblock_number()
+        .await
+        .context("get_sealed_prove_miniblock_number()")?;
+    let tx_mempool = storage
+        .transactions_dal()
+        .get_tx_memory()
+        .await
+        .context("get_tx_memory()")?;
+    let latest_proof_time = storage
+        .eth_sender_dal()
+        .get_latest_proof_time()
+        .await
+        .context("get_latest_proof_time()")?;
+
+    let mut throughput = Vec::with_capacity(THROUGHPUT_WINDOWS.len());
+    for window in THROUGHPUT_WINDOWS {
+        let (tx_count, gas_used) = storage
+            .blocks_web3_dal()
+            .get_tx_throughput_since(now.saturating_sub(window.as_secs()))
+            .await
+            .context("get_tx_throughput_since()")?;
+        throughput.push(ThroughputWindow::new(window, tx_count, gas_used));
+    }
+
+    let assignments = storage
+        .assignments_dal()
+        .get_assignment_statistics(ASSIGNMENTS_WINDOW)
+        .await
+        .context("get_assignment_statistics()")?;
+
+    let last_verified_block = U64::from(verified_miniblock.0);
+    let sealed_miniblock = U64::from(sealed_miniblock.0);
+    Ok(StatiticsInfo {
+        tx_mempool,
+        last_verified_block,
+        next_block_id: sealed_miniblock + 1,
+        un_verified_blocks: sealed_miniblock.saturating_sub(last_verified_block),
+        latest_proof_time,
+        throughput,
+        avg_batch_proving_time: assignments
+            .avg_proving_time
+            .map(|duration| duration.as_secs()),
+        active_provers: assignments.active_provers,
+        total_assignments: assignments.total_assignments,
+        penalized_assignments: assignments.penalized_assignments,
+        updated_at: now,
+    })
+}
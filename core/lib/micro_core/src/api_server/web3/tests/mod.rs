@@ -1,10 +1,14 @@
-use std::{sync::Arc, time::Instant};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Instant,
+};
 
 use assert_matches::assert_matches;
 use async_trait::async_trait;
 use micro_config::configs::{
     api::Web3JsonRpcConfig,
-    chain::{NetworkConfig, StateKeeperConfig},
+    chain::{MempoolConfig, NetworkConfig, StateKeeperConfig},
     ContractsConfig,
 };
 use micro_contracts::BaseSystemContractsHashes;
@@ -12,12 +16,38 @@ use micro_dal::{transactions_dal::L2TxSubmissionResult, ConnectionPool};
 use micro_health_check::CheckHealth;
 use micro_state::PostgresStorageCaches;
 use micro_types::{
-    block::MiniblockHeader, fee::TransactionExecutionMetrics, tx::IncludedTxLocation, Address,
-    L1BatchNumber, ProtocolVersionId, VmEvent, H256, U64,
+    api::{
+        bundle::{SimulateBundleOptions, SimulatedCallStatus},
+        protocol_upgrades::ProtocolUpgradeStage,
+        txpool::TxPoolBlockReason,
+        DebugTrace, StructLoggerConfig, SupportedTracers, TracerConfig,
+    },
+    block::MiniblockHeader,
+    deny_list::DenyListAction,
+    fee::{Fee, TransactionExecutionMetrics},
+    l2::{
+        prover::{AssignmentStatus, DepositStatus, Paging},
+        L2Tx,
+    },
+    protocol_version::ProtocolVersion,
+    transaction_request::{CallRequest, PaymasterParams},
+    tx::{
+        tx_execution_info::TxExecutionStatus, ExecutionMetrics, IncludedTxLocation,
+        TransactionExecutionResult,
+    },
+    utils::storage_key_for_eth_balance,
+    vm_trace::PrestateTrace,
+    web3::types::Bytes,
+    Address, L1BatchNumber, Nonce, PackedEthSignature, PriorityOpId, ProtocolVersionId, StorageLog,
+    Transaction, VmEvent, H256, L2_ETH_TOKEN_ADDRESS, MAX_GAS_PER_PUBDATA_BYTE, U256, U64,
 };
+use micro_utils::u256_to_h256;
 use micro_web3_decl::{
     jsonrpsee::{core::Error as RpcError, http_client::HttpClient, types::error::ErrorCode},
-    namespaces::{EthNamespaceClient, ZksNamespaceClient},
+    namespaces::{
+        AdminNamespaceClient, DebugNamespaceClient, EthNamespaceClient, ProverNamespaceClient,
+        TxPoolNamespaceClient, ZksNamespaceClient,
+    },
     types::FilterChanges,
 };
 use tokio::sync::watch;
@@ -25,8 +55,9 @@ use tokio::sync::watch;
 use super::{metrics::ApiTransportLabel, *};
 use crate::{
     api_server::tx_sender::TxSenderConfig,
+    deny_list::DenyList,
     genesis::{ensure_genesis_state, GenesisParams},
-    state_keeper::tests::create_l2_transaction,
+    state_keeper::{tests::create_l2_transaction, MempoolGuard},
 };
 
 mod ws;
@@ -81,31 +112,54 @@ pub(crate) async fn spawn_http_server(
     pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
 ) -> ApiServerHandles {
-    spawn_server(ApiTransportLabel::Http, network_config, pool, stop_receiver)
-        .await
-        .0
+    spawn_server(
+        ApiTransportLabel::Http,
+        network_config,
+        pool,
+        None,
+        None,
+        stop_receiver,
+    )
+    .await
+    .0
 }
 
 async fn spawn_ws_server(
     network_config: &NetworkConfig,
     pool: ConnectionPool,
+    miniblock_notifications: Option<MiniblockNotifications>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
-    spawn_server(ApiTransportLabel::Ws, network_config, pool, stop_receiver).await
+    spawn_server(
+        ApiTransportLabel::Ws,
+        network_config,
+        pool,
+        miniblock_notifications,
+        None,
+        stop_receiver,
+    )
+    .await
 }
 
 async fn spawn_server(
     transport: ApiTransportLabel,
     network_config: &NetworkConfig,
     pool: ConnectionPool,
+    miniblock_notifications: Option<MiniblockNotifications>,
+    mempool: Option<MempoolGuard>,
     stop_receiver: watch::Receiver<bool>,
 ) -> (ApiServerHandles, mpsc::UnboundedReceiver<PubSubEvent>) {
     let contracts_config = ContractsConfig::for_tests();
     let web3_config = Web3JsonRpcConfig::for_tests();
     let state_keeper_config = StateKeeperConfig::for_tests();
     let api_config = InternalApiConfig::new(network_config, &web3_config, &contracts_config);
-    let tx_sender_config =
-        TxSenderConfig::new(&state_keeper_config, &web3_config, api_config.l2_chain_id);
+    let mempool_config = MempoolConfig::for_tests();
+    let tx_sender_config = TxSenderConfig::new(
+        &state_keeper_config,
+        &web3_config,
+        &mempool_config,
+        api_config.l2_chain_id,
+    );
 
     let storage_caches = PostgresStorageCaches::new(1, 1);
     let gas_adjuster = Arc::new(MockL1GasPriceProvider(1));
@@ -117,22 +171,34 @@ async fn spawn_server(
         pool.clone(),
         gas_adjuster,
         storage_caches,
+        Some(DenyList::default()),
     )
     .await;
     let (pub_sub_events_sender, pub_sub_events_receiver) = mpsc::unbounded_channel();
 
-    let server_builder = match transport {
+    let mut server_builder = match transport {
         ApiTransportLabel::Http => ApiBuilder::jsonrpsee_backend(api_config, pool).http(0),
         ApiTransportLabel::Ws => ApiBuilder::jsonrpc_backend(api_config, pool)
             .ws(0)
             .with_polling_interval(POLL_INTERVAL)
             .with_subscriptions_limit(100),
     };
+    if let Some(notifications) = miniblock_notifications {
+        server_builder = server_builder.with_miniblock_notifications(notifications);
+    }
+    let mut namespaces = Namespace::DEFAULT.to_vec();
+    namespaces.push(Namespace::Admin);
+    namespaces.push(Namespace::Prover);
+    namespaces.push(Namespace::Debug);
+    if let Some(mempool) = mempool {
+        server_builder = server_builder.with_mempool(mempool);
+        namespaces.push(Namespace::TxPool);
+    }
     let server_handles = server_builder
         .with_threads(1)
         .with_tx_sender(tx_sender, vm_barrier)
         .with_pub_sub_events(pub_sub_events_sender)
-        .enable_api_namespaces(Namespace::DEFAULT.to_vec())
+        .enable_api_namespaces(namespaces)
         .build(stop_receiver)
         .await
         .expect("Failed spawning JSON-RPC server");
@@ -494,3 +560,638 @@ impl HttpTest for LogFilterChangesWithBlockBoundaries {
 async fn log_filter_changes_with_block_boundaries() {
     test_http_server(LogFilterChangesWithBlockBoundaries).await;
 }
+
+#[derive(Debug)]
+struct LogsWithCursor;
+
+#[async_trait]
+impl HttpTest for LogsWithCursor {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        let (_, mut events) = store_events(&mut storage, 1, 0).await?;
+        let (_, new_events) = store_events(&mut storage, 2, 4).await?;
+        drop(storage);
+        events.extend(new_events);
+        let events: Vec<_> = events.iter().collect();
+
+        let filter = Filter {
+            from_block: Some(api::BlockNumber::Number(1.into())),
+            ..Filter::default()
+        };
+        let mut cursor = None;
+        let mut logs = vec![];
+        let mut page_count = 0;
+        loop {
+            let page = client
+                .get_logs_with_cursor(filter.clone(), cursor, Some(3))
+                .await?;
+            page_count += 1;
+            logs.extend(page.logs);
+            cursor = page.next_cursor;
+            if cursor.is_none() {
+                break;
+            }
+        }
+        assert_eq!(page_count, 3);
+        assert_logs_match(&logs, &events);
+
+        let filter = Filter {
+            from_block: Some(api::BlockNumber::Number(1.into())),
+            address: Some(Address::repeat_byte(23).into()),
+            ..Filter::default()
+        };
+        let page = client.get_logs_with_cursor(filter, None, None).await?;
+        assert_eq!(page.next_cursor, None);
+        let expected_events: Vec<_> = events
+            .into_iter()
+            .filter(|event| event.address == Address::repeat_byte(23))
+            .collect();
+        assert_logs_match(&page.logs, &expected_events);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn logs_with_cursor() {
+    test_http_server(LogsWithCursor).await;
+}
+
+#[derive(Debug)]
+struct StatisticsInfoBasics;
+
+#[async_trait]
+impl HttpTest for StatisticsInfoBasics {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        // Statistics are aggregated in background, so we need to wait for the first update.
+        let stats = tokio::time::timeout(TEST_TIMEOUT, async {
+            loop {
+                let stats = client.get_statistics_info().await?;
+                if stats.updated_at > 0 {
+                    return anyhow::Ok(stats);
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        })
+        .await
+        .context("timed out waiting for statistics")??;
+
+        assert_eq!(stats.next_block_id, U64::from(1));
+        assert_eq!(stats.tx_mempool, 0);
+        let windows: Vec<_> = stats
+            .throughput
+            .iter()
+            .map(|window| window.window)
+            .collect();
+        assert_eq!(windows, [60, 600, 3_600]);
+        assert!(stats.throughput.iter().all(|window| window.tx_count == 0));
+        assert_eq!(stats.active_provers, 0);
+        assert_eq!(stats.total_assignments, 0);
+        assert_eq!(stats.assignment_timeout_rate(), None);
+        assert_eq!(stats.avg_batch_proving_time, None);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn statistics_info_basics() {
+    test_http_server(StatisticsInfoBasics).await;
+}
+
+#[tokio::test]
+async fn txpool_namespace_basics() {
+    let pool = ConnectionPool::test_pool().await;
+    let network_config = NetworkConfig::for_tests();
+    let mut storage = pool.access_storage().await.unwrap();
+    if storage.blocks_dal().is_genesis_needed().await.unwrap() {
+        ensure_genesis_state(
+            &mut storage,
+            network_config.micro_network_id,
+            &GenesisParams::mock(),
+        )
+        .await
+        .unwrap();
+    }
+    drop(storage);
+
+    let account = Address::repeat_byte(0x23);
+    let transactions = [(0, 0), (1, u64::MAX), (3, u64::MAX)].map(|(nonce, fee_per_gas)| {
+        let mut tx = create_l2_transaction(fee_per_gas, u32::MAX);
+        tx.common_data.initiator_address = account;
+        tx.common_data.nonce = Nonce(nonce);
+        Transaction::from(tx)
+    });
+    let mut mempool = MempoolGuard::new(PriorityOpId(0), 100);
+    mempool.insert(transactions.to_vec(), HashMap::new());
+
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let (server_handles, _) = spawn_server(
+        ApiTransportLabel::Http,
+        &network_config,
+        pool,
+        None,
+        Some(mempool),
+        stop_receiver,
+    )
+    .await;
+    server_handles.wait_until_ready().await;
+    let client = <HttpClient>::builder()
+        .build(format!("http://{}/", server_handles.local_addr))
+        .unwrap();
+
+    let status = client.status().await.unwrap();
+    assert_eq!(status.pending, 2.into());
+    assert_eq!(status.queued, 1.into());
+
+    let content = client.content_from(account).await.unwrap();
+    assert_eq!(content.pending.keys().copied().collect::<Vec<_>>(), [0, 1]);
+    assert_matches!(
+        content.pending[&0].blocked_by,
+        Some(TxPoolBlockReason::FeeTooLow { .. })
+    );
+    assert_eq!(content.pending[&1].blocked_by, None);
+    assert_eq!(content.pending[&1].hash, transactions[1].hash());
+    assert_eq!(
+        content.queued[&3].blocked_by,
+        Some(TxPoolBlockReason::NonceGap {
+            expected_nonce: 2.into()
+        })
+    );
+
+    let content = client.content().await.unwrap();
+    assert_eq!(content.pending[&account].len(), 2);
+    assert_eq!(content.queued[&account].len(), 1);
+    let inspect = client.inspect().await.unwrap();
+    assert!(inspect.queued[&account][&3].contains(" gas × "));
+
+    let content = client.content_from(Address::zero()).await.unwrap();
+    assert!(content.pending.is_empty() && content.queued.is_empty());
+
+    stop_sender.send_replace(true);
+    server_handles.shutdown().await;
+}
+
+#[derive(Debug)]
+struct AdminDenyListTest;
+
+#[async_trait]
+impl HttpTest for AdminDenyListTest {
+    async fn test(&self, client: &HttpClient, _pool: &ConnectionPool) -> anyhow::Result<()> {
+        let address = Address::repeat_byte(0x42);
+        assert!(client.get_deny_list().await?.is_empty());
+
+        client
+            .add_to_deny_list(address, "sanctioned".to_owned())
+            .await?;
+        let entries = client.get_deny_list().await?;
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].address, address);
+        assert_eq!(entries[0].reason, "sanctioned");
+
+        let removed = client
+            .remove_from_deny_list(address, "appealed".to_owned())
+            .await?;
+        assert!(removed);
+        let removed = client
+            .remove_from_deny_list(address, "appealed".to_owned())
+            .await?;
+        assert!(!removed);
+        assert!(client.get_deny_list().await?.is_empty());
+
+        let audit_log = client.get_deny_list_audit_log(Some(address), None).await?;
+        let actions: Vec<_> = audit_log.iter().map(|record| record.action).collect();
+        assert_eq!(actions, [DenyListAction::Removed, DenyListAction::Added]);
+        assert_eq!(audit_log[0].reason, "appealed");
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn managing_deny_list_via_admin_namespace() {
+    test_http_server(AdminDenyListTest).await;
+}
+
+#[derive(Debug)]
+struct ProtocolUpgradeSchedulingTest;
+
+#[async_trait]
+impl HttpTest for ProtocolUpgradeSchedulingTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let next_version = ProtocolVersionId::next();
+        let mut storage = pool.access_storage().await?;
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion {
+                id: next_version,
+                timestamp: 1_000_000,
+                ..ProtocolVersion::default()
+            })
+            .await;
+        drop(storage);
+
+        let report = client.get_protocol_upgrades_status().await?;
+        assert_eq!(
+            report.current_version,
+            Some(ProtocolVersionId::latest() as u16)
+        );
+        assert!(report.pending_upgrades.is_empty());
+
+        let err = client
+            .schedule_protocol_upgrade(next_version as u16, Some(1_000_001), None)
+            .await
+            .unwrap_err();
+        assert_matches!(err, RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code());
+        let scheduled = client
+            .schedule_protocol_upgrade(next_version as u16, Some(1_000_000), None)
+            .await?;
+        assert!(scheduled);
+        let scheduled = client
+            .schedule_protocol_upgrade(ProtocolVersionId::latest() as u16, None, None)
+            .await?;
+        assert!(!scheduled);
+
+        let report = client.get_protocol_upgrades_status().await?;
+        assert_eq!(report.pending_upgrades.len(), 1);
+        let upgrade = &report.pending_upgrades[0];
+        assert_eq!(upgrade.schedule.protocol_version, next_version as u16);
+        assert_eq!(upgrade.schedule.activation_timestamp, Some(1_000_000));
+        assert_eq!(upgrade.stage, ProtocolUpgradeStage::AwaitingPreflight);
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn scheduling_protocol_upgrades_via_admin_namespace() {
+    test_http_server(ProtocolUpgradeSchedulingTest).await;
+}
+
+#[derive(Debug)]
+struct ProverNamespaceTest;
+
+#[async_trait]
+impl HttpTest for ProverNamespaceTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let prover = Address::repeat_byte(1);
+        let mut storage = pool.access_storage().await?;
+        storage
+            .assignments_dal()
+            .insert_and_update_assignments(prover, L1BatchNumber(0), MiniblockNumber(0), 1)
+            .await?;
+        drop(storage);
+
+        let assignments = client.get_assignment(L1BatchNumber(0)).await?;
+        assert_eq!(assignments.len(), 1);
+        assert_eq!(assignments[0].prover, prover);
+        assert_eq!(assignments[0].storage_index, 1);
+        assert_eq!(
+            assignments[0].status,
+            AssignmentStatus::AssignedNotCertified
+        );
+        assert!(client.get_assignment(L1BatchNumber(1)).await?.is_empty());
+
+        let paging = Paging {
+            offset: 0,
+            limit: Some(10),
+        };
+        let assignments = client
+            .get_assignments_by_prover(prover, Some(paging))
+            .await?;
+        assert_eq!(assignments.len(), 1);
+        let paging = Paging {
+            offset: 1,
+            limit: None,
+        };
+        let assignments = client
+            .get_assignments_by_prover(prover, Some(paging))
+            .await?;
+        assert!(assignments.is_empty());
+        let assignments = client
+            .get_assignments_by_prover(Address::repeat_byte(2), None)
+            .await?;
+        assert!(assignments.is_empty());
+        assert!(client.get_penalties(None, None).await?.is_empty());
+
+        // The prover has no deposit in the genesis state.
+        let deposit_info = client.get_deposit_info(prover).await?;
+        assert_eq!(deposit_info.prover, prover);
+        assert!(!deposit_info.in_white_list);
+        assert_eq!(deposit_info.status, DepositStatus::NotDeposited);
+        assert_eq!(deposit_info.amount, U256::zero());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn prover_namespace_basics() {
+    test_http_server(ProverNamespaceTest).await;
+}
+
+/// Creates a signed transfer from the account with the specified private key. Returns raw transaction bytes
+/// as accepted by `eth_sendRawTransaction` and the corresponding parsed transaction.
+fn create_raw_transaction(private_key: &H256) -> (Bytes, L2Tx) {
+    let chain_id = NetworkConfig::for_tests().micro_network_id;
+    let fee = Fee {
+        gas_limit: 1_000_000_u64.into(),
+        max_fee_per_gas: StateKeeperConfig::for_tests().fair_l2_gas_price.into(),
+        max_priority_fee_per_gas: 0_u64.into(),
+        gas_per_pubdata_limit: MAX_GAS_PER_PUBDATA_BYTE.into(),
+    };
+    let mut tx = L2Tx::new_signed(
+        Address::repeat_byte(2),
+        vec![],
+        Nonce(0),
+        fee,
+        123_456.into(),
+        chain_id,
+        private_key,
+        None,
+        PaymasterParams::default(),
+    )
+    .unwrap();
+    let signature = PackedEthSignature::deserialize_packed(&tx.common_data.signature).unwrap();
+    let raw_tx = api::TransactionRequest::from(tx.clone()).get_signed_bytes(&signature, chain_id);
+    let (_, tx_hash) = api::TransactionRequest::from_bytes(&raw_tx, chain_id).unwrap();
+    tx.set_input(raw_tx.clone(), tx_hash);
+    (raw_tx.into(), tx)
+}
+
+async fn fund_account(pool: &ConnectionPool, address: Address) -> anyhow::Result<()> {
+    let mut storage = pool.access_storage().await?;
+    let balance = U256::from(10_u32).pow(32.into());
+    let storage_log =
+        StorageLog::new_write_log(storage_key_for_eth_balance(&address), u256_to_h256(balance));
+    storage
+        .storage_logs_dal()
+        .append_storage_logs(MiniblockNumber(0), &[(H256::zero(), vec![storage_log])])
+        .await;
+    storage
+        .storage_dal()
+        .apply_storage_logs(&[(H256::zero(), vec![storage_log])])
+        .await;
+    storage
+        .storage_logs_dedup_dal()
+        .insert_initial_writes(L1BatchNumber(0), &[storage_log.key])
+        .await;
+    Ok(())
+}
+
+#[derive(Debug)]
+struct SendRawTransactionWithDetailedOutputTest;
+
+impl SendRawTransactionWithDetailedOutputTest {
+    async fn wait_for_tx(pool: &ConnectionPool, tx_hash: H256) -> anyhow::Result<()> {
+        let started_at = Instant::now();
+        loop {
+            anyhow::ensure!(
+                started_at.elapsed() <= TEST_TIMEOUT,
+                "Timed out waiting for tx {tx_hash:?} to be persisted"
+            );
+            let mut storage = pool.access_storage().await?;
+            let details = storage
+                .transactions_web3_dal()
+                .get_transaction_details(tx_hash)
+                .await?;
+            if details.is_some() {
+                return Ok(());
+            }
+            drop(storage);
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    async fn seal_miniblock(pool: &ConnectionPool, tx: L2Tx) -> anyhow::Result<()> {
+        let mut storage = pool.access_storage().await?;
+        let mut miniblock = create_miniblock(1);
+        miniblock.l2_tx_count = 1;
+        storage.blocks_dal().insert_miniblock(&miniblock).await?;
+        let tx_result = TransactionExecutionResult {
+            hash: tx.hash(),
+            transaction: tx.into(),
+            execution_info: ExecutionMetrics::default(),
+            execution_status: TxExecutionStatus::Success,
+            refunded_gas: 0,
+            operator_suggested_refund: 0,
+            compressed_bytecodes: vec![],
+            call_traces: vec![],
+            revert_reason: None,
+        };
+        storage
+            .transactions_dal()
+            .mark_txs_as_executed_in_miniblock(MiniblockNumber(1), &[tx_result], 1.into())
+            .await;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl HttpTest for SendRawTransactionWithDetailedOutputTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        // The transaction is accepted, but is never included in a miniblock.
+        let (raw_tx, tx) = create_raw_transaction(&H256::repeat_byte(1));
+        fund_account(pool, tx.initiator_account()).await?;
+        let err = client
+            .send_raw_transaction_with_detailed_output(raw_tx, Some(100))
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            RpcError::Call(err) if err.code() == 5 && err.message().contains("timeout")
+        );
+        Self::wait_for_tx(pool, tx.hash()).await?;
+
+        let (raw_tx, tx) = create_raw_transaction(&H256::repeat_byte(2));
+        fund_account(pool, tx.initiator_account()).await?;
+        let tx_hash = tx.hash();
+        let send_task = tokio::spawn({
+            let client = client.clone();
+            async move {
+                client
+                    .send_raw_transaction_with_detailed_output(raw_tx, None)
+                    .await
+            }
+        });
+        Self::wait_for_tx(pool, tx_hash).await?;
+        Self::seal_miniblock(pool, tx).await?;
+
+        let output = tokio::time::timeout(TEST_TIMEOUT, send_task).await???;
+        assert_eq!(output.transaction_hash, tx_hash);
+        assert_eq!(output.receipt.transaction_hash, tx_hash);
+        assert_eq!(output.receipt.block_number, Some(1.into()));
+        assert_eq!(output.receipt.status, Some(1.into()));
+        assert!(output.storage_logs.is_empty());
+        assert!(output.events.is_empty());
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn sending_raw_transaction_with_detailed_output() {
+    test_http_server(SendRawTransactionWithDetailedOutputTest).await;
+}
+
+#[derive(Debug)]
+struct TraceCallWithVmTracersTest;
+
+impl TraceCallWithVmTracersTest {
+    /// Selector of the traced function. The calldata additionally contains a single 32-byte argument.
+    const SELECTOR: [u8; 4] = [0x12, 0x34, 0x56, 0x78];
+
+    fn call_request(from: Address, to: Address, value: U256) -> CallRequest {
+        CallRequest::builder()
+            .from(from)
+            .to(to)
+            .value(value)
+            .data([&Self::SELECTOR[..], &[0; 32]].concat().into())
+            .build()
+    }
+
+    fn tracer_config(tracer: SupportedTracers) -> TracerConfig {
+        TracerConfig {
+            tracer,
+            ..TracerConfig::default()
+        }
+    }
+}
+
+#[async_trait]
+impl HttpTest for TraceCallWithVmTracersTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let sender = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        fund_account(pool, sender).await?;
+
+        let request = Self::call_request(sender, recipient, 0.into());
+        let options = Self::tracer_config(SupportedTracers::FourByteTracer);
+        let trace = client.trace_call(request, None, Some(options)).await?;
+        let DebugTrace::FourByteTrace(trace) = trace else {
+            anyhow::bail!("Unexpected trace: {trace:?}");
+        };
+        assert_eq!(trace.0, BTreeMap::from([("0x12345678-32".to_owned(), 1)]));
+
+        let request = Self::call_request(sender, recipient, 0.into());
+        let mut options = Self::tracer_config(SupportedTracers::StructLogger);
+        options.struct_logger_config = StructLoggerConfig {
+            disable_stack: true,
+            ..StructLoggerConfig::default()
+        };
+        let trace = client
+            .trace_call(request.clone(), None, Some(options.clone()))
+            .await?;
+        let DebugTrace::StructLogs(logs) = trace else {
+            anyhow::bail!("Unexpected trace: {trace:?}");
+        };
+        assert!(!logs.failed);
+        assert!(!logs.struct_logs.is_empty());
+        // Only opcodes executed by the called account are logged.
+        assert!(logs.struct_logs.iter().all(|log| log.address == recipient));
+        assert!(logs.struct_logs.iter().all(|log| log.registers.is_none()));
+
+        options.struct_logger_config.limit = 3;
+        let trace = client.trace_call(request, None, Some(options)).await?;
+        let DebugTrace::StructLogs(limited_logs) = trace else {
+            anyhow::bail!("Unexpected trace: {trace:?}");
+        };
+        assert_eq!(limited_logs.struct_logs, logs.struct_logs[..3]);
+
+        let value = U256::from(123_456);
+        let request = Self::call_request(sender, recipient, value);
+        let mut options = Self::tracer_config(SupportedTracers::PrestateTracer);
+        let trace = client
+            .trace_call(request.clone(), None, Some(options.clone()))
+            .await?;
+        let DebugTrace::PrestateTrace(PrestateTrace::Prestate(accounts)) = trace else {
+            anyhow::bail!("Unexpected trace: {trace:?}");
+        };
+        // The recipient is touched by the call, but the transfer is not applied yet.
+        assert_eq!(accounts[&recipient].balance, Some(U256::zero()));
+        assert_eq!(accounts[&recipient].nonce, Some(0));
+        assert_eq!(accounts[&recipient].code, None);
+
+        options.tracer_config.prestate.diff_mode = true;
+        let trace = client.trace_call(request, None, Some(options)).await?;
+        let DebugTrace::PrestateTrace(PrestateTrace::Diff { pre, post }) = trace else {
+            anyhow::bail!("Unexpected trace: {trace:?}");
+        };
+        // The transferred value is reflected in the balance slot of the recipient.
+        let recipient_balance_slot = *storage_key_for_eth_balance(&recipient).key();
+        assert_eq!(
+            pre[&L2_ETH_TOKEN_ADDRESS].storage[&recipient_balance_slot],
+            H256::zero()
+        );
+        assert_eq!(
+            post[&L2_ETH_TOKEN_ADDRESS].storage[&recipient_balance_slot],
+            u256_to_h256(value)
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn tracing_call_with_vm_tracers() {
+    test_http_server(TraceCallWithVmTracersTest).await;
+}
+
+#[derive(Debug)]
+struct SimulateBundleTest;
+
+#[async_trait]
+impl HttpTest for SimulateBundleTest {
+    async fn test(&self, client: &HttpClient, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let sender = Address::repeat_byte(0x11);
+        let recipient = Address::repeat_byte(0x22);
+        fund_account(pool, sender).await?;
+
+        let value = U256::from(123_456);
+        let transfer = CallRequest::builder()
+            .from(sender)
+            .to(recipient)
+            .value(value)
+            .build();
+        let options = SimulateBundleOptions {
+            with_call_traces: true,
+            ..SimulateBundleOptions::default()
+        };
+        let calls = client
+            .simulate_bundle(vec![transfer.clone(); 2], None, Some(options))
+            .await?;
+        assert_eq!(calls.len(), 2);
+
+        // Each call is executed on top of the state produced by the previous calls.
+        let recipient_balance_slot = *storage_key_for_eth_balance(&recipient).key();
+        let mut expected_balance = U256::zero();
+        for call in &calls {
+            assert_eq!(call.status, SimulatedCallStatus::Success, "{call:?}");
+            assert!(call.trace.is_some());
+            let balance_diff = call
+                .state_diff
+                .iter()
+                .find(|diff| {
+                    diff.address == L2_ETH_TOKEN_ADDRESS && diff.key == recipient_balance_slot
+                })
+                .context("no recipient balance diff")?;
+            assert_eq!(balance_diff.previous_value, u256_to_h256(expected_balance));
+            expected_balance += value;
+            assert_eq!(balance_diff.new_value, u256_to_h256(expected_balance));
+        }
+
+        let calls = client
+            .simulate_bundle(vec![transfer.clone()], None, None)
+            .await?;
+        assert_eq!(calls.len(), 1);
+        assert!(calls[0].trace.is_none());
+
+        let err = client
+            .simulate_bundle(vec![transfer; 100], None, None)
+            .await
+            .unwrap_err();
+        assert_matches!(
+            err,
+            RpcError::Call(err) if err.code() == ErrorCode::InvalidParams.code()
+        );
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn simulating_bundle() {
+    test_http_server(SimulateBundleTest).await;
+}
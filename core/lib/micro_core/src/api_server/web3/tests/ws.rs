@@ -3,7 +3,15 @@
 use async_trait::async_trait;
 use micro_config::configs::chain::NetworkConfig;
 use micro_dal::ConnectionPool;
-use micro_types::{api, Address, L1BatchNumber, H256, U64};
+use micro_types::{
+    api::{
+        self,
+        finality::{L1BatchStage, L1BatchStatusUpdate},
+        TransactionRejection, TransactionRejectionCode,
+    },
+    block::{BlockGasCount, L1BatchHeader},
+    Address, L1BatchNumber, H256, U64,
+};
 use micro_web3_decl::{
     jsonrpsee::{
         core::client::{Subscription, SubscriptionClientT},
@@ -16,7 +24,7 @@ use micro_web3_decl::{
 use tokio::sync::watch;
 
 use super::*;
-use crate::api_server::web3::metrics::SubscriptionType;
+use crate::{api_server::web3::metrics::SubscriptionType, state_keeper::SealedMiniblock};
 
 #[allow(clippy::needless_pass_by_ref_mut)] // false positive
 async fn wait_for_subscription(
@@ -70,6 +78,10 @@ trait WsTest {
         pool: &ConnectionPool,
         pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
     ) -> anyhow::Result<()>;
+
+    fn miniblock_notifications(&self) -> Option<MiniblockNotifications> {
+        None
+    }
 }
 
 async fn test_ws_server(test: impl WsTest) {
@@ -88,8 +100,13 @@ async fn test_ws_server(test: impl WsTest) {
     drop(storage);
 
     let (stop_sender, stop_receiver) = watch::channel(false);
-    let (server_handles, pub_sub_events) =
-        spawn_ws_server(&network_config, pool.clone(), stop_receiver).await;
+    let (server_handles, pub_sub_events) = spawn_ws_server(
+        &network_config,
+        pool.clone(),
+        test.miniblock_notifications(),
+        stop_receiver,
+    )
+    .await;
     server_handles.wait_until_ready().await;
 
     let client = WsClientBuilder::default()
@@ -185,6 +202,117 @@ async fn basic_subscriptions() {
     test_ws_server(BasicSubscriptions).await;
 }
 
+#[derive(Debug)]
+struct L1BatchSubscriptions;
+
+#[async_trait]
+impl WsTest for L1BatchSubscriptions {
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifier(&mut pub_sub_events, SubscriptionType::L1Batches).await;
+
+        let params = rpc_params!["l1Batches"];
+        let mut subscription = client
+            .subscribe::<L1BatchStatusUpdate, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::L1Batches).await;
+
+        let mut header = L1BatchHeader::new(
+            L1BatchNumber(1),
+            1,
+            Address::default(),
+            BaseSystemContractsHashes::default(),
+            ProtocolVersionId::latest(),
+        );
+        header.is_finished = true;
+        pool.access_storage()
+            .await?
+            .blocks_dal()
+            .insert_l1_batch(&header, &[], BlockGasCount::default(), &[], &[])
+            .await?;
+
+        let update = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+            .await
+            .context("Timed out waiting for L1 batch update")?
+            .context("L1 batches subscription terminated")??;
+        assert_eq!(update.stage, L1BatchStage::Sealed);
+        assert_eq!(update.batch.number, L1BatchNumber(1));
+        assert_eq!(update.batch.timestamp, 1);
+        assert_eq!(update.batch.commit_tx_hash, None);
+        subscription.unsubscribe().await?;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn l1_batch_subscriptions() {
+    test_ws_server(L1BatchSubscriptions).await;
+}
+
+#[derive(Debug)]
+struct RejectedTxSubscriptions;
+
+#[async_trait]
+impl WsTest for RejectedTxSubscriptions {
+    async fn test(
+        &self,
+        client: &WsClient,
+        pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifier(&mut pub_sub_events, SubscriptionType::RejectedTxs).await;
+
+        let params = rpc_params!["rejectedTransactions"];
+        let mut subscription = client
+            .subscribe::<TransactionRejection, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::RejectedTxs).await;
+
+        let tx = create_l2_transaction(1, 2);
+        let tx_hash = tx.hash();
+        let mut storage = pool.access_storage().await?;
+        storage
+            .transactions_dal()
+            .insert_transaction_l2(tx, TransactionExecutionMetrics::default())
+            .await;
+        storage
+            .transactions_dal()
+            .mark_tx_as_rejected(
+                tx_hash,
+                TransactionRejectionCode::DenyList,
+                "rejected: Transaction interacts with a denied address",
+            )
+            .await;
+        drop(storage);
+
+        let rejection = tokio::time::timeout(TEST_TIMEOUT, subscription.next())
+            .await
+            .context("Timed out waiting for rejected transaction")?
+            .context("Rejected transactions subscription terminated")??;
+        assert_eq!(rejection.tx_hash, tx_hash);
+        assert_eq!(rejection.code, TransactionRejectionCode::DenyList);
+        assert!(rejection.reason.contains("denied address"));
+        subscription.unsubscribe().await?;
+
+        let details = client
+            .get_transaction_details(tx_hash)
+            .await?
+            .context("no transaction details")?;
+        assert_matches!(details.status, api::TransactionStatus::Failed);
+        assert_eq!(details.rejection, Some(rejection));
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn rejected_tx_subscriptions() {
+    test_ws_server(RejectedTxSubscriptions).await;
+}
+
 #[derive(Debug)]
 struct LogSubscriptions;
 
@@ -463,3 +591,71 @@ impl WsTest for LogSubscriptionsWithDelay {
 async fn log_subscriptions_with_delay() {
     test_ws_server(LogSubscriptionsWithDelay).await;
 }
+
+#[derive(Debug, Default)]
+struct PushedBlockSubscriptions {
+    notifications: MiniblockNotifications,
+}
+
+#[async_trait]
+impl WsTest for PushedBlockSubscriptions {
+    async fn test(
+        &self,
+        client: &WsClient,
+        _pool: &ConnectionPool,
+        mut pub_sub_events: mpsc::UnboundedReceiver<PubSubEvent>,
+    ) -> anyhow::Result<()> {
+        wait_for_notifier(&mut pub_sub_events, SubscriptionType::Blocks).await;
+        let params = rpc_params!["newHeads"];
+        let mut blocks_subscription = client
+            .subscribe::<BlockHeader, _>("eth_subscribe", params, "eth_unsubscribe")
+            .await?;
+        wait_for_subscription(&mut pub_sub_events, SubscriptionType::Blocks).await;
+
+        // The miniblock is not persisted, so it can only be delivered via in-process notifications.
+        let header = BlockHeader {
+            hash: Some(H256::repeat_byte(1)),
+            parent_hash: H256::zero(),
+            uncles_hash: H256::zero(),
+            author: Address::zero(),
+            state_root: H256::zero(),
+            transactions_root: H256::zero(),
+            receipts_root: H256::zero(),
+            number: Some(1.into()),
+            gas_used: 0.into(),
+            gas_limit: 0.into(),
+            base_fee_per_gas: None,
+            extra_data: Default::default(),
+            logs_bloom: Default::default(),
+            timestamp: 1.into(),
+            difficulty: 0.into(),
+            mix_hash: None,
+            nonce: None,
+        };
+        self.notifications.publish(SealedMiniblock {
+            number: MiniblockNumber(1),
+            header,
+            tx_hashes: vec![],
+            logs: vec![],
+            sealed_at: Instant::now(),
+        });
+
+        let received_block_header = tokio::time::timeout(TEST_TIMEOUT, blocks_subscription.next())
+            .await
+            .context("Timed out waiting for new block hash")?
+            .context("New blocks subscription terminated")??;
+        assert_eq!(received_block_header.number, Some(1.into()));
+        assert_eq!(received_block_header.hash, Some(H256::repeat_byte(1)));
+        blocks_subscription.unsubscribe().await?;
+        Ok(())
+    }
+
+    fn miniblock_notifications(&self) -> Option<MiniblockNotifications> {
+        Some(self.notifications.clone())
+    }
+}
+
+#[tokio::test]
+async fn pushed_block_subscriptions() {
+    test_ws_server(PushedBlockSubscriptions::default()).await;
+}
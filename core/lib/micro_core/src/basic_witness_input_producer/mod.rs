@@ -15,7 +15,7 @@ use self::{
 };
 
 mod metrics;
-mod vm_interactions;
+pub(crate) mod vm_interactions;
 
 /// Component that extracts all data (from DB) necessary to run a Basic Witness Generator.
 /// Does this by rerunning an entire L1Batch and extracting information from both the VM run and DB.
@@ -11,12 +11,12 @@ use tokio::runtime::Handle;
 
 use crate::state_keeper::io::common::load_l1_batch_params;
 
-pub(super) type VmAndStorage<'a> = (
+pub(crate) type VmAndStorage<'a> = (
     VmInstance<StorageView<PostgresStorage<'a>>, HistoryEnabled>,
     StoragePtr<StorageView<PostgresStorage<'a>>>,
 );
 
-pub(super) fn create_vm(
+pub(crate) fn create_vm(
     rt_handle: Handle,
     l1_batch_number: L1BatchNumber,
     mut connection: StorageProcessor<'_>,
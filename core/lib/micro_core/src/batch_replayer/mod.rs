@@ -0,0 +1,516 @@
+//! Offline re-execution of sealed L1 batches used to check execution determinism.
+//!
+//! [`BatchReplayer`] loads transactions of an L1 batch together with the state at the end of the previous
+//! batch, re-executes the batch with the VM version recorded for it and compares the outputs
+//! (transaction outcomes, events, L2-to-L1 logs and storage writes) against the data persisted
+//! by the state keeper. Replayed storage writes are additionally applied to the Merkle tree, and the resulting
+//! root hash is compared with the one persisted for the batch. The result is a serializable [`BatchReplayReport`].
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context as _;
+use micro_dal::{transactions_dal::StoredExecutionOutcome, ConnectionPool};
+use micro_merkle_tree::{domain::MicroTree, TreeInstruction};
+use micro_state::WriteStorage;
+use micro_storage::RocksDB;
+use micro_types::{
+    api::{GetLogsFilter, Log},
+    l2_to_l1_log::{SystemL2ToL1Log, UserL2ToL1Log},
+    tx::tx_execution_info::TxExecutionStatus,
+    AccountTreeId, Address, Bytes, L1BatchNumber, L2ChainId, ProtocolVersionId, StorageKey,
+    StorageLogQuery, Transaction, VmEvent, H256, U256,
+};
+use micro_utils::u256_to_h256;
+use multivm::{
+    interface::{L2BlockEnv, VmExecutionResultAndLogs, VmInterface, VmInterfaceHistoryEnabled},
+    vm_latest::HistoryEnabled,
+    VmInstance,
+};
+use serde::Serialize;
+use tokio::runtime::Handle;
+
+use crate::basic_witness_input_producer::vm_interactions::create_vm;
+
+#[cfg(test)]
+mod tests;
+
+/// Execution outcome of a single transaction.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionOutcome {
+    pub hash: H256,
+    pub success: bool,
+    pub gas_used: u64,
+    pub refunded_gas: u64,
+}
+
+impl From<StoredExecutionOutcome> for TransactionOutcome {
+    fn from(outcome: StoredExecutionOutcome) -> Self {
+        Self {
+            hash: outcome.hash,
+            success: outcome.status == TxExecutionStatus::Success,
+            gas_used: outcome.gas_used,
+            refunded_gas: outcome.refunded_gas,
+        }
+    }
+}
+
+/// Event emitted during batch execution. The event location is intentionally omitted, since it is
+/// represented differently by the VM and in Postgres; events are compared by their order instead.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventData {
+    pub address: Address,
+    pub topics: Vec<H256>,
+    pub data: Bytes,
+}
+
+impl From<VmEvent> for EventData {
+    fn from(event: VmEvent) -> Self {
+        Self {
+            address: event.address,
+            topics: event.indexed_topics,
+            data: Bytes(event.value),
+        }
+    }
+}
+
+impl From<Log> for EventData {
+    fn from(log: Log) -> Self {
+        Self {
+            address: log.address,
+            topics: log.topics,
+            data: log.data,
+        }
+    }
+}
+
+/// Outputs of an L1 batch execution, either loaded from Postgres or obtained by re-executing the batch.
+#[derive(Debug, Clone, Default)]
+struct BatchOutput {
+    transactions: Vec<TransactionOutcome>,
+    events: Vec<EventData>,
+    user_l2_to_l1_logs: Vec<UserL2ToL1Log>,
+    system_l2_to_l1_logs: Vec<SystemL2ToL1Log>,
+    /// Final values of storage slots changed by the batch. Slots whose value at the end of the batch
+    /// is equal to their value before the batch are not included.
+    storage_writes: HashMap<StorageKey, H256>,
+    /// Merkle tree root hash after the batch.
+    root_hash: H256,
+}
+
+/// Mismatch between stored and replayed entries at a certain position of an ordered list.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ListMismatch<T> {
+    pub index: usize,
+    pub stored: Option<T>,
+    pub replayed: Option<T>,
+}
+
+/// Mismatch between the stored and replayed final value of a storage slot. `None` means that the slot
+/// was not changed by the batch.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageWriteMismatch {
+    pub key: StorageKey,
+    pub stored: Option<H256>,
+    pub replayed: Option<H256>,
+}
+
+/// Result of re-executing an L1 batch.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchReplayReport {
+    pub l1_batch_number: L1BatchNumber,
+    pub protocol_version: Option<ProtocolVersionId>,
+    pub transaction_count: usize,
+    pub event_count: usize,
+    pub storage_write_count: usize,
+    pub transactions: Vec<ListMismatch<TransactionOutcome>>,
+    pub events: Vec<ListMismatch<EventData>>,
+    pub user_l2_to_l1_logs: Vec<ListMismatch<UserL2ToL1Log>>,
+    pub system_l2_to_l1_logs: Vec<ListMismatch<SystemL2ToL1Log>>,
+    pub storage_writes: Vec<StorageWriteMismatch>,
+    /// Root hash of the Merkle tree after the batch persisted in Postgres.
+    pub stored_root_hash: H256,
+    /// Root hash of the Merkle tree after applying replayed storage writes to the tree state
+    /// after the previous batch.
+    pub replayed_root_hash: H256,
+}
+
+impl BatchReplayReport {
+    fn new(
+        l1_batch_number: L1BatchNumber,
+        protocol_version: Option<ProtocolVersionId>,
+        stored: &BatchOutput,
+        replayed: &BatchOutput,
+    ) -> Self {
+        Self {
+            l1_batch_number,
+            protocol_version,
+            transaction_count: stored.transactions.len(),
+            event_count: stored.events.len(),
+            storage_write_count: stored.storage_writes.len(),
+            transactions: diff_lists(&stored.transactions, &replayed.transactions),
+            events: diff_lists(&stored.events, &replayed.events),
+            user_l2_to_l1_logs: diff_lists(
+                &stored.user_l2_to_l1_logs,
+                &replayed.user_l2_to_l1_logs,
+            ),
+            system_l2_to_l1_logs: diff_lists(
+                &stored.system_l2_to_l1_logs,
+                &replayed.system_l2_to_l1_logs,
+            ),
+            storage_writes: diff_storage_writes(&stored.storage_writes, &replayed.storage_writes),
+            stored_root_hash: stored.root_hash,
+            replayed_root_hash: replayed.root_hash,
+        }
+    }
+
+    /// Checks whether re-execution has produced the same outputs as the original execution.
+    pub fn is_deterministic(&self) -> bool {
+        self.transactions.is_empty()
+            && self.events.is_empty()
+            && self.user_l2_to_l1_logs.is_empty()
+            && self.system_l2_to_l1_logs.is_empty()
+            && self.storage_writes.is_empty()
+            && self.stored_root_hash == self.replayed_root_hash
+    }
+}
+
+fn diff_lists<T: Clone + PartialEq>(stored: &[T], replayed: &[T]) -> Vec<ListMismatch<T>> {
+    let len = stored.len().max(replayed.len());
+    (0..len)
+        .filter_map(|index| {
+            let stored = stored.get(index);
+            let replayed = replayed.get(index);
+            (stored != replayed).then(|| ListMismatch {
+                index,
+                stored: stored.cloned(),
+                replayed: replayed.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn diff_storage_writes(
+    stored: &HashMap<StorageKey, H256>,
+    replayed: &HashMap<StorageKey, H256>,
+) -> Vec<StorageWriteMismatch> {
+    let mut mismatches: Vec<_> = stored
+        .keys()
+        .chain(replayed.keys().filter(|key| !stored.contains_key(key)))
+        .filter_map(|key| {
+            let stored = stored.get(key).copied();
+            let replayed = replayed.get(key).copied();
+            (stored != replayed).then_some(StorageWriteMismatch {
+                key: *key,
+                stored,
+                replayed,
+            })
+        })
+        .collect();
+    mismatches.sort_unstable_by_key(|mismatch| mismatch.key.hashed_key());
+    mismatches
+}
+
+/// Computes final values of storage slots changed by the provided VM storage logs, taking rollbacks
+/// into account.
+fn final_storage_writes<'a>(
+    logs: impl IntoIterator<Item = &'a StorageLogQuery>,
+) -> HashMap<StorageKey, H256> {
+    let mut slots = HashMap::<StorageKey, (U256, U256)>::new();
+    for log in logs.into_iter().filter(|log| log.log_query.rw_flag) {
+        let log_query = &log.log_query;
+        let key = StorageKey::new(
+            AccountTreeId::new(log_query.address),
+            u256_to_h256(log_query.key),
+        );
+        let value = if log_query.rollback {
+            log_query.read_value
+        } else {
+            log_query.written_value
+        };
+        slots
+            .entry(key)
+            .and_modify(|(_, final_value)| *final_value = value)
+            .or_insert((log_query.read_value, value));
+    }
+
+    slots
+        .into_iter()
+        .filter(|(_, (initial_value, final_value))| initial_value != final_value)
+        .map(|(key, (_, final_value))| (key, u256_to_h256(final_value)))
+        .collect()
+}
+
+/// Re-executes sealed L1 batches and compares the results with the data persisted by the state keeper.
+#[derive(Debug)]
+pub struct BatchReplayer {
+    connection_pool: ConnectionPool,
+    l2_chain_id: L2ChainId,
+    merkle_tree_path: PathBuf,
+}
+
+impl BatchReplayer {
+    /// Creates a replayer. The Merkle tree at `merkle_tree_path` is only modified in memory, but it must not
+    /// be concurrently used by another process (e.g., by the metadata calculator); consider replaying batches
+    /// against a copy of the tree.
+    pub fn new(
+        connection_pool: ConnectionPool,
+        l2_chain_id: L2ChainId,
+        merkle_tree_path: PathBuf,
+    ) -> Self {
+        Self {
+            connection_pool,
+            l2_chain_id,
+            merkle_tree_path,
+        }
+    }
+
+    /// Re-executes the specified L1 batch and returns a report comparing the execution outputs
+    /// with the stored ones.
+    pub async fn replay(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<BatchReplayReport> {
+        anyhow::ensure!(
+            l1_batch_number > L1BatchNumber(0),
+            "genesis L1 batch cannot be replayed"
+        );
+        let (protocol_version, stored) = self.load_stored_output(l1_batch_number).await?;
+
+        let connection_pool = self.connection_pool.clone();
+        let l2_chain_id = self.l2_chain_id;
+        let merkle_tree_path = self.merkle_tree_path.clone();
+        let replayed = tokio::task::spawn_blocking(move || {
+            let rt_handle = Handle::current();
+            let mut output = Self::execute_batch(
+                rt_handle.clone(),
+                l1_batch_number,
+                &connection_pool,
+                l2_chain_id,
+            )?;
+            output.root_hash = Self::compute_root_hash(
+                &rt_handle,
+                l1_batch_number,
+                &connection_pool,
+                &merkle_tree_path,
+                &output.storage_writes,
+            )?;
+            anyhow::Ok(output)
+        })
+        .await
+        .context("L1 batch execution panicked")??;
+
+        let report = BatchReplayReport::new(l1_batch_number, protocol_version, &stored, &replayed);
+        if report.is_deterministic() {
+            tracing::info!("Replayed L1 batch #{l1_batch_number}; no mismatches found");
+        } else {
+            tracing::warn!("Replayed L1 batch #{l1_batch_number}; found mismatches: {report:?}");
+        }
+        Ok(report)
+    }
+
+    async fn load_stored_output(
+        &self,
+        l1_batch_number: L1BatchNumber,
+    ) -> anyhow::Result<(Option<ProtocolVersionId>, BatchOutput)> {
+        let mut storage = self
+            .connection_pool
+            .access_storage_tagged("batch_replayer")
+            .await?;
+        let header = storage
+            .blocks_dal()
+            .get_l1_batch_header(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} is not sealed"))?;
+        let root_hash = storage
+            .blocks_dal()
+            .get_l1_batch_state_root(l1_batch_number)
+            .await?
+            .with_context(|| {
+                format!("L1 batch #{l1_batch_number} has no root hash; it is not processed by Merkle tree yet")
+            })?;
+        let (from_miniblock, to_miniblock) = storage
+            .blocks_dal()
+            .get_miniblock_range_of_l1_batch(l1_batch_number)
+            .await?
+            .with_context(|| format!("L1 batch #{l1_batch_number} has no miniblocks"))?;
+
+        let transactions = storage
+            .transactions_dal()
+            .get_execution_outcomes_for_l1_batch(l1_batch_number)
+            .await
+            .context("failed loading transaction outcomes")?;
+        let filter = GetLogsFilter {
+            from_block: from_miniblock,
+            to_block: to_miniblock,
+            addresses: vec![],
+            topics: vec![],
+        };
+        let events = storage
+            .events_web3_dal()
+            .get_logs(filter, i32::MAX as usize)
+            .await
+            .context("failed loading events")?;
+
+        let touched_slots = storage
+            .storage_logs_dal()
+            .get_touched_slots_for_l1_batch(l1_batch_number)
+            .await;
+        let hashed_keys: Vec<_> = touched_slots.keys().map(StorageKey::hashed_key).collect();
+        let previous_values = storage
+            .storage_logs_dal()
+            .get_previous_storage_values(&hashed_keys, l1_batch_number)
+            .await;
+        let storage_writes = touched_slots
+            .into_iter()
+            .filter(|(key, value)| {
+                let previous_value = previous_values[&key.hashed_key()].unwrap_or_default();
+                *value != previous_value
+            })
+            .collect();
+
+        let output = BatchOutput {
+            transactions: transactions.into_iter().map(Into::into).collect(),
+            events: events.into_iter().map(Into::into).collect(),
+            user_l2_to_l1_logs: header.l2_to_l1_logs,
+            system_l2_to_l1_logs: header.system_logs,
+            storage_writes,
+            root_hash,
+        };
+        Ok((header.protocol_version, output))
+    }
+
+    fn execute_batch(
+        rt_handle: Handle,
+        l1_batch_number: L1BatchNumber,
+        connection_pool: &ConnectionPool,
+        l2_chain_id: L2ChainId,
+    ) -> anyhow::Result<BatchOutput> {
+        let mut connection = rt_handle
+            .block_on(connection_pool.access_storage_tagged("batch_replayer"))
+            .context("failed to get connection for BatchReplayer")?;
+        let miniblocks_execution_data = rt_handle.block_on(
+            connection
+                .transactions_dal()
+                .get_miniblocks_to_execute_for_l1_batch(l1_batch_number),
+        )?;
+
+        // The VM version is selected based on the protocol version recorded for the batch.
+        let (mut vm, _) = create_vm(rt_handle, l1_batch_number, connection, l2_chain_id)
+            .context("failed to create VM for BatchReplayer")?;
+        tracing::info!("Started re-execution of L1 batch #{l1_batch_number}");
+
+        let mut transactions = vec![];
+        let next_miniblocks_data = miniblocks_execution_data
+            .iter()
+            .skip(1)
+            .map(Some)
+            .chain([None]);
+        let miniblocks_data = miniblocks_execution_data.iter().zip(next_miniblocks_data);
+        for (miniblock_data, next_miniblock_data) in miniblocks_data {
+            for tx in &miniblock_data.txs {
+                let result = execute_tx(tx, &mut vm)
+                    .with_context(|| format!("failed to execute transaction {:?}", tx.hash()))?;
+                transactions.push(TransactionOutcome {
+                    hash: tx.hash(),
+                    success: !result.result.is_failed(),
+                    gas_used: result.statistics.gas_used.into(),
+                    refunded_gas: result.refunds.gas_refunded.into(),
+                });
+            }
+            if let Some(next_miniblock_data) = next_miniblock_data {
+                vm.start_new_l2_block(L2BlockEnv::from_miniblock_data(next_miniblock_data));
+            }
+        }
+
+        let finished_batch = vm.finish_batch();
+        tracing::info!("Finished re-execution of L1 batch #{l1_batch_number}");
+
+        let state = finished_batch.final_execution_state;
+        Ok(BatchOutput {
+            transactions,
+            events: state.events.into_iter().map(Into::into).collect(),
+            user_l2_to_l1_logs: state.user_l2_to_l1_logs,
+            system_l2_to_l1_logs: state.system_logs,
+            storage_writes: final_storage_writes(&state.storage_log_queries),
+            // Computed separately since it requires the Merkle tree.
+            root_hash: H256::zero(),
+        })
+    }
+
+    /// Computes the Merkle tree root hash after applying `storage_writes` to the tree state after the previous
+    /// L1 batch. Changes to the tree are never saved, so the tree on disk is left intact.
+    fn compute_root_hash(
+        rt_handle: &Handle,
+        l1_batch_number: L1BatchNumber,
+        connection_pool: &ConnectionPool,
+        merkle_tree_path: &Path,
+        storage_writes: &HashMap<StorageKey, H256>,
+    ) -> anyhow::Result<H256> {
+        let mut connection = rt_handle
+            .block_on(connection_pool.access_storage_tagged("batch_replayer"))
+            .context("failed to get connection for BatchReplayer")?;
+        let hashed_keys: Vec<_> = storage_writes.keys().map(StorageKey::hashed_key).collect();
+        let initial_writes = rt_handle.block_on(
+            connection
+                .storage_logs_dal()
+                .get_l1_batches_and_indices_for_initial_writes(&hashed_keys),
+        );
+        let max_leaf_index =
+            rt_handle.block_on(connection.storage_logs_dedup_dal().max_enumeration_index());
+        drop(connection);
+
+        let mut writes: Vec<_> = storage_writes.iter().collect();
+        writes.sort_unstable_by_key(|(key, _)| key.hashed_key());
+        let mut next_leaf_index = max_leaf_index.unwrap_or(0) + 1;
+        let instructions: Vec<_> = writes
+            .into_iter()
+            .map(|(key, value)| {
+                let leaf_index = match initial_writes.get(&key.hashed_key()) {
+                    Some(&(l1_batch, leaf_index)) if l1_batch <= l1_batch_number => leaf_index,
+                    // The slot was not written to during the original execution, so it has no index yet.
+                    // Any unused index will do: storage writes mismatch in this case anyway.
+                    _ => {
+                        next_leaf_index += 1;
+                        next_leaf_index - 1
+                    }
+                };
+                TreeInstruction::write(*key, leaf_index, *value)
+            })
+            .collect();
+
+        let db = RocksDB::new(merkle_tree_path);
+        let mut tree = MicroTree::new_lightweight(db.into());
+        anyhow::ensure!(
+            tree.next_l1_batch_number() > l1_batch_number,
+            "Merkle tree at {} has not processed L1 batch #{l1_batch_number}",
+            merkle_tree_path.display()
+        );
+        tree.revert_logs(l1_batch_number - 1);
+        Ok(tree.process_l1_batch(&instructions).root_hash)
+    }
+}
+
+/// Executes a transaction in the same way as the state keeper does, i.e., retrying without bytecode
+/// compression if compression fails.
+fn execute_tx<S: WriteStorage>(
+    tx: &Transaction,
+    vm: &mut VmInstance<S, HistoryEnabled>,
+) -> anyhow::Result<VmExecutionResultAndLogs> {
+    vm.make_snapshot();
+    if let Ok(result) = vm.execute_transaction_with_bytecode_compression(tx.clone(), true) {
+        vm.pop_snapshot_no_rollback();
+        return Ok(result);
+    }
+
+    vm.rollback_to_the_latest_snapshot();
+    vm.execute_transaction_with_bytecode_compression(tx.clone(), false)
+        .map_err(|_| anyhow::anyhow!("compression can't fail if we don't apply it"))
+}
@@ -0,0 +1,321 @@
+//! Tests for comparing stored and replayed L1 batch outputs.
+
+use std::time::Duration;
+
+use micro_test_account::Account;
+use micro_types::{Execute, LogQuery, MiniblockNumber, StorageLogQueryType, Timestamp};
+use tempfile::TempDir;
+use tokio::sync::watch;
+
+use super::*;
+use crate::{
+    consensus::testonly::MockMainNodeClient,
+    genesis::{ensure_genesis_state, GenesisParams},
+    metadata_calculator::tests::{run_calculator, setup_calculator},
+    state_keeper::{MainBatchExecutorBuilder, MicroStateKeeper, MiniblockSealer},
+    sync_layer::{sync_action::SyncAction, ActionQueue, ExternalIO, SyncState},
+};
+
+fn write_log(key: u64, read_value: u64, written_value: u64, rollback: bool) -> StorageLogQuery {
+    StorageLogQuery {
+        log_query: LogQuery {
+            timestamp: Timestamp(0),
+            tx_number_in_block: 0,
+            aux_byte: 0,
+            shard_id: 0,
+            address: Address::repeat_byte(1),
+            key: key.into(),
+            read_value: read_value.into(),
+            written_value: written_value.into(),
+            rw_flag: true,
+            rollback,
+            is_service: false,
+        },
+        log_type: StorageLogQueryType::RepeatedWrite,
+    }
+}
+
+fn storage_key(key: u64) -> StorageKey {
+    StorageKey::new(
+        AccountTreeId::new(Address::repeat_byte(1)),
+        u256_to_h256(key.into()),
+    )
+}
+
+fn tx_outcome(gas_used: u64) -> TransactionOutcome {
+    TransactionOutcome {
+        hash: H256::repeat_byte(gas_used as u8),
+        success: true,
+        gas_used,
+        refunded_gas: 0,
+    }
+}
+
+#[test]
+fn diffing_lists() {
+    let stored = [1, 2, 3];
+    assert!(diff_lists(&stored, &stored).is_empty());
+
+    let mismatches = diff_lists(&stored, &[1, 5]);
+    assert_eq!(
+        mismatches,
+        [
+            ListMismatch {
+                index: 1,
+                stored: Some(2),
+                replayed: Some(5),
+            },
+            ListMismatch {
+                index: 2,
+                stored: Some(3),
+                replayed: None,
+            },
+        ]
+    );
+}
+
+#[test]
+fn computing_final_storage_writes() {
+    let logs = [
+        write_log(1, 0, 10, false),
+        write_log(1, 10, 20, false),
+        // Slot 2 is written and then rolled back.
+        write_log(2, 5, 7, false),
+        write_log(2, 5, 7, true),
+        // Slot 3 is restored to its initial value.
+        write_log(3, 1, 2, false),
+        write_log(3, 2, 1, false),
+    ];
+    let writes = final_storage_writes(&logs);
+    assert_eq!(writes.len(), 1);
+    assert_eq!(writes[&storage_key(1)], u256_to_h256(20.into()));
+}
+
+#[test]
+fn diffing_storage_writes() {
+    let stored = HashMap::from([
+        (storage_key(1), H256::repeat_byte(1)),
+        (storage_key(2), H256::repeat_byte(2)),
+    ]);
+    let replayed = HashMap::from([
+        (storage_key(1), H256::repeat_byte(1)),
+        (storage_key(3), H256::repeat_byte(3)),
+    ]);
+    let mut mismatches = diff_storage_writes(&stored, &replayed);
+    mismatches.sort_unstable_by_key(|mismatch| mismatch.key.key().to_low_u64_be());
+
+    assert_eq!(
+        mismatches,
+        [
+            StorageWriteMismatch {
+                key: storage_key(2),
+                stored: Some(H256::repeat_byte(2)),
+                replayed: None,
+            },
+            StorageWriteMismatch {
+                key: storage_key(3),
+                stored: None,
+                replayed: Some(H256::repeat_byte(3)),
+            },
+        ]
+    );
+}
+
+#[test]
+fn report_for_mismatched_outputs() {
+    let stored = BatchOutput {
+        transactions: vec![tx_outcome(100), tx_outcome(200)],
+        storage_writes: HashMap::from([(storage_key(1), H256::repeat_byte(1))]),
+        root_hash: H256::repeat_byte(0xff),
+        ..BatchOutput::default()
+    };
+    let report = BatchReplayReport::new(L1BatchNumber(1), None, &stored, &stored);
+    assert!(report.is_deterministic());
+
+    let replayed = BatchOutput {
+        transactions: vec![tx_outcome(100), tx_outcome(201)],
+        storage_writes: HashMap::from([(storage_key(1), H256::repeat_byte(2))]),
+        root_hash: H256::repeat_byte(0xfe),
+        ..BatchOutput::default()
+    };
+    let report = BatchReplayReport::new(L1BatchNumber(1), None, &stored, &replayed);
+    assert!(!report.is_deterministic());
+    assert_eq!(report.transactions.len(), 1);
+    assert_eq!(report.transactions[0].index, 1);
+    assert_eq!(report.storage_writes.len(), 1);
+    assert_ne!(report.stored_root_hash, report.replayed_root_hash);
+
+    let report_json = serde_json::to_value(&report).unwrap();
+    assert_eq!(report_json["l1BatchNumber"], 1);
+    assert_eq!(report_json["transactions"][0]["replayed"]["gasUsed"], 201);
+}
+
+#[test]
+fn report_for_mismatched_root_hash() {
+    let stored = BatchOutput {
+        transactions: vec![tx_outcome(100)],
+        root_hash: H256::repeat_byte(0xff),
+        ..BatchOutput::default()
+    };
+    let replayed = BatchOutput {
+        root_hash: H256::repeat_byte(0xfe),
+        ..stored.clone()
+    };
+    let report = BatchReplayReport::new(L1BatchNumber(1), None, &stored, &replayed);
+    assert!(report.transactions.is_empty());
+    assert!(report.storage_writes.is_empty());
+    assert!(!report.is_deterministic());
+}
+
+async fn seal_l1_batch_with_real_vm(pool: &ConnectionPool, txs: Vec<Transaction>) {
+    let mut storage = pool.access_storage().await.unwrap();
+    if storage.blocks_dal().is_genesis_needed().await.unwrap() {
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+    }
+    drop(storage);
+
+    // Each transaction is placed into a separate miniblock, followed by a fictive miniblock.
+    let mut actions = vec![SyncAction::OpenBatch {
+        number: L1BatchNumber(1),
+        timestamp: 1,
+        l1_gas_price: 2,
+        l2_fair_gas_price: 3,
+        operator_address: Address::repeat_byte(1),
+        protocol_version: ProtocolVersionId::latest(),
+        first_miniblock_info: (MiniblockNumber(1), 1),
+    }];
+    let tx_count = txs.len() as u32;
+    for (i, tx) in (1..).zip(txs) {
+        if i > 1 {
+            actions.push(SyncAction::Miniblock {
+                number: MiniblockNumber(i),
+                timestamp: i.into(),
+                virtual_blocks: 1,
+            });
+        }
+        actions.push(SyncAction::Tx(Box::new(tx)));
+        actions.push(SyncAction::SealMiniblock(None));
+    }
+    actions.push(SyncAction::Miniblock {
+        number: MiniblockNumber(tx_count + 1),
+        timestamp: (tx_count + 1).into(),
+        virtual_blocks: 0,
+    });
+    actions.push(SyncAction::SealBatch {
+        virtual_blocks: 0,
+        consensus: None,
+    });
+
+    let (actions_sender, action_queue) = ActionQueue::new();
+    actions_sender.push_actions(actions).await;
+    let (miniblock_sealer, miniblock_sealer_handle) = MiniblockSealer::new(pool.clone(), 5);
+    tokio::spawn(miniblock_sealer.run());
+    let io = ExternalIO::new(
+        miniblock_sealer_handle,
+        pool.clone(),
+        action_queue,
+        SyncState::new(),
+        Box::<MockMainNodeClient>::default(),
+        Address::repeat_byte(1),
+        u32::MAX,
+        L2ChainId::default(),
+    )
+    .await;
+
+    let state_keeper_dir = TempDir::new().unwrap();
+    let batch_executor_base = MainBatchExecutorBuilder::new(
+        state_keeper_dir.path().to_str().unwrap().to_owned(),
+        pool.clone(),
+        u32::MAX.into(),
+        false,
+        false,
+        100,
+    );
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let state_keeper = MicroStateKeeper::without_sealer(
+        stop_receiver,
+        Box::new(io),
+        Box::new(batch_executor_base),
+    );
+    let state_keeper_task = tokio::spawn(state_keeper.run());
+
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let mut storage = pool.access_storage().await.unwrap();
+            let sealed_l1_batch_number = storage
+                .blocks_dal()
+                .get_sealed_l1_batch_number()
+                .await
+                .unwrap();
+            if sealed_l1_batch_number >= L1BatchNumber(1) {
+                break;
+            }
+            assert!(!state_keeper_task.is_finished(), "state keeper stopped");
+            drop(storage);
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .expect("timed out waiting for L1 batch #1 to be sealed");
+
+    stop_sender.send_replace(true);
+    state_keeper_task.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn replaying_l1_batch() {
+    let pool = ConnectionPool::test_pool().await;
+    let account = Account::random();
+    let txs: Vec<_> = (0..2)
+        .map(|serial_id| {
+            let execute = Execute {
+                contract_address: Address::random(),
+                calldata: vec![],
+                value: 0.into(),
+                factory_deps: None,
+            };
+            account.get_l1_tx(execute, serial_id)
+        })
+        .collect();
+    let tx_hashes: Vec<_> = txs.iter().map(Transaction::hash).collect();
+    seal_l1_batch_with_real_vm(&pool, txs).await;
+
+    let temp_dir = TempDir::new().unwrap();
+    let (calculator, _) = setup_calculator(temp_dir.path(), &pool).await;
+    let root_hash = run_calculator(calculator, pool.clone()).await;
+
+    // `setup_calculator()` places the tree into the `new` subdirectory.
+    let replayer = BatchReplayer::new(
+        pool.clone(),
+        L2ChainId::default(),
+        temp_dir.path().join("new"),
+    );
+    let report = replayer.replay(L1BatchNumber(1)).await.unwrap();
+
+    assert!(report.is_deterministic(), "{report:#?}");
+    assert_eq!(report.protocol_version, Some(ProtocolVersionId::latest()));
+    assert_eq!(report.transaction_count, 2);
+    assert!(report.event_count > 0);
+    assert!(report.storage_write_count > 0);
+    assert_eq!(report.stored_root_hash, root_hash);
+    assert_eq!(report.replayed_root_hash, root_hash);
+
+    let mut storage = pool.access_storage().await.unwrap();
+    let stored_outcomes = storage
+        .transactions_dal()
+        .get_execution_outcomes_for_l1_batch(L1BatchNumber(1))
+        .await
+        .unwrap();
+    let stored_hashes: Vec<_> = stored_outcomes.iter().map(|outcome| outcome.hash).collect();
+    assert_eq!(stored_hashes, tx_hashes);
+
+    let err = replayer.replay(L1BatchNumber(2)).await.unwrap_err();
+    assert!(err.to_string().contains("not sealed"), "{err}");
+
+    // The tree on disk must not be modified by replaying.
+    let tree = MicroTree::new_lightweight(RocksDB::new(&temp_dir.path().join("new")).into());
+    assert_eq!(tree.next_l1_batch_number(), L1BatchNumber(2));
+    assert_eq!(tree.root_hash(), root_hash);
+}
@@ -0,0 +1,144 @@
+//! Deny-list of accounts and contracts that are not allowed to interact with the sequencer.
+//!
+//! The deny-list is stored in Postgres and cached in memory. The cache is periodically reloaded
+//! by [`DenyListUpdater`], so changes made via the admin API (possibly on another node instance)
+//! are picked up without a restart. The deny-list is checked when transactions are submitted via the API
+//! and when L2 transactions are sequenced by the state keeper; priority (L1) operations are never filtered.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::Context as _;
+use micro_dal::{ConnectionPool, StorageProcessor};
+use micro_types::{vm_trace::Call, Address, ExecuteTransactionCommon, Transaction};
+use tokio::sync::watch;
+
+#[cfg(test)]
+mod tests;
+
+/// In-memory view of the deny-list. Cloning the deny-list is cheap; all clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct DenyList(Arc<RwLock<HashSet<Address>>>);
+
+impl DenyList {
+    /// Creates a deny-list with the specified addresses.
+    pub fn new(addresses: impl IntoIterator<Item = Address>) -> Self {
+        Self(Arc::new(RwLock::new(addresses.into_iter().collect())))
+    }
+
+    /// Loads the deny-list from the storage.
+    pub async fn load(storage: &mut StorageProcessor<'_>) -> anyhow::Result<Self> {
+        let this = Self::default();
+        this.reload(storage).await?;
+        Ok(this)
+    }
+
+    /// Reloads the deny-list from the storage.
+    pub async fn reload(&self, storage: &mut StorageProcessor<'_>) -> anyhow::Result<()> {
+        let entries = storage
+            .deny_list_dal()
+            .get_entries()
+            .await
+            .context("failed loading deny-list entries")?;
+        let addresses = entries.into_iter().map(|entry| entry.address).collect();
+        *self.0.write().expect("deny-list is poisoned") = addresses;
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.read().expect("deny-list is poisoned").is_empty()
+    }
+
+    pub fn contains(&self, address: &Address) -> bool {
+        self.0
+            .read()
+            .expect("deny-list is poisoned")
+            .contains(address)
+    }
+
+    /// Returns the first address from `addresses` on the deny-list, if any.
+    pub fn find_denied<'a>(
+        &self,
+        addresses: impl IntoIterator<Item = &'a Address>,
+    ) -> Option<Address> {
+        let denied = self.0.read().expect("deny-list is poisoned");
+        if denied.is_empty() {
+            return None;
+        }
+        addresses
+            .into_iter()
+            .find(|&address| denied.contains(address))
+            .copied()
+    }
+
+    /// Checks an executed transaction against the deny-list. The initiator and recipient of the transaction
+    /// are checked, as well as all addresses participating in `call_traces` (which are empty
+    /// if call tracing is disabled for the state keeper). Returns the first listed address, if any.
+    /// Priority operations and protocol upgrades are never denied.
+    pub fn check_executed_transaction(
+        &self,
+        tx: &Transaction,
+        call_traces: &[Call],
+    ) -> Option<Address> {
+        if !matches!(tx.common_data, ExecuteTransactionCommon::L2(_)) || self.is_empty() {
+            return None;
+        }
+        let direct_addresses = [tx.initiator_account(), tx.recipient_account()];
+        let traced_addresses: HashSet<_> = call_traces
+            .iter()
+            .flat_map(Call::participating_addresses)
+            .collect();
+        self.find_denied(direct_addresses.iter().chain(&traced_addresses))
+    }
+}
+
+/// Task periodically reloading a [`DenyList`] from Postgres.
+#[derive(Debug)]
+pub struct DenyListUpdater {
+    deny_list: DenyList,
+    pool: ConnectionPool,
+    reload_interval: Duration,
+}
+
+impl DenyListUpdater {
+    /// Default interval between deny-list reloads.
+    pub const DEFAULT_RELOAD_INTERVAL: Duration = Duration::from_secs(5);
+
+    pub fn new(deny_list: DenyList, pool: ConnectionPool) -> Self {
+        Self {
+            deny_list,
+            pool,
+            reload_interval: Self::DEFAULT_RELOAD_INTERVAL,
+        }
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        loop {
+            if *stop_receiver.borrow() {
+                break;
+            }
+
+            // Errors are not fatal; the deny-list will be reloaded on the next tick.
+            match self.pool.access_storage_tagged("deny_list").await {
+                Ok(mut storage) => {
+                    if let Err(err) = self.deny_list.reload(&mut storage).await {
+                        tracing::warn!("Failed reloading deny-list: {err:#}");
+                    }
+                }
+                Err(err) => {
+                    tracing::warn!("Failed accessing storage to reload deny-list: {err:#}");
+                }
+            }
+
+            tokio::select! {
+                _ = stop_receiver.changed() => break,
+                () = tokio::time::sleep(self.reload_interval) => { /* The delay has passed */ }
+            }
+        }
+        tracing::info!("Stop signal received, deny-list updater is shutting down");
+        Ok(())
+    }
+}
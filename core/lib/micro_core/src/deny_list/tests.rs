@@ -0,0 +1,90 @@
+//! Tests for the deny-list.
+
+use micro_dal::ConnectionPool;
+use micro_types::{
+    l1::{L1Tx, L1TxCommonData},
+    Execute, U256,
+};
+
+use super::*;
+use crate::state_keeper::tests::create_l2_transaction;
+
+fn create_l1_transaction(sender: Address, contract_address: Address) -> Transaction {
+    L1Tx {
+        execute: Execute {
+            contract_address,
+            calldata: vec![],
+            value: U256::zero(),
+            factory_deps: None,
+        },
+        common_data: L1TxCommonData {
+            sender,
+            ..L1TxCommonData::default()
+        },
+        received_timestamp_ms: 0,
+    }
+    .into()
+}
+
+#[tokio::test]
+async fn reloading_deny_list() {
+    let pool = ConnectionPool::test_pool().await;
+    let mut storage = pool.access_storage().await.unwrap();
+    let deny_list = DenyList::load(&mut storage).await.unwrap();
+    assert!(deny_list.is_empty());
+
+    let address = Address::repeat_byte(1);
+    storage
+        .deny_list_dal()
+        .add_address(address, "test")
+        .await
+        .unwrap();
+    assert!(!deny_list.contains(&address));
+    deny_list.reload(&mut storage).await.unwrap();
+    assert!(deny_list.contains(&address));
+
+    let cloned_list = deny_list.clone();
+    storage
+        .deny_list_dal()
+        .remove_address(address, "test")
+        .await
+        .unwrap();
+    deny_list.reload(&mut storage).await.unwrap();
+    assert!(!cloned_list.contains(&address));
+}
+
+#[test]
+fn checking_executed_transactions() {
+    let denied_contract = Address::repeat_byte(0xde);
+    let deny_list = DenyList::new([denied_contract]);
+
+    let tx = create_l2_transaction(10, 100);
+    let initiator = tx.initiator_account();
+    let tx = Transaction::from(tx);
+    assert_eq!(deny_list.check_executed_transaction(&tx, &[]), None);
+
+    let call = Call {
+        from: initiator,
+        to: Address::repeat_byte(1),
+        calls: vec![Call {
+            from: Address::repeat_byte(1),
+            to: denied_contract,
+            ..Call::default()
+        }],
+        ..Call::default()
+    };
+    assert_eq!(
+        deny_list.check_executed_transaction(&tx, &[call.clone()]),
+        Some(denied_contract)
+    );
+
+    deny_list.0.write().unwrap().insert(initiator);
+    assert_eq!(
+        deny_list.check_executed_transaction(&tx, &[]),
+        Some(initiator)
+    );
+
+    // Priority operations must never be denied.
+    let l1_tx = create_l1_transaction(initiator, denied_contract);
+    assert_eq!(deny_list.check_executed_transaction(&l1_tx, &[call]), None);
+}
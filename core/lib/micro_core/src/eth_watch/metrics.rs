@@ -2,7 +2,7 @@
 
 use std::time::Duration;
 
-use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Histogram, Metrics};
+use vise::{Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, Metrics};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
@@ -24,3 +24,40 @@ pub(super) struct EthWatcherMetrics {
 
 #[vise::register]
 pub(super) static METRICS: vise::Global<EthWatcherMetrics> = vise::Global::new();
+
+/// Buckets for priority op latencies: from 1 second to ~1.5 days.
+const PRIORITY_OP_LATENCY_BUCKETS: Buckets = Buckets::exponential(1.0..=131_072.0, 2.0);
+
+/// Processing stage of an unexecuted priority operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
+#[metrics(label = "stage", rename_all = "snake_case")]
+pub(super) enum PriorityOpStage {
+    /// Operation is not included into a miniblock yet.
+    Pending,
+    /// Operation is included into a miniblock, but not into a sealed L1 batch.
+    Included,
+    /// Operation is included into a sealed L1 batch that is not executed on L1 yet.
+    Batched,
+}
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_priority_ops_monitor")]
+pub(super) struct PriorityOpsMonitorMetrics {
+    /// Number of unexecuted priority operations split by the processing stage.
+    pub unexecuted_ops: Family<PriorityOpStage, Gauge<usize>>,
+    /// Number of priority operations close to (or past) their L1 deadline.
+    pub ops_near_deadline: Gauge<usize>,
+    /// Minimum number of L1 blocks left before the deadline among unexecuted operations.
+    pub min_blocks_to_deadline: Gauge<i64>,
+    /// Age of the oldest unexecuted priority operation.
+    pub oldest_op_age: Gauge<Duration>,
+    /// Latency between receiving a priority operation from L1 and including it into a miniblock.
+    #[metrics(buckets = PRIORITY_OP_LATENCY_BUCKETS)]
+    pub inclusion_latency: Histogram<Duration>,
+    /// Latency between receiving a priority operation from L1 and executing it on L1.
+    #[metrics(buckets = PRIORITY_OP_LATENCY_BUCKETS)]
+    pub execution_latency: Histogram<Duration>,
+}
+
+#[vise::register]
+pub(super) static MONITOR_METRICS: vise::Global<PriorityOpsMonitorMetrics> = vise::Global::new();
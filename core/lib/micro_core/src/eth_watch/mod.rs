@@ -17,6 +17,9 @@ use micro_types::{
 use tokio::{sync::watch, task::JoinHandle};
 use tracing::info;
 
+pub use self::priority_ops_monitor::{
+    PriorityOpsMonitor, PriorityOpsReportReceiver, PriorityOpsReportSender,
+};
 use self::{
     client::{Error, EthClient, EthHttpQueryClient, RETRY_LIMIT},
     event_processors::{
@@ -29,6 +32,7 @@ use self::{
 mod client;
 mod event_processors;
 mod metrics;
+mod priority_ops_monitor;
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,361 @@
+//! Monitor tracking L1 -> L2 priority operations from their L1 event to execution on L1.
+//!
+//! The monitor periodically loads priority operations that are not executed on L1 yet, reports their ages
+//! and latencies as metrics, and flags operations approaching their L1 deadline via a health check.
+//! The latest report is published via a [`watch`] channel so that it can be served by the API.
+
+use std::{collections::BTreeMap, time::Duration};
+
+use chrono::{DateTime, Utc};
+use micro_dal::ConnectionPool;
+use micro_eth_client::EthInterface;
+use micro_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use micro_system_constants::PRIORITY_EXPIRATION;
+use micro_types::{
+    api::priority_ops::{PriorityOpStatus, PriorityOpsReport},
+    PriorityOpId,
+};
+use serde::Serialize;
+use tokio::sync::watch;
+
+use super::metrics::{PriorityOpStage, MONITOR_METRICS};
+
+/// Maximum number of unexecuted priority operations loaded from the DB on each iteration.
+const MAX_TRACKED_OPS: usize = 1_000;
+
+/// Sender part of the channel used to publish priority ops reports.
+pub type PriorityOpsReportSender = watch::Sender<Option<PriorityOpsReport>>;
+/// Receiver part of the channel used to publish priority ops reports. Contains `None` until the monitor
+/// generates the first report.
+pub type PriorityOpsReportReceiver = watch::Receiver<Option<PriorityOpsReport>>;
+
+/// Health details reported by the monitor.
+#[derive(Debug, Serialize)]
+struct PriorityOpsHealthDetails {
+    l1_block_number: u64,
+    unexecuted_ops: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_unexecuted_op: Option<PriorityOpId>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    ops_near_deadline: Vec<PriorityOpId>,
+}
+
+/// Latencies of priority operations that have moved to a new stage since the previous update.
+#[derive(Debug, Default, PartialEq)]
+struct TrackerUpdate {
+    inclusion_latencies: Vec<Duration>,
+    execution_latencies: Vec<Duration>,
+}
+
+/// In-memory tracker of unexecuted priority operations used to detect stage transitions.
+#[derive(Debug, Default)]
+struct PriorityOpsTracker {
+    ops: BTreeMap<PriorityOpId, PriorityOpStatus>,
+    is_initialized: bool,
+}
+
+impl PriorityOpsTracker {
+    fn update(&mut self, ops: &[PriorityOpStatus], now: DateTime<Utc>) -> TrackerUpdate {
+        let mut update = TrackerUpdate::default();
+        for op in ops {
+            let Some(included_at) = op.included_at else {
+                continue;
+            };
+            let was_included = match self.ops.get(&op.serial_id) {
+                Some(prev_op) => prev_op.included_at.is_some(),
+                // Operations included before the monitor has started are not reported, since
+                // their inclusion could have happened long ago.
+                None => !self.is_initialized,
+            };
+            if !was_included {
+                update
+                    .inclusion_latencies
+                    .push(latency_between(op.received_at, included_at));
+            }
+        }
+
+        // Priority operations are executed in order, so all previously tracked operations
+        // with a lesser ID than the first unexecuted operation are executed.
+        let first_unexecuted_id = ops.first().map(|op| op.serial_id);
+        let executed_ops = self
+            .ops
+            .values()
+            .take_while(|op| first_unexecuted_id.map_or(true, |first_id| op.serial_id < first_id));
+        update.execution_latencies = executed_ops
+            .map(|op| latency_between(op.received_at, now))
+            .collect();
+
+        self.ops = ops.iter().map(|op| (op.serial_id, op.clone())).collect();
+        self.is_initialized = true;
+        update
+    }
+}
+
+fn latency_between(start: DateTime<Utc>, end: DateTime<Utc>) -> Duration {
+    (end - start).to_std().unwrap_or_default()
+}
+
+fn op_stage(op: &PriorityOpStatus) -> PriorityOpStage {
+    if op.l1_batch_number.is_some() {
+        PriorityOpStage::Batched
+    } else if op.miniblock_number.is_some() {
+        PriorityOpStage::Included
+    } else {
+        PriorityOpStage::Pending
+    }
+}
+
+/// Creates a report for the provided unexecuted priority operations.
+fn create_report(
+    ops: Vec<PriorityOpStatus>,
+    l1_block_number: u64,
+    warning_blocks: u64,
+    now: DateTime<Utc>,
+) -> PriorityOpsReport {
+    let ops_near_deadline = ops
+        .iter()
+        .filter(|op| {
+            op.blocks_to_deadline(l1_block_number)
+                .map_or(false, |blocks| blocks <= warning_blocks as i64)
+        })
+        .map(|op| op.serial_id)
+        .collect();
+    PriorityOpsReport {
+        l1_block_number,
+        generated_at: now,
+        pending_ops: ops,
+        ops_near_deadline,
+    }
+}
+
+/// Monitor of priority operations.
+#[derive(Debug)]
+pub struct PriorityOpsMonitor<E> {
+    eth_client: E,
+    poll_interval: Duration,
+    warning_blocks: u64,
+    tracker: PriorityOpsTracker,
+    report_sender: PriorityOpsReportSender,
+    health_updater: HealthUpdater,
+}
+
+impl<E: EthInterface> PriorityOpsMonitor<E> {
+    /// Creates a new monitor. If `warning_blocks` is not specified, operations are flagged
+    /// once a quarter of the expiration period is left before their deadline.
+    pub fn new(
+        eth_client: E,
+        poll_interval: Duration,
+        warning_blocks: Option<u64>,
+        report_sender: PriorityOpsReportSender,
+    ) -> Self {
+        let (_, health_updater) = ReactiveHealthCheck::new("priority_ops_monitor");
+        Self {
+            eth_client,
+            poll_interval,
+            warning_blocks: warning_blocks.unwrap_or(PRIORITY_EXPIRATION / 4),
+            tracker: PriorityOpsTracker::default(),
+            report_sender,
+            health_updater,
+        }
+    }
+
+    /// Returns the health check for this monitor.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    pub async fn run(
+        mut self,
+        pool: ConnectionPool,
+        mut stop_receiver: watch::Receiver<bool>,
+    ) -> anyhow::Result<()> {
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, priority ops monitor is shutting down");
+                break;
+            }
+
+            if let Err(err) = self.update(&pool).await {
+                // The monitor is not critical for the node operation, so we only log errors.
+                tracing::warn!("Failed updating priority ops report: {err:#}");
+            }
+            // Error here corresponds to a timeout w/o `stop_receiver` changed; we're OK with this.
+            tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .ok();
+        }
+        Ok(())
+    }
+
+    async fn update(&mut self, pool: &ConnectionPool) -> anyhow::Result<()> {
+        let l1_block_number = self
+            .eth_client
+            .block_number("priority_ops_monitor")
+            .await?
+            .as_u64();
+        let mut storage = pool.access_storage_tagged("eth_watch").await?;
+        let ops = storage
+            .transactions_dal()
+            .get_unexecuted_priority_ops(MAX_TRACKED_OPS)
+            .await?;
+        drop(storage);
+
+        let now = Utc::now();
+        let update = self.tracker.update(&ops, now);
+        for latency in update.inclusion_latencies {
+            MONITOR_METRICS.inclusion_latency.observe(latency);
+        }
+        for latency in update.execution_latencies {
+            MONITOR_METRICS.execution_latency.observe(latency);
+        }
+
+        let report = create_report(ops, l1_block_number, self.warning_blocks, now);
+        self.report_metrics(&report);
+        self.update_health(&report);
+        if !report.ops_near_deadline.is_empty() {
+            tracing::warn!(
+                "Priority operations {:?} are approaching their L1 deadline (current L1 block: {l1_block_number})",
+                report.ops_near_deadline
+            );
+        }
+        self.report_sender.send_replace(Some(report));
+        Ok(())
+    }
+
+    fn report_metrics(&self, report: &PriorityOpsReport) {
+        for stage in [
+            PriorityOpStage::Pending,
+            PriorityOpStage::Included,
+            PriorityOpStage::Batched,
+        ] {
+            let count = report
+                .pending_ops
+                .iter()
+                .filter(|&op| op_stage(op) == stage)
+                .count();
+            MONITOR_METRICS.unexecuted_ops[&stage].set(count);
+        }
+        MONITOR_METRICS
+            .ops_near_deadline
+            .set(report.ops_near_deadline.len());
+
+        let min_blocks_to_deadline = report
+            .pending_ops
+            .iter()
+            .filter_map(|op| op.blocks_to_deadline(report.l1_block_number))
+            .min()
+            .unwrap_or(PRIORITY_EXPIRATION as i64);
+        MONITOR_METRICS
+            .min_blocks_to_deadline
+            .set(min_blocks_to_deadline);
+        // Operations are ordered by serial ID, so the first operation is the oldest one.
+        let first_op = report.pending_ops.first();
+        let oldest_op_age = first_op.map_or(Duration::ZERO, |op| {
+            latency_between(op.received_at, report.generated_at)
+        });
+        MONITOR_METRICS.oldest_op_age.set(oldest_op_age);
+    }
+
+    fn update_health(&self, report: &PriorityOpsReport) {
+        let status = if report.ops_near_deadline.is_empty() {
+            HealthStatus::Ready
+        } else {
+            HealthStatus::Affected
+        };
+        let details = PriorityOpsHealthDetails {
+            l1_block_number: report.l1_block_number,
+            unexecuted_ops: report.pending_ops.len(),
+            first_unexecuted_op: report.pending_ops.first().map(|op| op.serial_id),
+            ops_near_deadline: report.ops_near_deadline.clone(),
+        };
+        self.health_updater
+            .update(Health::from(status).with_details(details));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use micro_types::{L1BatchNumber, L1BlockNumber, MiniblockNumber, H256};
+
+    use super::*;
+
+    fn op_status(
+        serial_id: u64,
+        l1_block_number: u32,
+        received_at: DateTime<Utc>,
+    ) -> PriorityOpStatus {
+        PriorityOpStatus {
+            serial_id: PriorityOpId(serial_id),
+            tx_hash: H256::from_low_u64_be(serial_id),
+            l1_block_number: Some(L1BlockNumber(l1_block_number)),
+            deadline_block: Some(u64::from(l1_block_number) + PRIORITY_EXPIRATION),
+            received_at,
+            miniblock_number: None,
+            included_at: None,
+            l1_batch_number: None,
+        }
+    }
+
+    fn include(op: &mut PriorityOpStatus, included_at: DateTime<Utc>) {
+        op.miniblock_number = Some(MiniblockNumber(1));
+        op.included_at = Some(included_at);
+    }
+
+    #[test]
+    fn tracking_priority_op_stages() {
+        let start = Utc::now();
+        let secs = |secs: i64| start + chrono::Duration::seconds(secs);
+        let mut tracker = PriorityOpsTracker::default();
+
+        let mut already_included_op = op_status(0, 10, start);
+        include(&mut already_included_op, secs(1));
+        let mut ops = vec![already_included_op, op_status(1, 10, start)];
+        let update = tracker.update(&ops, secs(2));
+        assert_eq!(update, TrackerUpdate::default());
+
+        include(&mut ops[1], secs(5));
+        ops.push(op_status(2, 11, secs(5)));
+        let update = tracker.update(&ops, secs(6));
+        assert_eq!(update.inclusion_latencies, [Duration::from_secs(5)]);
+        assert!(update.execution_latencies.is_empty());
+
+        // Operation #0 is executed.
+        ops.remove(0);
+        ops[0].l1_batch_number = Some(L1BatchNumber(1));
+        let update = tracker.update(&ops, secs(10));
+        assert!(update.inclusion_latencies.is_empty());
+        assert_eq!(update.execution_latencies, [Duration::from_secs(10)]);
+
+        // All operations are executed.
+        let update = tracker.update(&[], secs(20));
+        assert_eq!(
+            update.execution_latencies,
+            [Duration::from_secs(20), Duration::from_secs(15)]
+        );
+        assert!(tracker.ops.is_empty());
+    }
+
+    #[test]
+    fn flagging_ops_near_deadline() {
+        let now = Utc::now();
+        let ops = vec![op_status(0, 100, now), op_status(1, 500, now)];
+        let warning_blocks = 100;
+
+        let report = create_report(ops.clone(), 1_000, warning_blocks, now);
+        assert!(report.ops_near_deadline.is_empty());
+
+        // Op #0 has 100 blocks left before the deadline.
+        let l1_block_number = 100 + PRIORITY_EXPIRATION - warning_blocks;
+        let report = create_report(ops.clone(), l1_block_number, warning_blocks, now);
+        assert_eq!(report.ops_near_deadline, [PriorityOpId(0)]);
+
+        // Op #0 is past its deadline.
+        let l1_block_number = 500 + PRIORITY_EXPIRATION - warning_blocks;
+        let report = create_report(ops, l1_block_number, warning_blocks, now);
+        assert_eq!(report.ops_near_deadline, [PriorityOpId(0), PriorityOpId(1)]);
+        assert_eq!(
+            report.pending_ops[0].blocks_to_deadline(l1_block_number),
+            Some(-300)
+        );
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::HashMap;
+
+use anyhow::Context as _;
+use async_trait::async_trait;
+use micro_config::configs::house_keeper::{BlobRetentionPolicy, L1BatchRetentionStage};
+use micro_dal::ConnectionPool;
+use micro_object_store::{Bucket, ObjectMetadata, ObjectStore};
+use micro_prover_utils::periodic_job::PeriodicJob;
+use micro_types::{aggregated_operations::AggregatedActionType, L1BatchNumber};
+
+use super::metrics::BLOBS_CLEANER_METRICS;
+
+/// Maximum number of blobs listed and processed at once, so that large buckets are not loaded into memory entirely.
+const LISTING_PAGE_SIZE: usize = 1_000;
+
+/// Extracts the number of the L1 batch a blob belongs to from its key. Returns `None` if the key
+/// does not encode an L1 batch number.
+///
+/// All keys produced by `StoredObject` implementations encode the L1 batch number as the first
+/// numeric `_`-separated component (e.g., `merkel_tree_paths_42.bin` or `42_0_3_BasicCircuits_0.bin`),
+/// with the exception of FRI proofs (`proof_{job_id}.bin`), which are keyed by the prover job ID;
+/// see [`fri_proof_job_id_from_key()`].
+fn l1_batch_number_from_key(key: &str) -> Option<L1BatchNumber> {
+    if key.starts_with("proof_") {
+        return None;
+    }
+    let stem = key.split('.').next()?;
+    stem.split('_')
+        .find(|part| !part.is_empty() && part.bytes().all(|ch| ch.is_ascii_digit()))
+        .and_then(|part| part.parse().ok())
+        .map(L1BatchNumber)
+}
+
+/// Extracts the prover job ID from the key of an FRI proof (`proof_{job_id}.bin`).
+fn fri_proof_job_id_from_key(key: &str) -> Option<u32> {
+    key.strip_prefix("proof_")?
+        .strip_suffix(".bin")?
+        .parse()
+        .ok()
+}
+
+fn action_type_for_stage(stage: L1BatchRetentionStage) -> AggregatedActionType {
+    match stage {
+        L1BatchRetentionStage::Committed => AggregatedActionType::Commit,
+        L1BatchRetentionStage::Proven => AggregatedActionType::PublishProofOnchain,
+        L1BatchRetentionStage::Executed => AggregatedActionType::Execute,
+    }
+}
+
+/// Removes object store blobs for L1 batches that have passed the retention period
+/// configured for the bucket.
+///
+/// L1 batches for FRI proofs (which are keyed by the prover job ID) are resolved using
+/// the `prover_jobs_fri` table in the prover database.
+#[derive(Debug)]
+pub struct BlobsCleaner {
+    pool: ConnectionPool,
+    prover_pool: ConnectionPool,
+    object_store: Box<dyn ObjectStore>,
+    policies: Vec<(Bucket, BlobRetentionPolicy)>,
+    dry_run: bool,
+    cleaning_interval_ms: u64,
+}
+
+impl BlobsCleaner {
+    pub fn new(
+        policies: Vec<BlobRetentionPolicy>,
+        dry_run: bool,
+        cleaning_interval_ms: u64,
+        object_store: Box<dyn ObjectStore>,
+        pool: ConnectionPool,
+        prover_pool: ConnectionPool,
+    ) -> anyhow::Result<Self> {
+        let mut policies = policies
+            .into_iter()
+            .map(|policy| {
+                let bucket: Bucket = policy.bucket.parse().map_err(anyhow::Error::msg)?;
+                anyhow::Ok((bucket, policy))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()
+            .context("invalid blob retention policy")?;
+        if !policies.is_empty() && !object_store.supports_listing() {
+            tracing::warn!(
+                "Object store {object_store:?} does not support listing blobs; blobs will not be cleaned"
+            );
+            policies.clear();
+        }
+
+        Ok(Self {
+            pool,
+            prover_pool,
+            object_store,
+            policies,
+            dry_run,
+            cleaning_interval_ms,
+        })
+    }
+
+    /// Filters objects belonging to L1 batches up to `last_expired_l1_batch` (inclusive).
+    async fn expired_objects(
+        &self,
+        objects: Vec<ObjectMetadata>,
+        last_expired_l1_batch: L1BatchNumber,
+    ) -> anyhow::Result<Vec<ObjectMetadata>> {
+        let proof_job_ids: Vec<_> = objects
+            .iter()
+            .filter_map(|object| fri_proof_job_id_from_key(&object.key))
+            .collect();
+        let proof_l1_batches = if proof_job_ids.is_empty() {
+            HashMap::new()
+        } else {
+            let mut storage = self.prover_pool.access_storage().await?;
+            storage
+                .fri_prover_jobs_dal()
+                .get_l1_batch_numbers_for_jobs(&proof_job_ids)
+                .await
+                .context("failed getting L1 batch numbers for FRI proofs")?
+        };
+
+        let expired_objects = objects.into_iter().filter(|object| {
+            let l1_batch_number = match fri_proof_job_id_from_key(&object.key) {
+                Some(job_id) => proof_l1_batches.get(&job_id).copied(),
+                None => l1_batch_number_from_key(&object.key),
+            };
+            l1_batch_number.map_or(false, |number| number <= last_expired_l1_batch)
+        });
+        Ok(expired_objects.collect())
+    }
+
+    async fn clean_bucket(
+        &self,
+        bucket: Bucket,
+        policy: &BlobRetentionPolicy,
+    ) -> anyhow::Result<()> {
+        let mut storage = self.pool.access_storage().await?;
+        let last_expired_l1_batch = storage
+            .blocks_dal()
+            .get_last_l1_batch_with_eth_tx_confirmed_before(
+                action_type_for_stage(policy.stage),
+                policy.retention_period(),
+            )
+            .await?;
+        drop(storage);
+        let Some(last_expired_l1_batch) = last_expired_l1_batch else {
+            tracing::debug!("No L1 batches with expired blobs in bucket `{bucket}`");
+            return Ok(());
+        };
+
+        let mut removed_count = 0_u64;
+        let mut removed_bytes = 0_u64;
+        let mut start_after = None;
+        loop {
+            let page = self
+                .object_store
+                .list_raw(bucket, "", start_after.as_deref(), LISTING_PAGE_SIZE)
+                .await;
+            let page = match page {
+                Ok(page) => page,
+                Err(err) => {
+                    tracing::warn!("Failed listing blobs in bucket `{bucket}`: {err}");
+                    break;
+                }
+            };
+            let is_last_page = page.len() < LISTING_PAGE_SIZE;
+            start_after = page.last().map(|object| object.key.clone());
+
+            for object in self.expired_objects(page, last_expired_l1_batch).await? {
+                if self.dry_run {
+                    tracing::info!(
+                        "Blob `{}` ({} bytes) in bucket `{bucket}` would be removed",
+                        object.key,
+                        object.size
+                    );
+                } else if let Err(err) = self.object_store.remove_raw(bucket, &object.key).await {
+                    tracing::warn!(
+                        "Failed removing blob `{}` in bucket `{bucket}`: {err}",
+                        object.key
+                    );
+                    BLOBS_CLEANER_METRICS.failed_removals[&bucket.as_str()].inc();
+                    continue;
+                }
+                removed_count += 1;
+                removed_bytes += object.size;
+            }
+
+            if is_last_page {
+                break;
+            }
+        }
+
+        if self.dry_run {
+            BLOBS_CLEANER_METRICS.reclaimable_bytes[&bucket.as_str()].set(removed_bytes);
+        } else {
+            BLOBS_CLEANER_METRICS.removed_blobs[&bucket.as_str()].inc_by(removed_count);
+            BLOBS_CLEANER_METRICS.reclaimed_bytes[&bucket.as_str()].inc_by(removed_bytes);
+        }
+        tracing::info!(
+            "Cleaned {removed_count} blobs ({removed_bytes} bytes) for L1 batches up to \
+             #{last_expired_l1_batch} in bucket `{bucket}` (dry run: {})",
+            self.dry_run
+        );
+        Ok(())
+    }
+}
+
+/// Invoked periodically to remove blobs of L1 batches that are no longer needed.
+#[async_trait]
+impl PeriodicJob for BlobsCleaner {
+    const SERVICE_NAME: &'static str = "BlobsCleaner";
+
+    async fn run_routine_task(&mut self) -> anyhow::Result<()> {
+        for (bucket, policy) in &self.policies {
+            self.clean_bucket(*bucket, policy)
+                .await
+                .with_context(|| format!("failed cleaning bucket `{bucket}`"))?;
+        }
+        Ok(())
+    }
+
+    fn polling_interval_ms(&self) -> u64 {
+        self.cleaning_interval_ms
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracting_l1_batch_number_from_key() {
+        let keys_and_numbers = [
+            ("merkel_tree_paths_42.bin", Some(42)),
+            ("witness_block_state_for_l1_batch_7.bin", Some(7)),
+            ("42_0_3_BasicCircuits_0.bin", Some(42)),
+            ("closed_form_inputs_13_2.bin", Some(13)),
+            ("aggregations_5_1_0.bin", Some(5)),
+            ("l1_batch_proof_100.bin", Some(100)),
+            (
+                "snapshot_l1_batch_9_storage_logs_part_0001.json.gzip",
+                Some(9),
+            ),
+            ("proof_12345.bin", None),
+            ("unrelated.bin", None),
+        ];
+        for (key, expected) in keys_and_numbers {
+            assert_eq!(
+                l1_batch_number_from_key(key),
+                expected.map(L1BatchNumber),
+                "{key}"
+            );
+        }
+    }
+
+    #[test]
+    fn extracting_job_id_from_fri_proof_key() {
+        assert_eq!(fri_proof_job_id_from_key("proof_12345.bin"), Some(12345));
+        assert_eq!(fri_proof_job_id_from_key("proof_.bin"), None);
+        assert_eq!(fri_proof_job_id_from_key("l1_batch_proof_100.bin"), None);
+        assert_eq!(fri_proof_job_id_from_key("merkel_tree_paths_42.bin"), None);
+    }
+}
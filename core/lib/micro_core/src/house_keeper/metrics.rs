@@ -0,0 +1,23 @@
+//! Metrics for the house keeper.
+
+use vise::{Counter, Gauge, LabeledFamily, Metrics};
+
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_house_keeper_blobs_cleaner")]
+pub(super) struct BlobsCleanerMetrics {
+    /// Number of blobs removed from the object store.
+    #[metrics(labels = ["bucket"])]
+    pub removed_blobs: LabeledFamily<&'static str, Counter>,
+    /// Total size of blobs removed from the object store.
+    #[metrics(labels = ["bucket"])]
+    pub reclaimed_bytes: LabeledFamily<&'static str, Counter>,
+    /// Total size of blobs that would be removed if the cleaner was not in the dry-run mode.
+    #[metrics(labels = ["bucket"])]
+    pub reclaimable_bytes: LabeledFamily<&'static str, Gauge<u64>>,
+    /// Number of blobs that were not removed because of an object store error.
+    #[metrics(labels = ["bucket"])]
+    pub failed_removals: LabeledFamily<&'static str, Counter>,
+}
+
+#[vise::register]
+pub(super) static BLOBS_CLEANER_METRICS: vise::Global<BlobsCleanerMetrics> = vise::Global::new();
@@ -1,3 +1,4 @@
+pub mod blobs_cleaner;
 pub mod blocks_state_reporter;
 pub mod fri_proof_compressor_job_retry_manager;
 pub mod fri_proof_compressor_queue_monitor;
@@ -7,6 +8,7 @@ pub mod fri_scheduler_circuit_queuer;
 pub mod fri_witness_generator_jobs_retry_manager;
 pub mod fri_witness_generator_queue_monitor;
 pub mod gpu_prover_queue_monitor;
+mod metrics;
 pub mod prover_job_retry_manager;
 pub mod prover_queue_monitor;
 pub mod waiting_to_queued_fri_witness_job_mover;
@@ -157,7 +157,7 @@ impl<G: L1GasPriceProvider> L2Sender<G> {
 
         let tx = L2Tx::from_request(data.into(), USED_BOOTLOADER_MEMORY_BYTES)?;
 
-        let call_result = self.tx_sender.eth_call(block_args, tx).await;
+        let call_result = self.tx_sender.eth_call(block_args, tx, None).await;
         let call_result = call_result
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()));
 
@@ -184,7 +184,7 @@ impl<G: L1GasPriceProvider> L2Sender<G> {
 
         let fee = self
             .tx_sender
-            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation)
+            .get_txs_fee_in_wei(tx.into(), scale_factor, acceptable_overestimation, None)
             .await
             .map_err(|err| Web3Error::SubmitTransactionError(err.to_string(), err.data()));
 
@@ -219,7 +219,8 @@ impl<G: L1GasPriceProvider> L2Sender<G> {
         let mut l2_tx = L2Tx::from_request(tx_request, self.config.max_tx_size)?;
         l2_tx.set_input(signed_tx, hash);
 
-        let submit_result = self.tx_sender.submit_tx(l2_tx).await;
+        // Transactions are signed by the node itself, so per-IP limits don't apply.
+        let submit_result = self.tx_sender.submit_tx(l2_tx, None).await;
         let submit_result = submit_result.map(|_| hash).map_err(|err| {
             tracing::debug!("Send raw transaction error: {err}");
             metrics::counter!(
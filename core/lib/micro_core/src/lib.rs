@@ -1,6 +1,11 @@
 #![allow(clippy::upper_case_acronyms, clippy::derive_partial_eq_without_eq)]
 
-use std::{net::Ipv4Addr, str::FromStr, sync::Arc, time::Instant};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
 
 use anyhow::Context as _;
 use futures::channel::oneshot;
@@ -43,18 +48,23 @@ use crate::{
     api_server::{
         contract_verification,
         execution_sandbox::{VmConcurrencyBarrier, VmConcurrencyLimiter},
+        graphql::GraphQlApi,
         healthcheck::HealthCheckHandle,
         tx_sender::{ApiContracts, TxSender, TxSenderBuilder, TxSenderConfig},
         web3,
-        web3::{state::InternalApiConfig, ApiServerHandles, Namespace},
+        web3::{
+            rate_limiter::ClientRateLimits, state::InternalApiConfig, statistics::StatisticsCache,
+            ApiServerHandles, Namespace,
+        },
     },
     assignments::assignments_manager::AssignmentsManager,
     basic_witness_input_producer::BasicWitnessInputProducer,
     data_fetchers::run_data_fetchers,
+    deny_list::{DenyList, DenyListUpdater},
     eth_sender::{Aggregator, EthTxAggregator, EthTxManager},
-    eth_watch::start_eth_watch,
+    eth_watch::{start_eth_watch, PriorityOpsMonitor, PriorityOpsReportReceiver},
     house_keeper::{
-        blocks_state_reporter::L1BatchMetricsReporter,
+        blobs_cleaner::BlobsCleaner, blocks_state_reporter::L1BatchMetricsReporter,
         fri_proof_compressor_job_retry_manager::FriProofCompressorJobRetryManager,
         fri_proof_compressor_queue_monitor::FriProofCompressorStatsReporter,
         fri_prover_job_retry_manager::FriProverJobRetryManager,
@@ -72,16 +82,21 @@ use crate::{
         MetadataCalculator, MetadataCalculatorConfig, MetadataCalculatorModeConfig,
     },
     metrics::{InitStage, APP_METRICS},
-    state_keeper::{create_state_keeper, MempoolFetcher, MempoolGuard, MiniblockSealer},
+    state_keeper::{
+        create_state_keeper, MempoolFetcher, MempoolGuard, MiniblockNotifications, MiniblockSealer,
+        ProtocolUpgradeScheduler, ProverBacklogMonitor,
+    },
 };
 
 pub mod api_server;
 mod assignments;
 pub mod basic_witness_input_producer;
+pub mod batch_replayer;
 pub mod block_reverter;
 mod consensus;
 pub mod consistency_checker;
 pub mod data_fetchers;
+pub mod deny_list;
 pub mod eth_sender;
 pub mod eth_watch;
 pub mod gas_tracker;
@@ -381,6 +396,57 @@ pub async fn initialize_components(
     // program termination.
     let mut storage_caches = None;
 
+    // If the API runs in the same process as the state keeper, pubsub notifiers and waits for transaction
+    // inclusion can receive sealed miniblocks directly rather than only by polling Postgres.
+    let miniblock_notifications = ((components.contains(&Component::WsApi)
+        || components.contains(&Component::HttpApi))
+        && components.contains(&Component::StateKeeper))
+    .then(MiniblockNotifications::default);
+
+    // The mempool is created upfront so that, if the API runs in the same process as the state keeper,
+    // it can be exposed via the `txpool` namespace.
+    let mempool = if components.contains(&Component::StateKeeper) {
+        let mempool_config = configs.mempool_config.clone().context("mempool_config")?;
+        let next_priority_id = connection_pool
+            .access_storage()
+            .await
+            .context("access_storage()")?
+            .transactions_dal()
+            .next_priority_id()
+            .await;
+        Some(MempoolGuard::from_config(next_priority_id, &mempool_config))
+    } else {
+        None
+    };
+
+    // Similarly, the channel with priority ops reports is created upfront so that the report generated
+    // by the priority ops monitor can be served by the API servers running in this process.
+    let (priority_ops_report_sender, priority_ops_report) = watch::channel(None);
+    let priority_ops_report = components
+        .contains(&Component::EthWatcher)
+        .then_some(priority_ops_report);
+
+    // The deny-list is shared by the API servers and the state keeper running in this process,
+    // and is periodically reloaded from Postgres to pick up changes made via the admin API.
+    let deny_list = if components.contains(&Component::WsApi)
+        || components.contains(&Component::HttpApi)
+        || components.contains(&Component::StateKeeper)
+    {
+        let mut storage = connection_pool
+            .access_storage_tagged("deny_list")
+            .await
+            .context("access_storage_tagged()")?;
+        let deny_list = DenyList::load(&mut storage)
+            .await
+            .context("failed loading deny-list")?;
+        drop(storage);
+        let updater = DenyListUpdater::new(deny_list.clone(), connection_pool.clone());
+        task_futures.push(tokio::spawn(updater.run(stop_receiver.clone())));
+        Some(deny_list)
+    } else {
+        None
+    };
+
     if components.contains(&Component::WsApi)
         || components.contains(&Component::HttpApi)
         || components.contains(&Component::ContractVerificationApi)
@@ -391,9 +457,11 @@ pub async fn initialize_components(
             .clone()
             .context("state_keeper_config")?;
         let network_config = configs.network_config.clone().context("network_config")?;
+        let mempool_config = configs.mempool_config.clone().context("mempool_config")?;
         let tx_sender_config = TxSenderConfig::new(
             &state_keeper_config,
             &api_config.web3_json_rpc,
+            &mempool_config,
             network_config.micro_network_id,
         );
         let internal_api_config = InternalApiConfig::new(
@@ -402,6 +470,19 @@ pub async fn initialize_components(
             &contracts_config,
         );
 
+        // Network statistics are aggregated once and shared by all API servers.
+        let statistics = (components.contains(&Component::HttpApi)
+            || components.contains(&Component::WsApi))
+        .then(|| {
+            let (statistics, update_task) = StatisticsCache::new(
+                replica_connection_pool.clone(),
+                StatisticsCache::DEFAULT_UPDATE_INTERVAL,
+            );
+            // The update task takes care of its termination, so we don't need to retain its handle.
+            tokio::spawn(update_task);
+            statistics
+        });
+
         if components.contains(&Component::HttpApi) {
             storage_caches = Some(
                 build_storage_caches(configs, &replica_connection_pool, &mut task_futures)
@@ -426,6 +507,11 @@ pub async fn initialize_components(
                 bounded_gas_adjuster.clone(),
                 state_keeper_config.save_call_traces,
                 storage_caches.clone().unwrap(),
+                statistics.clone().unwrap(),
+                miniblock_notifications.clone(),
+                mempool.clone(),
+                deny_list.clone(),
+                priority_ops_report.clone(),
             )
             .await
             .context("run_http_api")?;
@@ -438,6 +524,41 @@ pub async fn initialize_components(
                 "Initialized HTTP API on {:?} in {elapsed:?}",
                 server_handles.local_addr
             );
+
+            if let Some(admin_addr) = api_config.web3_json_rpc.admin_http_addr() {
+                let server_handles = run_admin_api(
+                    &tx_sender_config,
+                    &state_keeper_config,
+                    &internal_api_config,
+                    &api_config,
+                    admin_addr,
+                    connection_pool.clone(),
+                    replica_connection_pool.clone(),
+                    stop_receiver.clone(),
+                    bounded_gas_adjuster.clone(),
+                    storage_caches.clone().unwrap(),
+                    statistics.clone().unwrap(),
+                    deny_list.clone(),
+                )
+                .await
+                .context("run_admin_api")?;
+
+                task_futures.extend(server_handles.tasks);
+                healthchecks.push(Box::new(server_handles.health_check));
+                tracing::info!("Initialized admin API on {:?}", server_handles.local_addr);
+            }
+
+            if let Some(graphql_addr) = api_config.web3_json_rpc.graphql_bind_addr() {
+                let graphql_api = GraphQlApi::new(
+                    replica_connection_pool.clone(),
+                    &api_config.web3_json_rpc,
+                    network_config.micro_network_id,
+                );
+                task_futures.push(tokio::spawn(
+                    graphql_api.run_server(graphql_addr, stop_receiver.clone()),
+                ));
+                tracing::info!("Initialized GraphQL API on {graphql_addr}");
+            }
         }
 
         if components.contains(&Component::WsApi) {
@@ -464,6 +585,11 @@ pub async fn initialize_components(
                 replica_connection_pool.clone(),
                 stop_receiver.clone(),
                 storage_caches,
+                statistics.clone().unwrap(),
+                miniblock_notifications.clone(),
+                mempool.clone(),
+                deny_list.clone(),
+                priority_ops_report.clone(),
             )
             .await
             .context("run_ws_api")?;
@@ -497,7 +623,10 @@ pub async fn initialize_components(
         .object_store_config
         .clone()
         .context("object_store_config")?;
-    let store_factory = ObjectStoreFactory::new(object_store_config);
+    let store_factory = ObjectStoreFactory::with_mirror(
+        object_store_config,
+        configs.mirror_object_store_config.clone(),
+    );
 
     if components.contains(&Component::StateKeeper) {
         let started_at = Instant::now();
@@ -508,6 +637,7 @@ pub async fn initialize_components(
             .context("gas_adjuster.get_or_init()")?;
         add_state_keeper_to_task_futures(
             &mut task_futures,
+            &mut healthchecks,
             &postgres_config,
             &contracts_config,
             configs
@@ -519,6 +649,9 @@ pub async fn initialize_components(
             &configs.mempool_config.clone().context("mempool_config")?,
             bounded_gas_adjuster,
             store_factory.create_store().await,
+            mempool.clone().context("mempool")?,
+            deny_list.clone().context("deny_list")?,
+            miniblock_notifications,
             stop_receiver.clone(),
         )
         .await
@@ -542,6 +675,21 @@ pub async fn initialize_components(
             .eth_watch_config
             .clone()
             .context("eth_watch_config")?;
+
+        let priority_ops_monitor_pool = ConnectionPool::singleton(postgres_config.replica_url()?)
+            .build()
+            .await
+            .context("failed to build priority_ops_monitor_pool")?;
+        let priority_ops_monitor = PriorityOpsMonitor::new(
+            query_client.clone(),
+            eth_watch_config.poll_interval(),
+            eth_watch_config.priority_op_deadline_warning_blocks,
+            priority_ops_report_sender,
+        );
+        healthchecks.push(Box::new(priority_ops_monitor.health_check()));
+        task_futures.push(tokio::spawn(
+            priority_ops_monitor.run(priority_ops_monitor_pool, stop_receiver.clone()),
+        ));
         task_futures.push(
             start_eth_watch(
                 eth_watch_config,
@@ -666,9 +814,11 @@ pub async fn initialize_components(
             .clone()
             .context("state_keeper_config")?;
         let api_config = configs.api_config.clone().context("api_config")?;
+        let mempool_config = configs.mempool_config.clone().context("mempool_config")?;
         let tx_sender_config = TxSenderConfig::new(
             &state_keeper_config,
             &api_config.web3_json_rpc,
+            &mempool_config,
             network_config.micro_network_id,
         );
 
@@ -685,6 +835,7 @@ pub async fn initialize_components(
             connection_pool.clone(),
             bounded_gas_adjuster.clone(),
             storage_caches.clone(),
+            None,
         )
         .await;
 
@@ -762,7 +913,7 @@ pub async fn initialize_components(
     }
 
     if components.contains(&Component::Housekeeper) {
-        add_house_keeper_to_task_futures(configs, &mut task_futures)
+        add_house_keeper_to_task_futures(configs, &store_factory, &mut task_futures)
             .await
             .context("add_house_keeper_to_task_futures()")?;
     }
@@ -804,6 +955,7 @@ pub async fn initialize_components(
 #[allow(clippy::too_many_arguments)]
 async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync + 'static>(
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
+    healthchecks: &mut Vec<Box<dyn CheckHealth>>,
     postgres_config: &PostgresConfig,
     contracts_config: &ContractsConfig,
     state_keeper_config: StateKeeperConfig,
@@ -812,6 +964,9 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
     mempool_config: &MempoolConfig,
     gas_adjuster: Arc<E>,
     object_store: Box<dyn ObjectStore>,
+    mempool: MempoolGuard,
+    deny_list: DenyList,
+    miniblock_notifications: Option<MiniblockNotifications>,
     stop_receiver: watch::Receiver<bool>,
 ) -> anyhow::Result<()> {
     let fair_l2_gas_price = state_keeper_config.fair_l2_gas_price;
@@ -820,14 +975,6 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         .build()
         .await
         .context("failed to build state_keeper_pool")?;
-    let next_priority_id = state_keeper_pool
-        .access_storage()
-        .await
-        .unwrap()
-        .transactions_dal()
-        .next_priority_id()
-        .await;
-    let mempool = MempoolGuard::new(next_priority_id, mempool_config.capacity);
     mempool.register_metrics();
 
     let miniblock_sealer_pool = pool_builder
@@ -838,8 +985,37 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         miniblock_sealer_pool,
         state_keeper_config.miniblock_seal_queue_capacity,
     );
+    let miniblock_sealer = match miniblock_notifications {
+        Some(notifications) => miniblock_sealer.with_notifications(notifications),
+        None => miniblock_sealer,
+    };
     task_futures.push(tokio::spawn(miniblock_sealer.run()));
 
+    // The prover backlog is only monitored if the backlog-aware L1 batch sealing is enabled.
+    let prover_backlog = if state_keeper_config
+        .prover_backlog_max_block_commit_deadline_ms
+        .is_some()
+    {
+        let prover_backlog_pool = pool_builder
+            .build()
+            .await
+            .context("failed to build prover_backlog_pool")?;
+        let (monitor, prover_backlog) = ProverBacklogMonitor::new(prover_backlog_pool);
+        task_futures.push(tokio::spawn(monitor.run(stop_receiver.clone())));
+        Some(prover_backlog)
+    } else {
+        None
+    };
+
+    // The state keeper only activates protocol upgrades that are checked by the scheduler.
+    let upgrade_scheduler_pool = pool_builder
+        .build()
+        .await
+        .context("failed to build upgrade_scheduler_pool")?;
+    let upgrade_scheduler = ProtocolUpgradeScheduler::new(upgrade_scheduler_pool);
+    healthchecks.push(Box::new(upgrade_scheduler.health_check()));
+    task_futures.push(tokio::spawn(upgrade_scheduler.run(stop_receiver.clone())));
+
     let state_keeper = create_state_keeper(
         contracts_config,
         state_keeper_config,
@@ -851,9 +1027,11 @@ async fn add_state_keeper_to_task_futures<E: L1GasPriceProvider + Send + Sync +
         gas_adjuster.clone(),
         miniblock_sealer_handle,
         object_store,
+        prover_backlog,
         stop_receiver.clone(),
     )
-    .await;
+    .await
+    .with_deny_list(deny_list);
     task_futures.push(tokio::spawn(state_keeper.run()));
 
     let mempool_fetcher_pool = pool_builder
@@ -1014,6 +1192,7 @@ async fn add_basic_witness_input_producer_to_task_futures(
 
 async fn add_house_keeper_to_task_futures(
     configs: &TempConfigStore,
+    store_factory: &ObjectStoreFactory,
     task_futures: &mut Vec<JoinHandle<anyhow::Result<()>>>,
 ) -> anyhow::Result<()> {
     let house_keeper_config = configs
@@ -1139,6 +1318,19 @@ async fn add_house_keeper_to_task_futures(
         prover_connection_pool.clone(),
     );
     task_futures.push(tokio::spawn(fri_proof_compressor_retry_manager.run()));
+
+    if let Some(cleaning_interval_ms) = house_keeper_config.blobs_cleaning_interval_ms {
+        let blobs_cleaner = BlobsCleaner::new(
+            house_keeper_config.blobs_retention_policies.clone(),
+            house_keeper_config.blobs_cleaning_dry_run,
+            cleaning_interval_ms,
+            store_factory.create_store().await,
+            connection_pool.clone(),
+            prover_connection_pool.clone(),
+        )
+        .context("BlobsCleaner::new()")?;
+        task_futures.push(tokio::spawn(blobs_cleaner.run()));
+    }
     Ok(())
 }
 
@@ -1176,6 +1368,7 @@ async fn build_tx_sender<G: L1GasPriceProvider>(
     master_pool: ConnectionPool,
     l1_gas_price_provider: Arc<G>,
     storage_caches: PostgresStorageCaches,
+    deny_list: Option<DenyList>,
 ) -> (TxSender<G>, VmConcurrencyBarrier) {
     let mut tx_sender_builder = TxSenderBuilder::new(tx_sender_config.clone(), replica_pool)
         .with_main_connection_pool(master_pool)
@@ -1185,6 +1378,9 @@ async fn build_tx_sender<G: L1GasPriceProvider>(
     if let Some(transactions_per_sec_limit) = web3_json_config.transactions_per_sec_limit {
         tx_sender_builder = tx_sender_builder.with_rate_limiter(transactions_per_sec_limit);
     };
+    if let Some(deny_list) = deny_list {
+        tx_sender_builder = tx_sender_builder.with_deny_list(deny_list);
+    }
 
     let max_concurrency = web3_json_config.vm_concurrency_limit();
     let (vm_concurrency_limiter, vm_barrier) = VmConcurrencyLimiter::new(max_concurrency);
@@ -1213,6 +1409,11 @@ async fn run_http_api<G: L1GasPriceProvider + Send + Sync + 'static>(
     gas_adjuster: Arc<G>,
     with_debug_namespace: bool,
     storage_caches: PostgresStorageCaches,
+    statistics: StatisticsCache,
+    miniblock_notifications: Option<MiniblockNotifications>,
+    mempool: Option<MempoolGuard>,
+    deny_list: Option<DenyList>,
+    priority_ops_report: Option<PriorityOpsReportReceiver>,
 ) -> anyhow::Result<ApiServerHandles> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1222,21 +1423,32 @@ async fn run_http_api<G: L1GasPriceProvider + Send + Sync + 'static>(
         master_connection_pool,
         gas_adjuster,
         storage_caches,
+        deny_list,
     )
     .await;
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
     if with_debug_namespace {
-        namespaces.push(Namespace::Debug)
+        // Both namespaces rely on call traces persisted by the state keeper.
+        namespaces.push(Namespace::Debug);
+        namespaces.push(Namespace::Trace);
     }
     namespaces.push(Namespace::Snapshots);
+    if api_config.web3_json_rpc.prover_namespace_enabled() {
+        namespaces.push(Namespace::Prover);
+    }
+    if mempool.is_some() && api_config.web3_json_rpc.txpool_namespace_enabled() {
+        namespaces.push(Namespace::TxPool);
+    }
 
     let last_miniblock_pool = ConnectionPool::singleton(postgres_config.replica_url()?)
         .build()
         .await
         .context("failed to build last_miniblock_pool")?;
 
-    let api_builder =
+    let client_rate_limits = ClientRateLimits::from_config(&api_config.web3_json_rpc)
+        .context("invalid per-client rate limits")?;
+    let mut api_builder =
         web3::ApiBuilder::jsonrpsee_backend(internal_api.clone(), replica_connection_pool)
             .http(api_config.web3_json_rpc.http_port)
             .with_last_miniblock_pool(last_miniblock_pool)
@@ -1245,11 +1457,63 @@ async fn run_http_api<G: L1GasPriceProvider + Send + Sync + 'static>(
             .with_tree_api(api_config.web3_json_rpc.tree_api_url())
             .with_batch_request_size_limit(api_config.web3_json_rpc.max_batch_request_size())
             .with_response_body_size_limit(api_config.web3_json_rpc.max_response_body_size())
+            .with_client_rate_limits(client_rate_limits)
+            .with_trusted_proxies(api_config.web3_json_rpc.trusted_proxies().to_vec())
             .with_tx_sender(tx_sender, vm_barrier)
+            .with_statistics(statistics)
             .enable_api_namespaces(namespaces);
+    if let Some(notifications) = miniblock_notifications {
+        api_builder = api_builder.with_miniblock_notifications(notifications);
+    }
+    if let Some(mempool) = mempool {
+        api_builder = api_builder.with_mempool(mempool);
+    }
+    if let Some(report) = priority_ops_report {
+        api_builder = api_builder.with_priority_ops_report(report);
+    }
     api_builder.build(stop_receiver).await
 }
 
+/// Runs the internal HTTP server exposing only the `admin` namespace. The server has no authentication,
+/// so it is bound to a dedicated address separate from the public API servers.
+#[allow(clippy::too_many_arguments)]
+async fn run_admin_api<G: L1GasPriceProvider + Send + Sync + 'static>(
+    tx_sender_config: &TxSenderConfig,
+    state_keeper_config: &StateKeeperConfig,
+    internal_api: &InternalApiConfig,
+    api_config: &ApiConfig,
+    addr: SocketAddr,
+    master_connection_pool: ConnectionPool,
+    replica_connection_pool: ConnectionPool,
+    stop_receiver: watch::Receiver<bool>,
+    gas_adjuster: Arc<G>,
+    storage_caches: PostgresStorageCaches,
+    statistics: StatisticsCache,
+    deny_list: Option<DenyList>,
+) -> anyhow::Result<ApiServerHandles> {
+    let (tx_sender, vm_barrier) = build_tx_sender(
+        tx_sender_config,
+        &api_config.web3_json_rpc,
+        state_keeper_config,
+        replica_connection_pool.clone(),
+        master_connection_pool,
+        gas_adjuster,
+        storage_caches,
+        deny_list,
+    )
+    .await;
+
+    web3::ApiBuilder::jsonrpsee_backend(internal_api.clone(), replica_connection_pool)
+        .http_addr(addr)
+        .with_health_check_name("admin_api")
+        .with_threads(1)
+        .with_tx_sender(tx_sender, vm_barrier)
+        .with_statistics(statistics)
+        .enable_api_namespaces(vec![Namespace::Admin])
+        .build(stop_receiver)
+        .await
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
     postgres_config: &PostgresConfig,
@@ -1262,6 +1526,11 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
     replica_connection_pool: ConnectionPool,
     stop_receiver: watch::Receiver<bool>,
     storage_caches: PostgresStorageCaches,
+    statistics: StatisticsCache,
+    miniblock_notifications: Option<MiniblockNotifications>,
+    mempool: Option<MempoolGuard>,
+    deny_list: Option<DenyList>,
+    priority_ops_report: Option<PriorityOpsReportReceiver>,
 ) -> anyhow::Result<ApiServerHandles> {
     let (tx_sender, vm_barrier) = build_tx_sender(
         tx_sender_config,
@@ -1271,6 +1540,7 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
         master_connection_pool,
         gas_adjuster,
         storage_caches,
+        deny_list,
     )
     .await;
     let last_miniblock_pool = ConnectionPool::singleton(postgres_config.replica_url()?)
@@ -1280,8 +1550,16 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
 
     let mut namespaces = Namespace::DEFAULT.to_vec();
     namespaces.push(Namespace::Snapshots);
+    if api_config.web3_json_rpc.prover_namespace_enabled() {
+        namespaces.push(Namespace::Prover);
+    }
+    if mempool.is_some() && api_config.web3_json_rpc.txpool_namespace_enabled() {
+        namespaces.push(Namespace::TxPool);
+    }
 
-    let api_builder =
+    let client_rate_limits = ClientRateLimits::from_config(&api_config.web3_json_rpc)
+        .context("invalid per-client rate limits")?;
+    let mut api_builder =
         web3::ApiBuilder::jsonrpc_backend(internal_api.clone(), replica_connection_pool)
             .ws(api_config.web3_json_rpc.ws_port)
             .with_last_miniblock_pool(last_miniblock_pool)
@@ -1294,11 +1572,23 @@ async fn run_ws_api<G: L1GasPriceProvider + Send + Sync + 'static>(
                     .web3_json_rpc
                     .websocket_requests_per_minute_limit(),
             )
+            .with_client_rate_limits(client_rate_limits)
+            .with_trusted_proxies(api_config.web3_json_rpc.trusted_proxies().to_vec())
             .with_polling_interval(api_config.web3_json_rpc.pubsub_interval())
             .with_threads(api_config.web3_json_rpc.ws_server_threads())
             .with_tree_api(api_config.web3_json_rpc.tree_api_url())
             .with_tx_sender(tx_sender, vm_barrier)
+            .with_statistics(statistics)
             .enable_api_namespaces(namespaces);
+    if let Some(notifications) = miniblock_notifications {
+        api_builder = api_builder.with_miniblock_notifications(notifications);
+    }
+    if let Some(mempool) = mempool {
+        api_builder = api_builder.with_mempool(mempool);
+    }
+    if let Some(report) = priority_ops_report {
+        api_builder = api_builder.with_priority_ops_report(report);
+    }
 
     api_builder.build(stop_receiver.clone()).await
 }
@@ -32,7 +32,7 @@ use crate::{
         },
         mempool_actor::l2_tx_filter,
         metrics::KEEPER_METRICS,
-        seal_criteria::{IoSealCriteria, TimeoutSealer},
+        seal_criteria::{IoSealCriteria, ProverBacklogDeadline, TimeoutSealer, UnexecutableReason},
         updates::UpdatesManager,
         MempoolGuard,
     },
@@ -172,10 +172,28 @@ where
                 self.fair_l2_gas_price
             );
             let mut storage = self.pool.access_storage().await.unwrap();
-            let (base_system_contracts, protocol_version) = storage
+            // Protocol upgrades are only activated once they pass pre-flight checks and reach
+            // the activation conditions set by the operator.
+            let protocol_version = storage
+                .protocol_upgrade_schedules_dal()
+                .activatable_version(current_timestamp, self.current_l1_batch_number)
+                .await
+                .unwrap();
+            let Some(protocol_version) = protocol_version else {
+                // May happen if the initial protocol version is not yet activated; we wait until it is.
+                tracing::warn!(
+                    "No protocol versions can be activated for L1 batch #{} with timestamp {current_timestamp}",
+                    self.current_l1_batch_number.0
+                );
+                drop(storage);
+                tokio::time::sleep(self.delay_interval).await;
+                continue;
+            };
+            let base_system_contracts = storage
                 .protocol_versions_dal()
-                .base_system_contracts_by_timestamp(current_timestamp)
-                .await;
+                .load_base_system_contracts_by_version_id(protocol_version as u16)
+                .await
+                .expect("base system contracts for the activated protocol version are missing");
 
             return Some(l1_batch_params(
                 self.current_l1_batch_number,
@@ -241,11 +259,11 @@ where
         self.mempool.insert(vec![tx], HashMap::new());
     }
 
-    async fn reject(&mut self, rejected: &Transaction, error: &str) {
+    async fn reject(&mut self, rejected: &Transaction, reason: &UnexecutableReason) {
         assert!(
             !rejected.is_l1(),
             "L1 transactions should not be rejected: {}",
-            error
+            reason
         );
 
         // Reset the nonces in the mempool, but don't insert the transaction back.
@@ -257,15 +275,20 @@ where
             .access_storage_tagged("state_keeper")
             .await
             .unwrap();
-        KEEPER_METRICS.rejected_transactions.inc();
+        let code: &'static str = reason.code.into();
+        KEEPER_METRICS.rejected_transactions[&code].inc();
         tracing::warn!(
-            "transaction {} is rejected with error {}",
+            "transaction {} is rejected ({code}) with error {}",
             rejected.hash(),
-            error
+            reason
         );
         storage
             .transactions_dal()
-            .mark_tx_as_rejected(rejected.hash(), &format!("rejected: {}", error))
+            .mark_tx_as_rejected(
+                rejected.hash(),
+                reason.code,
+                &format!("rejected: {}", reason),
+            )
             .await;
     }
 
@@ -460,6 +483,12 @@ impl<G: L1GasPriceProvider> MempoolIO<G> {
         }
     }
 
+    /// Makes the L1 batch commit deadline depend on the prover backlog.
+    pub(crate) fn with_prover_backlog(mut self, prover_backlog: ProverBacklogDeadline) -> Self {
+        self.timeout_sealer = self.timeout_sealer.with_prover_backlog(prover_backlog);
+        self
+    }
+
     async fn load_previous_l1_batch_hash(&self) -> U256 {
         tracing::info!(
             "Getting previous L1 batch hash for L1 batch #{}",
@@ -14,14 +14,16 @@ use multivm::interface::{FinishedL1Batch, L1BatchEnv, SystemEnv};
 use tokio::sync::{mpsc, oneshot};
 
 pub(crate) use self::mempool::MempoolIO;
+pub use self::notifications::{MiniblockNotifications, SealedMiniblock};
 use super::{
     metrics::{MiniblockQueueStage, MINIBLOCK_METRICS},
-    seal_criteria::IoSealCriteria,
+    seal_criteria::{IoSealCriteria, UnexecutableReason},
     updates::{MiniblockSealCommand, UpdatesManager},
 };
 
 pub(crate) mod common;
 pub(crate) mod mempool;
+mod notifications;
 pub(crate) mod seal_logic;
 #[cfg(test)]
 mod tests;
@@ -89,7 +91,7 @@ pub trait StateKeeperIO: 'static + Send + IoSealCriteria {
     /// Marks the transaction as "not executed", so it can be retrieved from the IO again.
     async fn rollback(&mut self, tx: Transaction);
     /// Marks the transaction as "rejected", e.g. one that is not correct and can't be executed.
-    async fn reject(&mut self, tx: &Transaction, error: &str);
+    async fn reject(&mut self, tx: &Transaction, reason: &UnexecutableReason);
     /// Marks the miniblock (aka L2 block) as sealed.
     /// Returns the timestamp for the next miniblock.
     async fn seal_miniblock(&mut self, updates_manager: &UpdatesManager);
@@ -212,6 +214,7 @@ pub struct MiniblockSealer {
     // Weak sender handle to get queue capacity stats.
     commands_sender: mpsc::WeakSender<Completable<MiniblockSealCommand>>,
     commands_receiver: mpsc::Receiver<Completable<MiniblockSealCommand>>,
+    notifications: Option<MiniblockNotifications>,
 }
 
 impl MiniblockSealer {
@@ -227,6 +230,7 @@ impl MiniblockSealer {
             is_sync,
             commands_sender: commands_sender.downgrade(),
             commands_receiver,
+            notifications: None,
         };
         let handle = MiniblockSealerHandle {
             commands_sender,
@@ -236,6 +240,12 @@ impl MiniblockSealer {
         (this, handle)
     }
 
+    /// Publishes data of sealed miniblocks to the provided channel.
+    pub fn with_notifications(mut self, notifications: MiniblockNotifications) -> Self {
+        self.notifications = Some(notifications);
+        self
+    }
+
     /// Seals miniblocks as they are received from the [`MiniblockSealerHandle`]. This should be run
     /// on a separate Tokio task.
     pub async fn run(mut self) -> anyhow::Result<()> {
@@ -260,6 +270,12 @@ impl MiniblockSealer {
                 .await
                 .unwrap();
             completable.command.seal(&mut conn).await;
+            if let Some(notifications) = &self.notifications {
+                if notifications.has_receivers() {
+                    let sealed_at = Instant::now();
+                    notifications.publish(completable.command.sealed_miniblock(sealed_at));
+                }
+            }
             if let Some(delta) = miniblock_seal_delta {
                 MINIBLOCK_METRICS.seal_delta.observe(delta.elapsed());
             }
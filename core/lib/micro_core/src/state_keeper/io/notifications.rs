@@ -0,0 +1,54 @@
+//! In-process notifications about sealed miniblocks.
+
+use std::{sync::Arc, time::Instant};
+
+use micro_types::{api::Log, web3::types::BlockHeader, MiniblockNumber, H256};
+use tokio::sync::broadcast;
+
+/// Data of a sealed miniblock published to in-process consumers, such as Web3 pubsub notifiers.
+/// Headers and logs have the same shape as ones returned by the DAL for freshly sealed miniblocks.
+#[derive(Debug)]
+pub struct SealedMiniblock {
+    pub number: MiniblockNumber,
+    pub header: BlockHeader,
+    pub tx_hashes: Vec<H256>,
+    pub logs: Vec<Log>,
+    /// Instant at which the miniblock was committed to Postgres.
+    pub sealed_at: Instant,
+}
+
+/// Broadcast channel for [`SealedMiniblock`]s. Notifications are best-effort: they are only delivered
+/// to currently subscribed receivers, and slow receivers may miss some of them.
+#[derive(Debug, Clone)]
+pub struct MiniblockNotifications {
+    sender: broadcast::Sender<Arc<SealedMiniblock>>,
+}
+
+impl Default for MiniblockNotifications {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl MiniblockNotifications {
+    const DEFAULT_CAPACITY: usize = 128;
+
+    /// Creates a channel buffering up to `capacity` notifications for each receiver.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<SealedMiniblock>> {
+        self.sender.subscribe()
+    }
+
+    pub(crate) fn publish(&self, miniblock: SealedMiniblock) {
+        // An error only means that there are no receivers at the moment, which is fine.
+        self.sender.send(Arc::new(miniblock)).ok();
+    }
+
+    pub(crate) fn has_receivers(&self) -> bool {
+        self.sender.receiver_count() > 0
+    }
+}
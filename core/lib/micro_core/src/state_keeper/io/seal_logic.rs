@@ -8,8 +8,9 @@ use std::{
 
 use itertools::Itertools;
 use micro_dal::{blocks_dal::ConsensusBlockFields, StorageProcessor};
-use micro_system_constants::ACCOUNT_CODE_STORAGE_ADDRESS;
+use micro_system_constants::{ACCOUNT_CODE_STORAGE_ADDRESS, EMPTY_UNCLES_HASH};
 use micro_types::{
+    api::Log,
     block::{unpack_block_info, L1BatchHeader, MiniblockHeader},
     event::{extract_added_tokens, extract_long_l2_to_l1_messages},
     l1::L1Tx,
@@ -21,10 +22,11 @@ use micro_types::{
         tx_execution_info::DeduplicatedWritesMetrics, IncludedTxLocation,
         TransactionExecutionResult,
     },
+    web3::types::{BlockHeader, Index, U64},
     zkevm_test_harness::witness::sort_storage_access::sort_storage_access_queries,
-    AccountTreeId, Address, ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber, LogQuery,
-    MiniblockNumber, StorageKey, StorageLog, StorageLogQuery, StorageValue, Transaction, VmEvent,
-    CURRENT_VIRTUAL_BLOCK_INFO_POSITION, H256, SYSTEM_CONTEXT_ADDRESS,
+    AccountTreeId, Address, Bytes, ExecuteTransactionCommon, L1BatchNumber, L1BlockNumber,
+    LogQuery, MiniblockNumber, StorageKey, StorageLog, StorageLogQuery, StorageValue, Transaction,
+    VmEvent, CURRENT_VIRTUAL_BLOCK_INFO_POSITION, H2048, H256, SYSTEM_CONTEXT_ADDRESS, U256,
 };
 // TODO (SMA-1206): use seconds instead of milliseconds.
 use micro_utils::{h256_to_u256, time::millis_since_epoch, u256_to_h256};
@@ -34,6 +36,7 @@ use crate::{
     metrics::{BlockStage, MiniblockStage, APP_METRICS},
     state_keeper::{
         extractors,
+        io::notifications::SealedMiniblock,
         metrics::{L1BatchSealStage, MiniblockSealStage, L1_BATCH_METRICS, MINIBLOCK_METRICS},
         updates::{MiniblockSealCommand, UpdatesManager},
     },
@@ -265,6 +268,68 @@ impl MiniblockSealCommand {
         self.seal_inner(storage, false).await;
     }
 
+    /// Converts this command to a notification about the sealed miniblock. Should be called after
+    /// the miniblock is sealed.
+    pub(super) fn sealed_miniblock(&self, sealed_at: Instant) -> SealedMiniblock {
+        let block_hash = self.miniblock.get_miniblock_hash();
+        let block_number = U64::from(self.miniblock_number.0);
+        let header = BlockHeader {
+            hash: Some(block_hash),
+            parent_hash: H256::zero(),
+            uncles_hash: EMPTY_UNCLES_HASH,
+            author: Address::zero(),
+            state_root: H256::zero(),
+            transactions_root: H256::zero(),
+            receipts_root: H256::zero(),
+            number: Some(block_number),
+            gas_used: U256::zero(),
+            gas_limit: U256::zero(),
+            base_fee_per_gas: None,
+            extra_data: Bytes::default(),
+            logs_bloom: H2048::default(),
+            timestamp: U256::from(self.miniblock.timestamp),
+            difficulty: U256::zero(),
+            mix_hash: None,
+            nonce: None,
+        };
+
+        let mut logs = Vec::with_capacity(self.miniblock.events.len());
+        for (location, events) in self.extract_events(false) {
+            for event in events {
+                let log_index = U256::from(logs.len());
+                logs.push(Log {
+                    address: event.address,
+                    topics: event.indexed_topics.clone(),
+                    data: Bytes(event.value.clone()),
+                    block_hash: Some(block_hash),
+                    block_number: Some(block_number),
+                    // The L1 batch is not sealed yet.
+                    l1_batch_number: None,
+                    transaction_hash: Some(location.tx_hash),
+                    transaction_index: Some(Index::from(location.tx_index_in_miniblock)),
+                    log_index: Some(log_index),
+                    // Matches the representation returned by the DAL.
+                    transaction_log_index: Some(log_index),
+                    log_type: None,
+                    removed: Some(false),
+                });
+            }
+        }
+
+        SealedMiniblock {
+            number: self.miniblock_number,
+            header,
+            tx_hashes: self
+                .miniblock
+                .executed_transactions
+                .iter()
+                .map(|tx| tx.hash)
+                .collect(),
+            logs,
+            sealed_at,
+        }
+    }
+
     /// Seals a miniblock with the given number.
     ///
     /// If `is_fictive` flag is set to true, then it is assumed that we should seal a fictive miniblock
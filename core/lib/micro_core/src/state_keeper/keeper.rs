@@ -6,7 +6,7 @@ use std::{
 use anyhow::Context as _;
 use micro_types::{
     block::MiniblockExecutionData, l2::TransactionType, protocol_version::ProtocolUpgradeTx,
-    storage_writes_deduplicator::StorageWritesDeduplicator, Transaction,
+    storage_writes_deduplicator::StorageWritesDeduplicator, vm_trace::Call, Transaction,
 };
 use multivm::interface::{Halt, L1BatchEnv, SystemEnv};
 use tokio::sync::watch;
@@ -16,11 +16,11 @@ use super::{
     extractors,
     io::{MiniblockParams, PendingBatchData, StateKeeperIO},
     metrics::{AGGREGATION_METRICS, KEEPER_METRICS, L1_BATCH_METRICS},
-    seal_criteria::{ConditionalSealer, SealData, SealResolution},
+    seal_criteria::{ConditionalSealer, SealData, SealResolution, UnexecutableReason},
     types::ExecutionMetricsForCriteria,
     updates::UpdatesManager,
 };
-use crate::gas_tracker::gas_count_from_writes;
+use crate::{deny_list::DenyList, gas_tracker::gas_count_from_writes};
 
 /// Amount of time to block on waiting for some resource. The exact value is not really important,
 /// we only need it to not block on waiting indefinitely and be able to process cancellation requests.
@@ -59,6 +59,7 @@ pub struct MicroStateKeeper {
     io: Box<dyn StateKeeperIO>,
     batch_executor_base: Box<dyn L1BatchExecutorBuilder>,
     sealer: Option<ConditionalSealer>,
+    deny_list: Option<DenyList>,
 }
 
 impl MicroStateKeeper {
@@ -73,6 +74,7 @@ impl MicroStateKeeper {
             io,
             batch_executor_base,
             sealer: Some(sealer),
+            deny_list: None,
         }
     }
 
@@ -86,9 +88,17 @@ impl MicroStateKeeper {
             io,
             batch_executor_base,
             sealer: None,
+            deny_list: None,
         }
     }
 
+    /// Sets the deny-list for L2 transactions. Transactions initiated by, sent to or (if call tracing is enabled)
+    /// calling a listed address are rejected. Priority operations are not affected.
+    pub fn with_deny_list(mut self, deny_list: DenyList) -> Self {
+        self.deny_list = Some(deny_list);
+        self
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         match self.run_inner().await {
             Ok(_) => unreachable!(),
@@ -575,8 +585,15 @@ impl MicroStateKeeper {
                     );
                     SealResolution::ExcludeAndSeal
                 }
-                _ => SealResolution::Unexecutable(reason.to_string()),
+                _ => SealResolution::Unexecutable(UnexecutableReason::halt(reason)),
             },
+            TxExecutionResult::Success {
+                call_tracer_result, ..
+            } if self.is_denied(&tx, call_tracer_result) => {
+                let resolution = SealResolution::Unexecutable(UnexecutableReason::denied_address());
+                AGGREGATION_METRICS.inc("denied_address", &resolution);
+                resolution
+            }
             TxExecutionResult::Success {
                 tx_result,
                 tx_metrics,
@@ -667,4 +684,19 @@ impl MicroStateKeeper {
         };
         (resolution, exec_result)
     }
+
+    fn is_denied(&self, tx: &Transaction, call_traces: &[Call]) -> bool {
+        let Some(deny_list) = &self.deny_list else {
+            return false;
+        };
+        let Some(address) = deny_list.check_executed_transaction(tx, call_traces) else {
+            return false;
+        };
+        tracing::info!(
+            "Transaction {:?} by {:?} is rejected because it interacts with denied address {address:?}",
+            tx.hash(),
+            tx.initiator_account()
+        );
+        true
+    }
 }
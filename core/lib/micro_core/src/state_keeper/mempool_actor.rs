@@ -1,5 +1,6 @@
 use std::{sync::Arc, time::Duration};
 
+use anyhow::Context as _;
 use micro_config::configs::chain::MempoolConfig;
 use micro_dal::ConnectionPool;
 use micro_mempool::L2TxFilter;
@@ -77,6 +78,20 @@ impl<G: L1GasPriceProvider> MempoolFetcher<G> {
             let latency = KEEPER_METRICS.mempool_sync.start();
             let mut storage = pool.access_storage_tagged("state_keeper").await.unwrap();
             let mempool_info = self.mempool.get_mempool_info();
+            if !mempool_info.evicted_transactions.is_empty() {
+                tracing::info!(
+                    "Evicted {} transactions from the full mempool",
+                    mempool_info.evicted_transactions.len()
+                );
+                KEEPER_METRICS
+                    .evicted_transactions
+                    .inc_by(mempool_info.evicted_transactions.len() as u64);
+                storage
+                    .transactions_dal()
+                    .mark_txs_as_evicted(&mempool_info.evicted_transactions)
+                    .await
+                    .context("failed marking evicted transactions")?;
+            }
             let l2_tx_filter = l2_tx_filter(self.l1_gas_price_provider.as_ref(), fair_l2_gas_price);
 
             let (transactions, nonces) = storage
@@ -7,8 +7,8 @@ use std::{
 
 use micro_mempool::MempoolStore;
 use vise::{
-    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LatencyObserver,
-    Metrics,
+    Buckets, Counter, EncodeLabelSet, EncodeLabelValue, Family, Gauge, Histogram, LabeledFamily,
+    LatencyObserver, Metrics,
 };
 
 use super::seal_criteria::SealResolution;
@@ -44,8 +44,11 @@ pub(crate) struct StateKeeperMetrics {
     /// Latency of the state keeper getting a transaction from the mempool.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub get_tx_from_mempool: Histogram<Duration>,
-    /// Number of transactions rejected by the state keeper.
-    pub rejected_transactions: Counter,
+    /// Number of transactions rejected by the state keeper, grouped by the rejection reason.
+    #[metrics(labels = ["reason"])]
+    pub rejected_transactions: LabeledFamily<&'static str, Counter>,
+    /// Number of L2 transactions evicted from the full mempool.
+    pub evicted_transactions: Counter,
     /// Time spent waiting for the hash of a previous L1 batch.
     #[metrics(buckets = Buckets::LATENCIES)]
     pub wait_for_prev_hash_time: Histogram<Duration>,
@@ -154,6 +157,21 @@ impl TxAggregationMetrics {
 #[vise::register]
 pub(super) static AGGREGATION_METRICS: vise::Global<TxAggregationMetrics> = vise::Global::new();
 
+/// Metrics related to prover backlog-aware L1 batch sealing.
+#[derive(Debug, Metrics)]
+#[metrics(prefix = "server_state_keeper_prover_backlog")]
+pub(super) struct ProverBacklogMetrics {
+    /// Number of L1 batches for which proofs are not generated yet.
+    pub unproven_l1_batches: Gauge<u64>,
+    /// Share of prover assignments timed out within the observation window.
+    pub assignment_timeout_rate: Gauge<f64>,
+    /// Current L1 batch commit deadline adjusted for the prover backlog.
+    pub block_commit_deadline: Gauge<Duration>,
+}
+
+#[vise::register]
+pub(super) static PROVER_BACKLOG_METRICS: vise::Global<ProverBacklogMetrics> = vise::Global::new();
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EncodeLabelValue, EncodeLabelSet)]
 #[metrics(label = "stage", rename_all = "snake_case")]
 pub(super) enum L1BatchSealStage {
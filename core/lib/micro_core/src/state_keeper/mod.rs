@@ -9,14 +9,17 @@ use micro_object_store::ObjectStore;
 use micro_system_constants::MAX_TXS_IN_BLOCK;
 use tokio::sync::watch;
 
-use self::io::MempoolIO;
 pub use self::{
     batch_executor::{L1BatchExecutorBuilder, MainBatchExecutorBuilder},
-    io::{MiniblockSealer, MiniblockSealerHandle},
+    io::{MiniblockNotifications, MiniblockSealer, MiniblockSealerHandle, SealedMiniblock},
     keeper::MicroStateKeeper,
 };
+use self::{io::MempoolIO, seal_criteria::ProverBacklogDeadline};
 pub(crate) use self::{
-    mempool_actor::MempoolFetcher, seal_criteria::ConditionalSealer, types::MempoolGuard,
+    mempool_actor::{l2_tx_filter, MempoolFetcher},
+    seal_criteria::{ConditionalSealer, ProverBacklog, ProverBacklogMonitor},
+    types::MempoolGuard,
+    upgrade_scheduler::{load_protocol_upgrades_report, ProtocolUpgradeScheduler},
 };
 use crate::l1_gas_price::L1GasPriceProvider;
 
@@ -31,6 +34,7 @@ pub(crate) mod seal_criteria;
 pub(crate) mod tests;
 pub(crate) mod types;
 pub(crate) mod updates;
+mod upgrade_scheduler;
 
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn create_state_keeper<G>(
@@ -44,6 +48,7 @@ pub(crate) async fn create_state_keeper<G>(
     l1_gas_price_provider: Arc<G>,
     miniblock_sealer_handle: MiniblockSealerHandle,
     object_store: Box<dyn ObjectStore>,
+    prover_backlog: Option<watch::Receiver<ProverBacklog>>,
     stop_receiver: watch::Receiver<bool>,
 ) -> MicroStateKeeper
 where
@@ -78,6 +83,12 @@ where
         network_config.micro_network_id,
     )
     .await;
+    let prover_backlog = prover_backlog
+        .and_then(|backlog| ProverBacklogDeadline::new(&state_keeper_config, backlog));
+    let io = match prover_backlog {
+        Some(prover_backlog) => io.with_prover_backlog(prover_backlog),
+        None => io,
+    };
 
     let sealer = ConditionalSealer::new(state_keeper_config);
     MicroStateKeeper::new(
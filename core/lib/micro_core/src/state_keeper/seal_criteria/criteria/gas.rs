@@ -2,7 +2,9 @@ use micro_types::ProtocolVersionId;
 
 use crate::{
     gas_tracker::new_block_gas_count,
-    state_keeper::seal_criteria::{SealCriterion, SealData, SealResolution, StateKeeperConfig},
+    state_keeper::seal_criteria::{
+        SealCriterion, SealData, SealResolution, StateKeeperConfig, UnexecutableReason,
+    },
 };
 
 /// This is a temporary solution.
@@ -30,7 +32,9 @@ impl SealCriterion for GasCriterion {
             (config.max_single_tx_gas as f64 * config.close_block_at_gas_percentage).round() as u64;
 
         if (tx_data.gas_count + new_block_gas_count()).any_field_greater_than(tx_bound) {
-            SealResolution::Unexecutable("Transaction requires too much gas".into())
+            SealResolution::Unexecutable(UnexecutableReason::too_big(
+                "Transaction requires too much gas",
+            ))
         } else if block_data
             .gas_count
             .any_field_greater_than(config.max_single_tx_gas)
@@ -103,7 +107,9 @@ mod tests {
         );
         assert_eq!(
             huge_transaction_resolution,
-            SealResolution::Unexecutable("Transaction requires too much gas".into())
+            SealResolution::Unexecutable(UnexecutableReason::too_big(
+                "Transaction requires too much gas"
+            ))
         );
 
         // Check criterion workflow
@@ -9,7 +9,9 @@ use micro_types::{
 use multivm::vm_latest::constants::{ERGS_PER_CIRCUIT, MAX_CYCLES_FOR_TX};
 
 // Local uses
-use crate::state_keeper::seal_criteria::{SealCriterion, SealData, SealResolution};
+use crate::state_keeper::seal_criteria::{
+    SealCriterion, SealData, SealResolution, UnexecutableReason,
+};
 
 // Collected vm execution metrics should fit into geometry limits.
 // Otherwise witness generation will fail and proof won't be generated.
@@ -52,7 +54,9 @@ where
             .round();
 
         if T::extract(&tx_data.execution_metrics, &tx_data.writes_metrics) > reject_bound as usize {
-            SealResolution::Unexecutable("ZK proof cannot be generated for a transaction".into())
+            SealResolution::Unexecutable(UnexecutableReason::too_big(
+                "ZK proof cannot be generated for a transaction",
+            ))
         } else if T::extract(&block_data.execution_metrics, &block_data.writes_metrics)
             >= T::limit_per_block(protocol_version_id)
         {
@@ -253,7 +257,9 @@ mod tests {
 
         assert_eq!(
             block_resolution,
-            SealResolution::Unexecutable("ZK proof cannot be generated for a transaction".into())
+            SealResolution::Unexecutable(UnexecutableReason::too_big(
+                "ZK proof cannot be generated for a transaction"
+            ))
         );
     }
 
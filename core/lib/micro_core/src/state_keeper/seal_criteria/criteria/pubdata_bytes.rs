@@ -1,7 +1,7 @@
 use micro_types::{ProtocolVersionId, MAX_PUBDATA_PER_L1_BATCH};
 
 use crate::state_keeper::seal_criteria::{
-    SealCriterion, SealData, SealResolution, StateKeeperConfig,
+    SealCriterion, SealData, SealResolution, StateKeeperConfig, UnexecutableReason,
 };
 
 #[derive(Debug)]
@@ -35,7 +35,7 @@ impl SealCriterion for PubDataBytesCriterion {
         };
         if tx_size > reject_bound as usize {
             let message = "Transaction cannot be sent to L1 due to pubdata limits";
-            SealResolution::Unexecutable(message.into())
+            SealResolution::Unexecutable(UnexecutableReason::too_big(message))
         } else if block_size > max_pubdata_per_l1_batch {
             SealResolution::ExcludeAndSeal
         } else if block_size > include_and_seal_bound as usize {
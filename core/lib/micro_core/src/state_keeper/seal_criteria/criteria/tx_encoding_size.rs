@@ -2,7 +2,7 @@ use micro_types::ProtocolVersionId;
 use multivm::vm_latest::constants::BOOTLOADER_TX_ENCODING_SPACE;
 
 use crate::state_keeper::seal_criteria::{
-    SealCriterion, SealData, SealResolution, StateKeeperConfig,
+    SealCriterion, SealData, SealResolution, StateKeeperConfig, UnexecutableReason,
 };
 
 #[derive(Debug)]
@@ -26,7 +26,7 @@ impl SealCriterion for TxEncodingSizeCriterion {
 
         if tx_data.cumulative_size > reject_bound as usize {
             let message = "Transaction cannot be included due to large encoding size";
-            SealResolution::Unexecutable(message.into())
+            SealResolution::Unexecutable(UnexecutableReason::too_big(message))
         } else if block_data.cumulative_size > BOOTLOADER_TX_ENCODING_SPACE as usize {
             SealResolution::ExcludeAndSeal
         } else if block_data.cumulative_size > include_and_seal_bound as usize {
@@ -79,9 +79,9 @@ mod tests {
         );
         assert_eq!(
             unexecutable_resolution,
-            SealResolution::Unexecutable(
-                "Transaction cannot be included due to large encoding size".into()
-            )
+            SealResolution::Unexecutable(UnexecutableReason::too_big(
+                "Transaction cannot be included due to large encoding size"
+            ))
         );
 
         let exclude_and_seal_resolution = criterion.should_seal(
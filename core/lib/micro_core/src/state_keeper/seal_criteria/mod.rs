@@ -14,18 +14,23 @@ use std::fmt;
 
 use micro_config::configs::chain::StateKeeperConfig;
 use micro_types::{
+    api::TransactionRejectionCode,
     block::BlockGasCount,
     fee::TransactionExecutionMetrics,
     tx::tx_execution_info::{DeduplicatedWritesMetrics, ExecutionMetrics},
     ProtocolVersionId, Transaction,
 };
 use micro_utils::time::millis_since;
-use multivm::vm_latest::TransactionVmExt;
+use multivm::{interface::Halt, vm_latest::TransactionVmExt};
 
 mod conditional_sealer;
 pub(super) mod criteria;
+mod prover_backlog;
 
-pub(crate) use self::conditional_sealer::ConditionalSealer;
+pub(crate) use self::{
+    conditional_sealer::ConditionalSealer,
+    prover_backlog::{ProverBacklog, ProverBacklogDeadline, ProverBacklogMonitor},
+};
 use super::{extractors, metrics::AGGREGATION_METRICS, updates::UpdatesManager};
 use crate::gas_tracker::{gas_count_from_tx_and_metrics, gas_count_from_writes};
 
@@ -48,7 +53,56 @@ pub enum SealResolution {
     /// if the block will consist of it solely. Such a transaction must be rejected.
     ///
     /// Contains a reason for why transaction was considered unexecutable.
-    Unexecutable(String),
+    Unexecutable(UnexecutableReason),
+}
+
+/// Reason for why a transaction was considered unexecutable, together with its structured code
+/// persisted in Postgres and exposed via the API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnexecutableReason {
+    pub code: TransactionRejectionCode,
+    pub message: String,
+}
+
+impl UnexecutableReason {
+    /// Transaction cannot fit into an L1 batch even if the batch will consist of it solely.
+    pub fn too_big(message: impl Into<String>) -> Self {
+        Self {
+            code: TransactionRejectionCode::TooBig,
+            message: message.into(),
+        }
+    }
+
+    /// Transaction execution was halted by the VM.
+    pub fn halt(reason: &Halt) -> Self {
+        let code = match reason {
+            Halt::ValidationFailed(_)
+            | Halt::PaymasterValidationFailed(_)
+            | Halt::PrePaymasterPreparationFailed(_)
+            | Halt::FromIsNotAnAccount
+            | Halt::ValidationOutOfGas => TransactionRejectionCode::ValidationFailed,
+            Halt::TooBigGasLimit => TransactionRejectionCode::TooBig,
+            _ => TransactionRejectionCode::Halt,
+        };
+        Self {
+            code,
+            message: reason.to_string(),
+        }
+    }
+
+    /// Transaction interacts with an address on the deny-list.
+    pub fn denied_address() -> Self {
+        Self {
+            code: TransactionRejectionCode::DenyList,
+            message: "Transaction interacts with a denied address".to_owned(),
+        }
+    }
+}
+
+impl fmt::Display for UnexecutableReason {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(&self.message)
+    }
 }
 
 impl SealResolution {
@@ -129,10 +183,12 @@ pub trait IoSealCriteria {
     fn should_seal_miniblock(&mut self, manager: &UpdatesManager) -> bool;
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub(super) struct TimeoutSealer {
     block_commit_deadline_ms: u64,
     miniblock_commit_deadline_ms: u64,
+    /// If set, the L1 batch commit deadline is stretched depending on the prover backlog.
+    prover_backlog: Option<ProverBacklogDeadline>,
 }
 
 impl TimeoutSealer {
@@ -140,28 +196,45 @@ impl TimeoutSealer {
         Self {
             block_commit_deadline_ms: config.block_commit_deadline_ms,
             miniblock_commit_deadline_ms: config.miniblock_commit_deadline_ms,
+            prover_backlog: None,
         }
     }
+
+    pub fn with_prover_backlog(mut self, prover_backlog: ProverBacklogDeadline) -> Self {
+        self.prover_backlog = Some(prover_backlog);
+        self
+    }
 }
 
 impl IoSealCriteria for TimeoutSealer {
     fn should_seal_l1_batch_unconditionally(&mut self, manager: &UpdatesManager) -> bool {
         const RULE_NAME: &str = "no_txs_timeout";
+        const PROVER_BACKLOG_RULE_NAME: &str = "prover_backlog_timeout";
 
         if manager.pending_executed_transactions_len() == 0 {
             // Regardless of which sealers are provided, we never want to seal an empty batch.
             return false;
         }
 
-        let block_commit_deadline_ms = self.block_commit_deadline_ms;
+        let block_commit_deadline_ms = self
+            .prover_backlog
+            .as_ref()
+            .map_or(self.block_commit_deadline_ms, |backlog| {
+                backlog.block_commit_deadline_ms()
+            });
+        let rule_name = if block_commit_deadline_ms > self.block_commit_deadline_ms {
+            PROVER_BACKLOG_RULE_NAME
+        } else {
+            RULE_NAME
+        };
         // Verify timestamp
         let should_seal_timeout =
             millis_since(manager.batch_timestamp()) > block_commit_deadline_ms;
 
         if should_seal_timeout {
-            AGGREGATION_METRICS.inc_criterion(RULE_NAME);
+            AGGREGATION_METRICS.inc_criterion(rule_name);
             tracing::debug!(
-                "Decided to seal L1 batch using rule `{RULE_NAME}`; batch timestamp: {}, \
+                "Decided to seal L1 batch using rule `{rule_name}`; batch timestamp: {}, \
                  commit deadline: {block_commit_deadline_ms}ms",
                 extractors::display_timestamp(manager.batch_timestamp())
             );
@@ -177,11 +250,13 @@ impl IoSealCriteria for TimeoutSealer {
 
 #[cfg(test)]
 mod tests {
+    use micro_types::Address;
     use micro_utils::time::seconds_since_epoch;
+    use tokio::sync::watch;
 
     use super::*;
     use crate::state_keeper::tests::{
-        create_execution_result, create_transaction, create_updates_manager,
+        create_execution_result, create_transaction, create_updates_manager, default_l1_batch_env,
     };
 
     fn apply_tx_to_manager(manager: &mut UpdatesManager) {
@@ -202,6 +277,7 @@ mod tests {
         let mut timeout_miniblock_sealer = TimeoutSealer {
             block_commit_deadline_ms: 10_000,
             miniblock_commit_deadline_ms: 10_000,
+            prover_backlog: None,
         };
 
         let mut manager = create_updates_manager();
@@ -228,4 +304,53 @@ mod tests {
             "Non-empty miniblock with too recent timestamp shouldn't be sealed"
         );
     }
+
+    #[test]
+    fn timeout_l1_batch_sealer_with_prover_backlog() {
+        let config = StateKeeperConfig {
+            block_commit_deadline_ms: 1_000,
+            prover_backlog_max_block_commit_deadline_ms: Some(100_000),
+            prover_backlog_threshold: Some(0),
+            prover_backlog_saturation: Some(10),
+            ..StateKeeperConfig::default()
+        };
+        let (backlog_sender, backlog_receiver) = watch::channel(ProverBacklog::default());
+        let prover_backlog = ProverBacklogDeadline::new(&config, backlog_receiver).unwrap();
+        let mut sealer = TimeoutSealer::new(&config).with_prover_backlog(prover_backlog);
+
+        // The batch was opened 10 seconds ago, i.e., after the base deadline.
+        let l1_batch_env = default_l1_batch_env(1, seconds_since_epoch() - 10, Address::default());
+        let mut manager = UpdatesManager::new(
+            l1_batch_env,
+            Default::default(),
+            ProtocolVersionId::latest(),
+        );
+        apply_tx_to_manager(&mut manager);
+        assert!(
+            sealer.should_seal_l1_batch_unconditionally(&manager),
+            "L1 batch should be sealed if there's no prover backlog"
+        );
+
+        // The deadline is stretched to 1s + 99s * 0.5 = 50.5s.
+        backlog_sender.send_replace(ProverBacklog {
+            unproven_l1_batches: 5,
+            assignment_timeout_rate: 0.0,
+        });
+        assert!(
+            !sealer.should_seal_l1_batch_unconditionally(&manager),
+            "L1 batch sealing should be postponed if provers lag behind"
+        );
+    }
+
+    #[test]
+    fn unexecutable_reason_codes_for_halts() {
+        let reason = UnexecutableReason::halt(&Halt::FromIsNotAnAccount);
+        assert_eq!(reason.code, TransactionRejectionCode::ValidationFailed);
+        assert_eq!(reason.message, Halt::FromIsNotAnAccount.to_string());
+
+        let reason = UnexecutableReason::halt(&Halt::TooBigGasLimit);
+        assert_eq!(reason.code, TransactionRejectionCode::TooBig);
+        let reason = UnexecutableReason::halt(&Halt::BootloaderOutOfGas);
+        assert_eq!(reason.code, TransactionRejectionCode::Halt);
+    }
 }
@@ -0,0 +1,224 @@
+//! Prover backlog-aware L1 batch sealing.
+//!
+//! If provers lag behind, sealing small L1 batches only adds proving overhead. This criterion stretches
+//! the L1 batch commit deadline (i.e., the timeout after which a batch is sealed unconditionally) within
+//! configured bounds depending on the number of unproven L1 batches and the share of prover assignments
+//! that timed out recently. The backlog is periodically loaded from Postgres by [`ProverBacklogMonitor`].
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use micro_config::configs::chain::StateKeeperConfig;
+use micro_dal::ConnectionPool;
+use tokio::sync::watch;
+
+use crate::state_keeper::metrics::PROVER_BACKLOG_METRICS;
+
+/// Window used to compute the share of timed out prover assignments.
+const ASSIGNMENT_STATISTICS_WINDOW: Duration = Duration::from_secs(3_600);
+
+/// Snapshot of the prover backlog.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub(crate) struct ProverBacklog {
+    /// Number of L1 batches for which proofs are not generated yet.
+    pub unproven_l1_batches: u64,
+    /// Share of prover assignments timed out within the observation window, in `[0, 1]`.
+    pub assignment_timeout_rate: f64,
+}
+
+/// Computes the L1 batch commit deadline based on the current prover backlog.
+#[derive(Debug, Clone)]
+pub(crate) struct ProverBacklogDeadline {
+    base_deadline_ms: u64,
+    max_deadline_ms: u64,
+    threshold: u64,
+    saturation: u64,
+    backlog: watch::Receiver<ProverBacklog>,
+}
+
+impl ProverBacklogDeadline {
+    /// Creates the deadline calculator if backlog-aware sealing is enabled in the config.
+    pub fn new(
+        config: &StateKeeperConfig,
+        backlog: watch::Receiver<ProverBacklog>,
+    ) -> Option<Self> {
+        let max_deadline_ms = config.prover_backlog_max_block_commit_deadline_ms?;
+        Some(Self {
+            base_deadline_ms: config.block_commit_deadline_ms,
+            max_deadline_ms: max_deadline_ms.max(config.block_commit_deadline_ms),
+            threshold: config.prover_backlog_threshold().into(),
+            saturation: config.prover_backlog_saturation().into(),
+            backlog,
+        })
+    }
+
+    /// Returns the commit deadline for the latest observed backlog.
+    pub fn block_commit_deadline_ms(&self) -> u64 {
+        let deadline_ms = self.deadline_for(&self.backlog.borrow());
+        PROVER_BACKLOG_METRICS
+            .block_commit_deadline
+            .set(Duration::from_millis(deadline_ms));
+        deadline_ms
+    }
+
+    /// Linearly interpolates the deadline between the base and max values. The backlog contributes
+    /// once it exceeds the threshold and saturates at the configured number of unproven batches;
+    /// the assignment timeout rate is added on top, since timeouts mean that provers cannot keep up
+    /// even if the backlog is small.
+    fn deadline_for(&self, backlog: &ProverBacklog) -> u64 {
+        let backlog_pressure = if backlog.unproven_l1_batches <= self.threshold {
+            0.0
+        } else if self.saturation <= self.threshold {
+            1.0
+        } else {
+            let excess = backlog.unproven_l1_batches - self.threshold;
+            excess as f64 / (self.saturation - self.threshold) as f64
+        };
+        let timeout_pressure = backlog.assignment_timeout_rate.clamp(0.0, 1.0);
+        let pressure = (backlog_pressure + timeout_pressure).min(1.0);
+
+        let stretch = (self.max_deadline_ms - self.base_deadline_ms) as f64 * pressure;
+        self.base_deadline_ms + stretch.round() as u64
+    }
+}
+
+/// Periodically loads the prover backlog from Postgres.
+#[derive(Debug)]
+pub(crate) struct ProverBacklogMonitor {
+    pool: ConnectionPool,
+    sender: watch::Sender<ProverBacklog>,
+    poll_interval: Duration,
+}
+
+impl ProverBacklogMonitor {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+    pub fn new(pool: ConnectionPool) -> (Self, watch::Receiver<ProverBacklog>) {
+        let (sender, receiver) = watch::channel(ProverBacklog::default());
+        let this = Self {
+            pool,
+            sender,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+        };
+        (this, receiver)
+    }
+
+    async fn load_backlog(&self) -> anyhow::Result<ProverBacklog> {
+        let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+        let unproven_l1_batches = storage
+            .proof_generation_dal()
+            .get_unproven_l1_batch_count()
+            .await
+            .context("failed getting unproven L1 batch count")?;
+        let stats = storage
+            .assignments_dal()
+            .get_assignment_statistics(ASSIGNMENT_STATISTICS_WINDOW)
+            .await
+            .context("failed getting assignment statistics")?;
+        let assignment_timeout_rate = if stats.total_assignments == 0 {
+            0.0
+        } else {
+            stats.penalized_assignments as f64 / stats.total_assignments as f64
+        };
+        Ok(ProverBacklog {
+            unproven_l1_batches,
+            assignment_timeout_rate,
+        })
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, prover backlog monitor is shutting down");
+                return Ok(());
+            }
+
+            // Errors are not fatal: the state keeper continues using the last observed backlog.
+            match self.load_backlog().await {
+                Ok(backlog) => {
+                    PROVER_BACKLOG_METRICS
+                        .unproven_l1_batches
+                        .set(backlog.unproven_l1_batches);
+                    PROVER_BACKLOG_METRICS
+                        .assignment_timeout_rate
+                        .set(backlog.assignment_timeout_rate);
+                    self.sender.send_replace(backlog);
+                }
+                Err(err) => tracing::warn!("Failed loading prover backlog: {err:#}"),
+            }
+
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                tracing::info!("Stop signal received, prover backlog monitor is shutting down");
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deadline(backlog: ProverBacklog) -> u64 {
+        let config = StateKeeperConfig {
+            block_commit_deadline_ms: 1_000,
+            prover_backlog_max_block_commit_deadline_ms: Some(11_000),
+            prover_backlog_threshold: Some(10),
+            prover_backlog_saturation: Some(20),
+            ..StateKeeperConfig::default()
+        };
+        let (_sender, receiver) = watch::channel(backlog);
+        let deadline = ProverBacklogDeadline::new(&config, receiver).unwrap();
+        deadline.block_commit_deadline_ms()
+    }
+
+    fn backlog(unproven_l1_batches: u64, assignment_timeout_rate: f64) -> ProverBacklog {
+        ProverBacklog {
+            unproven_l1_batches,
+            assignment_timeout_rate,
+        }
+    }
+
+    #[test]
+    fn deadline_is_not_stretched_without_backlog() {
+        assert_eq!(deadline(backlog(0, 0.0)), 1_000);
+        assert_eq!(deadline(backlog(10, 0.0)), 1_000);
+    }
+
+    #[test]
+    fn deadline_is_stretched_proportionally_to_backlog() {
+        assert_eq!(deadline(backlog(11, 0.0)), 2_000);
+        assert_eq!(deadline(backlog(15, 0.0)), 6_000);
+        assert_eq!(deadline(backlog(20, 0.0)), 11_000);
+        assert_eq!(deadline(backlog(1_000, 0.0)), 11_000);
+    }
+
+    #[test]
+    fn assignment_timeouts_stretch_deadline() {
+        assert_eq!(deadline(backlog(0, 0.5)), 6_000);
+        assert_eq!(deadline(backlog(15, 0.2)), 8_000);
+        assert_eq!(deadline(backlog(15, 1.0)), 11_000);
+    }
+
+    #[test]
+    fn criterion_is_disabled_by_default() {
+        let (_sender, receiver) = watch::channel(ProverBacklog::default());
+        let deadline = ProverBacklogDeadline::new(&StateKeeperConfig::for_tests(), receiver);
+        assert!(deadline.is_none());
+    }
+
+    #[test]
+    fn max_deadline_is_never_lower_than_base_deadline() {
+        let config = StateKeeperConfig {
+            block_commit_deadline_ms: 5_000,
+            prover_backlog_max_block_commit_deadline_ms: Some(1_000),
+            ..StateKeeperConfig::default()
+        };
+        let (_sender, receiver) = watch::channel(backlog(100, 1.0));
+        let deadline = ProverBacklogDeadline::new(&config, receiver).unwrap();
+        assert_eq!(deadline.block_commit_deadline_ms(), 5_000);
+    }
+}
@@ -17,6 +17,7 @@ use micro_types::{
     l2::L2Tx,
     transaction_request::PaymasterParams,
     tx::tx_execution_info::ExecutionMetrics,
+    vm_trace::Call,
     Address, L1BatchNumber, L2ChainId, LogQuery, MiniblockNumber, Nonce, ProtocolVersionId,
     StorageLogQuery, StorageLogQueryType, Timestamp, Transaction, H256, U256,
 };
@@ -37,8 +38,10 @@ use self::tester::{
 };
 pub(crate) use self::tester::{MockBatchExecutorBuilder, TestBatchExecutorBuilder};
 use crate::{
+    deny_list::DenyList,
     gas_tracker::l1_batch_base_cost,
     state_keeper::{
+        batch_executor::TxExecutionResult,
         keeper::POLL_WAIT_DURATION,
         seal_criteria::{
             criteria::{GasCriterion, SlotsCriterion},
@@ -397,6 +400,63 @@ async fn rejected_tx() {
         .await;
 }
 
+#[tokio::test]
+async fn denied_tx() {
+    let config = StateKeeperConfig {
+        transaction_slots: 2,
+        ..StateKeeperConfig::default()
+    };
+    let sealer = ConditionalSealer::with_sealers(config, vec![Box::new(SlotsCriterion)]);
+
+    let denied_initiator_tx = random_tx(1);
+    let denied_contract = Address::repeat_byte(0xde);
+    let deny_list = DenyList::new([denied_initiator_tx.initiator_account(), denied_contract]);
+
+    let denied_callee_tx = random_tx(2);
+    let mut denied_callee_exec = successful_exec();
+    if let TxExecutionResult::Success {
+        call_tracer_result, ..
+    } = &mut denied_callee_exec
+    {
+        *call_tracer_result = vec![Call {
+            from: denied_callee_tx.initiator_account(),
+            to: denied_contract,
+            ..Call::default()
+        }];
+    }
+
+    TestScenario::new()
+        .with_deny_list(deny_list)
+        .seal_miniblock_when(|updates| updates.miniblock.executed_transactions.len() == 1)
+        .next_tx(
+            "Tx from denied initiator",
+            denied_initiator_tx.clone(),
+            successful_exec(),
+        )
+        .tx_rejected(
+            "Tx from denied initiator got rejected",
+            denied_initiator_tx,
+            Some("denied address".to_owned()),
+        )
+        .next_tx(
+            "Tx calling denied contract",
+            denied_callee_tx.clone(),
+            denied_callee_exec,
+        )
+        .tx_rejected(
+            "Tx calling denied contract got rejected",
+            denied_callee_tx,
+            Some("denied address".to_owned()),
+        )
+        .next_tx("Successful tx", random_tx(3), successful_exec())
+        .miniblock_sealed("Miniblock with successful tx")
+        .next_tx("Second successful tx", random_tx(4), successful_exec())
+        .miniblock_sealed("Second miniblock")
+        .batch_sealed("Batch with 2 successful txs")
+        .run(sealer)
+        .await;
+}
+
 #[tokio::test]
 async fn bootloader_tip_out_of_gas_flow() {
     let config = StateKeeperConfig {
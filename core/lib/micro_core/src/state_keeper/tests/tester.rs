@@ -20,16 +20,20 @@ use multivm::{
 };
 use tokio::sync::{mpsc, watch};
 
-use crate::state_keeper::{
-    batch_executor::{BatchExecutorHandle, Command, L1BatchExecutorBuilder, TxExecutionResult},
-    io::{MiniblockParams, PendingBatchData, StateKeeperIO},
-    seal_criteria::{ConditionalSealer, IoSealCriteria},
-    tests::{
-        create_l2_transaction, default_l1_batch_env, default_vm_block_result, BASE_SYSTEM_CONTRACTS,
+use crate::{
+    deny_list::DenyList,
+    state_keeper::{
+        batch_executor::{BatchExecutorHandle, Command, L1BatchExecutorBuilder, TxExecutionResult},
+        io::{MiniblockParams, PendingBatchData, StateKeeperIO},
+        seal_criteria::{ConditionalSealer, IoSealCriteria, UnexecutableReason},
+        tests::{
+            create_l2_transaction, default_l1_batch_env, default_vm_block_result,
+            BASE_SYSTEM_CONTRACTS,
+        },
+        types::ExecutionMetricsForCriteria,
+        updates::UpdatesManager,
+        MicroStateKeeper,
     },
-    types::ExecutionMetricsForCriteria,
-    updates::UpdatesManager,
-    MicroStateKeeper,
 };
 
 const FEE_ACCOUNT: Address = Address::repeat_byte(0x11);
@@ -51,6 +55,7 @@ pub(crate) struct TestScenario {
     pending_batch: Option<PendingBatchData>,
     l1_batch_seal_fn: Box<SealFn>,
     miniblock_seal_fn: Box<SealFn>,
+    deny_list: Option<DenyList>,
 }
 
 type SealFn = dyn FnMut(&UpdatesManager) -> bool + Send;
@@ -72,6 +77,7 @@ impl TestScenario {
             pending_batch: None,
             l1_batch_seal_fn: Box::new(|_| false),
             miniblock_seal_fn: Box::new(|_| false),
+            deny_list: None,
         }
     }
 
@@ -187,20 +193,30 @@ impl TestScenario {
         self
     }
 
+    /// Sets the deny-list used by the state keeper.
+    pub(crate) fn with_deny_list(mut self, deny_list: DenyList) -> Self {
+        self.deny_list = Some(deny_list);
+        self
+    }
+
     /// Launches the test.
     /// Provided `SealManager` is expected to be externally configured to adhere the written scenario logic.
-    pub(crate) async fn run(self, sealer: ConditionalSealer) {
+    pub(crate) async fn run(mut self, sealer: ConditionalSealer) {
         assert!(!self.actions.is_empty(), "Test scenario can't be empty");
 
         let batch_executor_base = TestBatchExecutorBuilder::new(&self);
         let (stop_sender, stop_receiver) = watch::channel(false);
+        let deny_list = self.deny_list.take();
         let io = TestIO::new(stop_sender, self);
-        let sk = MicroStateKeeper::new(
+        let mut sk = MicroStateKeeper::new(
             stop_receiver,
             Box::new(io),
             Box::new(batch_executor_base),
             sealer,
         );
+        if let Some(deny_list) = deny_list {
+            sk = sk.with_deny_list(deny_list);
+        }
         let sk_thread = tokio::spawn(sk.run());
 
         // We must assume that *theoretically* state keeper may ignore the stop signal from IO once scenario is
@@ -702,7 +718,7 @@ impl StateKeeperIO for TestIO {
         self.skipping_txs = false;
     }
 
-    async fn reject(&mut self, tx: &Transaction, error: &str) {
+    async fn reject(&mut self, tx: &Transaction, reason: &UnexecutableReason) {
         let action = self.pop_next_item("reject");
         let ScenarioItem::Reject(_, expected_tx, expected_err) = action else {
             panic!("Unexpected action: {:?}", action);
@@ -710,10 +726,10 @@ impl StateKeeperIO for TestIO {
         assert_eq!(tx, &expected_tx, "Incorrect transaction has been rejected");
         if let Some(expected_err) = expected_err {
             assert!(
-                error.contains(&expected_err),
+                reason.message.contains(&expected_err),
                 "Transaction was rejected with an unexpected error. Expected part was {}, but the actual error was {}",
                 expected_err,
-                error
+                reason
             );
         }
         self.skipping_txs = false;
@@ -3,7 +3,14 @@ use std::{
     sync::{Arc, Mutex},
 };
 
-use micro_mempool::{L2TxFilter, MempoolInfo, MempoolStore};
+use micro_config::configs::chain::{
+    MempoolConfig, MempoolEvictionPolicy as MempoolEvictionPolicyConfig,
+    MempoolOrdering as MempoolOrderingConfig,
+};
+use micro_mempool::{
+    AccountMempoolSnapshot, L2TransactionCounts, L2TxFilter, MempoolEvictionPolicy, MempoolInfo,
+    MempoolOrdering, MempoolStore,
+};
 use micro_types::{
     block::BlockGasCount, tx::ExecutionMetrics, Address, Nonce, PriorityOpId, Transaction,
 };
@@ -19,6 +26,24 @@ impl MempoolGuard {
         Self(Arc::new(Mutex::new(store)))
     }
 
+    /// Creates a mempool with the capacity, ordering, replacement and eviction policies taken from the config.
+    pub fn from_config(next_priority_id: PriorityOpId, config: &MempoolConfig) -> Self {
+        let ordering = match config.ordering {
+            MempoolOrderingConfig::Fifo => MempoolOrdering::Fifo,
+            MempoolOrderingConfig::PriorityFee => MempoolOrdering::PriorityFee,
+        };
+        let eviction_policy = match config.eviction_policy {
+            MempoolEvictionPolicyConfig::Disabled => MempoolEvictionPolicy::Disabled,
+            MempoolEvictionPolicyConfig::LowestFee => MempoolEvictionPolicy::LowestFee,
+            MempoolEvictionPolicyConfig::Oldest => MempoolEvictionPolicy::Oldest,
+        };
+        let store = MempoolStore::new(next_priority_id, config.capacity)
+            .with_ordering(ordering)
+            .with_min_replacement_fee_bump(config.replacement_fee_bump_percent())
+            .with_eviction_policy(eviction_policy);
+        Self(Arc::new(Mutex::new(store)))
+    }
+
     pub fn insert(&mut self, transactions: Vec<Transaction>, nonces: HashMap<Address, Nonce>) {
         self.0
             .lock()
@@ -54,6 +79,31 @@ impl MempoolGuard {
             .get_mempool_info()
     }
 
+    pub fn account_snapshot(
+        &self,
+        account: Address,
+        limit: usize,
+    ) -> Option<AccountMempoolSnapshot> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .account_snapshot(account, limit)
+    }
+
+    pub fn snapshot(&self, limit: usize) -> Vec<AccountMempoolSnapshot> {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .snapshot(limit)
+    }
+
+    pub fn l2_transaction_counts(&self) -> L2TransactionCounts {
+        self.0
+            .lock()
+            .expect("failed to acquire mempool lock")
+            .l2_transaction_counts()
+    }
+
     pub fn register_metrics(&self) {
         StateKeeperGauges::register(Arc::downgrade(&self.0));
     }
@@ -0,0 +1,369 @@
+//! Scheduler of protocol upgrade activation.
+//!
+//! Protocol versions are saved to Postgres by the L1 watcher. The scheduler records newly seen versions as pending
+//! upgrades and runs pre-flight checks for them; the state keeper only activates an upgrade once its checks have
+//! passed and the activation timestamp / L1 batch number set by the operator (if any) is reached. An upgrade that
+//! is already activated on L1, but cannot be activated by the state keeper makes the scheduler health check fail,
+//! since L1 batches sealed in this state cannot be committed.
+
+use std::time::Duration;
+
+use anyhow::Context as _;
+use chrono::Utc;
+use micro_dal::{ConnectionPool, StorageProcessor};
+use micro_health_check::{Health, HealthStatus, HealthUpdater, ReactiveHealthCheck};
+use micro_types::{
+    api::protocol_upgrades::{ProtocolUpgradeStage, ProtocolUpgradeStatus, ProtocolUpgradesReport},
+    vm_version::VmVersion,
+    ProtocolVersionId, H256,
+};
+use serde::Serialize;
+use tokio::sync::watch;
+
+/// Reason a protocol upgrade cannot be activated.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub(crate) enum PreflightError {
+    #[error(
+        "protocol version {0} is not supported by this server; the server must be updated first"
+    )]
+    UnsupportedVersion(u16),
+    #[error("protocol version {0:?} is not found in the database")]
+    MissingVersion(ProtocolVersionId),
+    #[error("bootloader bytecode {0:?} is missing from factory deps")]
+    MissingBootloader(H256),
+    #[error("default account bytecode {0:?} is missing from factory deps")]
+    MissingDefaultAccount(H256),
+}
+
+/// Performs pre-flight checks for an upgrade to the specified raw protocol version. Returns the VM version
+/// used by the upgraded protocol if all checks pass.
+pub(crate) async fn run_preflight_checks(
+    storage: &mut StorageProcessor<'_>,
+    protocol_version: u16,
+) -> Result<VmVersion, PreflightError> {
+    // All protocol versions known to this server are mapped to a VM version supported by the multi-VM.
+    let Ok(version_id) = ProtocolVersionId::try_from(protocol_version) else {
+        return Err(PreflightError::UnsupportedVersion(protocol_version));
+    };
+    let Some(version) = storage
+        .protocol_versions_dal()
+        .get_protocol_version(version_id)
+        .await
+    else {
+        return Err(PreflightError::MissingVersion(version_id));
+    };
+
+    let hashes = version.base_system_contracts_hashes;
+    let mut storage_dal = storage.storage_dal();
+    if storage_dal
+        .get_factory_dep(hashes.bootloader)
+        .await
+        .is_none()
+    {
+        return Err(PreflightError::MissingBootloader(hashes.bootloader));
+    }
+    if storage_dal
+        .get_factory_dep(hashes.default_aa)
+        .await
+        .is_none()
+    {
+        return Err(PreflightError::MissingDefaultAccount(hashes.default_aa));
+    }
+    Ok(VmVersion::from(version_id))
+}
+
+/// Loads the status of pending protocol upgrades.
+pub(crate) async fn load_protocol_upgrades_report(
+    storage: &mut StorageProcessor<'_>,
+) -> anyhow::Result<ProtocolUpgradesReport> {
+    let sealed_miniblock_number = storage
+        .blocks_dal()
+        .get_sealed_miniblock_number()
+        .await
+        .context("get_sealed_miniblock_number()")?;
+    let current_version = storage
+        .blocks_dal()
+        .get_miniblock_protocol_version_id(sealed_miniblock_number)
+        .await
+        .context("get_miniblock_protocol_version_id()")?;
+    let next_l1_batch_number = storage
+        .blocks_dal()
+        .get_sealed_l1_batch_number()
+        .await
+        .context("get_sealed_l1_batch_number()")?
+        + 1;
+    let schedules = storage
+        .protocol_upgrade_schedules_dal()
+        .get_pending_upgrades()
+        .await
+        .context("get_pending_upgrades()")?;
+
+    let timestamp = Utc::now().timestamp() as u64;
+    let pending_upgrades = schedules
+        .into_iter()
+        .map(|schedule| ProtocolUpgradeStatus {
+            stage: schedule.stage(timestamp, next_l1_batch_number),
+            overdue: schedule.is_overdue(timestamp, next_l1_batch_number),
+            schedule,
+        })
+        .collect();
+    Ok(ProtocolUpgradesReport {
+        current_version: current_version.map(|version| version as u16),
+        pending_upgrades,
+    })
+}
+
+/// Health details reported by the scheduler.
+#[derive(Debug, Serialize)]
+struct UpgradeSchedulerHealthDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current_version: Option<u16>,
+    pending_upgrades: Vec<PendingUpgradeDetails>,
+}
+
+#[derive(Debug, Serialize)]
+struct PendingUpgradeDetails {
+    protocol_version: u16,
+    stage: ProtocolUpgradeStage,
+    l1_activation_timestamp: u64,
+    overdue: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    preflight_error: Option<String>,
+}
+
+/// Periodically records pending protocol upgrades and runs pre-flight checks for them.
+#[derive(Debug)]
+pub(crate) struct ProtocolUpgradeScheduler {
+    pool: ConnectionPool,
+    poll_interval: Duration,
+    health_updater: HealthUpdater,
+}
+
+impl ProtocolUpgradeScheduler {
+    const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(10);
+
+    pub fn new(pool: ConnectionPool) -> Self {
+        let (_, health_updater) = ReactiveHealthCheck::new("protocol_upgrade_scheduler");
+        Self {
+            pool,
+            poll_interval: Self::DEFAULT_POLL_INTERVAL,
+            health_updater,
+        }
+    }
+
+    /// Returns the health check for this scheduler.
+    pub fn health_check(&self) -> ReactiveHealthCheck {
+        self.health_updater.subscribe()
+    }
+
+    pub async fn run(self, mut stop_receiver: watch::Receiver<bool>) -> anyhow::Result<()> {
+        loop {
+            if *stop_receiver.borrow() {
+                tracing::info!("Stop signal received, protocol upgrade scheduler is shutting down");
+                return Ok(());
+            }
+
+            // Errors are not fatal: pending upgrades are not activated until they are checked.
+            if let Err(err) = self.update().await {
+                tracing::warn!("Failed updating protocol upgrade schedules: {err:#}");
+            }
+
+            if tokio::time::timeout(self.poll_interval, stop_receiver.changed())
+                .await
+                .is_ok()
+            {
+                tracing::info!("Stop signal received, protocol upgrade scheduler is shutting down");
+                return Ok(());
+            }
+        }
+    }
+
+    async fn update(&self) -> anyhow::Result<ProtocolUpgradesReport> {
+        let mut storage = self.pool.access_storage_tagged("state_keeper").await?;
+        let recorded_versions = storage
+            .protocol_upgrade_schedules_dal()
+            .record_pending_upgrades()
+            .await
+            .context("record_pending_upgrades()")?;
+        if !recorded_versions.is_empty() {
+            tracing::info!("Recorded pending protocol upgrades to versions {recorded_versions:?}");
+        }
+
+        let schedules = storage
+            .protocol_upgrade_schedules_dal()
+            .get_pending_upgrades()
+            .await
+            .context("get_pending_upgrades()")?;
+        // Checks are re-run on each iteration since their outcome may change, e.g. once bytecodes are published.
+        for schedule in &schedules {
+            let version = schedule.protocol_version;
+            let error = match run_preflight_checks(&mut storage, version).await {
+                Ok(vm_version) => {
+                    if schedule.preflight_checked_at.is_none() || schedule.preflight_error.is_some()
+                    {
+                        tracing::info!(
+                            "Protocol upgrade to version {version} (VM version {vm_version:?}) has passed pre-flight checks"
+                        );
+                    }
+                    None
+                }
+                Err(err) => {
+                    let err = err.to_string();
+                    if schedule.preflight_error.as_ref() != Some(&err) {
+                        tracing::warn!(
+                            "Protocol upgrade to version {version} has failed pre-flight checks: {err}"
+                        );
+                    }
+                    Some(err)
+                }
+            };
+            storage
+                .protocol_upgrade_schedules_dal()
+                .save_preflight_result(version, error.as_deref())
+                .await
+                .context("save_preflight_result()")?;
+        }
+
+        let report = load_protocol_upgrades_report(&mut storage).await?;
+        self.update_health(&report);
+        Ok(report)
+    }
+
+    fn update_health(&self, report: &ProtocolUpgradesReport) {
+        let pending_upgrades: Vec<_> = report
+            .pending_upgrades
+            .iter()
+            .map(|upgrade| PendingUpgradeDetails {
+                protocol_version: upgrade.schedule.protocol_version,
+                stage: upgrade.stage,
+                l1_activation_timestamp: upgrade.schedule.l1_activation_timestamp,
+                overdue: upgrade.overdue,
+                preflight_error: upgrade.schedule.preflight_error.clone(),
+            })
+            .collect();
+
+        let overdue_versions: Vec<_> = pending_upgrades
+            .iter()
+            .filter(|upgrade| upgrade.overdue)
+            .map(|upgrade| upgrade.protocol_version)
+            .collect();
+        let has_failed_upgrades = report
+            .pending_upgrades
+            .iter()
+            .any(|upgrade| upgrade.stage == ProtocolUpgradeStage::PreflightFailed);
+        let status = if !overdue_versions.is_empty() {
+            tracing::error!(
+                "Protocol upgrades to versions {overdue_versions:?} are activated on L1, but cannot be activated \
+                 by the state keeper; L1 batches sealed without them will fail to be committed"
+            );
+            HealthStatus::NotReady
+        } else if has_failed_upgrades {
+            HealthStatus::Affected
+        } else {
+            HealthStatus::Ready
+        };
+        let details = UpgradeSchedulerHealthDetails {
+            current_version: report.current_version,
+            pending_upgrades,
+        };
+        self.health_updater
+            .update(Health::from(status).with_details(details));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use assert_matches::assert_matches;
+    use micro_contracts::BaseSystemContractsHashes;
+    use micro_health_check::CheckHealth;
+    use micro_types::{
+        protocol_version::ProtocolVersion, L1BatchNumber, L2ChainId, MiniblockNumber,
+    };
+
+    use super::*;
+    use crate::genesis::{ensure_genesis_state, GenesisParams};
+
+    #[tokio::test]
+    async fn scheduling_upgrade_with_missing_bytecodes() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        ensure_genesis_state(&mut storage, L2ChainId::default(), &GenesisParams::mock())
+            .await
+            .unwrap();
+        let bootloader_hash = H256::repeat_byte(1);
+        let default_aa_hash = H256::repeat_byte(2);
+        let next_version = ProtocolVersionId::next();
+        storage
+            .protocol_versions_dal()
+            .save_protocol_version_with_tx(ProtocolVersion {
+                id: next_version,
+                base_system_contracts_hashes: BaseSystemContractsHashes {
+                    bootloader: bootloader_hash,
+                    default_aa: default_aa_hash,
+                },
+                ..ProtocolVersion::default()
+            })
+            .await;
+
+        let timestamp = Utc::now().timestamp() as u64;
+        let scheduler = ProtocolUpgradeScheduler::new(pool.clone());
+        let health_check = scheduler.health_check();
+        let report = scheduler.update().await.unwrap();
+        assert_eq!(
+            report.current_version,
+            Some(ProtocolVersionId::latest() as u16)
+        );
+        assert_eq!(report.pending_upgrades.len(), 1);
+        let upgrade = &report.pending_upgrades[0];
+        assert_eq!(upgrade.schedule.protocol_version, next_version as u16);
+        assert_eq!(upgrade.stage, ProtocolUpgradeStage::PreflightFailed);
+        let expected_error = PreflightError::MissingBootloader(bootloader_hash).to_string();
+        assert_eq!(upgrade.schedule.preflight_error, Some(expected_error));
+        // The upgrade is already activated on L1, so it cannot wait for the missing bytecodes.
+        assert!(upgrade.overdue);
+        let health = health_check.check_health().await;
+        assert_matches!(health.status(), HealthStatus::NotReady);
+
+        let version = storage
+            .protocol_upgrade_schedules_dal()
+            .activatable_version(timestamp, L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(ProtocolVersionId::latest()));
+
+        let factory_deps = HashMap::from([
+            (bootloader_hash, vec![0; 32]),
+            (default_aa_hash, vec![0; 32]),
+        ]);
+        storage
+            .storage_dal()
+            .insert_factory_deps(MiniblockNumber(0), &factory_deps)
+            .await;
+        let report = scheduler.update().await.unwrap();
+        let upgrade = &report.pending_upgrades[0];
+        assert_eq!(upgrade.stage, ProtocolUpgradeStage::Ready);
+        assert_eq!(upgrade.schedule.preflight_error, None);
+        assert!(!upgrade.overdue);
+        let health = health_check.check_health().await;
+        assert_matches!(health.status(), HealthStatus::Ready);
+
+        let version = storage
+            .protocol_upgrade_schedules_dal()
+            .activatable_version(timestamp, L1BatchNumber(1))
+            .await
+            .unwrap();
+        assert_eq!(version, Some(next_version));
+    }
+
+    #[tokio::test]
+    async fn preflight_checks_reject_unknown_versions() {
+        let pool = ConnectionPool::test_pool().await;
+        let mut storage = pool.access_storage().await.unwrap();
+        let err = run_preflight_checks(&mut storage, u16::MAX)
+            .await
+            .unwrap_err();
+        assert_eq!(err, PreflightError::UnsupportedVersion(u16::MAX));
+    }
+}
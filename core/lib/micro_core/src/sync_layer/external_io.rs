@@ -25,7 +25,7 @@ use crate::{
             MiniblockParams, MiniblockSealerHandle, PendingBatchData, StateKeeperIO,
         },
         metrics::KEEPER_METRICS,
-        seal_criteria::IoSealCriteria,
+        seal_criteria::{IoSealCriteria, UnexecutableReason},
         updates::UpdatesManager,
     },
 };
@@ -441,11 +441,11 @@ impl StateKeeperIO for ExternalIO {
         panic!("Rollback requested. Transaction hash: {:?}", tx.hash());
     }
 
-    async fn reject(&mut self, tx: &Transaction, error: &str) {
+    async fn reject(&mut self, tx: &Transaction, reason: &UnexecutableReason) {
         // We are replaying the already executed transactions so no rejections are expected to occur.
         panic!(
             "Reject requested because of the following error: {}.\n Transaction hash is: {:?}",
-            error,
+            reason,
             tx.hash()
         );
     }
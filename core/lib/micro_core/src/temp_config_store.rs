@@ -47,4 +47,7 @@ pub struct TempConfigStore {
     pub gas_adjuster_config: Option<GasAdjusterConfig>,
     pub prover_configs: Option<ProverConfigs>,
     pub object_store_config: Option<ObjectStoreConfig>,
+    /// Secondary object store config. If set, objects are mirrored to this store, and it is used
+    /// as a fallback for reads from the primary store.
+    pub mirror_object_store_config: Option<ObjectStoreConfig>,
 }
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+
+use micro_system_constants::CONTRACT_DEPLOYER_ADDRESS;
+use micro_types::{vm_trace::FourByteTrace, Address, FarCallOpcode};
+use once_cell::sync::OnceCell;
+
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer counting calls to user contracts by the function selector and the calldata size,
+/// similarly to the `4byteTracer` in Geth.
+#[derive(Debug, Clone)]
+pub struct FourByteTracer {
+    trace: FourByteTrace,
+    result: Arc<OnceCell<FourByteTrace>>,
+}
+
+impl FourByteTracer {
+    pub fn new(result: Arc<OnceCell<FourByteTrace>>) -> Self {
+        Self {
+            trace: FourByteTrace::default(),
+            result,
+        }
+    }
+
+    /// Checks whether a far call should be recorded. Constructor calls (i.e., mimic calls made by
+    /// the contract deployer) are skipped, similarly to contract creation in Geth. Calls that haven't
+    /// started executing the callee code (e.g., calls to accounts without code, or calls that ran out of gas)
+    /// are skipped as well.
+    fn should_record(
+        far_call: FarCallOpcode,
+        caller_frame_address: Option<Address>,
+        callee_code_page: u32,
+        ergs_remaining: u32,
+    ) -> bool {
+        let is_constructor_call = far_call == FarCallOpcode::Mimic
+            && caller_frame_address == Some(CONTRACT_DEPLOYER_ADDRESS);
+        !is_constructor_call && callee_code_page != 0 && ergs_remaining != 0
+    }
+
+    fn store_result(&mut self) {
+        let result = std::mem::take(&mut self.trace);
+        let cell = self.result.as_ref();
+        cell.set(result).unwrap();
+    }
+}
@@ -0,0 +1,64 @@
+use micro_state::{StoragePtr, WriteStorage};
+use zk_evm_1_3_3::{
+    tracing::{AfterExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{FarCallABI, Opcode, CALL_IMPLICIT_CALLDATA_FAT_PTR_REGISTER},
+};
+
+use crate::{
+    interface::{tracer::VmExecutionStopReason, traits::tracers::dyn_tracers::vm_1_3_3::DynTracer},
+    tracers::four_byte_tracer::FourByteTracer,
+    vm_refunds_enhancement::{BootloaderState, HistoryMode, MicroVmState, SimpleMemory, VmTracer},
+};
+
+impl<S, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for FourByteTracer {
+    fn after_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: AfterExecutionData,
+        memory: &SimpleMemory<H>,
+        _storage: StoragePtr<S>,
+    ) {
+        let Opcode::FarCall(far_call) = data.opcode.variant.opcode else {
+            return;
+        };
+        let callstack = &state.vm_local_state.callstack;
+        let caller_frame_address = callstack.inner.last().map(|call| call.this_address);
+        let current = callstack.current;
+        if !Self::should_record(
+            far_call,
+            caller_frame_address,
+            current.code_page.0,
+            current.ergs_remaining,
+        ) {
+            return;
+        }
+        let packed_abi =
+            state.vm_local_state.registers[CALL_IMPLICIT_CALLDATA_FAT_PTR_REGISTER as usize];
+        if !packed_abi.is_pointer {
+            return;
+        }
+        let pointer = FarCallABI::from_u256(packed_abi.value).memory_quasi_fat_pointer;
+        let calldata_prefix = memory.read_unaligned_bytes(
+            pointer.memory_page as usize,
+            pointer.start as usize,
+            pointer.length.min(4) as usize,
+        );
+        self.trace.record_call(
+            current.msg_sender,
+            current.this_address,
+            &calldata_prefix,
+            pointer.length as usize,
+        );
+    }
+}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for FourByteTracer {
+    fn after_vm_execution(
+        &mut self,
+        _state: &mut MicroVmState<S, H>,
+        _bootloader_state: &BootloaderState,
+        _stop_reason: VmExecutionStopReason,
+    ) {
+        self.store_result()
+    }
+}
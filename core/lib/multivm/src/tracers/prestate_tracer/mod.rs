@@ -0,0 +1,229 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::Arc,
+};
+
+use micro_state::{StoragePtr, WriteStorage};
+use micro_types::{
+    get_code_key, get_nonce_key,
+    utils::{decompose_full_nonce, storage_key_for_eth_balance},
+    vm_trace::{is_system_contract, AccountState, PrestateTrace},
+    web3::types::Bytes,
+    AccountTreeId, Address, StorageKey, H256, U256,
+};
+use micro_utils::{h256_to_u256, u256_to_h256};
+use once_cell::sync::OnceCell;
+
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+/// Tracer collecting the state of accounts touched during execution, similarly to the `prestateTracer` in Geth.
+///
+/// Storage slots accessed by contracts are reported as is. Additionally, balance, nonce and code are reported
+/// for user accounts (i.e., not system contracts) participating in far calls or having accessed storage.
+/// In the diff mode, only modified accounts and slots are reported, both before and after the execution.
+#[derive(Debug, Clone)]
+pub struct PrestateTracer {
+    diff_mode: bool,
+    /// Values of storage slots before they were accessed for the first time.
+    initial_values: HashMap<StorageKey, H256>,
+    touched_accounts: BTreeSet<Address>,
+    result: Arc<OnceCell<PrestateTrace>>,
+}
+
+impl PrestateTracer {
+    pub fn new(diff_mode: bool, result: Arc<OnceCell<PrestateTrace>>) -> Self {
+        Self {
+            diff_mode,
+            initial_values: HashMap::new(),
+            touched_accounts: BTreeSet::new(),
+            result,
+        }
+    }
+
+    fn record_account(&mut self, address: Address) {
+        self.touched_accounts.insert(address);
+    }
+
+    fn record_storage_access<S: WriteStorage>(
+        &mut self,
+        address: Address,
+        slot: U256,
+        storage: &StoragePtr<S>,
+    ) {
+        self.touched_accounts.insert(address);
+        let key = StorageKey::new(AccountTreeId::new(address), u256_to_h256(slot));
+        self.initial_values
+            .entry(key)
+            .or_insert_with(|| storage.borrow_mut().read_value(&key));
+    }
+
+    fn store_result<S: WriteStorage>(&mut self, storage: StoragePtr<S>) {
+        let mut storage = storage.borrow_mut();
+        let storage = &mut *storage;
+        let initial_values = &self.initial_values;
+        // Slots that were never accessed during execution retain their initial values.
+        let mut read_initial_value = |storage: &mut S, key: &StorageKey| {
+            initial_values
+                .get(key)
+                .copied()
+                .unwrap_or_else(|| storage.read_value(key))
+        };
+
+        let user_accounts = self
+            .touched_accounts
+            .iter()
+            .filter(|address| !is_system_contract(address));
+        let mut pre = BTreeMap::new();
+        for &address in user_accounts.clone() {
+            pre.insert(
+                address,
+                account_state(storage, &address, &mut read_initial_value),
+            );
+        }
+        for (key, value) in initial_values {
+            let account = pre.entry(*key.address()).or_default();
+            account.storage.insert(*key.key(), *value);
+        }
+
+        let result = if self.diff_mode {
+            let mut post = BTreeMap::new();
+            for &address in user_accounts {
+                let state = account_state(
+                    storage,
+                    &address,
+                    &mut |storage: &mut S, key: &StorageKey| storage.read_value(key),
+                );
+                post.insert(address, state);
+            }
+            for key in initial_values.keys() {
+                let account = post.entry(*key.address()).or_default();
+                account.storage.insert(*key.key(), storage.read_value(key));
+            }
+            let (pre, post) = diff_states(pre, post);
+            PrestateTrace::Diff { pre, post }
+        } else {
+            PrestateTrace::Prestate(pre)
+        };
+
+        let cell = self.result.as_ref();
+        cell.set(result).unwrap();
+    }
+}
+
+fn account_state<S: WriteStorage>(
+    storage: &mut S,
+    address: &Address,
+    read_value: &mut impl FnMut(&mut S, &StorageKey) -> H256,
+) -> AccountState {
+    let balance = h256_to_u256(read_value(storage, &storage_key_for_eth_balance(address)));
+    let full_nonce = h256_to_u256(read_value(storage, &get_nonce_key(address)));
+    let (nonce, _) = decompose_full_nonce(full_nonce);
+    let code_hash = read_value(storage, &get_code_key(address));
+    let code = if code_hash == H256::zero() {
+        None
+    } else {
+        storage.load_factory_dep(code_hash).map(Bytes::from)
+    };
+
+    AccountState {
+        balance: Some(balance),
+        nonce: Some(nonce.low_u64()),
+        code,
+        storage: BTreeMap::new(),
+    }
+}
+
+/// Removes unchanged fields and accounts from the pre- and post-execution states.
+fn diff_states(
+    mut pre: BTreeMap<Address, AccountState>,
+    mut post: BTreeMap<Address, AccountState>,
+) -> (
+    BTreeMap<Address, AccountState>,
+    BTreeMap<Address, AccountState>,
+) {
+    for (address, pre_state) in &mut pre {
+        let Some(post_state) = post.get_mut(address) else {
+            continue;
+        };
+        if pre_state.balance == post_state.balance {
+            pre_state.balance = None;
+            post_state.balance = None;
+        }
+        if pre_state.nonce == post_state.nonce {
+            pre_state.nonce = None;
+            post_state.nonce = None;
+        }
+        if pre_state.code == post_state.code {
+            pre_state.code = None;
+            post_state.code = None;
+        }
+        pre_state
+            .storage
+            .retain(|slot, value| post_state.storage.get(slot) != Some(value));
+        post_state
+            .storage
+            .retain(|slot, _| pre_state.storage.contains_key(slot));
+    }
+    pre.retain(|_, state| !state.is_empty());
+    post.retain(|_, state| !state.is_empty());
+    (pre, post)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diffing_states() {
+        let unchanged_address = Address::repeat_byte(1);
+        let changed_address = Address::repeat_byte(2);
+        let unchanged_state = AccountState {
+            balance: Some(100.into()),
+            nonce: Some(1),
+            code: None,
+            storage: BTreeMap::from([(H256::zero(), H256::repeat_byte(1))]),
+        };
+        let pre_state = AccountState {
+            balance: Some(100.into()),
+            nonce: Some(1),
+            code: None,
+            storage: BTreeMap::from([
+                (H256::zero(), H256::repeat_byte(1)),
+                (H256::repeat_byte(1), H256::zero()),
+            ]),
+        };
+        let post_state = AccountState {
+            balance: Some(50.into()),
+            nonce: Some(1),
+            code: None,
+            storage: BTreeMap::from([
+                (H256::zero(), H256::repeat_byte(1)),
+                (H256::repeat_byte(1), H256::repeat_byte(2)),
+            ]),
+        };
+        let pre = BTreeMap::from([
+            (unchanged_address, unchanged_state.clone()),
+            (changed_address, pre_state),
+        ]);
+        let post = BTreeMap::from([
+            (unchanged_address, unchanged_state),
+            (changed_address, post_state),
+        ]);
+
+        let (pre, post) = diff_states(pre, post);
+        let expected_pre = AccountState {
+            balance: Some(100.into()),
+            storage: BTreeMap::from([(H256::repeat_byte(1), H256::zero())]),
+            ..AccountState::default()
+        };
+        let expected_post = AccountState {
+            balance: Some(50.into()),
+            storage: BTreeMap::from([(H256::repeat_byte(1), H256::repeat_byte(2))]),
+            ..AccountState::default()
+        };
+        assert_eq!(pre, BTreeMap::from([(changed_address, expected_pre)]));
+        assert_eq!(post, BTreeMap::from([(changed_address, expected_post)]));
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use micro_state::{StoragePtr, WriteStorage};
+use micro_system_constants::BOOTLOADER_ADDRESS;
+use micro_types::{
+    vm_trace::{is_system_contract, StructLog},
+    AccountTreeId, Address, StorageKey, H256, U256,
+};
+use micro_utils::u256_to_h256;
+use once_cell::sync::OnceCell;
+
+pub mod vm_latest;
+pub mod vm_refunds_enhancement;
+pub mod vm_virtual_blocks;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StructLoggerParams {
+    /// Report VM registers for each step.
+    pub enable_registers: bool,
+    /// Report accessed storage slots for storage access steps.
+    pub enable_storage: bool,
+    /// Maximum number of logged steps.
+    pub limit: Option<usize>,
+}
+
+/// Tracer logging executed opcodes, similarly to the struct logger in Geth.
+///
+/// Only opcodes executed by user contracts are logged; opcodes executed by system contracts
+/// (including the bootloader) and by the account entry points called by the bootloader
+/// (transaction validation, execution and fee payment) are skipped.
+#[derive(Debug, Clone)]
+pub struct StructLogger {
+    params: StructLoggerParams,
+    logs: Vec<StructLog>,
+    /// Storage slots accessed by each contract so far.
+    storage: HashMap<Address, BTreeMap<H256, H256>>,
+    result: Arc<OnceCell<Vec<StructLog>>>,
+}
+
+impl StructLogger {
+    pub fn new(params: StructLoggerParams, result: Arc<OnceCell<Vec<StructLog>>>) -> Self {
+        Self {
+            params,
+            logs: vec![],
+            storage: HashMap::new(),
+            result,
+        }
+    }
+
+    fn should_log(&self, this_address: &Address, msg_sender: &Address) -> bool {
+        let limit_reached = self
+            .params
+            .limit
+            .map_or(false, |limit| self.logs.len() >= limit);
+        !limit_reached && *msg_sender != BOOTLOADER_ADDRESS && !is_system_contract(this_address)
+    }
+
+    /// Logs an executed opcode. `log` is expected to have registers and storage unset; these are filled
+    /// based on the tracer params. `storage_access` is the accessed slot together with the written value
+    /// for storage writes.
+    fn log_step<S: WriteStorage>(
+        &mut self,
+        mut log: StructLog,
+        registers: impl FnOnce() -> Vec<U256>,
+        storage_access: Option<(U256, Option<U256>)>,
+        storage: &StoragePtr<S>,
+    ) {
+        if self.params.enable_registers {
+            log.registers = Some(registers());
+        }
+        if let Some((slot, written_value)) = storage_access {
+            log.storage = self.record_storage_access(log.address, slot, written_value, storage);
+        }
+        self.logs.push(log);
+    }
+
+    /// Records a storage access and returns the snapshot of the contract storage
+    /// to be included into the log.
+    fn record_storage_access<S: WriteStorage>(
+        &mut self,
+        address: Address,
+        slot: U256,
+        written_value: Option<U256>,
+        storage: &StoragePtr<S>,
+    ) -> Option<BTreeMap<H256, H256>> {
+        if !self.params.enable_storage {
+            return None;
+        }
+        let slot = u256_to_h256(slot);
+        let value = written_value.map(u256_to_h256).unwrap_or_else(|| {
+            let key = StorageKey::new(AccountTreeId::new(address), slot);
+            storage.borrow_mut().read_value(&key)
+        });
+        let contract_storage = self.storage.entry(address).or_default();
+        contract_storage.insert(slot, value);
+        Some(contract_storage.clone())
+    }
+
+    fn store_result(&mut self) {
+        let result = std::mem::take(&mut self.logs);
+        let cell = self.result.as_ref();
+        cell.set(result).unwrap();
+    }
+}
@@ -0,0 +1,67 @@
+use micro_state::{StoragePtr, WriteStorage};
+use micro_types::vm_trace::StructLog;
+use zk_evm_1_3_3::{
+    tracing::{BeforeExecutionData, VmLocalStateData},
+    zkevm_opcode_defs::{LogOpcode, Opcode},
+};
+
+use crate::{
+    interface::{dyn_tracers::vm_1_3_3::DynTracer, VmExecutionResultAndLogs},
+    tracers::struct_logger::StructLogger,
+    vm_virtual_blocks::{
+        ExecutionEndTracer, ExecutionProcessing, HistoryMode, SimpleMemory, VmTracer,
+    },
+};
+
+impl<S: WriteStorage, H: HistoryMode> DynTracer<S, SimpleMemory<H>> for StructLogger {
+    fn before_execution(
+        &mut self,
+        state: VmLocalStateData<'_>,
+        data: BeforeExecutionData,
+        _memory: &SimpleMemory<H>,
+        storage: StoragePtr<S>,
+    ) {
+        let current = state.vm_local_state.callstack.current;
+        if !self.should_log(&current.this_address, &current.msg_sender) {
+            return;
+        }
+
+        let opcode = data.opcode.variant.opcode;
+        let storage_access = match opcode {
+            Opcode::Log(LogOpcode::StorageRead) => Some((data.src0_value.value, None)),
+            Opcode::Log(LogOpcode::StorageWrite) => {
+                Some((data.src0_value.value, Some(data.src1_value.value)))
+            }
+            _ => None,
+        };
+        let log = StructLog {
+            pc: current.pc,
+            op: format!("{opcode:?}"),
+            gas: current.ergs_remaining,
+            gas_cost: data.opcode.inner.variant.ergs_price(),
+            depth: state.vm_local_state.callstack.depth(),
+            address: current.this_address,
+            registers: None,
+            storage: None,
+        };
+        let registers = || {
+            state
+                .vm_local_state
+                .registers
+                .iter()
+                .map(|register| register.value)
+                .collect()
+        };
+        self.log_step(log, registers, storage_access, &storage);
+    }
+}
+
+impl<H: HistoryMode> ExecutionEndTracer<H> for StructLogger {}
+
+impl<S: WriteStorage, H: HistoryMode> ExecutionProcessing<S, H> for StructLogger {}
+
+impl<S: WriteStorage, H: HistoryMode> VmTracer<S, H> for StructLogger {
+    fn save_results(&mut self, _result: &mut VmExecutionResultAndLogs) {
+        self.store_result()
+    }
+}
@@ -0,0 +1,157 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use micro_types::{
+    vm_trace::{AccountState, PrestateTrace},
+    Address, Execute, Transaction, H256, U256,
+};
+use once_cell::sync::OnceCell;
+
+use crate::{
+    interface::{TxExecutionMode, VmExecutionMode, VmInterface},
+    tracers::{FourByteTracer, PrestateTracer, StructLogger, StructLoggerParams},
+    vm_latest::{
+        constants::BLOCK_GAS_LIMIT,
+        tests::{
+            tester::{VmTester, VmTesterBuilder},
+            utils::read_test_contract,
+        },
+        HistoryEnabled, ToTracerPointer,
+    },
+};
+
+const INCREMENT_BY_6_CALLDATA: &str =
+    "7cf5dab00000000000000000000000000000000000000000000000000000000000000006";
+
+fn prepare_tester() -> (VmTester<HistoryEnabled>, Address, Transaction) {
+    let contract = read_test_contract();
+    let address = Address::random();
+    let mut vm = VmTesterBuilder::new(HistoryEnabled)
+        .with_empty_in_memory_storage()
+        .with_random_rich_accounts(1)
+        .with_deployer()
+        .with_gas_limit(BLOCK_GAS_LIMIT)
+        .with_execution_mode(TxExecutionMode::VerifyExecute)
+        .with_custom_contracts(vec![(contract, address, true)])
+        .build();
+
+    let account = &mut vm.rich_accounts[0];
+    let tx = account.get_l2_tx_for_execute(
+        Execute {
+            contract_address: address,
+            calldata: hex::decode(INCREMENT_BY_6_CALLDATA).unwrap(),
+            value: Default::default(),
+            factory_deps: None,
+        },
+        None,
+    );
+    (vm, address, tx)
+}
+
+#[test]
+fn four_byte_tracer_basics() {
+    let (mut vm, _, tx) = prepare_tester();
+    let result = Arc::new(OnceCell::new());
+    let tracer = FourByteTracer::new(result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let trace = result.get().unwrap();
+    let expected_trace = BTreeMap::from([("0x7cf5dab0-32".to_owned(), 1)]);
+    assert_eq!(trace.0, expected_trace);
+}
+
+#[test]
+fn prestate_tracer_basics() {
+    let (mut vm, contract_address, tx) = prepare_tester();
+    let account_address = vm.rich_accounts[0].address;
+    let result = Arc::new(OnceCell::new());
+    let tracer = PrestateTracer::new(false, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let PrestateTrace::Prestate(accounts) = result.get().unwrap() else {
+        panic!("Unexpected trace: {:?}", result.get());
+    };
+    let contract_state = &accounts[&contract_address];
+    assert_eq!(
+        contract_state.storage,
+        BTreeMap::from([(H256::zero(), H256::zero())])
+    );
+    let account_state = &accounts[&account_address];
+    assert_eq!(account_state.nonce, Some(0));
+    assert!(account_state.balance.unwrap() > U256::zero());
+}
+
+#[test]
+fn prestate_tracer_in_diff_mode() {
+    let (mut vm, contract_address, tx) = prepare_tester();
+    let account_address = vm.rich_accounts[0].address;
+    let result = Arc::new(OnceCell::new());
+    let tracer = PrestateTracer::new(true, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let PrestateTrace::Diff { pre, post } = result.get().unwrap() else {
+        panic!("Unexpected trace: {:?}", result.get());
+    };
+    let expected_pre_state = AccountState {
+        storage: BTreeMap::from([(H256::zero(), H256::zero())]),
+        ..AccountState::default()
+    };
+    assert_eq!(pre[&contract_address], expected_pre_state);
+    let expected_post_state = AccountState {
+        storage: BTreeMap::from([(H256::zero(), H256::from_low_u64_be(6))]),
+        ..AccountState::default()
+    };
+    assert_eq!(post[&contract_address], expected_post_state);
+
+    // The account nonce is incremented and the fee is charged.
+    assert_eq!(pre[&account_address].nonce, Some(0));
+    assert_eq!(post[&account_address].nonce, Some(1));
+    assert!(pre[&account_address].balance > post[&account_address].balance);
+}
+
+#[test]
+fn struct_logger_basics() {
+    let (mut vm, contract_address, tx) = prepare_tester();
+    let params = StructLoggerParams {
+        enable_registers: true,
+        enable_storage: true,
+        limit: None,
+    };
+    let result = Arc::new(OnceCell::new());
+    let tracer = StructLogger::new(params, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    let res = vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+    assert!(!res.result.is_failed());
+
+    let logs = result.get().unwrap();
+    assert!(!logs.is_empty());
+    assert!(logs.iter().all(|log| log.address == contract_address));
+    assert!(logs.iter().all(|log| log.registers.is_some()));
+    let storage_write = logs
+        .iter()
+        .find(|log| log.op.contains("StorageWrite"))
+        .expect("no storage writes");
+    let expected_storage = BTreeMap::from([(H256::zero(), H256::from_low_u64_be(6))]);
+    assert_eq!(storage_write.storage.as_ref(), Some(&expected_storage));
+
+    let (mut vm, _, tx) = prepare_tester();
+    let params = StructLoggerParams {
+        limit: Some(5),
+        ..StructLoggerParams::default()
+    };
+    let result = Arc::new(OnceCell::new());
+    let tracer = StructLogger::new(params, result.clone()).into_tracer_pointer();
+    vm.vm.push_transaction(tx);
+    vm.vm.inspect(tracer.into(), VmExecutionMode::OneTx);
+
+    let logs = result.get().unwrap();
+    assert_eq!(logs.len(), 5);
+    assert!(logs
+        .iter()
+        .all(|log| log.registers.is_none() && log.storage.is_none()));
+}
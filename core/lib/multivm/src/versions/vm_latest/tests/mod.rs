@@ -4,6 +4,7 @@ mod default_aa;
 // mod invalid_bytecode;
 mod bytecode_publishing;
 mod call_tracer;
+mod debug_tracers;
 mod gas_limit;
 mod get_used_contracts;
 mod is_write_initial;
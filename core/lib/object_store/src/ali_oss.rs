@@ -6,7 +6,7 @@ use async_trait::async_trait;
 use oss_rust_sdk::{async_object::*, oss::OSS};
 use serde::{Deserialize, Serialize};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
 
 async fn retry<T, E, Fut, F>(max_retries: u16, mut f: F) -> Result<T, E>
 where
@@ -138,6 +138,55 @@ impl<'a> ObjectStore for AliyunOssStorage<'a> {
         Ok(())
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        /// Maximum number of objects returned by OSS in a single response.
+        const MAX_KEYS_PER_REQUEST: usize = 1_000;
+
+        let bucket_dir = self.filename(&self.bucket_prefix, bucket.as_str(), "");
+        let full_prefix = format!("{bucket_dir}{prefix}");
+        let mut objects = vec![];
+        // OSS lists objects in the lexicographic order starting after `marker` (exclusive).
+        let mut marker = start_after.map(|key| format!("{bucket_dir}{key}"));
+        loop {
+            let max_keys = (limit - objects.len())
+                .min(MAX_KEYS_PER_REQUEST)
+                .to_string();
+            let page = retry(self.max_retries, || {
+                let mut resources: HashMap<&str, Option<&str>> = HashMap::new();
+                resources.insert("prefix", Some(&full_prefix));
+                resources.insert("max-keys", Some(&max_keys));
+                if let Some(marker) = &marker {
+                    resources.insert("marker", Some(marker));
+                }
+                self.client
+                    .list_object(HashMap::<&str, &str>::new(), resources)
+            })
+            .await?;
+
+            let contents = page.contents();
+            marker = contents.last().map(|object| object.key().to_owned());
+            objects.extend(contents.iter().filter_map(|object| {
+                let key = object.key().strip_prefix(&bucket_dir)?;
+                Some(ObjectMetadata {
+                    key: key.to_owned(),
+                    size: object.size() as u64,
+                })
+            }));
+
+            if objects.len() >= limit || !page.is_truncated() || marker.is_none() {
+                break;
+            }
+        }
+        objects.truncate(limit);
+        Ok(objects)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.bucket_prefix, bucket)
     }
@@ -3,7 +3,7 @@ use std::fmt::Debug;
 use async_trait::async_trait;
 use tokio::{fs, io};
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
 
 impl From<io::Error> for ObjectStoreError {
     fn from(err: io::Error) -> Self {
@@ -21,19 +21,7 @@ pub(crate) struct FileBackedObjectStore {
 
 impl FileBackedObjectStore {
     pub async fn new(base_dir: String) -> Self {
-        for bucket in &[
-            Bucket::ProverJobs,
-            Bucket::WitnessInput,
-            Bucket::LeafAggregationWitnessJobs,
-            Bucket::NodeAggregationWitnessJobs,
-            Bucket::SchedulerWitnessJobs,
-            Bucket::ProverJobsFri,
-            Bucket::LeafAggregationWitnessJobsFri,
-            Bucket::NodeAggregationWitnessJobsFri,
-            Bucket::SchedulerWitnessJobsFri,
-            Bucket::ProofsFri,
-            Bucket::StorageSnapshot,
-        ] {
+        for bucket in Bucket::ALL {
             let bucket_path = format!("{base_dir}/{bucket}");
             fs::create_dir_all(&bucket_path)
                 .await
@@ -71,6 +59,42 @@ impl ObjectStore for FileBackedObjectStore {
         fs::remove_file(filename).await.map_err(From::from)
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        let mut entries = fs::read_dir(self.storage_prefix_raw(bucket)).await?;
+        let mut keys = vec![];
+        while let Some(entry) = entries.next_entry().await? {
+            let Ok(key) = entry.file_name().into_string() else {
+                continue; // Keys are always valid UTF-8, so the file cannot belong to the store
+            };
+            if key.starts_with(prefix) && start_after.map_or(true, |start| key.as_str() > start) {
+                keys.push(key);
+            }
+        }
+        keys.sort_unstable();
+
+        // Only file metadata for the requested page is fetched.
+        let mut objects = Vec::with_capacity(limit.min(keys.len()));
+        for key in keys {
+            if objects.len() >= limit {
+                break;
+            }
+            let metadata = fs::metadata(self.filename(bucket, &key)).await?;
+            if metadata.is_file() {
+                objects.push(ObjectMetadata {
+                    key,
+                    size: metadata.len(),
+                });
+            }
+        }
+        Ok(objects)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.base_dir, bucket)
     }
@@ -125,4 +149,47 @@ mod test {
             .await;
         assert!(result.is_ok(), "result must be OK");
     }
+
+    #[tokio::test]
+    async fn test_list() {
+        let dir = TempDir::new("test-data").unwrap();
+        let path = dir.into_path().into_os_string().into_string().unwrap();
+        let object_store = FileBackedObjectStore::new(path).await;
+        for (key, value) in [
+            ("1_a.bin", vec![0]),
+            ("1_b.bin", vec![0, 1]),
+            ("2_a.bin", vec![]),
+        ] {
+            object_store
+                .put_raw(Bucket::ProverJobs, key, value)
+                .await
+                .unwrap();
+        }
+
+        let objects = object_store
+            .list_raw(Bucket::ProverJobs, "1_", None, 10)
+            .await
+            .unwrap();
+        let expected = [("1_a.bin", 1), ("1_b.bin", 2)].map(|(key, size)| ObjectMetadata {
+            key: key.to_owned(),
+            size,
+        });
+        assert_eq!(objects, expected);
+
+        let all_objects = object_store
+            .list_raw(Bucket::ProverJobs, "", None, 10)
+            .await
+            .unwrap();
+        assert_eq!(all_objects.len(), 3);
+        let page = object_store
+            .list_raw(Bucket::ProverJobs, "", Some("1_a.bin"), 1)
+            .await
+            .unwrap();
+        assert_eq!(page, [expected[1].clone()]);
+        let other_bucket_objects = object_store
+            .list_raw(Bucket::WitnessInput, "", None, 10)
+            .await
+            .unwrap();
+        assert!(other_bucket_objects.is_empty());
+    }
 }
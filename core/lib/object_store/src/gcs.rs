@@ -11,6 +11,7 @@ use google_cloud_storage::{
             delete::DeleteObjectRequest,
             download::Range,
             get::GetObjectRequest,
+            list::ListObjectsRequest,
             upload::{Media, UploadObjectRequest, UploadType},
         },
         Error as HttpError,
@@ -20,7 +21,7 @@ use http::StatusCode;
 
 use crate::{
     metrics::GCS_METRICS,
-    raw::{Bucket, ObjectStore, ObjectStoreError},
+    raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError},
 };
 
 async fn retry<T, E, Fut, F>(max_retries: u16, mut f: F) -> Result<T, E>
@@ -208,6 +209,53 @@ impl ObjectStore for GoogleCloudStorage {
         self.remove_inner(bucket.as_str(), key).await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        let bucket_dir = Self::filename(bucket.as_str(), "");
+        tracing::trace!(
+            "Listing objects in GCS with prefix {bucket_dir}{prefix} from bucket {}",
+            self.bucket_prefix
+        );
+
+        // GCS lists objects in the lexicographic order; `start_offset` is inclusive, so the `start_after` key
+        // itself is filtered out below.
+        let start_after = start_after.map(|key| format!("{bucket_dir}{key}"));
+        let mut request = ListObjectsRequest {
+            bucket: self.bucket_prefix.clone(),
+            prefix: Some(format!("{bucket_dir}{prefix}")),
+            start_offset: start_after.clone(),
+            max_results: Some(i32::try_from(limit).unwrap_or(i32::MAX)),
+            ..ListObjectsRequest::default()
+        };
+        let mut objects = vec![];
+        loop {
+            let response = retry(self.max_retries, || self.client.list_objects(&request)).await?;
+            let items = response.items.unwrap_or_default();
+            objects.extend(items.into_iter().filter_map(|object| {
+                if start_after.as_ref() == Some(&object.name) {
+                    return None;
+                }
+                let key = object.name.strip_prefix(&bucket_dir)?;
+                Some(ObjectMetadata {
+                    key: key.to_owned(),
+                    size: object.size.try_into().unwrap_or(0),
+                })
+            }));
+
+            if objects.len() >= limit || response.next_page_token.is_none() {
+                break;
+            }
+            request.page_token = response.next_page_token;
+        }
+        objects.truncate(limit);
+        Ok(objects)
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!(
             "https://storage.googleapis.com/{}/{}",
@@ -7,7 +7,7 @@ use std::{
 use async_trait::async_trait;
 use reqwest::Client;
 
-use crate::raw::{Bucket, ObjectStore, ObjectStoreError};
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
 
 impl From<reqwest::Error> for ObjectStoreError {
     fn from(err: reqwest::Error) -> Self {
@@ -94,6 +94,22 @@ impl ObjectStore for HttpBackedObjectStore {
         Ok(())
     }
 
+    /// The HTTP object store protocol has no listing endpoint, so listing is not supported.
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        _prefix: &str,
+        _start_after: Option<&str>,
+        _limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        let err = format!("listing bucket `{bucket}` is not supported by HTTP-backed object store");
+        Err(ObjectStoreError::Other(err.into()))
+    }
+
+    fn supports_listing(&self) -> bool {
+        false
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         format!("{}/{}", self.base_url, bucket)
     }
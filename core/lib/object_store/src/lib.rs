@@ -1,8 +1,9 @@
 //! This crate provides the [object storage abstraction](ObjectStore) that allows to get,
-//! put and remove binary blobs. The following implementations are available:
+//! put, remove and list binary blobs. The following implementations are available:
 //!
 //! - File-based storage saving blobs as separate files in the local filesystem
 //! - GCS-based storage
+//! - Mirrored storage writing to two other stores, which is useful for migrations between backends
 //!
 //! These implementations are not exposed externally. Instead, a store trait object
 //! can be constructed using an [`ObjectStoreFactory`] based on the configuration.
@@ -28,6 +29,7 @@ mod file;
 mod gcs;
 mod http;
 mod metrics;
+mod mirrored;
 mod mock;
 mod objects;
 mod raw;
@@ -42,5 +44,5 @@ pub mod _reexports {
 
 pub use self::{
     objects::{AggregationsKey, CircuitKey, ClosedFormInputKey, FriCircuitKey, StoredObject},
-    raw::{Bucket, ObjectStore, ObjectStoreError, ObjectStoreFactory},
+    raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError, ObjectStoreFactory},
 };
@@ -0,0 +1,360 @@
+//! [`ObjectStore`] mirroring writes to two stores, used for migrating between storage backends.
+//! See the `object_store_migrate` tool README for the migration procedure.
+
+use std::collections::BTreeMap;
+
+use async_trait::async_trait;
+
+use crate::raw::{Bucket, ObjectMetadata, ObjectStore, ObjectStoreError};
+
+/// Object store that writes to both primary and secondary (aka mirror) stores, and reads from the primary store,
+/// falling back to the secondary store if the object cannot be read from the primary one.
+///
+/// This allows migrating between storage backends without downtime. While existing objects are copied
+/// by the `object_store_migrate` tool, the old backend is the primary store and the new backend is the secondary one,
+/// so that new objects are written to both backends. After the copy is complete, the new backend becomes
+/// the primary store; the old backend may be kept as the secondary store for a while, so that objects
+/// missed by the tool are still readable.
+#[derive(Debug)]
+pub(crate) struct MirroredObjectStore {
+    primary: Box<dyn ObjectStore>,
+    secondary: Box<dyn ObjectStore>,
+}
+
+impl MirroredObjectStore {
+    pub fn new(primary: Box<dyn ObjectStore>, secondary: Box<dyn ObjectStore>) -> Self {
+        Self { primary, secondary }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for MirroredObjectStore {
+    async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+        match self.primary.get_raw(bucket, key).await {
+            Ok(value) => Ok(value),
+            Err(err) => {
+                tracing::debug!(
+                    "Failed fetching key {key} in bucket {bucket} from primary store ({err}); \
+                     falling back to secondary store"
+                );
+                self.secondary.get_raw(bucket, key).await
+            }
+        }
+    }
+
+    async fn put_raw(
+        &self,
+        bucket: Bucket,
+        key: &str,
+        value: Vec<u8>,
+    ) -> Result<(), ObjectStoreError> {
+        self.primary.put_raw(bucket, key, value.clone()).await?;
+        self.secondary.put_raw(bucket, key, value).await
+    }
+
+    async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError> {
+        // Removal is attempted in both stores even if one of them fails, so that objects are not left behind
+        // in the other store. A missing key is not an error, since objects may be present in only one
+        // of the stores during migration.
+        let primary_result = ignore_missing_key(self.primary.remove_raw(bucket, key).await);
+        let secondary_result = ignore_missing_key(self.secondary.remove_raw(bucket, key).await);
+        match (primary_result, secondary_result) {
+            (Ok(()), Ok(())) => Ok(()),
+            (Err(err), Ok(())) => {
+                tracing::warn!(
+                    "Failed removing key {key} in bucket {bucket} from primary store, \
+                     but removed it from secondary store: {err}"
+                );
+                Err(err)
+            }
+            (Ok(()), Err(err)) => {
+                tracing::warn!(
+                    "Failed removing key {key} in bucket {bucket} from secondary store, \
+                     but removed it from primary store: {err}"
+                );
+                Err(err)
+            }
+            (Err(primary_err), Err(secondary_err)) => {
+                tracing::warn!(
+                    "Failed removing key {key} in bucket {bucket} from secondary store: {secondary_err}"
+                );
+                Err(primary_err)
+            }
+        }
+    }
+
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        // Pages from both stores are ordered by key and are only shorter than `limit` if a store has no more objects,
+        // so the first `limit` keys of the merged pages are the first `limit` keys in the union of both stores.
+        let primary_objects = self
+            .primary
+            .list_raw(bucket, prefix, start_after, limit)
+            .await?;
+        let secondary_objects = self
+            .secondary
+            .list_raw(bucket, prefix, start_after, limit)
+            .await?;
+
+        let mut objects: BTreeMap<_, _> = secondary_objects
+            .into_iter()
+            .map(|object| (object.key.clone(), object))
+            .collect();
+        // Metadata from the primary store takes precedence.
+        objects.extend(
+            primary_objects
+                .into_iter()
+                .map(|object| (object.key.clone(), object)),
+        );
+        let mut objects: Vec<_> = objects.into_values().collect();
+        objects.truncate(limit);
+        Ok(objects)
+    }
+
+    fn supports_listing(&self) -> bool {
+        self.primary.supports_listing() && self.secondary.supports_listing()
+    }
+
+    fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+        self.primary.storage_prefix_raw(bucket)
+    }
+}
+
+fn ignore_missing_key(result: Result<(), ObjectStoreError>) -> Result<(), ObjectStoreError> {
+    match result {
+        Err(ObjectStoreError::KeyNotFound(_)) => Ok(()),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tempdir::TempDir;
+
+    use super::*;
+    use crate::{file::FileBackedObjectStore, mock::MockStore};
+
+    /// Store wrapper failing all removals.
+    #[derive(Debug)]
+    struct NonRemovableStore(Arc<MockStore>);
+
+    #[async_trait]
+    impl ObjectStore for NonRemovableStore {
+        async fn get_raw(&self, bucket: Bucket, key: &str) -> Result<Vec<u8>, ObjectStoreError> {
+            self.0.get_raw(bucket, key).await
+        }
+
+        async fn put_raw(
+            &self,
+            bucket: Bucket,
+            key: &str,
+            value: Vec<u8>,
+        ) -> Result<(), ObjectStoreError> {
+            self.0.put_raw(bucket, key, value).await
+        }
+
+        async fn remove_raw(&self, _bucket: Bucket, _key: &str) -> Result<(), ObjectStoreError> {
+            Err(ObjectStoreError::Other("removals are not allowed".into()))
+        }
+
+        async fn list_raw(
+            &self,
+            bucket: Bucket,
+            prefix: &str,
+            start_after: Option<&str>,
+            limit: usize,
+        ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+            self.0.list_raw(bucket, prefix, start_after, limit).await
+        }
+
+        fn storage_prefix_raw(&self, bucket: Bucket) -> String {
+            self.0.storage_prefix_raw(bucket)
+        }
+    }
+
+    fn create_stores() -> (Arc<MockStore>, Arc<MockStore>, MirroredObjectStore) {
+        let primary = Arc::new(MockStore::default());
+        let secondary = Arc::new(MockStore::default());
+        let store =
+            MirroredObjectStore::new(Box::new(primary.clone()), Box::new(secondary.clone()));
+        (primary, secondary, store)
+    }
+
+    #[tokio::test]
+    async fn writes_are_mirrored() {
+        let (primary, secondary, store) = create_stores();
+        store
+            .put_raw(Bucket::ProofsFri, "test.bin", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        for backend in [&primary, &secondary] {
+            let value = backend
+                .get_raw(Bucket::ProofsFri, "test.bin")
+                .await
+                .unwrap();
+            assert_eq!(value, [1, 2, 3]);
+        }
+
+        store
+            .remove_raw(Bucket::ProofsFri, "test.bin")
+            .await
+            .unwrap();
+        for backend in [&primary, &secondary] {
+            let err = backend
+                .get_raw(Bucket::ProofsFri, "test.bin")
+                .await
+                .unwrap_err();
+            assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+        }
+    }
+
+    #[tokio::test]
+    async fn reads_fall_back_to_secondary_store() {
+        let (primary, secondary, store) = create_stores();
+        secondary
+            .put_raw(Bucket::ProofsFri, "old.bin", vec![1])
+            .await
+            .unwrap();
+        primary
+            .put_raw(Bucket::ProofsFri, "new.bin", vec![2])
+            .await
+            .unwrap();
+
+        let value = store.get_raw(Bucket::ProofsFri, "old.bin").await.unwrap();
+        assert_eq!(value, [1]);
+        let value = store.get_raw(Bucket::ProofsFri, "new.bin").await.unwrap();
+        assert_eq!(value, [2]);
+        let err = store
+            .get_raw(Bucket::ProofsFri, "missing.bin")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+
+        let keys: Vec<_> = store
+            .list_raw(Bucket::ProofsFri, "", None, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|object| object.key)
+            .collect();
+        assert_eq!(keys, ["new.bin", "old.bin"]);
+    }
+
+    #[tokio::test]
+    async fn listing_objects_in_pages() {
+        let (primary, secondary, store) = create_stores();
+        for key in ["a.bin", "c.bin", "d.bin"] {
+            primary
+                .put_raw(Bucket::ProofsFri, key, vec![1])
+                .await
+                .unwrap();
+        }
+        for key in ["a.bin", "b.bin", "e.bin"] {
+            secondary
+                .put_raw(Bucket::ProofsFri, key, vec![2, 2])
+                .await
+                .unwrap();
+        }
+
+        let mut pages = vec![];
+        let mut start_after = None;
+        loop {
+            let page = store
+                .list_raw(Bucket::ProofsFri, "", start_after.as_deref(), 2)
+                .await
+                .unwrap();
+            start_after = page.last().map(|object| object.key.clone());
+            let is_last_page = page.len() < 2;
+            pages.push(page);
+            if is_last_page {
+                break;
+            }
+        }
+
+        let keys: Vec<Vec<_>> = pages
+            .iter()
+            .map(|page| page.iter().map(|object| object.key.as_str()).collect())
+            .collect();
+        assert_eq!(
+            keys,
+            [
+                vec!["a.bin", "b.bin"],
+                vec!["c.bin", "d.bin"],
+                vec!["e.bin"]
+            ]
+        );
+        // Metadata is taken from the primary store for objects present in both stores.
+        assert_eq!(pages[0][0].size, 1);
+    }
+
+    #[tokio::test]
+    async fn removal_is_attempted_in_both_stores() {
+        let primary = Arc::new(MockStore::default());
+        let secondary = Arc::new(MockStore::default());
+        let store = MirroredObjectStore::new(
+            Box::new(NonRemovableStore(primary.clone())),
+            Box::new(secondary.clone()),
+        );
+        store
+            .put_raw(Bucket::ProofsFri, "test.bin", vec![1, 2, 3])
+            .await
+            .unwrap();
+
+        let err = store
+            .remove_raw(Bucket::ProofsFri, "test.bin")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::Other(_)), "{err}");
+        primary
+            .get_raw(Bucket::ProofsFri, "test.bin")
+            .await
+            .unwrap();
+        let err = secondary
+            .get_raw(Bucket::ProofsFri, "test.bin")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ObjectStoreError::KeyNotFound(_)), "{err}");
+    }
+
+    #[tokio::test]
+    async fn removing_key_missing_in_one_store() {
+        // Unlike the mock store, the file-backed store returns `KeyNotFound` on removing a missing key.
+        let primary_dir = TempDir::new("primary").unwrap();
+        let secondary_dir = TempDir::new("secondary").unwrap();
+        let primary =
+            FileBackedObjectStore::new(primary_dir.path().to_str().unwrap().to_owned()).await;
+        let secondary =
+            FileBackedObjectStore::new(secondary_dir.path().to_str().unwrap().to_owned()).await;
+        primary
+            .put_raw(Bucket::ProofsFri, "new.bin", vec![1])
+            .await
+            .unwrap();
+        secondary
+            .put_raw(Bucket::ProofsFri, "old.bin", vec![2])
+            .await
+            .unwrap();
+        let store = MirroredObjectStore::new(Box::new(primary), Box::new(secondary));
+
+        store
+            .remove_raw(Bucket::ProofsFri, "new.bin")
+            .await
+            .unwrap();
+        store
+            .remove_raw(Bucket::ProofsFri, "old.bin")
+            .await
+            .unwrap();
+        let objects = store
+            .list_raw(Bucket::ProofsFri, "", None, 10)
+            .await
+            .unwrap();
+        assert!(objects.is_empty(), "{objects:?}");
+    }
+}
@@ -1,11 +1,11 @@
-use std::{error, fmt, sync::Arc};
+use std::{error, fmt, str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
 use micro_config::configs::object_store::{ObjectStoreConfig, ObjectStoreMode};
 
 use crate::{
     ali_oss::AliyunOssStorage, file::FileBackedObjectStore, gcs::GoogleCloudStorage,
-    http::HttpBackedObjectStore, mock::MockStore,
+    http::HttpBackedObjectStore, mirrored::MirroredObjectStore, mock::MockStore,
 };
 
 /// Bucket for [`ObjectStore`] in which objects can be placed.
@@ -26,7 +26,22 @@ pub enum Bucket {
 }
 
 impl Bucket {
-    pub(crate) fn as_str(self) -> &'static str {
+    /// All buckets known to the object store.
+    pub const ALL: [Self; 11] = [
+        Self::ProverJobs,
+        Self::WitnessInput,
+        Self::LeafAggregationWitnessJobs,
+        Self::NodeAggregationWitnessJobs,
+        Self::SchedulerWitnessJobs,
+        Self::ProverJobsFri,
+        Self::LeafAggregationWitnessJobsFri,
+        Self::NodeAggregationWitnessJobsFri,
+        Self::SchedulerWitnessJobsFri,
+        Self::ProofsFri,
+        Self::StorageSnapshot,
+    ];
+
+    pub fn as_str(self) -> &'static str {
         match self {
             Self::ProverJobs => "prover_jobs",
             Self::WitnessInput => "witness_inputs",
@@ -49,6 +64,26 @@ impl fmt::Display for Bucket {
     }
 }
 
+impl FromStr for Bucket {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|bucket| bucket.as_str() == s)
+            .ok_or_else(|| format!("unknown object store bucket: `{s}`"))
+    }
+}
+
+/// Metadata of an object returned by [`ObjectStore::list_raw()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectMetadata {
+    /// Key of the object within its bucket (i.e., without the bucket prefix).
+    pub key: String,
+    /// Size of the object in bytes.
+    pub size: u64,
+}
+
 /// Thread-safe boxed error.
 pub type BoxedError = Box<dyn error::Error + Send + Sync>;
 
@@ -119,6 +154,31 @@ pub trait ObjectStore: fmt::Debug + Send + Sync {
     /// Returns an error if removal fails.
     async fn remove_raw(&self, bucket: Bucket, key: &str) -> Result<(), ObjectStoreError>;
 
+    /// Lists objects in the given bucket whose keys start with `prefix`. An empty `prefix`
+    /// matches the entire bucket. Objects are returned ordered by key; only keys lexicographically greater
+    /// than `start_after` (if specified) are returned. At most `limit` objects are returned; fewer objects
+    /// are only returned if there are no more matching objects, so a bucket can be processed in chunks
+    /// by passing the last returned key as `start_after` for the next call.
+    ///
+    /// Listing is an optional capability; callers should check [`Self::supports_listing()`] first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the bucket cannot be accessed or listing is not supported by the store.
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError>;
+
+    /// Checks whether the store supports [listing objects](Self::list_raw()). Some backends (e.g.,
+    /// the HTTP-backed store) have no way to enumerate stored objects.
+    fn supports_listing(&self) -> bool {
+        true
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String;
 }
 
@@ -141,6 +201,20 @@ impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
         (**self).remove_raw(bucket, key).await
     }
 
+    async fn list_raw(
+        &self,
+        bucket: Bucket,
+        prefix: &str,
+        start_after: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<ObjectMetadata>, ObjectStoreError> {
+        (**self).list_raw(bucket, prefix, start_after, limit).await
+    }
+
+    fn supports_listing(&self) -> bool {
+        (**self).supports_listing()
+    }
+
     fn storage_prefix_raw(&self, bucket: Bucket) -> String {
         (**self).storage_prefix_raw(bucket)
     }
@@ -149,6 +223,10 @@ impl<T: ObjectStore + ?Sized> ObjectStore for Arc<T> {
 #[derive(Debug)]
 enum ObjectStoreOrigin {
     Config(ObjectStoreConfig),
+    Mirrored {
+        primary: ObjectStoreConfig,
+        secondary: ObjectStoreConfig,
+    },
     Mock(Arc<MockStore>),
 }
 
@@ -171,6 +249,35 @@ impl ObjectStoreFactory {
         }
     }
 
+    /// Creates an object store factory producing stores that write to both `primary` and `secondary`
+    /// backends and read from `primary`, falling back to `secondary` if an object cannot be read
+    /// from `primary`. This is useful to migrate between backends with zero downtime.
+    ///
+    /// # Panics
+    ///
+    /// Same as for [`Self::new()`].
+    pub fn mirrored(primary: ObjectStoreConfig, secondary: ObjectStoreConfig) -> Self {
+        Self {
+            origin: ObjectStoreOrigin::Mirrored { primary, secondary },
+        }
+    }
+
+    /// Creates an object store factory based on the provided `config`, mirroring writes to the store
+    /// configured by `mirror_config` if it is set. See [`Self::mirrored()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Same as for [`Self::new()`].
+    pub fn with_mirror(
+        config: ObjectStoreConfig,
+        mirror_config: Option<ObjectStoreConfig>,
+    ) -> Self {
+        match mirror_config {
+            Some(mirror_config) => Self::mirrored(config, mirror_config),
+            None => Self::new(config),
+        }
+    }
+
     /// Creates an object store factory with a mock in-memory store.
     /// All calls to [`Self::create_store()`] will return the same store; thus, the testing code
     /// can use [`ObjectStore`] methods for assertions.
@@ -184,6 +291,11 @@ impl ObjectStoreFactory {
     pub async fn create_store(&self) -> Box<dyn ObjectStore> {
         match &self.origin {
             ObjectStoreOrigin::Config(config) => Self::create_from_config(config).await,
+            ObjectStoreOrigin::Mirrored { primary, secondary } => {
+                let primary = Self::create_from_config(primary).await;
+                let secondary = Self::create_from_config(secondary).await;
+                Box::new(MirroredObjectStore::new(primary, secondary))
+            }
             ObjectStoreOrigin::Mock(store) => Box::new(Arc::clone(store)),
         }
     }
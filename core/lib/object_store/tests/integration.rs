@@ -46,6 +46,42 @@ fn assert_job_integrity(next_enumeration_index: u64, merkle_paths: Vec<StorageLo
     assert!(merkle_paths.iter().all(|log| log.merkle_paths.len() == 256));
 }
 
+/// Tests that objects put via the typesafe API are discoverable via listing.
+#[tokio::test]
+async fn listing_objects_by_prefix() {
+    let store = ObjectStoreFactory::mock().create_store().await;
+    for l1_batch_number in [1, 2, 10] {
+        let job = PrepareBasicCircuitsJob::new(0);
+        store
+            .put(L1BatchNumber(l1_batch_number), &job)
+            .await
+            .unwrap();
+    }
+
+    let objects = store
+        .list_raw(Bucket::WitnessInput, "merkel_tree_paths_1", None, 10)
+        .await
+        .unwrap();
+    let keys: Vec<_> = objects.into_iter().map(|object| object.key).collect();
+    assert_eq!(
+        keys,
+        ["merkel_tree_paths_1.bin", "merkel_tree_paths_10.bin"]
+    );
+
+    let objects = store
+        .list_raw(Bucket::WitnessInput, "", Some("merkel_tree_paths_1.bin"), 1)
+        .await
+        .unwrap();
+    let keys: Vec<_> = objects.into_iter().map(|object| object.key).collect();
+    assert_eq!(keys, ["merkel_tree_paths_10.bin"]);
+
+    let objects = store
+        .list_raw(Bucket::ProverJobs, "", None, 10)
+        .await
+        .unwrap();
+    assert!(objects.is_empty());
+}
+
 /// Test that serialization works the same as with a tuple of the job fields.
 #[tokio::test]
 async fn prepare_basic_circuits_job_compatibility() {
@@ -0,0 +1,131 @@
+//! Types for simulating transaction bundles via `zks_simulateBundle`.
+
+use std::collections::BTreeMap;
+
+use micro_utils::u256_to_h256;
+use serde::{Deserialize, Serialize};
+
+use super::{state_override::StateOverride, DebugCall, Log};
+use crate::{web3::types::Bytes, Address, StorageLogQuery, H256, U256};
+
+/// Options for `zks_simulateBundle`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct SimulateBundleOptions {
+    /// Whether to return call traces for the simulated calls.
+    pub with_call_traces: bool,
+    /// State overrides applied before executing the first call in the bundle.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<StateOverride>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SimulatedCallStatus {
+    Success,
+    Reverted,
+    Halted,
+}
+
+/// Result of a single call in a simulated bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SimulatedCall {
+    pub status: SimulatedCallStatus,
+    pub return_data: Bytes,
+    /// Revert or halt reason; `None` for successful calls.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub gas_used: U256,
+    pub logs: Vec<Log>,
+    pub state_diff: Vec<StorageDiff>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DebugCall>,
+}
+
+/// Storage slot modified by a simulated call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageDiff {
+    pub address: Address,
+    pub key: H256,
+    pub previous_value: H256,
+    pub new_value: H256,
+}
+
+impl StorageDiff {
+    /// Collects net changes from the storage logs produced by a call, taking rollbacks into account.
+    /// Slots that were written to, but ended up with their original values are omitted.
+    pub fn from_storage_logs<'a>(logs: impl IntoIterator<Item = &'a StorageLogQuery>) -> Vec<Self> {
+        let mut values = BTreeMap::<_, (U256, U256)>::new();
+        for log in logs.into_iter().filter(|log| log.log_query.rw_flag) {
+            let log_query = &log.log_query;
+            let new_value = if log_query.rollback {
+                log_query.read_value
+            } else {
+                log_query.written_value
+            };
+            let slot_values = values
+                .entry((log_query.address, log_query.key))
+                .or_insert((log_query.read_value, new_value));
+            slot_values.1 = new_value;
+        }
+
+        values
+            .into_iter()
+            .filter(|(_, (previous_value, new_value))| previous_value != new_value)
+            .map(|((address, key), (previous_value, new_value))| Self {
+                address,
+                key: u256_to_h256(key),
+                previous_value: u256_to_h256(previous_value),
+                new_value: u256_to_h256(new_value),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AccountTreeId, StorageKey, StorageLog, StorageLogQueryType};
+
+    fn write_log(key: StorageKey, read_value: u64, written_value: u64) -> StorageLogQuery {
+        let mut log_query = StorageLog::new_write_log(key, H256::zero()).to_test_log_query();
+        log_query.read_value = read_value.into();
+        log_query.written_value = written_value.into();
+        StorageLogQuery {
+            log_query,
+            log_type: StorageLogQueryType::RepeatedWrite,
+        }
+    }
+
+    #[test]
+    fn collecting_storage_diffs() {
+        let account = AccountTreeId::new(Address::repeat_byte(1));
+        let changed_key = StorageKey::new(account, H256::from_low_u64_be(1));
+        let restored_key = StorageKey::new(account, H256::from_low_u64_be(2));
+        let rolled_back_key = StorageKey::new(account, H256::from_low_u64_be(3));
+
+        let mut rollback = write_log(rolled_back_key, 5, 6);
+        rollback.log_query.rollback = true;
+        let logs = [
+            write_log(changed_key, 1, 2),
+            write_log(restored_key, 3, 4),
+            write_log(changed_key, 2, 7),
+            write_log(restored_key, 4, 3),
+            write_log(rolled_back_key, 5, 6),
+            rollback,
+        ];
+
+        let diffs = StorageDiff::from_storage_logs(&logs);
+        assert_eq!(
+            diffs,
+            [StorageDiff {
+                address: Address::repeat_byte(1),
+                key: H256::from_low_u64_be(1),
+                previous_value: H256::from_low_u64_be(1),
+                new_value: H256::from_low_u64_be(7),
+            }]
+        );
+    }
+}
@@ -0,0 +1,133 @@
+//! Types describing the lifecycle of L1 batches and transactions, from sealing to execution on L1.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::TransactionStatus;
+use crate::{Address, L1BatchNumber, MiniblockNumber, H256};
+
+/// Stage of the L1 batch lifecycle. Stages are ordered; a batch at a certain stage has passed all previous stages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum L1BatchStage {
+    /// The batch is sealed by the state keeper.
+    Sealed,
+    /// The commit transaction for the batch is confirmed on L1.
+    Committed,
+    /// The prove transaction for the batch is confirmed on L1.
+    Proven,
+    /// The execute transaction for the batch is confirmed on L1.
+    Executed,
+}
+
+impl L1BatchStage {
+    pub const ALL: [Self; 4] = [Self::Sealed, Self::Committed, Self::Proven, Self::Executed];
+}
+
+/// Assignment of an L1 batch to a prover.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProverAssignment {
+    /// Verification address of the prover.
+    pub prover: Address,
+    /// Assignment status, e.g. `assigned_not_certified`, `picked_by_prover` or `successful`.
+    pub status: String,
+    pub assigned_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Lifecycle information for an L1 batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchLifecycle {
+    pub number: L1BatchNumber,
+    pub timestamp: u64,
+    pub root_hash: Option<H256>,
+    pub commit_tx_hash: Option<H256>,
+    pub committed_at: Option<DateTime<Utc>>,
+    pub prove_tx_hash: Option<H256>,
+    pub proven_at: Option<DateTime<Utc>>,
+    pub execute_tx_hash: Option<H256>,
+    pub executed_at: Option<DateTime<Utc>>,
+    /// The latest prover assignment for the batch, preferring successful assignments.
+    pub prover_assignment: Option<ProverAssignment>,
+}
+
+impl L1BatchLifecycle {
+    /// Returns the latest stage reached by the batch.
+    pub fn stage(&self) -> L1BatchStage {
+        if self.executed_at.is_some() {
+            L1BatchStage::Executed
+        } else if self.proven_at.is_some() {
+            L1BatchStage::Proven
+        } else if self.committed_at.is_some() {
+            L1BatchStage::Committed
+        } else {
+            L1BatchStage::Sealed
+        }
+    }
+}
+
+/// Notification about an L1 batch reaching a certain stage, emitted for `l1Batches` subscriptions.
+/// If a batch passes several stages between notifier iterations, a separate notification is emitted
+/// for each stage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct L1BatchStatusUpdate {
+    pub stage: L1BatchStage,
+    pub batch: L1BatchLifecycle,
+}
+
+/// Full lifecycle of a transaction returned by `zks_getTransactionFinality`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionFinality {
+    pub status: TransactionStatus,
+    pub received_at: DateTime<Utc>,
+    /// Error message for failed transactions.
+    pub error: Option<String>,
+    pub miniblock_number: Option<MiniblockNumber>,
+    /// Latest stage of the L1 batch containing the transaction; `None` if the batch is not sealed yet.
+    pub l1_batch_stage: Option<L1BatchStage>,
+    pub l1_batch: Option<L1BatchLifecycle>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn l1_batch_stage_is_determined_by_latest_confirmed_tx() {
+        let mut batch = L1BatchLifecycle {
+            number: L1BatchNumber(1),
+            timestamp: 1,
+            root_hash: None,
+            commit_tx_hash: None,
+            committed_at: None,
+            prove_tx_hash: None,
+            proven_at: None,
+            execute_tx_hash: None,
+            executed_at: None,
+            prover_assignment: None,
+        };
+        assert_eq!(batch.stage(), L1BatchStage::Sealed);
+
+        batch.commit_tx_hash = Some(H256::repeat_byte(1));
+        batch.committed_at = Some(Utc::now());
+        assert_eq!(batch.stage(), L1BatchStage::Committed);
+        batch.prove_tx_hash = Some(H256::repeat_byte(2));
+        batch.proven_at = Some(Utc::now());
+        assert_eq!(batch.stage(), L1BatchStage::Proven);
+        batch.execute_tx_hash = Some(H256::repeat_byte(3));
+        batch.executed_at = Some(Utc::now());
+        assert_eq!(batch.stage(), L1BatchStage::Executed);
+
+        let update = L1BatchStatusUpdate {
+            stage: L1BatchStage::Proven,
+            batch,
+        };
+        let update_json = serde_json::to_value(&update).unwrap();
+        assert_eq!(update_json["stage"], "proven");
+        assert_eq!(update_json["batch"]["number"], 1);
+    }
+}
@@ -12,12 +12,21 @@ pub use crate::transaction_request::{
 };
 use crate::{
     protocol_version::L1VerifierConfig,
-    vm_trace::{Call, CallType},
+    vm_trace::{Call, CallType, FourByteTrace, PrestateTrace, StructLog},
     web3::types::{AccessList, Index, H2048},
     Address, MiniblockNumber, ProtocolVersionId,
 };
 
+pub mod bundle;
 pub mod en;
+pub mod finality;
+pub mod priority_ops;
+pub mod protocol_upgrades;
+pub mod state_override;
+#[cfg(test)]
+mod tests;
+pub mod trace;
+pub mod txpool;
 
 /// Block Number
 #[derive(Copy, Clone, Debug, PartialEq, Display)]
@@ -544,6 +553,49 @@ pub struct TransactionDetails {
     pub eth_commit_tx_hash: Option<H256>,
     pub eth_prove_tx_hash: Option<H256>,
     pub eth_execute_tx_hash: Option<H256>,
+    /// Reason of the transaction rejection by the state keeper. Only set for rejected transactions.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rejection: Option<TransactionRejection>,
+}
+
+/// Structured code of a transaction rejection by the state keeper.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Hash,
+    Serialize,
+    Deserialize,
+    strum::EnumString,
+    strum::AsRefStr,
+    strum::IntoStaticStr,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TransactionRejectionCode {
+    /// Transaction execution was halted by the VM.
+    Halt,
+    /// Transaction cannot fit into an L1 batch according to seal criteria.
+    TooBig,
+    /// Account or paymaster validation failed.
+    ValidationFailed,
+    /// Transaction interacts with an address on the deny-list.
+    DenyList,
+    /// Transaction was evicted from the full mempool.
+    Evicted,
+}
+
+/// Information about a transaction rejected by the state keeper or evicted from the mempool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionRejection {
+    pub tx_hash: H256,
+    pub code: TransactionRejectionCode,
+    /// Human-readable rejection reason.
+    pub reason: String,
+    pub rejected_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone)]
@@ -554,12 +606,84 @@ pub struct GetLogsFilter {
     pub topics: Vec<(u32, Vec<H256>)>,
 }
 
+/// Storage write performed by a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiStorageLog {
+    pub address: Address,
+    pub key: H256,
+    pub written_value: H256,
+}
+
+/// Result of `zks_sendRawTransactionWithDetailedOutput` for a transaction included in a miniblock.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionDetailedResult {
+    pub transaction_hash: H256,
+    pub receipt: TransactionReceipt,
+    pub storage_logs: Vec<ApiStorageLog>,
+    pub events: Vec<Log>,
+}
+
+/// Position of a log in the chain used to paginate logs returned by `zks_getLogsWithCursor`.
+/// Logs are ordered by the miniblock number and then by the log index in the miniblock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogCursor {
+    pub block_number: MiniblockNumber,
+    pub log_index: u32,
+}
+
+/// Page of logs returned by `zks_getLogsWithCursor`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsPage {
+    pub logs: Vec<Log>,
+    /// Cursor to pass to get the next page; `None` if there are no more logs matching the filter.
+    pub next_cursor: Option<LogCursor>,
+}
+
 /// Result of debugging block
 /// For some reasons geth returns result as {result: DebugCall}
+///
+/// Block traces produced by tracers other than the call tracer use [`DebugTrace`] as the result type.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResultDebugCall<T = DebugCall> {
+    pub result: T,
+}
+
+/// Result of tracing a transaction or a call. The shape of the result depends on the used tracer;
+/// call traces are serialized exactly as [`DebugCall`].
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum DebugTrace {
+    CallTrace(DebugCall),
+    StructLogs(DebugStructLogs),
+    PrestateTrace(PrestateTrace),
+    FourByteTrace(FourByteTrace),
+}
+
+impl From<DebugCall> for DebugTrace {
+    fn from(value: DebugCall) -> Self {
+        Self::CallTrace(value)
+    }
+}
+
+impl From<Call> for DebugTrace {
+    fn from(value: Call) -> Self {
+        Self::CallTrace(value.into())
+    }
+}
+
+/// Output of the struct logger.
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
-pub struct ResultDebugCall {
-    pub result: DebugCall,
+pub struct DebugStructLogs {
+    pub gas: U256,
+    pub failed: bool,
+    pub return_value: Bytes,
+    pub struct_logs: Vec<StructLog>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -622,24 +746,61 @@ pub struct ProtocolVersion {
     pub l2_system_upgrade_tx_hash: Option<H256>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub enum SupportedTracers {
+    #[default]
     CallTracer,
+    PrestateTracer,
+    #[serde(rename = "4byteTracer")]
+    FourByteTracer,
+    StructLogger,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, Default)]
-#[serde(rename_all = "camelCase")]
+#[serde(default, rename_all = "camelCase")]
 pub struct CallTracerConfig {
     pub only_top_call: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PrestateTracerConfig {
+    /// Return the state of modified accounts before and after the execution
+    /// instead of the state of all touched accounts before the execution.
+    pub diff_mode: bool,
+}
+
+/// Tracer-specific options. Options not applicable to the selected tracer are ignored.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TracerSpecificConfig {
+    #[serde(flatten)]
+    pub call: CallTracerConfig,
+    #[serde(flatten)]
+    pub prestate: PrestateTracerConfig,
+}
+
+/// Options of the struct logger. Similarly to Geth, they are specified at the top level of tracer options.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+#[serde(default, rename_all = "camelCase")]
+pub struct StructLoggerConfig {
+    pub disable_storage: bool,
+    pub disable_stack: bool,
+    /// Maximum number of logged steps; 0 means no limit.
+    pub limit: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TracerConfig {
     pub tracer: SupportedTracers,
     #[serde(default)]
-    pub tracer_config: CallTracerConfig,
+    pub tracer_config: TracerSpecificConfig,
+    #[serde(flatten)]
+    pub struct_logger_config: StructLoggerConfig,
+    /// State overrides applied before executing the call. Only used by `debug_traceCall`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<state_override::StateOverride>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -702,3 +863,21 @@ pub struct Proof {
     pub address: Address,
     pub storage_proof: Vec<StorageProof>,
 }
+
+/// Storage keys of a single account requested via `zks_getProofs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountStorageKeys {
+    pub address: Address,
+    pub keys: Vec<H256>,
+}
+
+/// Storage proofs for multiple accounts at a certain L1 batch returned by `zks_getProofs`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchedProof {
+    pub l1_batch_number: L1BatchNumber,
+    /// Root hash of the Merkle tree after the L1 batch; all proofs are verified against it.
+    pub root_hash: H256,
+    pub proofs: Vec<Proof>,
+}
@@ -0,0 +1,50 @@
+//! Types describing the progress of L1 -> L2 priority operations from their L1 event to execution on L1.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{L1BatchNumber, L1BlockNumber, MiniblockNumber, PriorityOpId, H256};
+
+/// Processing status of a single priority operation that is not yet executed on L1.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityOpStatus {
+    pub serial_id: PriorityOpId,
+    pub tx_hash: H256,
+    /// L1 block containing the event that requested the operation. `None` if it was not stored with the operation.
+    pub l1_block_number: Option<L1BlockNumber>,
+    /// L1 block before which the operation must be processed. `None` if it is unknown, i.e., neither the deadline
+    /// nor the L1 block number was stored with the operation.
+    pub deadline_block: Option<u64>,
+    /// Time when the operation was picked up from L1.
+    pub received_at: DateTime<Utc>,
+    /// Miniblock including the operation, if any.
+    pub miniblock_number: Option<MiniblockNumber>,
+    /// Timestamp of the miniblock including the operation, if any.
+    pub included_at: Option<DateTime<Utc>>,
+    /// L1 batch including the operation, if it is sealed.
+    pub l1_batch_number: Option<L1BatchNumber>,
+}
+
+impl PriorityOpStatus {
+    /// Returns the number of L1 blocks left before the operation deadline. Negative values
+    /// mean that the deadline has already passed; `None` means that the deadline is unknown.
+    pub fn blocks_to_deadline(&self, current_l1_block: u64) -> Option<i64> {
+        self.deadline_block
+            .map(|deadline_block| deadline_block as i64 - current_l1_block as i64)
+    }
+}
+
+/// Snapshot of priority operations that are not yet executed on L1, as tracked by the priority ops monitor.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PriorityOpsReport {
+    /// L1 block number used to compute distances to operation deadlines.
+    pub l1_block_number: u64,
+    /// Time when the report was generated.
+    pub generated_at: DateTime<Utc>,
+    /// Unexecuted operations ordered by serial ID. The list is capped, so it may not contain all operations.
+    pub pending_ops: Vec<PriorityOpStatus>,
+    /// Serial IDs of operations that are close to (or past) their deadline.
+    pub ops_near_deadline: Vec<PriorityOpId>,
+}
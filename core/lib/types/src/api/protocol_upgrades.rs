@@ -0,0 +1,96 @@
+//! Types describing protocol upgrades that are waiting to be activated by the state keeper.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::L1BatchNumber;
+
+/// Stage of a pending protocol upgrade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProtocolUpgradeStage {
+    /// Pre-flight checks were not performed yet.
+    AwaitingPreflight,
+    /// Pre-flight checks have failed; the upgrade will not be activated.
+    PreflightFailed,
+    /// Waiting for the activation timestamp or L1 batch number set by the operator.
+    Scheduled,
+    /// The upgrade will be activated in the next L1 batch.
+    Ready,
+}
+
+/// Activation schedule of a protocol upgrade together with the results of its pre-flight checks.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradeSchedule {
+    /// Raw protocol version ID. It may be unknown to this server if the server is outdated.
+    pub protocol_version: u16,
+    /// Timestamp at which the upgrade is activated on L1. L1 batches with later timestamps can only be committed
+    /// if they use the upgraded protocol.
+    pub l1_activation_timestamp: u64,
+    /// Earliest timestamp of the L1 batch activating the upgrade, if set by the operator.
+    pub activation_timestamp: Option<u64>,
+    /// Earliest L1 batch activating the upgrade, if set by the operator.
+    pub activation_l1_batch_number: Option<L1BatchNumber>,
+    pub preflight_checked_at: Option<DateTime<Utc>>,
+    /// Reason the pre-flight checks have failed, if any.
+    pub preflight_error: Option<String>,
+    /// Time when the upgrade was recorded as pending.
+    pub created_at: DateTime<Utc>,
+}
+
+impl ProtocolUpgradeSchedule {
+    /// Returns the stage of the upgrade for an L1 batch with the specified timestamp and number.
+    pub fn stage(&self, timestamp: u64, l1_batch_number: L1BatchNumber) -> ProtocolUpgradeStage {
+        if self.preflight_error.is_some() {
+            return ProtocolUpgradeStage::PreflightFailed;
+        }
+        if self.preflight_checked_at.is_none() {
+            return ProtocolUpgradeStage::AwaitingPreflight;
+        }
+        let timestamp_reached = self
+            .activation_timestamp
+            .map_or(true, |activation_timestamp| {
+                timestamp >= activation_timestamp
+            });
+        let l1_batch_reached = self
+            .activation_l1_batch_number
+            .map_or(true, |activation_number| {
+                l1_batch_number >= activation_number
+            });
+        if timestamp_reached && l1_batch_reached {
+            ProtocolUpgradeStage::Ready
+        } else {
+            ProtocolUpgradeStage::Scheduled
+        }
+    }
+
+    /// Checks whether the upgrade is activated on L1, but cannot be activated by an L1 batch with
+    /// the specified timestamp and number. Such an L1 batch will fail to be committed.
+    pub fn is_overdue(&self, timestamp: u64, l1_batch_number: L1BatchNumber) -> bool {
+        timestamp >= self.l1_activation_timestamp
+            && self.stage(timestamp, l1_batch_number) != ProtocolUpgradeStage::Ready
+    }
+}
+
+/// Status of a pending protocol upgrade.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradeStatus {
+    #[serde(flatten)]
+    pub schedule: ProtocolUpgradeSchedule,
+    pub stage: ProtocolUpgradeStage,
+    /// Whether the upgrade is activated on L1, but cannot be activated in the next L1 batch.
+    pub overdue: bool,
+}
+
+/// Protocol upgrades that are not activated yet.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProtocolUpgradesReport {
+    /// Protocol version used by the latest sealed miniblock.
+    pub current_version: Option<u16>,
+    /// Pending upgrades ordered by protocol version. Upgrades are activated in this order, so an upgrade
+    /// that is not ready blocks all subsequent upgrades.
+    pub pending_upgrades: Vec<ProtocolUpgradeStatus>,
+}
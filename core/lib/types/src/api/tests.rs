@@ -0,0 +1,236 @@
+use std::collections::BTreeMap;
+
+use zk_evm::zkevm_opcode_defs::FarCallOpcode;
+
+use super::*;
+use crate::{vm_trace::AccountState, CONTRACT_DEPLOYER_ADDRESS};
+
+fn assert_matches_fixture(trace: &DebugTrace, fixture: &str) {
+    let expected: serde_json::Value = serde_json::from_str(fixture).unwrap();
+    assert_eq!(serde_json::to_value(trace).unwrap(), expected);
+}
+
+fn call(from: Address, to: Address, input: Vec<u8>, calls: Vec<Call>) -> Call {
+    Call {
+        r#type: CallType::Call(FarCallOpcode::Normal),
+        from,
+        to,
+        input,
+        calls,
+        ..Call::default()
+    }
+}
+
+fn calldata(selector: [u8; 4], args_len: usize) -> Vec<u8> {
+    let mut calldata = selector.to_vec();
+    calldata.resize(4 + args_len, 0);
+    calldata
+}
+
+#[test]
+fn tracer_config_deserialization() {
+    let config: TracerConfig =
+        serde_json::from_str(r#"{"tracer":"callTracer","tracerConfig":{"onlyTopCall":true}}"#)
+            .unwrap();
+    assert_eq!(config.tracer, SupportedTracers::CallTracer);
+    assert!(config.tracer_config.call.only_top_call);
+
+    let config: TracerConfig =
+        serde_json::from_str(r#"{"tracer":"prestateTracer","tracerConfig":{"diffMode":true}}"#)
+            .unwrap();
+    assert_eq!(config.tracer, SupportedTracers::PrestateTracer);
+    assert!(config.tracer_config.prestate.diff_mode);
+
+    let config: TracerConfig = serde_json::from_str(r#"{"tracer":"4byteTracer"}"#).unwrap();
+    assert_eq!(config.tracer, SupportedTracers::FourByteTracer);
+
+    let config: TracerConfig =
+        serde_json::from_str(r#"{"tracer":"structLogger","disableStack":true,"limit":10}"#)
+            .unwrap();
+    assert_eq!(config.tracer, SupportedTracers::StructLogger);
+    assert!(config.struct_logger_config.disable_stack);
+    assert!(!config.struct_logger_config.disable_storage);
+    assert_eq!(config.struct_logger_config.limit, 10);
+}
+
+#[test]
+fn state_override_deserialization() {
+    let config: TracerConfig = serde_json::from_str(
+        r#"{
+            "tracer": "callTracer",
+            "stateOverrides": {
+                "0x0101010101010101010101010101010101010101": {
+                    "balance": "0x100",
+                    "nonce": "0x5",
+                    "stateDiff": {
+                        "0x0000000000000000000000000000000000000000000000000000000000000001":
+                        "0x0000000000000000000000000000000000000000000000000000000000000002"
+                    }
+                }
+            }
+        }"#,
+    )
+    .unwrap();
+
+    let state_override = config.state_overrides.unwrap();
+    let account = state_override.get(&Address::repeat_byte(1)).unwrap();
+    assert_eq!(account.balance, Some(U256::from(0x100)));
+    assert_eq!(account.nonce, Some(U256::from(5)));
+    assert_eq!(account.code, None);
+    assert_eq!(account.state, None);
+    let state_diff = account.state_diff.as_ref().unwrap();
+    assert_eq!(
+        state_diff[&H256::from_low_u64_be(1)],
+        H256::from_low_u64_be(2)
+    );
+}
+
+#[test]
+fn call_trace_serialization_is_unchanged() {
+    let root = call(
+        Address::repeat_byte(0xaa),
+        Address::repeat_byte(0x11),
+        calldata([1; 4], 32),
+        vec![call(
+            Address::repeat_byte(0x11),
+            Address::repeat_byte(0x22),
+            calldata([2; 4], 0),
+            vec![],
+        )],
+    );
+    let call_result = ResultDebugCall {
+        result: DebugCall::from(root.clone()),
+    };
+    let trace_result = ResultDebugCall {
+        result: DebugTrace::from(root),
+    };
+    let call_json = serde_json::to_value(&call_result).unwrap();
+    assert_eq!(serde_json::to_value(&trace_result).unwrap(), call_json);
+
+    // Call traces are deserialized into the matching variant.
+    let trace_result: ResultDebugCall<DebugTrace> = serde_json::from_value(call_json).unwrap();
+    assert!(matches!(trace_result.result, DebugTrace::CallTrace(_)));
+}
+
+#[test]
+fn four_byte_trace_from_call_trace() {
+    let user = Address::repeat_byte(0xaa);
+    let contract = Address::repeat_byte(0x11);
+    let token = Address::repeat_byte(0x22);
+    let transfer_selector = [0xa9, 0x05, 0x9c, 0xbb];
+
+    let subcalls = vec![
+        call(contract, token, calldata(transfer_selector, 64), vec![]),
+        call(contract, token, calldata(transfer_selector, 64), vec![]),
+        // Calls to system contracts and calls without a selector are ignored.
+        call(
+            contract,
+            CONTRACT_DEPLOYER_ADDRESS,
+            calldata([1; 4], 32),
+            vec![],
+        ),
+        call(contract, token, vec![1, 2], vec![]),
+        Call {
+            r#type: CallType::Create,
+            ..call(contract, token, calldata([2; 4], 0), vec![])
+        },
+    ];
+    let root = call(
+        user,
+        contract,
+        calldata([0x7c, 0xf5, 0xda, 0xb0], 32),
+        subcalls,
+    );
+
+    let trace = DebugTrace::FourByteTrace(FourByteTrace::from_call(&root));
+    assert_matches_fixture(&trace, include_str!("fixtures/four_byte_trace.json"));
+}
+
+#[test]
+fn prestate_diff_trace_serialization() {
+    let user = Address::repeat_byte(0xaa);
+    let contract = Address::repeat_byte(0x11);
+    let slot = H256::zero();
+
+    let pre = BTreeMap::from([
+        (
+            user,
+            AccountState {
+                balance: Some(1_000.into()),
+                nonce: Some(0),
+                ..AccountState::default()
+            },
+        ),
+        (
+            contract,
+            AccountState {
+                code: Some(Bytes(vec![0x60, 0x01])),
+                storage: BTreeMap::from([(slot, H256::zero())]),
+                ..AccountState::default()
+            },
+        ),
+    ]);
+    let post = BTreeMap::from([
+        (
+            user,
+            AccountState {
+                balance: Some(900.into()),
+                nonce: Some(1),
+                ..AccountState::default()
+            },
+        ),
+        (
+            contract,
+            AccountState {
+                storage: BTreeMap::from([(slot, H256::from_low_u64_be(6))]),
+                ..AccountState::default()
+            },
+        ),
+    ]);
+
+    let trace = DebugTrace::PrestateTrace(PrestateTrace::Diff { pre, post });
+    let fixture = include_str!("fixtures/prestate_diff_trace.json");
+    assert_matches_fixture(&trace, fixture);
+
+    let deserialized: PrestateTrace = serde_json::from_str(fixture).unwrap();
+    let DebugTrace::PrestateTrace(trace) = trace else {
+        unreachable!();
+    };
+    assert_eq!(deserialized, trace);
+}
+
+#[test]
+fn struct_logs_serialization() {
+    let contract = Address::repeat_byte(0x11);
+    let registers = vec![U256::zero(), U256::from(6)];
+    let struct_logs = vec![
+        StructLog {
+            pc: 12,
+            op: "Add".to_owned(),
+            gas: 5_000,
+            gas_cost: 6,
+            depth: 2,
+            address: contract,
+            registers: Some(registers.clone()),
+            storage: None,
+        },
+        StructLog {
+            pc: 13,
+            op: "StorageWrite".to_owned(),
+            gas: 4_994,
+            gas_cost: 5_511,
+            depth: 2,
+            address: contract,
+            registers: Some(registers),
+            storage: Some(BTreeMap::from([(H256::zero(), H256::from_low_u64_be(6))])),
+        },
+    ];
+
+    let trace = DebugTrace::StructLogs(DebugStructLogs {
+        gas: 10_000.into(),
+        failed: false,
+        return_value: Bytes::default(),
+        struct_logs,
+    });
+    assert_matches_fixture(&trace, include_str!("fixtures/struct_logs.json"));
+}
@@ -0,0 +1,361 @@
+//! Types for the Parity-style `trace` namespace.
+
+use serde::{Deserialize, Serialize};
+use zk_evm::zkevm_opcode_defs::FarCallOpcode;
+
+use super::BlockNumber;
+use crate::{
+    vm_trace::{Call, CallType},
+    web3::types::Bytes,
+    Address, MiniblockNumber, H256, U256,
+};
+
+/// Location of a traced transaction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TraceLocation {
+    pub block_number: MiniblockNumber,
+    pub block_hash: H256,
+    pub transaction_hash: H256,
+    pub transaction_position: u32,
+}
+
+/// Filter for `trace_filter`. Traces match the filter if their caller is in `from_address`
+/// and their callee (or the deployed contract for contract creations) is in `to_address`;
+/// missing or empty address lists match any address.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceFilter {
+    pub from_block: Option<BlockNumber>,
+    pub to_block: Option<BlockNumber>,
+    pub from_address: Option<Vec<Address>>,
+    pub to_address: Option<Vec<Address>>,
+    /// Number of matching traces to skip.
+    pub after: Option<usize>,
+    /// Maximum number of matching traces to return.
+    pub count: Option<usize>,
+}
+
+impl TraceFilter {
+    /// Returns all addresses mentioned in the filter. A transaction may have matching traces only
+    /// if one of these addresses participates in its call trace.
+    pub fn addresses(&self) -> Vec<Address> {
+        let from_addresses = self.from_address.iter().flatten();
+        let to_addresses = self.to_address.iter().flatten();
+        let mut addresses: Vec<_> = from_addresses.chain(to_addresses).copied().collect();
+        addresses.sort_unstable();
+        addresses.dedup();
+        addresses
+    }
+
+    pub fn matches(&self, trace: &Trace) -> bool {
+        let (from, to) = match (&trace.action, &trace.result) {
+            (TraceAction::Call(action), _) => (action.from, Some(action.to)),
+            (TraceAction::Create(action), Some(TraceResult::Create(result))) => {
+                (action.from, Some(result.address))
+            }
+            (TraceAction::Create(action), _) => (action.from, None),
+        };
+        let from_matches = match &self.from_address {
+            Some(addresses) if !addresses.is_empty() => addresses.contains(&from),
+            _ => true,
+        };
+        let to_matches = match &self.to_address {
+            Some(addresses) if !addresses.is_empty() => {
+                to.map_or(false, |to| addresses.contains(&to))
+            }
+            _ => true,
+        };
+        from_matches && to_matches
+    }
+}
+
+/// Trace types that can be requested in `trace_call`. Only `trace` is currently supported.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TraceResultType {
+    Trace,
+    VmTrace,
+    StateDiff,
+}
+
+/// Result of `trace_call`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceResults {
+    pub output: Bytes,
+    pub trace: Vec<Trace>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallActionType {
+    Call,
+    DelegateCall,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallAction {
+    pub call_type: CallActionType,
+    pub from: Address,
+    pub to: Address,
+    pub gas: U256,
+    pub input: Bytes,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateAction {
+    pub from: Address,
+    pub gas: U256,
+    pub init: Bytes,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TraceAction {
+    Call(CallAction),
+    Create(CreateAction),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallResult {
+    pub gas_used: U256,
+    pub output: Bytes,
+}
+
+/// Result of a contract creation. The deployed bytecode is not known to the call tracer,
+/// so `code` is always empty.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateResult {
+    pub address: Address,
+    pub code: Bytes,
+    pub gas_used: U256,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TraceResult {
+    Create(CreateResult),
+    Call(CallResult),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TraceType {
+    Call,
+    Create,
+}
+
+/// Single call in the flattened call trace of a transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Trace {
+    pub action: TraceAction,
+    /// Set to `None` if the call has failed.
+    pub result: Option<TraceResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Number of direct subcalls.
+    pub subtraces: usize,
+    /// Indices of the call and its ancestors among their siblings; empty for the top-level call.
+    pub trace_address: Vec<usize>,
+    pub transaction_hash: Option<H256>,
+    pub transaction_position: Option<u32>,
+    pub block_hash: Option<H256>,
+    pub block_number: Option<u32>,
+    #[serde(rename = "type")]
+    pub trace_type: TraceType,
+}
+
+impl Trace {
+    /// Flattens a call tree into a list of traces in the depth-first order.
+    pub fn flatten(call: Call, location: Option<TraceLocation>) -> Vec<Self> {
+        let mut traces = vec![];
+        Self::flatten_call(call, vec![], location, &mut traces);
+        traces
+    }
+
+    fn flatten_call(
+        call: Call,
+        trace_address: Vec<usize>,
+        location: Option<TraceLocation>,
+        traces: &mut Vec<Self>,
+    ) {
+        let subcalls: Vec<_> = call
+            .calls
+            .into_iter()
+            .filter(|subcall| subcall.r#type != CallType::NearCall)
+            .collect();
+        let error = call
+            .error
+            .or_else(|| call.revert_reason.map(|_| "Reverted".to_owned()));
+
+        let (trace_type, action, result) = match call.r#type {
+            CallType::Create => {
+                let action = CreateAction {
+                    from: call.from,
+                    gas: call.gas.into(),
+                    init: Bytes(call.input),
+                    value: call.value,
+                };
+                let result = CreateResult {
+                    address: call.to,
+                    code: Bytes::default(),
+                    gas_used: call.gas_used.into(),
+                };
+                (
+                    TraceType::Create,
+                    TraceAction::Create(action),
+                    TraceResult::Create(result),
+                )
+            }
+            CallType::Call(far_call) => {
+                let call_type = match far_call {
+                    FarCallOpcode::Delegate => CallActionType::DelegateCall,
+                    FarCallOpcode::Normal | FarCallOpcode::Mimic => CallActionType::Call,
+                };
+                let action = CallAction {
+                    call_type,
+                    from: call.from,
+                    to: call.to,
+                    gas: call.gas.into(),
+                    input: Bytes(call.input),
+                    value: call.value,
+                };
+                let result = CallResult {
+                    gas_used: call.gas_used.into(),
+                    output: Bytes(call.output),
+                };
+                (
+                    TraceType::Call,
+                    TraceAction::Call(action),
+                    TraceResult::Call(result),
+                )
+            }
+            CallType::NearCall => unreachable!("near calls are filtered out"),
+        };
+
+        traces.push(Self {
+            action,
+            result: error.is_none().then_some(result),
+            error,
+            subtraces: subcalls.len(),
+            trace_address: trace_address.clone(),
+            transaction_hash: location.map(|location| location.transaction_hash),
+            transaction_position: location.map(|location| location.transaction_position),
+            block_hash: location.map(|location| location.block_hash),
+            block_number: location.map(|location| location.block_number.0),
+            trace_type,
+        });
+
+        for (i, subcall) in subcalls.into_iter().enumerate() {
+            let mut subcall_address = trace_address.clone();
+            subcall_address.push(i);
+            Self::flatten_call(subcall, subcall_address, location, traces);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn call(r#type: CallType, from: Address, to: Address, calls: Vec<Call>) -> Call {
+        Call {
+            r#type,
+            from,
+            to,
+            gas: 1_000,
+            gas_used: 100,
+            calls,
+            ..Call::default()
+        }
+    }
+
+    #[test]
+    fn flattening_call_tree() {
+        let user = Address::repeat_byte(1);
+        let contract = Address::repeat_byte(2);
+        let library = Address::repeat_byte(3);
+        let deployed = Address::repeat_byte(4);
+
+        let mut reverted_call = call(
+            CallType::Call(FarCallOpcode::Normal),
+            contract,
+            user,
+            vec![],
+        );
+        reverted_call.revert_reason = Some("oops".to_owned());
+        let root = call(
+            CallType::Call(FarCallOpcode::Mimic),
+            user,
+            contract,
+            vec![
+                call(
+                    CallType::Call(FarCallOpcode::Delegate),
+                    contract,
+                    library,
+                    vec![call(CallType::Create, contract, deployed, vec![])],
+                ),
+                reverted_call,
+            ],
+        );
+        let location = TraceLocation {
+            block_number: MiniblockNumber(5),
+            block_hash: H256::repeat_byte(5),
+            transaction_hash: H256::repeat_byte(6),
+            transaction_position: 2,
+        };
+
+        let traces = Trace::flatten(root, Some(location));
+        let trace_addresses: Vec<_> = traces
+            .iter()
+            .map(|trace| trace.trace_address.as_slice())
+            .collect();
+        assert_eq!(trace_addresses, [&[] as &[_], &[0], &[0, 0], &[1]]);
+        let subtraces: Vec<_> = traces.iter().map(|trace| trace.subtraces).collect();
+        assert_eq!(subtraces, [2, 1, 0, 0]);
+        assert!(traces
+            .iter()
+            .all(|trace| trace.block_number == Some(5) && trace.transaction_position == Some(2)));
+
+        assert_eq!(traces[1].trace_type, TraceType::Call);
+        assert_matches_call_type(&traces[1], CallActionType::DelegateCall);
+        assert_eq!(traces[2].trace_type, TraceType::Create);
+        assert_eq!(
+            traces[2].result,
+            Some(TraceResult::Create(CreateResult {
+                address: deployed,
+                code: Bytes::default(),
+                gas_used: 100.into(),
+            }))
+        );
+        assert_eq!(traces[3].result, None);
+        assert_eq!(traces[3].error.as_deref(), Some("Reverted"));
+
+        let filter = TraceFilter {
+            from_address: Some(vec![contract]),
+            to_address: Some(vec![deployed, user]),
+            ..TraceFilter::default()
+        };
+        assert_eq!(filter.addresses(), [user, contract, deployed]);
+        let matching_traces: Vec<_> = traces
+            .iter()
+            .filter(|trace| filter.matches(trace))
+            .map(|trace| trace.trace_address.as_slice())
+            .collect();
+        assert_eq!(matching_traces, [&[0, 0] as &[_], &[1]]);
+    }
+
+    fn assert_matches_call_type(trace: &Trace, expected: CallActionType) {
+        let TraceAction::Call(action) = &trace.action else {
+            panic!("unexpected trace action: {:?}", trace.action);
+        };
+        assert_eq!(action.call_type, expected);
+    }
+}
@@ -0,0 +1,109 @@
+//! Types returned by the `txpool` namespace, which exposes the contents of the mempool.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{l2::L2Tx, Address, Bytes, H256, U256, U64};
+
+/// Reason why a transaction is not picked from the mempool by the state keeper.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "camelCase")]
+pub enum TxPoolBlockReason {
+    /// A transaction with a lower nonce from the same account is missing.
+    #[serde(rename_all = "camelCase")]
+    NonceGap { expected_nonce: U256 },
+    /// `max_fee_per_gas` of the transaction is lower than the current fee required by the state keeper.
+    #[serde(rename_all = "camelCase")]
+    FeeTooLow {
+        max_fee_per_gas: U256,
+        required_fee_per_gas: U256,
+    },
+    /// `gas_per_pubdata_limit` of the transaction is lower than the current gas price per pubdata byte.
+    #[serde(rename_all = "camelCase")]
+    GasPerPubdataTooLow {
+        gas_per_pubdata_limit: U256,
+        required_gas_per_pubdata: U256,
+    },
+}
+
+/// Transaction in the mempool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxPoolTransaction {
+    pub hash: H256,
+    pub nonce: U256,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    pub gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    pub gas_per_pubdata_limit: U256,
+    pub input: Bytes,
+    /// Timestamp (in milliseconds) when the transaction was received by the node.
+    pub received_at_ms: u64,
+    /// Reason why the transaction is currently not picked by the state keeper, if any.
+    pub blocked_by: Option<TxPoolBlockReason>,
+}
+
+impl From<&L2Tx> for TxPoolTransaction {
+    fn from(tx: &L2Tx) -> Self {
+        let fee = &tx.common_data.fee;
+        Self {
+            hash: tx.hash(),
+            nonce: tx.nonce().0.into(),
+            from: tx.initiator_account(),
+            to: tx.recipient_account(),
+            value: tx.execute.value,
+            gas: fee.gas_limit,
+            max_fee_per_gas: fee.max_fee_per_gas,
+            max_priority_fee_per_gas: fee.max_priority_fee_per_gas,
+            gas_per_pubdata_limit: fee.gas_per_pubdata_limit,
+            input: Bytes(tx.execute.calldata.clone()),
+            received_at_ms: tx.received_timestamp_ms,
+            blocked_by: None,
+        }
+    }
+}
+
+/// Mempool transactions of a single account keyed by nonce.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolAccountContent<T> {
+    /// Transactions that can be executed in the nonce order.
+    pub pending: BTreeMap<u32, T>,
+    /// Transactions blocked by a nonce gap.
+    pub queued: BTreeMap<u32, T>,
+}
+
+impl<T> Default for TxPoolAccountContent<T> {
+    fn default() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            queued: BTreeMap::new(),
+        }
+    }
+}
+
+/// Mempool transactions keyed by the initiator account and nonce.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolContent<T> {
+    pub pending: BTreeMap<Address, BTreeMap<u32, T>>,
+    pub queued: BTreeMap<Address, BTreeMap<u32, T>>,
+}
+
+impl<T> Default for TxPoolContent<T> {
+    fn default() -> Self {
+        Self {
+            pending: BTreeMap::new(),
+            queued: BTreeMap::new(),
+        }
+    }
+}
+
+/// Number of transactions in the mempool.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TxPoolStatus {
+    pub pending: U64,
+    pub queued: U64,
+}
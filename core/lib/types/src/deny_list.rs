@@ -0,0 +1,64 @@
+//! Types describing the deny-list of accounts and contracts that cannot interact with the sequencer.
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::Address;
+
+/// Address on the deny-list.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DenyListEntry {
+    pub address: Address,
+    /// Human-readable reason for listing the address.
+    pub reason: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Change made to the deny-list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DenyListAction {
+    Added,
+    Removed,
+}
+
+impl DenyListAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Added => "added",
+            Self::Removed => "removed",
+        }
+    }
+}
+
+impl fmt::Display for DenyListAction {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DenyListAction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "added" => Ok(Self::Added),
+            "removed" => Ok(Self::Removed),
+            _ => Err(format!("unknown deny-list action: {s}")),
+        }
+    }
+}
+
+/// Record in the deny-list audit log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DenyListAuditRecord {
+    pub id: u64,
+    pub address: Address,
+    pub action: DenyListAction,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
@@ -24,6 +24,7 @@ pub mod error;
 pub mod event_map;
 pub mod new_batch;
 pub mod penalize;
+pub mod prover;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, TryFromPrimitive)]
 #[repr(u32)]
@@ -0,0 +1,101 @@
+//! Prover market state returned by the `prover` Web3 namespace.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{Address, L1BatchNumber, MiniblockNumber, H256, U256};
+
+/// Status of a batch assignment to a prover.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AssignmentStatus {
+    /// The batch is assigned to the prover, but the prover hasn't picked it yet.
+    AssignedNotCertified,
+    /// The prover has picked the batch and is generating a proof for it.
+    PickedByProver,
+    /// The prover has submitted a proof for the batch.
+    Successful,
+    /// The prover has missed the proving deadline or submitted an invalid proof; the penalty is pending.
+    BePunished,
+    /// The prover has been penalized for the assignment.
+    Failed,
+    /// The assignment was reverted together with the batch.
+    Rollbacked,
+}
+
+impl AssignmentStatus {
+    /// Checks whether the assignment has led (or will lead) to a penalty for the prover.
+    pub fn is_penalty(self) -> bool {
+        matches!(self, Self::BePunished | Self::Failed)
+    }
+}
+
+/// Assignment of an L1 batch to a prover, as recorded from the assignment system contract events.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Assignment {
+    pub prover: Address,
+    pub l1_batch_number: L1BatchNumber,
+    /// Miniblock containing the assignment event.
+    pub miniblock_number: MiniblockNumber,
+    /// Index of the assignment in the assignment contract storage.
+    pub storage_index: u64,
+    /// Root hash of the assigned batch; `None` if the batch had no root hash at the time of assignment.
+    pub batch_hash: Option<H256>,
+    pub status: AssignmentStatus,
+    /// Hash of the L2 transaction penalizing the prover, if any.
+    pub penalty_tx_hash: Option<H256>,
+    pub assigned_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Paging parameters for `prover` namespace methods returning lists.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Paging {
+    /// Number of entries to skip.
+    pub offset: usize,
+    /// Maximum number of entries to return. Capped by the server.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// Status of the prover deposit in the deposit system contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DepositStatus {
+    NotDeposited,
+    Normal,
+    Frozen,
+    /// The prover has applied to withdraw the deposit.
+    Applying,
+}
+
+impl DepositStatus {
+    /// Converts the status code used by the deposit contract.
+    pub fn from_code(code: U256) -> Self {
+        match code.low_u32() {
+            1 => Self::Normal,
+            2 => Self::Frozen,
+            3 => Self::Applying,
+            _ => Self::NotDeposited,
+        }
+    }
+}
+
+/// Deposit of a prover in the main deposit token.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositInfo {
+    pub prover: Address,
+    pub in_white_list: bool,
+    /// Token used for deposits; the zero address stands for the base token.
+    pub token: Address,
+    pub status: DepositStatus,
+    /// Deposited amount in the smallest token units.
+    pub amount: U256,
+    /// UNIX timestamp of the latest deposit.
+    pub deposit_time: U256,
+    /// UNIX timestamp of the withdrawal application; zero if the prover has not applied.
+    pub apply_time: U256,
+}
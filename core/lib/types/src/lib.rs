@@ -37,6 +37,7 @@ pub mod circuit;
 pub mod commitment;
 pub mod contract_verification_api;
 pub mod contracts;
+pub mod deny_list;
 pub mod event;
 pub mod fee;
 pub mod l1;
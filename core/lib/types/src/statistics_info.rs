@@ -1,6 +1,10 @@
-use micro_basic_types::U64;
+use std::time::Duration;
+
+use micro_basic_types::{U256, U64};
 use serde::{Deserialize, Serialize};
 
+/// Network statistics returned by `zks_getStatisticsInfo`. Statistics are aggregated in background
+/// by the API server, so they may lag behind the latest chain state by several seconds.
 #[derive(Default, Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
 pub struct StatiticsInfo {
     pub tx_mempool: u32,
@@ -8,6 +12,59 @@ pub struct StatiticsInfo {
     pub next_block_id: U64,
     pub un_verified_blocks: U64,
     pub latest_proof_time: i64,
+    /// Transaction throughput over sliding windows ending at `updated_at`, ordered from the shortest window.
+    #[serde(default)]
+    pub throughput: Vec<ThroughputWindow>,
+    /// Average time between assigning an L1 batch to a prover and receiving its proof, in seconds.
+    /// `None` if no batches were proven within the statistics window.
+    #[serde(default)]
+    pub avg_batch_proving_time: Option<u64>,
+    /// Number of distinct provers that picked or proved assignments within the statistics window.
+    #[serde(default)]
+    pub active_provers: u32,
+    /// Number of prover assignments within the statistics window.
+    #[serde(default)]
+    pub total_assignments: u64,
+    /// Number of assignments within the statistics window for which provers were penalized,
+    /// e.g. because of a missed proving deadline.
+    #[serde(default)]
+    pub penalized_assignments: u64,
+    /// UNIX timestamp at which the statistics were aggregated; 0 if they weren't aggregated yet.
+    #[serde(default)]
+    pub updated_at: u64,
+}
+
+impl StatiticsInfo {
+    /// Returns the share of assignments within the statistics window for which provers were penalized,
+    /// or `None` if there were no assignments.
+    pub fn assignment_timeout_rate(&self) -> Option<f64> {
+        (self.total_assignments > 0)
+            .then(|| self.penalized_assignments as f64 / self.total_assignments as f64)
+    }
+}
+
+/// Throughput of the network over a certain time window.
+#[derive(Default, Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ThroughputWindow {
+    /// Duration of the window in seconds.
+    pub window: u64,
+    /// Number of transactions included into miniblocks within the window.
+    pub tx_count: u64,
+    /// Total gas used by transactions within the window.
+    pub gas_used: U256,
 }
 
-impl StatiticsInfo {}
+impl ThroughputWindow {
+    pub fn new(window: Duration, tx_count: u64, gas_used: U256) -> Self {
+        Self {
+            window: window.as_secs().max(1),
+            tx_count,
+            gas_used,
+        }
+    }
+
+    /// Returns the number of transactions per second within the window.
+    pub fn tps(&self) -> f64 {
+        self.tx_count as f64 / self.window as f64
+    }
+}
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     fmt::Display,
 };
@@ -9,7 +9,7 @@ use micro_utils::u256_to_h256;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use zk_evm::zkevm_opcode_defs::FarCallOpcode;
 
-use crate::{Address, U256};
+use crate::{web3::types::Bytes, Address, H256, U256};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum VmTrace {
@@ -127,6 +127,21 @@ impl Call {
             calls,
         }
     }
+
+    /// Returns addresses of all callers and callees in this call and its subcalls.
+    pub fn participating_addresses(&self) -> HashSet<Address> {
+        let mut addresses = HashSet::new();
+        self.collect_addresses(&mut addresses);
+        addresses
+    }
+
+    fn collect_addresses(&self, addresses: &mut HashSet<Address>) {
+        addresses.insert(self.from);
+        addresses.insert(self.to);
+        for subcall in &self.calls {
+            subcall.collect_addresses(addresses);
+        }
+    }
 }
 
 impl PartialEq for Call {
@@ -201,6 +216,106 @@ impl fmt::Debug for Call {
     }
 }
 
+/// Checks whether the address belongs to the kernel space, i.e. is reserved for system contracts.
+pub fn is_system_contract(address: &Address) -> bool {
+    address.as_bytes()[..18].iter().all(|&byte| byte == 0)
+}
+
+/// Numbers of calls to user contracts keyed by the function selector and the calldata size
+/// (e.g., `0x27dc297e-128`), as reported by the 4byte tracer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct FourByteTrace(pub BTreeMap<String, u64>);
+
+impl FourByteTrace {
+    /// Records a far call with the specified calldata prefix (at least the function selector if present)
+    /// and the full calldata length.
+    ///
+    /// Calls to system contracts, calls made by the bootloader (i.e., account validation, execution
+    /// and fee payment entry points) and calls with the calldata shorter than a function selector are ignored.
+    pub fn record_call(&mut self, from: Address, to: Address, calldata_prefix: &[u8], len: usize) {
+        if from == BOOTLOADER_ADDRESS || is_system_contract(&to) || len < 4 {
+            return;
+        }
+        let key = format!("0x{}-{}", hex::encode(&calldata_prefix[..4]), len - 4);
+        *self.0.entry(key).or_default() += 1;
+    }
+
+    /// Derives the trace from a call trace of a transaction.
+    pub fn from_call(call: &Call) -> Self {
+        let mut trace = Self::default();
+        trace.record_call_recursively(call);
+        trace
+    }
+
+    fn record_call_recursively(&mut self, call: &Call) {
+        if let CallType::Call(_) = call.r#type {
+            self.record_call(call.from, call.to, &call.input, call.input.len());
+        }
+        for subcall in &call.calls {
+            self.record_call_recursively(subcall);
+        }
+    }
+}
+
+/// State of an account touched during transaction execution, as reported by the prestate tracer.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountState {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub storage: BTreeMap<H256, H256>,
+}
+
+impl AccountState {
+    pub fn is_empty(&self) -> bool {
+        self.balance.is_none()
+            && self.nonce.is_none()
+            && self.code.is_none()
+            && self.storage.is_empty()
+    }
+}
+
+/// Output of the prestate tracer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum PrestateTrace {
+    /// State of accounts modified by the transaction before and after its execution.
+    Diff {
+        pre: BTreeMap<Address, AccountState>,
+        post: BTreeMap<Address, AccountState>,
+    },
+    /// State of all accounts touched by the transaction before its execution.
+    Prestate(BTreeMap<Address, AccountState>),
+}
+
+/// Single VM execution step, as reported by the struct logger.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    pub pc: u16,
+    pub op: String,
+    /// Gas remaining before executing the opcode.
+    pub gas: u32,
+    pub gas_cost: u32,
+    /// Depth of the VM call stack (including near calls).
+    pub depth: usize,
+    /// Address of the contract in which context the opcode is executed.
+    pub address: Address,
+    /// VM register values before executing the opcode. zkEVM is a register machine, so registers
+    /// are reported instead of the stack reported by EVM tracers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registers: Option<Vec<U256>>,
+    /// Storage slots of the contract accessed so far. Only reported for storage access opcodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
 #[derive(Debug, Clone)]
 pub enum ViolatedValidationRule {
     TouchedUnallowedStorageSlots(Address, U256),
@@ -1,6 +1,6 @@
 //! Definition of errors that can occur in the micro Web3 API.
 
-use micro_types::api::SerializationTransactionError;
+use micro_types::{api::SerializationTransactionError, H256};
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -23,6 +23,12 @@ pub enum Web3Error {
     SerializationError(#[from] SerializationTransactionError),
     #[error("Invalid fee parameters: {0}")]
     InvalidFeeParams(String),
+    #[error("Invalid state override: {0}")]
+    InvalidStateOverride(String),
+    #[error("Bundle contains more than {0} calls")]
+    BundleTooLarge(usize),
+    #[error("Proof request contains more than {0} storage keys")]
+    TooManyProofKeys(usize),
     #[error("More than four topics in filter")]
     TooManyTopics,
     #[error("Your connection time exceeded the limit")]
@@ -33,8 +39,20 @@ pub enum Web3Error {
     NotImplemented,
     #[error("Query returned more than {0} results. Try with this block range [{1:#x}, {2:#x}].")]
     LogsLimitExceeded(usize, u32, u32),
+    #[error("Trace filter scanned more than {0} transactions. Try with this block range [{1:#x}, {2:#x}] or set `count`.")]
+    TraceLimitExceeded(usize, u32, u32),
     #[error("invalid filter: if blockHash is supplied fromBlock and toBlock must not be")]
     InvalidFilterBlockHash,
     #[error("Tree API is not available")]
     TreeApiUnavailable,
+    #[error("Mempool is not available on this node")]
+    MempoolUnavailable,
+    #[error("Priority ops monitor is not available on this node")]
+    PriorityOpsMonitorUnavailable,
+    #[error(
+        "Transaction {0:?} was submitted, but was not included in a miniblock before the timeout"
+    )]
+    TxInclusionTimeout(H256),
+    #[error("Protocol upgrade cannot be activated after its L1 activation timestamp {0}")]
+    UpgradeActivationAfterL1(u64),
 }
@@ -0,0 +1,44 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use micro_types::{
+    deny_list::{DenyListAuditRecord, DenyListEntry},
+    l2::prover::Paging,
+    Address, L1BatchNumber,
+};
+
+#[cfg_attr(
+    all(feature = "client", feature = "server"),
+    rpc(server, client, namespace = "admin")
+)]
+#[cfg_attr(
+    all(feature = "client", not(feature = "server")),
+    rpc(client, namespace = "admin")
+)]
+#[cfg_attr(
+    all(not(feature = "client"), feature = "server"),
+    rpc(server, namespace = "admin")
+)]
+pub trait AdminNamespace {
+    #[method(name = "getDenyList")]
+    async fn get_deny_list(&self) -> RpcResult<Vec<DenyListEntry>>;
+
+    #[method(name = "addToDenyList")]
+    async fn add_to_deny_list(&self, address: Address, reason: String) -> RpcResult<()>;
+
+    #[method(name = "removeFromDenyList")]
+    async fn remove_from_deny_list(&self, address: Address, reason: String) -> RpcResult<bool>;
+
+    #[method(name = "getDenyListAuditLog")]
+    async fn get_deny_list_audit_log(
+        &self,
+        address: Option<Address>,
+        paging: Option<Paging>,
+    ) -> RpcResult<Vec<DenyListAuditRecord>>;
+
+    #[method(name = "scheduleProtocolUpgrade")]
+    async fn schedule_protocol_upgrade(
+        &self,
+        protocol_version: u16,
+        activation_timestamp: Option<u64>,
+        activation_l1_batch_number: Option<L1BatchNumber>,
+    ) -> RpcResult<bool>;
+}
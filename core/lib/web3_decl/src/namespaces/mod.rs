@@ -1,21 +1,27 @@
+pub mod admin;
 pub mod debug;
 pub mod en;
 pub mod eth;
 pub mod eth_subscribe;
 pub mod net;
+pub mod prover;
 pub mod snapshots;
+pub mod trace;
+pub mod txpool;
 pub mod web3;
 pub mod zks;
 
 #[cfg(feature = "client")]
 pub use self::{
-    debug::DebugNamespaceClient, en::EnNamespaceClient, eth::EthNamespaceClient,
-    net::NetNamespaceClient, snapshots::SnapshotsNamespaceClient, web3::Web3NamespaceClient,
-    zks::ZksNamespaceClient,
+    admin::AdminNamespaceClient, debug::DebugNamespaceClient, en::EnNamespaceClient,
+    eth::EthNamespaceClient, net::NetNamespaceClient, prover::ProverNamespaceClient,
+    snapshots::SnapshotsNamespaceClient, trace::TraceNamespaceClient,
+    txpool::TxPoolNamespaceClient, web3::Web3NamespaceClient, zks::ZksNamespaceClient,
 };
 #[cfg(feature = "server")]
 pub use self::{
-    debug::DebugNamespaceServer, en::EnNamespaceServer, eth::EthNamespaceServer,
-    net::NetNamespaceServer, snapshots::SnapshotsNamespaceServer, web3::Web3NamespaceServer,
-    zks::ZksNamespaceServer,
+    admin::AdminNamespaceServer, debug::DebugNamespaceServer, en::EnNamespaceServer,
+    eth::EthNamespaceServer, net::NetNamespaceServer, prover::ProverNamespaceServer,
+    snapshots::SnapshotsNamespaceServer, trace::TraceNamespaceServer,
+    txpool::TxPoolNamespaceServer, web3::Web3NamespaceServer, zks::ZksNamespaceServer,
 };
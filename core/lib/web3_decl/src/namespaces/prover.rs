@@ -0,0 +1,39 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use micro_types::{
+    l2::prover::{Assignment, DepositInfo, Paging},
+    Address, L1BatchNumber,
+};
+
+#[cfg_attr(
+    all(feature = "client", feature = "server"),
+    rpc(server, client, namespace = "prover")
+)]
+#[cfg_attr(
+    all(feature = "client", not(feature = "server")),
+    rpc(client, namespace = "prover")
+)]
+#[cfg_attr(
+    all(not(feature = "client"), feature = "server"),
+    rpc(server, namespace = "prover")
+)]
+pub trait ProverNamespace {
+    #[method(name = "getAssignment")]
+    async fn get_assignment(&self, l1_batch_number: L1BatchNumber) -> RpcResult<Vec<Assignment>>;
+
+    #[method(name = "getAssignmentsByProver")]
+    async fn get_assignments_by_prover(
+        &self,
+        prover: Address,
+        paging: Option<Paging>,
+    ) -> RpcResult<Vec<Assignment>>;
+
+    #[method(name = "getDepositInfo")]
+    async fn get_deposit_info(&self, prover: Address) -> RpcResult<DepositInfo>;
+
+    #[method(name = "getPenalties")]
+    async fn get_penalties(
+        &self,
+        prover: Option<Address>,
+        paging: Option<Paging>,
+    ) -> RpcResult<Vec<Assignment>>;
+}
@@ -0,0 +1,40 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use micro_types::{
+    api::{
+        trace::{Trace, TraceFilter, TraceResultType, TraceResults},
+        BlockId, BlockNumber,
+    },
+    transaction_request::CallRequest,
+    H256,
+};
+
+#[cfg_attr(
+    all(feature = "client", feature = "server"),
+    rpc(server, client, namespace = "trace")
+)]
+#[cfg_attr(
+    all(feature = "client", not(feature = "server")),
+    rpc(client, namespace = "trace")
+)]
+#[cfg_attr(
+    all(not(feature = "client"), feature = "server"),
+    rpc(server, namespace = "trace")
+)]
+pub trait TraceNamespace {
+    #[method(name = "block")]
+    async fn trace_block(&self, block: BlockNumber) -> RpcResult<Vec<Trace>>;
+
+    #[method(name = "transaction")]
+    async fn trace_transaction(&self, tx_hash: H256) -> RpcResult<Option<Vec<Trace>>>;
+
+    #[method(name = "filter")]
+    async fn trace_filter(&self, filter: TraceFilter) -> RpcResult<Vec<Trace>>;
+
+    #[method(name = "call")]
+    async fn trace_call(
+        &self,
+        request: CallRequest,
+        trace_types: Vec<TraceResultType>,
+        block: Option<BlockId>,
+    ) -> RpcResult<TraceResults>;
+}
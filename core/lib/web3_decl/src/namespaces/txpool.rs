@@ -0,0 +1,34 @@
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use micro_types::{
+    api::txpool::{TxPoolAccountContent, TxPoolContent, TxPoolStatus, TxPoolTransaction},
+    Address,
+};
+
+#[cfg_attr(
+    all(feature = "client", feature = "server"),
+    rpc(server, client, namespace = "txpool")
+)]
+#[cfg_attr(
+    all(feature = "client", not(feature = "server")),
+    rpc(client, namespace = "txpool")
+)]
+#[cfg_attr(
+    all(not(feature = "client"), feature = "server"),
+    rpc(server, namespace = "txpool")
+)]
+pub trait TxPoolNamespace {
+    #[method(name = "status")]
+    async fn status(&self) -> RpcResult<TxPoolStatus>;
+
+    #[method(name = "content")]
+    async fn content(&self) -> RpcResult<TxPoolContent<TxPoolTransaction>>;
+
+    #[method(name = "contentFrom")]
+    async fn content_from(
+        &self,
+        address: Address,
+    ) -> RpcResult<TxPoolAccountContent<TxPoolTransaction>>;
+
+    #[method(name = "inspect")]
+    async fn inspect(&self) -> RpcResult<TxPoolContent<String>>;
+}
@@ -4,16 +4,22 @@ use bigdecimal::BigDecimal;
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
 use micro_types::{
     api::{
-        BlockDetails, BridgeAddresses, L1BatchDetails, L2ToL1LogProof, Proof, ProtocolVersion,
-        TransactionDetails,
+        bundle::{SimulateBundleOptions, SimulatedCall},
+        finality::TransactionFinality,
+        priority_ops::PriorityOpsReport,
+        protocol_upgrades::ProtocolUpgradesReport,
+        AccountStorageKeys, BatchedProof, BlockDetails, BlockIdVariant, BridgeAddresses,
+        L1BatchDetails, L2ToL1LogProof, LogCursor, LogsPage, Proof, ProtocolVersion,
+        TransactionDetailedResult, TransactionDetails,
     },
     fee::Fee,
     statistics_info::StatiticsInfo,
     transaction_request::CallRequest,
+    web3::types::Bytes,
     Address, L1BatchNumber, MiniblockNumber, H256, U256, U64,
 };
 
-use crate::types::Token;
+use crate::types::{Filter, Token};
 
 #[cfg_attr(
     all(feature = "client", feature = "server"),
@@ -86,6 +92,22 @@ pub trait ZksNamespace {
     #[method(name = "getTransactionDetails")]
     async fn get_transaction_details(&self, hash: H256) -> RpcResult<Option<TransactionDetails>>;
 
+    #[method(name = "getTransactionFinality")]
+    async fn get_transaction_finality(&self, hash: H256) -> RpcResult<Option<TransactionFinality>>;
+
+    #[method(name = "getPriorityOpsStatus")]
+    async fn get_priority_ops_status(&self) -> RpcResult<Option<PriorityOpsReport>>;
+
+    #[method(name = "getProtocolUpgradesStatus")]
+    async fn get_protocol_upgrades_status(&self) -> RpcResult<ProtocolUpgradesReport>;
+
+    #[method(name = "sendRawTransactionWithDetailedOutput")]
+    async fn send_raw_transaction_with_detailed_output(
+        &self,
+        tx_bytes: Bytes,
+        timeout_ms: Option<u64>,
+    ) -> RpcResult<TransactionDetailedResult>;
+
     #[method(name = "getRawBlockTransactions")]
     async fn get_raw_block_transactions(
         &self,
@@ -116,6 +138,29 @@ pub trait ZksNamespace {
         l1_batch_number: L1BatchNumber,
     ) -> RpcResult<Proof>;
 
+    #[method(name = "getProofs")]
+    async fn get_proofs(
+        &self,
+        accounts: Vec<AccountStorageKeys>,
+        l1_batch_number: L1BatchNumber,
+    ) -> RpcResult<BatchedProof>;
+
     #[method(name = "getStatistics")]
     async fn get_statistics_info(&self) -> RpcResult<StatiticsInfo>;
+
+    #[method(name = "getLogsWithCursor")]
+    async fn get_logs_with_cursor(
+        &self,
+        filter: Filter,
+        cursor: Option<LogCursor>,
+        limit: Option<usize>,
+    ) -> RpcResult<LogsPage>;
+
+    #[method(name = "simulateBundle")]
+    async fn simulate_bundle(
+        &self,
+        calls: Vec<CallRequest>,
+        block: Option<BlockIdVariant>,
+        options: Option<SimulateBundleOptions>,
+    ) -> RpcResult<Vec<SimulatedCall>>;
 }
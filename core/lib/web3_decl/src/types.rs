@@ -13,7 +13,10 @@ use core::{
 
 use itertools::unfold;
 pub use micro_types::{
-    api::{Block, BlockNumber, Log, TransactionReceipt, TransactionRequest},
+    api::{
+        finality::L1BatchStatusUpdate, Block, BlockNumber, Log, TransactionReceipt,
+        TransactionRejection, TransactionRequest,
+    },
     vm_trace::{ContractSourceDebugInfo, VmDebugTrace, VmExecutionStep},
     web3::{
         ethabi,
@@ -345,6 +348,8 @@ pub enum PubSubResult {
     Log(Log),
     TxHash(H256),
     Syncing(bool),
+    L1BatchStatus(L1BatchStatusUpdate),
+    RejectedTransaction(TransactionRejection),
 }
 
 #[cfg(test)]
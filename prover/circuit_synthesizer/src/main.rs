@@ -3,7 +3,7 @@ use micro_config::configs::{
     AlertsConfig, CircuitSynthesizerConfig, ObjectStoreConfig, PostgresConfig, ProverGroupConfig,
 };
 use micro_dal::ConnectionPool;
-use micro_env_config::FromEnv;
+use micro_env_config::{object_store::MirrorObjectStoreConfig, FromEnv};
 use micro_object_store::ObjectStoreFactory;
 use micro_queued_job_processor::JobProcessor;
 use micro_utils::wait_for_tasks::wait_for_tasks;
@@ -58,10 +58,13 @@ async fn main() -> anyhow::Result<()> {
 
     let object_store_config =
         ObjectStoreConfig::from_env().context("ObjectStoreConfig::from_env()")?;
+    let mirror_object_store_config = MirrorObjectStoreConfig::from_env()
+        .ok()
+        .map(|config| config.0);
     let circuit_synthesizer = CircuitSynthesizer::new(
         config.clone(),
         ProverGroupConfig::from_env().context("ProverGroupConfig::from_env()")?,
-        &ObjectStoreFactory::new(object_store_config),
+        &ObjectStoreFactory::with_mirror(object_store_config, mirror_object_store_config),
         vk_commitments,
         pool,
     )
@@ -9,7 +9,7 @@ use micro_config::{
     ApiConfig, PostgresConfig, ProverConfig, ProverConfigs,
 };
 use micro_dal::ConnectionPool;
-use micro_env_config::FromEnv;
+use micro_env_config::{object_store::MirrorObjectStoreConfig, FromEnv};
 use micro_object_store::ObjectStoreFactory;
 use micro_prover_utils::region_fetcher::{get_region, get_zone};
 use micro_types::proofs::{GpuProverInstanceStatus, SocketAddress};
@@ -195,7 +195,11 @@ pub async fn run() -> anyhow::Result<()> {
     let params = ProverParams::new(&prover_config);
     let object_store_config =
         ObjectStoreConfig::from_env().context("ObjectStoreConfig::from_env()")?;
-    let store_factory = ObjectStoreFactory::new(object_store_config);
+    let mirror_object_store_config = MirrorObjectStoreConfig::from_env()
+        .ok()
+        .map(|config| config.0);
+    let store_factory =
+        ObjectStoreFactory::with_mirror(object_store_config, mirror_object_store_config);
 
     let circuit_provider_pool = ConnectionPool::singleton(postgres_config.prover_url()?)
         .build()
@@ -11,7 +11,9 @@ use micro_config::configs::{
 };
 use micro_dal::ConnectionPool;
 use micro_env_config::{
-    object_store::{ProverObjectStoreConfig, PublicObjectStoreConfig},
+    object_store::{
+        ProverMirrorObjectStoreConfig, ProverObjectStoreConfig, PublicObjectStoreConfig,
+    },
     FromEnv,
 };
 use micro_object_store::{ObjectStore, ObjectStoreFactory};
@@ -100,7 +102,11 @@ async fn main() -> anyhow::Result<()> {
     let (stop_sender, stop_receiver) = tokio::sync::watch::channel(false);
     let object_store_config =
         ProverObjectStoreConfig::from_env().context("ProverObjectStoreConfig::from_env()")?;
-    let object_store_factory = ObjectStoreFactory::new(object_store_config.0);
+    let mirror_object_store_config = ProverMirrorObjectStoreConfig::from_env()
+        .ok()
+        .map(|config| config.0);
+    let object_store_factory =
+        ObjectStoreFactory::with_mirror(object_store_config.0, mirror_object_store_config);
 
     let public_blob_store = match prover_config.shall_save_to_public_bucket {
         false => None,
@@ -63,7 +63,7 @@ pub async fn get(State(state): State<Arc<Dashboard>>) -> Result<Json<Response>,
 
     let white_list_resp = state
         .client
-        .call(white_list_req, block)
+        .call(white_list_req, block, None)
         .await
         .map_err(|e| DashboardError::RpcError(e.to_string()))?;
 
@@ -89,7 +89,7 @@ pub async fn get(State(state): State<Arc<Dashboard>>) -> Result<Json<Response>,
     };
     let main_token_resp = state
         .client
-        .call(main_token_req, block)
+        .call(main_token_req, block, None)
         .await
         .map_err(|e| DashboardError::RpcError(e.to_string()))?;
 
@@ -120,7 +120,7 @@ pub async fn get(State(state): State<Arc<Dashboard>>) -> Result<Json<Response>,
     };
     let deposit_info_resp = state
         .client
-        .call(deposit_info_req, block)
+        .call(deposit_info_req, block, None)
         .await
         .map_err(|e| DashboardError::RpcError(e.to_string()))?;
 
@@ -165,7 +165,7 @@ pub async fn get(State(state): State<Arc<Dashboard>>) -> Result<Json<Response>,
         };
         let decimals_resp = state
             .client
-            .call(decimals_req, block)
+            .call(decimals_req, block, None)
             .await
             .map_err(|e| DashboardError::RpcError(e.to_string()))?;
 
@@ -1,7 +1,10 @@
 use anyhow::Context as _;
 use micro_config::configs::{FriProverGatewayConfig, FriProverTaskApplyConfig, PostgresConfig};
 use micro_dal::ConnectionPool;
-use micro_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
+use micro_env_config::{
+    object_store::{ProverMirrorObjectStoreConfig, ProverObjectStoreConfig},
+    FromEnv,
+};
 use micro_object_store::ObjectStoreFactory;
 use micro_prover_fri_utils::app_monitor::{AppMonitor, AppMonitorJob};
 use micro_types::prover_server_api::{ProofGenerationDataRequest, SubmitProofRequest};
@@ -48,7 +51,11 @@ async fn main() -> anyhow::Result<()> {
     .context("failed to build a connection pool")?;
     let object_store_config =
         ProverObjectStoreConfig::from_env().context("ProverObjectStoreConfig::from_env()")?;
-    let store_factory = ObjectStoreFactory::new(object_store_config.0);
+    let mirror_object_store_config = ProverMirrorObjectStoreConfig::from_env()
+        .ok()
+        .map(|config| config.0);
+    let store_factory =
+        ObjectStoreFactory::with_mirror(object_store_config.0, mirror_object_store_config);
 
     let mut tasks = vec![];
     let (stop_sender, stop_receiver) = watch::channel(false);
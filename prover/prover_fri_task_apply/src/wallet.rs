@@ -112,7 +112,7 @@ impl TaskApplyWallet {
         let bytes = self
             .wallet
             .provider
-            .call(req, block)
+            .call(req, block, None)
             .await
             .map_err(|e| TaskApplyError::WalletError(e.to_string()))?;
 
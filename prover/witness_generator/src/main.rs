@@ -8,7 +8,12 @@ use micro_config::{
     ObjectStoreConfig,
 };
 use micro_dal::ConnectionPool;
-use micro_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
+use micro_env_config::{
+    object_store::{
+        MirrorObjectStoreConfig, ProverMirrorObjectStoreConfig, ProverObjectStoreConfig,
+    },
+    FromEnv,
+};
 use micro_object_store::ObjectStoreFactory;
 use micro_prover_fri_utils::app_monitor::{AppMonitor, AppMonitorJob};
 use micro_prover_utils::get_stop_signal_receiver;
@@ -84,7 +89,11 @@ async fn main() -> anyhow::Result<()> {
 
     let object_store_config =
         ProverObjectStoreConfig::from_env().context("ProverObjectStoreConfig::from_env()")?;
-    let store_factory = ObjectStoreFactory::new(object_store_config.0);
+    let mirror_object_store_config = ProverMirrorObjectStoreConfig::from_env()
+        .ok()
+        .map(|config| config.0);
+    let store_factory =
+        ObjectStoreFactory::with_mirror(object_store_config.0, mirror_object_store_config);
     let config =
         FriWitnessGeneratorConfig::from_env().context("FriWitnessGeneratorConfig::from_env()")?;
     // let prometheus_config = PrometheusConfig::from_env().context("PrometheusConfig::from_env()")?;
@@ -192,9 +201,12 @@ async fn main() -> anyhow::Result<()> {
                 let public_blob_store = match config.shall_save_to_public_bucket {
                     false => None,
                     true => Some(
-                        ObjectStoreFactory::new(
+                        ObjectStoreFactory::with_mirror(
                             ObjectStoreConfig::from_env()
                                 .context("ObjectStoreConfig::from_env()")?,
+                            MirrorObjectStoreConfig::from_env()
+                                .ok()
+                                .map(|config| config.0),
                         )
                         .create_store()
                         .await,
@@ -6,7 +6,10 @@ use micro_config::configs::{
     PostgresConfig, ProverGroupConfig,
 };
 use micro_dal::ConnectionPool;
-use micro_env_config::{object_store::ProverObjectStoreConfig, FromEnv};
+use micro_env_config::{
+    object_store::{ProverMirrorObjectStoreConfig, ProverObjectStoreConfig},
+    FromEnv,
+};
 use micro_object_store::ObjectStoreFactory;
 use micro_prover_fri_utils::{
     app_monitor::{AppMonitor, AppMonitorJob},
@@ -70,9 +73,13 @@ async fn main() -> anyhow::Result<()> {
     .context("failed to build a connection pool")?;
     let object_store_config =
         ProverObjectStoreConfig::from_env().context("ProverObjectStoreConfig::from_env()")?;
-    let blob_store = ObjectStoreFactory::new(object_store_config.0)
-        .create_store()
-        .await;
+    let mirror_object_store_config = ProverMirrorObjectStoreConfig::from_env()
+        .ok()
+        .map(|config| config.0);
+    let blob_store =
+        ObjectStoreFactory::with_mirror(object_store_config.0, mirror_object_store_config)
+            .create_store()
+            .await;
     let circuit_ids_for_round_to_be_proven = FriProverGroupConfig::from_env()
         .context("FriProverGroupConfig::from_env()")?
         .get_circuit_ids_for_group_id(specialized_group_id)
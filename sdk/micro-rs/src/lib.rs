@@ -10,6 +10,7 @@ pub use crate::{ethereum::EthereumProvider, wallet::Wallet};
 pub mod error;
 pub mod ethereum;
 pub mod operations;
+pub mod proofs;
 pub mod signer;
 pub mod utils;
 pub mod wallet;
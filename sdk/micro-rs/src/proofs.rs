@@ -0,0 +1,381 @@
+//! Client-side verification of storage proofs returned by `zks_getProof` and `zks_getProofs`.
+//!
+//! Proofs are Merkle paths in the sparse binary Merkle tree storing L2 state. Hashing follows
+//! the tree implementation in the server: leaves are hashed together with their enumeration indices,
+//! and Blake2s is used for all hashing.
+
+use micro_crypto::hasher::{blake2::Blake2Hasher, Hasher};
+use micro_types::{
+    api::{AccountStorageKeys, BatchedProof, Proof, StorageProof},
+    AccountTreeId, Address, StorageKey, H256, U256,
+};
+
+/// Depth of the Merkle tree storing L2 state.
+const TREE_DEPTH: usize = 256;
+
+/// Errors that can occur when verifying storage proofs.
+#[derive(Debug, thiserror::Error)]
+pub enum ProofError {
+    #[error("Merkle path for key {key:?} of account {address:?} is longer than the tree depth")]
+    PathTooLong { address: Address, key: H256 },
+    #[error("Proof for missing key {key:?} of account {address:?} has a non-zero value")]
+    NonZeroMissingValue { address: Address, key: H256 },
+    #[error(
+        "Proof for key {key:?} of account {address:?} resolves to root hash {actual:?}, \
+         while {expected:?} was expected"
+    )]
+    RootHashMismatch {
+        address: Address,
+        key: H256,
+        expected: H256,
+        actual: H256,
+    },
+    #[error(
+        "Response contains proofs for {actual} accounts, while {expected} accounts were requested"
+    )]
+    AccountCountMismatch { expected: usize, actual: usize },
+    #[error("Response contains proofs for account {actual:?}, while {expected:?} was requested")]
+    AddressMismatch { expected: Address, actual: Address },
+    #[error(
+        "Response contains {actual} proofs for account {address:?}, while {expected} keys were requested"
+    )]
+    ProofCountMismatch {
+        address: Address,
+        expected: usize,
+        actual: usize,
+    },
+    #[error(
+        "Response contains a proof for key {actual:?} of account {address:?}, while {expected:?} was requested"
+    )]
+    KeyMismatch {
+        address: Address,
+        expected: H256,
+        actual: H256,
+    },
+}
+
+/// Verifies a proof for a single storage slot of the specified account against the trusted tree root hash.
+/// Proofs for missing slots (with zero leaf index) are verified as well; they prove that the slot is empty.
+pub fn verify_storage_proof(
+    address: Address,
+    proof: &StorageProof,
+    root_hash: H256,
+) -> Result<(), ProofError> {
+    let key = proof.key;
+    if proof.proof.len() > TREE_DEPTH {
+        return Err(ProofError::PathTooLong { address, key });
+    }
+    if proof.index == 0 && !proof.value.is_zero() {
+        return Err(ProofError::NonZeroMissingValue { address, key });
+    }
+
+    let hashed_key = StorageKey::new(AccountTreeId::new(address), key).hashed_key_u256();
+    let actual = fold_merkle_path(hashed_key, proof);
+    if actual == root_hash {
+        Ok(())
+    } else {
+        Err(ProofError::RootHashMismatch {
+            address,
+            key,
+            expected: root_hash,
+            actual,
+        })
+    }
+}
+
+/// Verifies all storage proofs for an account against the trusted tree root hash.
+pub fn verify_proof(proof: &Proof, root_hash: H256) -> Result<(), ProofError> {
+    for storage_proof in &proof.storage_proof {
+        verify_storage_proof(proof.address, storage_proof, root_hash)?;
+    }
+    Ok(())
+}
+
+/// Verifies proofs returned by `zks_getProofs` against the root hash included into the response.
+/// Proofs must correspond one-to-one to the `requested` accounts and keys, in the same order;
+/// otherwise, a response with omitted proofs would pass verification.
+/// The caller is responsible for checking that the root hash corresponds to the L1 batch,
+/// e.g. by comparing it with the root hash committed on L1.
+pub fn verify_batched_proof(
+    proof: &BatchedProof,
+    requested: &[AccountStorageKeys],
+) -> Result<(), ProofError> {
+    if proof.proofs.len() != requested.len() {
+        return Err(ProofError::AccountCountMismatch {
+            expected: requested.len(),
+            actual: proof.proofs.len(),
+        });
+    }
+    for (account_proof, account) in proof.proofs.iter().zip(requested) {
+        let address = account.address;
+        if account_proof.address != address {
+            return Err(ProofError::AddressMismatch {
+                expected: address,
+                actual: account_proof.address,
+            });
+        }
+        if account_proof.storage_proof.len() != account.keys.len() {
+            return Err(ProofError::ProofCountMismatch {
+                address,
+                expected: account.keys.len(),
+                actual: account_proof.storage_proof.len(),
+            });
+        }
+        for (storage_proof, &key) in account_proof.storage_proof.iter().zip(&account.keys) {
+            if storage_proof.key != key {
+                return Err(ProofError::KeyMismatch {
+                    address,
+                    expected: key,
+                    actual: storage_proof.key,
+                });
+            }
+        }
+        verify_proof(account_proof, proof.root_hash)?;
+    }
+    Ok(())
+}
+
+/// Computes the tree root hash from the leaf and its Merkle path. The path returned by the API is ordered
+/// from the root to the leaf, and hashes for empty subtrees at the bottom of the tree are omitted.
+fn fold_merkle_path(hashed_key: U256, proof: &StorageProof) -> H256 {
+    let hasher = Blake2Hasher;
+    let mut leaf_bytes = [0_u8; 40];
+    leaf_bytes[..8].copy_from_slice(&proof.index.to_be_bytes());
+    leaf_bytes[8..].copy_from_slice(proof.value.as_bytes());
+    let mut hash = hasher.hash_bytes(&leaf_bytes);
+
+    let empty_hash_count = TREE_DEPTH - proof.proof.len();
+    let mut empty_subtree_hash = hasher.hash_bytes(&[0_u8; 40]);
+    for depth in 0..TREE_DEPTH {
+        let adjacent_hash = if depth < empty_hash_count {
+            empty_subtree_hash
+        } else {
+            proof.proof[TREE_DEPTH - 1 - depth]
+        };
+        hash = if hashed_key.bit(depth) {
+            hasher.compress(&adjacent_hash, &hash)
+        } else {
+            hasher.compress(&hash, &adjacent_hash)
+        };
+        empty_subtree_hash = hasher.compress(&empty_subtree_hash, &empty_subtree_hash);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use micro_merkle_tree::{MerkleTree, PatchSet, TreeEntry};
+    use micro_types::L1BatchNumber;
+
+    use super::*;
+
+    fn storage_proof(key: H256, entry: micro_merkle_tree::TreeEntryWithProof) -> StorageProof {
+        let mut merkle_path = entry.merkle_path;
+        merkle_path.reverse(); // The API uses root-to-leaf ordering
+        StorageProof {
+            key,
+            proof: merkle_path,
+            value: entry.base.value,
+            index: entry.base.leaf_index,
+        }
+    }
+
+    fn create_tree(
+        accounts: &[Address],
+        keys_per_account: u64,
+    ) -> (MerkleTree<PatchSet>, Vec<StorageKey>) {
+        let storage_keys: Vec<_> = accounts
+            .iter()
+            .flat_map(|&address| {
+                (0..keys_per_account).map(move |i| {
+                    StorageKey::new(AccountTreeId::new(address), H256::from_low_u64_be(i))
+                })
+            })
+            .collect();
+        let entries = storage_keys.iter().enumerate().map(|(i, key)| {
+            let leaf_index = i as u64 + 1;
+            TreeEntry::new(
+                key.hashed_key_u256(),
+                leaf_index,
+                H256::from_low_u64_be(leaf_index),
+            )
+        });
+
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(entries.collect());
+        (tree, storage_keys)
+    }
+
+    fn requested_keys(keys: &[(Address, Vec<H256>)]) -> Vec<AccountStorageKeys> {
+        keys.iter()
+            .map(|(address, keys)| AccountStorageKeys {
+                address: *address,
+                keys: keys.clone(),
+            })
+            .collect()
+    }
+
+    fn batched_proof(
+        tree: &MerkleTree<PatchSet>,
+        requested_keys: &[AccountStorageKeys],
+    ) -> BatchedProof {
+        let proofs = requested_keys
+            .iter()
+            .map(|account| {
+                let account_id = AccountTreeId::new(account.address);
+                let hashed_keys: Vec<_> = account
+                    .keys
+                    .iter()
+                    .map(|&key| StorageKey::new(account_id, key).hashed_key_u256())
+                    .collect();
+                let entries = tree.entries_with_proofs(0, &hashed_keys).unwrap();
+                Proof {
+                    address: account.address,
+                    storage_proof: account
+                        .keys
+                        .iter()
+                        .zip(entries)
+                        .map(|(&key, entry)| storage_proof(key, entry))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        BatchedProof {
+            l1_batch_number: L1BatchNumber(0),
+            root_hash: tree.latest_root_hash(),
+            proofs,
+        }
+    }
+
+    #[test]
+    fn verifying_proofs_produced_by_tree() {
+        let accounts = [Address::repeat_byte(1), Address::repeat_byte(2)];
+        let (tree, storage_keys) = create_tree(&accounts, 5);
+        assert_eq!(storage_keys.len(), 10);
+
+        let requested_keys = requested_keys(&[
+            (
+                accounts[0],
+                vec![H256::from_low_u64_be(0), H256::from_low_u64_be(3)],
+            ),
+            // Include a missing key to check non-membership proofs.
+            (
+                accounts[1],
+                vec![H256::from_low_u64_be(4), H256::repeat_byte(0xff)],
+            ),
+            (Address::repeat_byte(3), vec![H256::zero()]),
+        ]);
+        let proof = batched_proof(&tree, &requested_keys);
+        let missing_proof = &proof.proofs[1].storage_proof[1];
+        assert_eq!(missing_proof.index, 0);
+        assert!(missing_proof.value.is_zero());
+        verify_batched_proof(&proof, &requested_keys).unwrap();
+
+        // Check that the proof survives a JSON round trip as it would when received from the API.
+        let proof_json = serde_json::to_string(&proof).unwrap();
+        let proof: BatchedProof = serde_json::from_str(&proof_json).unwrap();
+        verify_batched_proof(&proof, &requested_keys).unwrap();
+    }
+
+    #[test]
+    fn verifying_proofs_for_empty_tree() {
+        let mut tree = MerkleTree::new(PatchSet::default());
+        tree.extend(vec![]);
+        let requested_keys = requested_keys(&[(Address::repeat_byte(1), vec![H256::zero()])]);
+        let proof = batched_proof(&tree, &requested_keys);
+        assert!(proof.proofs[0].storage_proof[0].proof.is_empty());
+        verify_batched_proof(&proof, &requested_keys).unwrap();
+    }
+
+    #[test]
+    fn incomplete_proofs_are_rejected() {
+        let accounts = [Address::repeat_byte(1), Address::repeat_byte(2)];
+        let (tree, _) = create_tree(&accounts, 3);
+        let requested_keys = requested_keys(&[
+            (
+                accounts[0],
+                vec![H256::from_low_u64_be(0), H256::from_low_u64_be(1)],
+            ),
+            (accounts[1], vec![H256::from_low_u64_be(2)]),
+        ]);
+        let proof = batched_proof(&tree, &requested_keys);
+        verify_batched_proof(&proof, &requested_keys).unwrap();
+
+        let mut truncated = proof.clone();
+        truncated.proofs[0].storage_proof.pop();
+        let err = verify_batched_proof(&truncated, &requested_keys).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ProofError::ProofCountMismatch {
+                    expected: 2,
+                    actual: 1,
+                    ..
+                }
+            ),
+            "{err}"
+        );
+
+        let mut truncated = proof.clone();
+        truncated.proofs.pop();
+        let err = verify_batched_proof(&truncated, &requested_keys).unwrap_err();
+        assert!(
+            matches!(
+                err,
+                ProofError::AccountCountMismatch {
+                    expected: 2,
+                    actual: 1
+                }
+            ),
+            "{err}"
+        );
+
+        let mut reordered = proof.clone();
+        reordered.proofs[0].storage_proof.swap(0, 1);
+        let err = verify_batched_proof(&reordered, &requested_keys).unwrap_err();
+        assert!(matches!(err, ProofError::KeyMismatch { .. }), "{err}");
+
+        let mut reordered = proof;
+        reordered.proofs.swap(0, 1);
+        let err = verify_batched_proof(&reordered, &requested_keys).unwrap_err();
+        assert!(matches!(err, ProofError::AddressMismatch { .. }), "{err}");
+    }
+
+    #[test]
+    fn tampered_proofs_are_rejected() {
+        let address = Address::repeat_byte(1);
+        let (tree, _) = create_tree(&[address], 3);
+        let requested_keys = requested_keys(&[(address, vec![H256::from_low_u64_be(1)])]);
+        let proof = batched_proof(&tree, &requested_keys);
+        verify_batched_proof(&proof, &requested_keys).unwrap();
+        let storage_proof = &proof.proofs[0].storage_proof[0];
+
+        let mut tampered = storage_proof.clone();
+        tampered.value = H256::repeat_byte(0xff);
+        let err = verify_storage_proof(address, &tampered, proof.root_hash).unwrap_err();
+        assert!(matches!(err, ProofError::RootHashMismatch { .. }), "{err}");
+
+        let mut tampered = storage_proof.clone();
+        tampered.index += 1;
+        let err = verify_storage_proof(address, &tampered, proof.root_hash).unwrap_err();
+        assert!(matches!(err, ProofError::RootHashMismatch { .. }), "{err}");
+
+        let err = verify_storage_proof(Address::repeat_byte(2), storage_proof, proof.root_hash)
+            .unwrap_err();
+        assert!(matches!(err, ProofError::RootHashMismatch { .. }), "{err}");
+
+        let mut tampered = storage_proof.clone();
+        tampered.index = 0;
+        let err = verify_storage_proof(address, &tampered, proof.root_hash).unwrap_err();
+        assert!(
+            matches!(err, ProofError::NonZeroMissingValue { .. }),
+            "{err}"
+        );
+
+        let mut tampered = storage_proof.clone();
+        tampered.proof = vec![H256::zero(); TREE_DEPTH + 1];
+        let err = verify_storage_proof(address, &tampered, proof.root_hash).unwrap_err();
+        assert!(matches!(err, ProofError::PathTooLong { .. }), "{err}");
+    }
+}